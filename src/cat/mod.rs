@@ -0,0 +1,390 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Common Access Token (CAT) functionality, as per CTA-5007.
+//!
+//! A CAT is a CWT used to authorize requests to a content delivery network. [`CatClaims`] holds
+//! the CAT claims of a claims set in typed form, and [`CatClaims::check_request`] evaluates the
+//! request-matching claims against a [`CatRequest`].
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    cwt::{ClaimName, ClaimsSet},
+    iana,
+    util::{set_once, set_once_vec, ValueTryAs},
+    CoseError, Label,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::convert::TryInto;
+
+mod request;
+pub use request::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Replay policy, as held in a `catreplay` claim.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Replay {
+    /// The token may be used for multiple requests.
+    Permitted = 0,
+    /// The token may only be used for a single request.
+    Prohibited = 1,
+    /// Reuse of the token is detected (and may be acted on) by the recipient.
+    ReuseDetection = 2,
+}
+
+impl AsCborValue for Replay {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let value: u64 = value.try_as_integer()?.try_into()?;
+        match value {
+            x if x == Replay::Permitted as u64 => Ok(Replay::Permitted),
+            x if x == Replay::Prohibited as u64 => Ok(Replay::Prohibited),
+            x if x == Replay::ReuseDetection as u64 => Ok(Replay::ReuseDetection),
+            _ => Err(CoseError::OutOfRangeIntegerValue),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::from(self as u64))
+    }
+}
+
+/// Types of token renewal, as held in a `catr` claim.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RenewalType {
+    /// Automatic renewal.
+    Automatic = 0,
+    /// Renewed token is returned in a cookie.
+    Cookie = 1,
+    /// Renewed token is returned in a header.
+    Header = 2,
+    /// Renewed token is returned in a redirect.
+    Redirect = 3,
+}
+
+/// Token renewal, as held in a `catr` claim.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Renewal {
+    /// Type of renewal.
+    pub renewal_type: RenewalType,
+    /// Number of seconds to add to the expiration time of a renewed token.
+    pub exp_add: Option<u64>,
+    /// Number of seconds before expiration that renewal should happen.
+    pub deadline: Option<u64>,
+    /// Any additional renewal parameters, such as cookie or header names.
+    pub params: Vec<(Label, Value)>,
+}
+
+const RENEWAL_TYPE: Label = Label::Int(0);
+const EXP_ADD: Label = Label::Int(1);
+const DEADLINE: Label = Label::Int(2);
+
+impl AsCborValue for Renewal {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut renewal_type = None;
+        let mut exp_add = None;
+        let mut deadline = None;
+        let mut params: Vec<(Label, Value)> = Vec::new();
+        for (k, v) in value.try_as_map()? {
+            let k = Label::from_cbor_value(k)?;
+            let duplicate = match &k {
+                x if *x == RENEWAL_TYPE => renewal_type.is_some(),
+                x if *x == EXP_ADD => exp_add.is_some(),
+                x if *x == DEADLINE => deadline.is_some(),
+                x => params.iter().any(|(p, _)| p == x),
+            };
+            if duplicate {
+                return Err(CoseError::DuplicateMapKey);
+            }
+            match k {
+                x if x == RENEWAL_TYPE => {
+                    let t: u64 = v.try_as_integer()?.try_into()?;
+                    renewal_type = Some(match t {
+                        x if x == RenewalType::Automatic as u64 => RenewalType::Automatic,
+                        x if x == RenewalType::Cookie as u64 => RenewalType::Cookie,
+                        x if x == RenewalType::Header as u64 => RenewalType::Header,
+                        x if x == RenewalType::Redirect as u64 => RenewalType::Redirect,
+                        _ => return Err(CoseError::OutOfRangeIntegerValue),
+                    });
+                }
+                x if x == EXP_ADD => exp_add = Some(v.try_as_integer()?.try_into()?),
+                x if x == DEADLINE => deadline = Some(v.try_as_integer()?.try_into()?),
+                k => params.push((k, v)),
+            }
+        }
+        Ok(Self {
+            renewal_type: renewal_type
+                .ok_or(CoseError::UnexpectedItem("map", "map with renewal type"))?,
+            exp_add,
+            deadline,
+            params,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut map = vec![(
+            RENEWAL_TYPE.to_cbor_value()?,
+            Value::from(self.renewal_type as u64),
+        )];
+        if let Some(exp_add) = self.exp_add {
+            map.push((EXP_ADD.to_cbor_value()?, Value::from(exp_add)));
+        }
+        if let Some(deadline) = self.deadline {
+            map.push((DEADLINE.to_cbor_value()?, Value::from(deadline)));
+        }
+        for (k, v) in self.params {
+            map.push((k.to_cbor_value()?, v));
+        }
+        Ok(Value::Map(map))
+    }
+}
+
+/// Structure representing the claims of a CAT.
+///
+/// Claims that can hold multiple entries are represented as a [`Vec`], which is empty if the claim
+/// is absent.  CAT claims that are not represented here are held in [`CatClaims::claims`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CatClaims {
+    /// Replay policy (`catreplay`).
+    pub replay: Option<Replay>,
+    /// CAT version (`catv`).
+    pub version: Option<u64>,
+    /// Match conditions on the components of the request URI (`catu`).
+    pub uri: Vec<(UriComponent, Vec<Match>)>,
+    /// Permitted HTTP methods (`catm`).
+    pub methods: Vec<String>,
+    /// Permitted TLS ALPN protocol IDs (`catalpn`).
+    pub alpn: Vec<String>,
+    /// Match conditions on request headers, keyed by header name (`cath`).
+    pub headers: Vec<(String, Vec<Match>)>,
+    /// Permitted ISO 3166 country or subdivision codes (`catgeoiso3166`).
+    pub geo_iso3166: Vec<String>,
+    /// TLS public key (`cattpk`).
+    pub tls_public_key: Option<Vec<u8>>,
+    /// Token renewal (`catr`).
+    pub renewal: Option<Renewal>,
+    /// Any other claims.
+    pub claims: ClaimsSet,
+}
+
+impl crate::CborSerializable for CatClaims {}
+
+/// Extract a non-empty array of text strings.
+fn strings_from_cbor(value: Value) -> Result<Vec<String>, CoseError> {
+    let a = value.try_as_array_then_convert(|v| v.try_as_string())?;
+    if a.is_empty() {
+        return Err(CoseError::UnexpectedItem("empty array", "non-empty array"));
+    }
+    Ok(a)
+}
+
+fn strings_to_cbor(strings: Vec<String>) -> Value {
+    Value::Array(strings.into_iter().map(Value::Text).collect())
+}
+
+/// Extract a match object, which holds one or more match conditions.
+fn matches_from_cbor(value: Value) -> Result<Vec<Match>, CoseError> {
+    let m = value.try_as_map()?;
+    if m.is_empty() {
+        return Err(CoseError::UnexpectedItem("empty map", "non-empty map"));
+    }
+    let mut matches: Vec<Match> = Vec::new();
+    for (k, v) in m {
+        let cond = Match::from_entry(k, v)?;
+        if matches.iter().any(|m| m.match_type() == cond.match_type()) {
+            return Err(CoseError::DuplicateMapKey);
+        }
+        matches.push(cond);
+    }
+    Ok(matches)
+}
+
+fn matches_to_cbor(matches: Vec<Match>) -> Value {
+    Value::Map(matches.into_iter().map(Match::into_entry).collect())
+}
+
+impl CatClaims {
+    /// Extract the CAT claims from a claims set, leaving any other claims in
+    /// [`CatClaims::claims`].
+    pub fn from_claims_set(mut claims: ClaimsSet) -> Result<Self, CoseError> {
+        use iana::CwtClaimName as N;
+        let rest = core::mem::take(&mut claims.rest);
+        let mut cat = Self {
+            claims,
+            ..Default::default()
+        };
+        for (name, value) in rest {
+            let name = match name {
+                ClaimName::Assigned(name) => name,
+                name => {
+                    cat.claims.rest.push((name, value));
+                    continue;
+                }
+            };
+            match name {
+                N::CatReplay => set_once(&mut cat.replay, Replay::from_cbor_value(value)?)?,
+                N::CatVersion => set_once(&mut cat.version, value.try_as_integer()?.try_into()?)?,
+                N::CatUri => {
+                    let mut uri: Vec<(UriComponent, Vec<Match>)> = Vec::new();
+                    for (k, v) in value.try_as_map()? {
+                        let component = UriComponent::from_cbor_value(k)?;
+                        if uri.iter().any(|(c, _)| *c == component) {
+                            return Err(CoseError::DuplicateMapKey);
+                        }
+                        uri.push((component, matches_from_cbor(v)?));
+                    }
+                    if uri.is_empty() {
+                        return Err(CoseError::UnexpectedItem("empty map", "non-empty map"));
+                    }
+                    set_once_vec(&mut cat.uri, uri)?;
+                }
+                N::CatMethod => set_once_vec(&mut cat.methods, strings_from_cbor(value)?)?,
+                N::CatAlpn => set_once_vec(&mut cat.alpn, strings_from_cbor(value)?)?,
+                N::CatHeader => {
+                    let mut headers: Vec<(String, Vec<Match>)> = Vec::new();
+                    for (k, v) in value.try_as_map()? {
+                        let name = k.try_as_string()?;
+                        if headers.iter().any(|(n, _)| n.eq_ignore_ascii_case(&name)) {
+                            return Err(CoseError::DuplicateMapKey);
+                        }
+                        headers.push((name, matches_from_cbor(v)?));
+                    }
+                    if headers.is_empty() {
+                        return Err(CoseError::UnexpectedItem("empty map", "non-empty map"));
+                    }
+                    set_once_vec(&mut cat.headers, headers)?;
+                }
+                N::CatGeoIso3166 => set_once_vec(&mut cat.geo_iso3166, strings_from_cbor(value)?)?,
+                N::CatTlsPublicKey => set_once(&mut cat.tls_public_key, value.try_as_bytes()?)?,
+                N::CatRenewal => set_once(&mut cat.renewal, Renewal::from_cbor_value(value)?)?,
+                name => cat.claims.rest.push((ClaimName::Assigned(name), value)),
+            }
+        }
+        Ok(cat)
+    }
+
+    /// Convert to a claims set, with the CAT claims preceding any other claims.
+    pub fn to_claims_set(self) -> Result<ClaimsSet, CoseError> {
+        use iana::CwtClaimName as N;
+        let mut cat = Vec::new();
+        if let Some(replay) = self.replay {
+            cat.push((N::CatReplay, replay.to_cbor_value()?));
+        }
+        if let Some(version) = self.version {
+            cat.push((N::CatVersion, Value::from(version)));
+        }
+        if !self.uri.is_empty() {
+            let uri = self
+                .uri
+                .into_iter()
+                .map(|(c, m)| Ok((c.to_cbor_value()?, matches_to_cbor(m))))
+                .collect::<Result<Vec<_>, CoseError>>()?;
+            cat.push((N::CatUri, Value::Map(uri)));
+        }
+        if !self.methods.is_empty() {
+            cat.push((N::CatMethod, strings_to_cbor(self.methods)));
+        }
+        if !self.alpn.is_empty() {
+            cat.push((N::CatAlpn, strings_to_cbor(self.alpn)));
+        }
+        if !self.headers.is_empty() {
+            let headers = self
+                .headers
+                .into_iter()
+                .map(|(name, m)| (Value::Text(name), matches_to_cbor(m)))
+                .collect();
+            cat.push((N::CatHeader, Value::Map(headers)));
+        }
+        if !self.geo_iso3166.is_empty() {
+            cat.push((N::CatGeoIso3166, strings_to_cbor(self.geo_iso3166)));
+        }
+        if let Some(key) = self.tls_public_key {
+            cat.push((N::CatTlsPublicKey, Value::Bytes(key)));
+        }
+        if let Some(renewal) = self.renewal {
+            cat.push((N::CatRenewal, renewal.to_cbor_value()?));
+        }
+
+        let mut claims = self.claims;
+        let rest = core::mem::take(&mut claims.rest);
+        claims.rest = cat
+            .into_iter()
+            .map(|(name, value)| (ClaimName::Assigned(name), value))
+            .chain(rest)
+            .collect();
+        Ok(claims)
+    }
+}
+
+impl AsCborValue for CatClaims {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        Self::from_claims_set(ClaimsSet::from_cbor_value(value)?)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        self.to_claims_set()?.to_cbor_value()
+    }
+}
+
+/// Builder for [`CatClaims`] objects.
+#[derive(Default)]
+pub struct CatClaimsBuilder(CatClaims);
+
+impl CatClaimsBuilder {
+    builder! {CatClaims}
+    builder_set_optional! {replay: Replay}
+    builder_set_optional! {version: u64}
+    builder_set_optional! {tls_public_key: Vec<u8>}
+    builder_set_optional! {renewal: Renewal}
+    builder_set! {claims: ClaimsSet}
+
+    /// Add match conditions for a component of the request URI.
+    #[must_use]
+    pub fn uri_match(mut self, component: UriComponent, matches: Vec<Match>) -> Self {
+        self.0.uri.push((component, matches));
+        self
+    }
+
+    /// Add a permitted HTTP method.
+    #[must_use]
+    pub fn method(mut self, method: String) -> Self {
+        self.0.methods.push(method);
+        self
+    }
+
+    /// Add a permitted TLS ALPN protocol ID.
+    #[must_use]
+    pub fn alpn(mut self, alpn: String) -> Self {
+        self.0.alpn.push(alpn);
+        self
+    }
+
+    /// Add match conditions for a request header.
+    #[must_use]
+    pub fn header_match(mut self, name: String, matches: Vec<Match>) -> Self {
+        self.0.headers.push((name, matches));
+        self
+    }
+
+    /// Add a permitted ISO 3166 country or subdivision code.
+    #[must_use]
+    pub fn geo_iso3166(mut self, code: String) -> Self {
+        self.0.geo_iso3166.push(code);
+        self
+    }
+}
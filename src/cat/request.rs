@@ -0,0 +1,309 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Evaluation of request-matching CAT claims.
+
+use super::CatClaims;
+use crate::{
+    cbor::value::Value, common::AsCborValue, cwt::ClaimName, iana, util::ValueTryAs, CoseError,
+};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::convert::TryInto;
+
+/// Components of a request URI, as used as keys in a `catu` claim.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UriComponent {
+    /// Scheme, such as "https".
+    Scheme = 0,
+    /// Host.
+    Host = 1,
+    /// Port, as a decimal string.
+    Port = 2,
+    /// Path, including the leading "/".
+    Path = 3,
+    /// Query, without the leading "?".
+    Query = 4,
+    /// Path up to (but not including) the final "/".
+    ParentPath = 5,
+    /// Final segment of the path.
+    Filename = 6,
+    /// Filename up to (but not including) the final ".".
+    Stem = 7,
+    /// Filename from the final "." onwards.
+    Extension = 8,
+}
+
+impl AsCborValue for UriComponent {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let value: i64 = value.try_as_integer()?.try_into()?;
+        Ok(match value {
+            x if x == UriComponent::Scheme as i64 => UriComponent::Scheme,
+            x if x == UriComponent::Host as i64 => UriComponent::Host,
+            x if x == UriComponent::Port as i64 => UriComponent::Port,
+            x if x == UriComponent::Path as i64 => UriComponent::Path,
+            x if x == UriComponent::Query as i64 => UriComponent::Query,
+            x if x == UriComponent::ParentPath as i64 => UriComponent::ParentPath,
+            x if x == UriComponent::Filename as i64 => UriComponent::Filename,
+            x if x == UriComponent::Stem as i64 => UriComponent::Stem,
+            x if x == UriComponent::Extension as i64 => UriComponent::Extension,
+            _ => return Err(CoseError::OutOfRangeIntegerValue),
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::from(self as i64))
+    }
+}
+
+/// Match condition on a string, as held in a match object in `catu` and `cath` claims.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Match {
+    /// The string is equal to the value.
+    Exact(String),
+    /// The string starts with the value.
+    Prefix(String),
+    /// The string ends with the value.
+    Suffix(String),
+    /// The string contains the value.
+    Contains(String),
+    /// The string matches a regular expression.
+    Regex(Value),
+    /// The SHA-256 hash of the string is equal to the value.
+    Sha256(Vec<u8>),
+    /// The SHA-512/256 hash of the string is equal to the value.
+    Sha512_256(Vec<u8>),
+}
+
+const EXACT: i64 = 0;
+const PREFIX: i64 = 1;
+const SUFFIX: i64 = 2;
+const CONTAINS: i64 = 3;
+const REGEX: i64 = 4;
+const SHA256: i64 = -1;
+const SHA512_256: i64 = -2;
+
+impl Match {
+    /// Return the key identifying the type of match in a match object.
+    pub fn match_type(&self) -> i64 {
+        match self {
+            Match::Exact(_) => EXACT,
+            Match::Prefix(_) => PREFIX,
+            Match::Suffix(_) => SUFFIX,
+            Match::Contains(_) => CONTAINS,
+            Match::Regex(_) => REGEX,
+            Match::Sha256(_) => SHA256,
+            Match::Sha512_256(_) => SHA512_256,
+        }
+    }
+
+    /// Create a match condition from an entry in a match object.
+    pub(crate) fn from_entry(k: Value, v: Value) -> Result<Self, CoseError> {
+        let k: i64 = k.try_as_integer()?.try_into()?;
+        Ok(match k {
+            EXACT => Match::Exact(v.try_as_string()?),
+            PREFIX => Match::Prefix(v.try_as_string()?),
+            SUFFIX => Match::Suffix(v.try_as_string()?),
+            CONTAINS => Match::Contains(v.try_as_string()?),
+            REGEX => Match::Regex(v),
+            SHA256 => Match::Sha256(v.try_as_bytes()?),
+            SHA512_256 => Match::Sha512_256(v.try_as_bytes()?),
+            _ => return Err(CoseError::OutOfRangeIntegerValue),
+        })
+    }
+
+    /// Convert the match condition into an entry in a match object.
+    pub(crate) fn into_entry(self) -> (Value, Value) {
+        let k = Value::from(self.match_type());
+        let v = match self {
+            Match::Exact(s) | Match::Prefix(s) | Match::Suffix(s) | Match::Contains(s) => {
+                Value::Text(s)
+            }
+            Match::Regex(v) => v,
+            Match::Sha256(h) | Match::Sha512_256(h) => Value::Bytes(h),
+        };
+        (k, v)
+    }
+
+    /// Indicate whether `s` satisfies the match condition, or return `None` if the condition
+    /// cannot be evaluated.
+    fn is_match(&self, s: &str) -> Option<bool> {
+        match self {
+            Match::Exact(v) => Some(s == v),
+            Match::Prefix(v) => Some(s.starts_with(v.as_str())),
+            Match::Suffix(v) => Some(s.ends_with(v.as_str())),
+            Match::Contains(v) => Some(s.contains(v.as_str())),
+            Match::Regex(_) => None,
+            #[cfg(feature = "sha2")]
+            Match::Sha256(h) => Some(crate::crypto::sha256(s.as_bytes()) == *h),
+            #[cfg(feature = "sha2")]
+            Match::Sha512_256(h) => {
+                use sha2::Digest;
+                Some(sha2::Sha512_256::digest(s.as_bytes()).as_slice() == h.as_slice())
+            }
+            #[cfg(not(feature = "sha2"))]
+            Match::Sha256(_) | Match::Sha512_256(_) => None,
+        }
+    }
+}
+
+/// Description of a request, for evaluation against the request-matching claims of a CAT.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CatRequest {
+    /// HTTP method, such as "GET".
+    pub method: String,
+    /// URI scheme, such as "https".
+    pub scheme: String,
+    /// URI host.
+    pub host: String,
+    /// URI port, if explicitly present in the URI.
+    pub port: Option<u16>,
+    /// URI path, including the leading "/".
+    pub path: String,
+    /// URI query, without the leading "?".
+    pub query: Option<String>,
+    /// Request headers, as name:value pairs.
+    pub headers: Vec<(String, String)>,
+    /// TLS ALPN protocol ID negotiated for the connection, if any.
+    pub alpn: Option<String>,
+    /// ISO 3166 country or subdivision code of the client, if known.
+    pub geo_iso3166: Option<String>,
+}
+
+impl CatRequest {
+    /// Return the value of a component of the request URI.  If the port is not explicitly
+    /// present, the default port for "http" and "https" schemes is used.
+    pub fn uri_component(&self, component: UriComponent) -> String {
+        let (parent, filename) = match self.path.rfind('/') {
+            Some(i) => (&self.path[..i], &self.path[i + 1..]),
+            None => ("", self.path.as_str()),
+        };
+        let (stem, extension) = match filename.rfind('.') {
+            Some(i) => (&filename[..i], &filename[i..]),
+            None => (filename, ""),
+        };
+        match component {
+            UriComponent::Scheme => self.scheme.clone(),
+            UriComponent::Host => self.host.clone(),
+            UriComponent::Port => match (self.port, self.scheme.as_str()) {
+                (Some(port), _) => port.to_string(),
+                (None, "http") => "80".to_owned(),
+                (None, "https") => "443".to_owned(),
+                (None, _) => String::new(),
+            },
+            UriComponent::Path => self.path.clone(),
+            UriComponent::Query => self.query.clone().unwrap_or_default(),
+            UriComponent::ParentPath => parent.to_owned(),
+            UriComponent::Filename => filename.to_owned(),
+            UriComponent::Stem => stem.to_owned(),
+            UriComponent::Extension => extension.to_owned(),
+        }
+    }
+}
+
+/// The error type returned when a request fails evaluation against the claims of a CAT.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CatError {
+    /// The request does not satisfy the claim.
+    Mismatch(ClaimName),
+    /// The claim holds a match condition that cannot be evaluated, such as a regular expression (or
+    /// a hash, without the `sha2` feature).
+    Unsupported(ClaimName),
+}
+
+impl core::error::Error for CatError {}
+
+impl core::fmt::Display for CatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CatError::Mismatch(name) => write!(f, "request does not match claim {name:?}"),
+            CatError::Unsupported(name) => write!(f, "unsupported match in claim {name:?}"),
+        }
+    }
+}
+
+/// Check that `s` satisfies all of the match conditions in `matches`.
+fn check_matches(name: iana::CwtClaimName, matches: &[Match], s: &str) -> Result<bool, CatError> {
+    for m in matches {
+        match m.is_match(s) {
+            Some(true) => {}
+            Some(false) => return Ok(false),
+            None => return Err(CatError::Unsupported(ClaimName::Assigned(name))),
+        }
+    }
+    Ok(true)
+}
+
+/// Indicate whether the ISO 3166 `code` of a request is covered by the `allowed` code, which may
+/// be a country code covering all of its subdivisions.
+fn geo_matches(allowed: &str, code: &str) -> bool {
+    code.eq_ignore_ascii_case(allowed)
+        || (code.len() > allowed.len()
+            && code.is_char_boundary(allowed.len())
+            && code[..allowed.len()].eq_ignore_ascii_case(allowed)
+            && code[allowed.len()..].starts_with('-'))
+}
+
+impl CatClaims {
+    /// Evaluate the request-matching claims (`catu`, `catm`, `catalpn`, `cath` and
+    /// `catgeoiso3166`) against a request.  Absent claims place no restriction on the request.
+    pub fn check_request(&self, request: &CatRequest) -> Result<(), CatError> {
+        use iana::CwtClaimName as N;
+        let mismatch = |name| CatError::Mismatch(ClaimName::Assigned(name));
+
+        for (component, matches) in &self.uri {
+            let value = request.uri_component(*component);
+            if !check_matches(N::CatUri, matches, &value)? {
+                return Err(mismatch(N::CatUri));
+            }
+        }
+        if !self.methods.is_empty() && !self.methods.contains(&request.method) {
+            return Err(mismatch(N::CatMethod));
+        }
+        if !self.alpn.is_empty() {
+            match &request.alpn {
+                Some(alpn) if self.alpn.contains(alpn) => {}
+                _ => return Err(mismatch(N::CatAlpn)),
+            }
+        }
+        for (name, matches) in &self.headers {
+            let mut found = false;
+            for (_, value) in request
+                .headers
+                .iter()
+                .filter(|(n, _)| n.eq_ignore_ascii_case(name))
+            {
+                if check_matches(N::CatHeader, matches, value)? {
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                return Err(mismatch(N::CatHeader));
+            }
+        }
+        if !self.geo_iso3166.is_empty() {
+            match &request.geo_iso3166 {
+                Some(code) if self.geo_iso3166.iter().any(|g| geo_matches(g, code)) => {}
+                _ => return Err(mismatch(N::CatGeoIso3166)),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{cwt::ClaimsSetBuilder, util::expect_err, CborSerializable};
+use alloc::{borrow::ToOwned, string::ToString, vec};
+
+#[test]
+fn test_cat_claims_encode() {
+    let claims = CatClaimsBuilder::new()
+        .version(1)
+        .uri_match(UriComponent::Path, vec![Match::Prefix("/a".to_owned())])
+        .method("GET".to_owned())
+        .build();
+    let data = claims.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "a3", // 3-map
+            "190136", "01", // 310 (catv) => 1
+            "190138", "a1", // 312 (catu) => {path => {prefix => "/a"}}
+            "03", "a1", "01", "622f61", "190139", "81", // 313 (catm) => ["GET"]
+            "63474554",
+        )
+    );
+    let got = CatClaims::from_slice(&data).unwrap();
+    assert_eq!(got, claims);
+
+    // All claims, along with some non-CAT claims.
+    let claims = CatClaimsBuilder::new()
+        .replay(Replay::Prohibited)
+        .version(1)
+        .uri_match(
+            UriComponent::Host,
+            vec![
+                Match::Suffix(".example.com".to_owned()),
+                Match::Contains("cdn".to_owned()),
+            ],
+        )
+        .uri_match(
+            UriComponent::Extension,
+            vec![Match::Regex(Value::Array(vec![Value::Text(
+                "^\\.m3u8$".to_owned(),
+            )]))],
+        )
+        .uri_match(UriComponent::Filename, vec![Match::Sha256(vec![1; 32])])
+        .uri_match(UriComponent::Stem, vec![Match::Sha512_256(vec![2; 32])])
+        .method("GET".to_owned())
+        .method("HEAD".to_owned())
+        .alpn("h2".to_owned())
+        .header_match(
+            "User-Agent".to_owned(),
+            vec![Match::Exact("player".to_owned())],
+        )
+        .geo_iso3166("US".to_owned())
+        .geo_iso3166("GB-LND".to_owned())
+        .tls_public_key(vec![3; 16])
+        .renewal(Renewal {
+            renewal_type: RenewalType::Cookie,
+            exp_add: Some(60),
+            deadline: Some(10),
+            params: vec![(Label::Int(4), Value::Text("token".to_owned()))],
+        })
+        .claims(
+            ClaimsSetBuilder::new()
+                .issuer("iss".to_owned())
+                .claim(iana::CwtClaimName::CatPor, Value::Bool(true))
+                .private_claim(-70_000, Value::from(1))
+                .build(),
+        )
+        .build();
+    let data = claims.clone().to_vec().unwrap();
+    let got = CatClaims::from_slice(&data).unwrap();
+    assert_eq!(got, claims);
+    assert_eq!(got.claims.issuer, Some("iss".to_owned()));
+    assert_eq!(got.claims.rest.len(), 2);
+}
+
+#[test]
+fn test_cat_claims_decode_fail() {
+    let tests = [
+        ("a119013403", "out of range integer value"),
+        ("a119013440", "expected int"),
+        ("a119013620", "out of range integer value"),
+        ("a119013801", "expected map"),
+        ("a1190138a0", "non-empty map"),
+        ("a1190138a109a10160", "out of range integer value"),
+        ("a1190138a103a0", "non-empty map"),
+        ("a1190138a103a10501", "out of range integer value"),
+        ("a1190138a103a10101", "expected tstr"),
+        ("a1190138a103a12001", "expected bstr"),
+        ("a1190138a103a2016001 60", "duplicate map key"),
+        ("a1190138a203a1016003a10160", "duplicate map key"),
+        ("a119013980", "non-empty array"),
+        ("a11901398101", "expected tstr"),
+        ("a119013a01", "expected array"),
+        ("a119013ba0", "non-empty map"),
+        ("a119013ba101a10060", "expected tstr"),
+        ("a119013ba2614ba1006061 6ba10060", "duplicate map key"),
+        ("a119013c80", "non-empty array"),
+        ("a119013f60", "expected bstr"),
+        ("a119014301", "expected map"),
+        ("a1190143a0", "map with renewal type"),
+        ("a1190143a10004", "out of range integer value"),
+        ("a1190143a2000100 02", "duplicate map key"),
+        ("a1190143a2000101 20", "out of range integer value"),
+        ("a1190143a3000104 6004 60", "duplicate map key"),
+    ];
+    for (claims_data, err_msg) in tests.iter() {
+        let data = hex::decode(claims_data.replace(' ', "")).unwrap();
+        let result = CatClaims::from_slice(&data);
+        expect_err(result, err_msg);
+    }
+
+    let claims = ClaimsSetBuilder::new()
+        .claim(iana::CwtClaimName::CatVersion, Value::from(1))
+        .claim(iana::CwtClaimName::CatVersion, Value::from(1))
+        .build();
+    expect_err(CatClaims::from_claims_set(claims), "duplicate map key");
+}
+
+#[test]
+fn test_uri_component() {
+    let request = CatRequest {
+        scheme: "https".to_owned(),
+        host: "cdn.example.com".to_owned(),
+        path: "/a/b/c.m3u8".to_owned(),
+        query: Some("x=1".to_owned()),
+        ..Default::default()
+    };
+    let tests = [
+        (UriComponent::Scheme, "https"),
+        (UriComponent::Host, "cdn.example.com"),
+        (UriComponent::Port, "443"),
+        (UriComponent::Path, "/a/b/c.m3u8"),
+        (UriComponent::Query, "x=1"),
+        (UriComponent::ParentPath, "/a/b"),
+        (UriComponent::Filename, "c.m3u8"),
+        (UriComponent::Stem, "c"),
+        (UriComponent::Extension, ".m3u8"),
+    ];
+    for (component, want) in tests {
+        assert_eq!(
+            request.uri_component(component),
+            want,
+            "for {:?}",
+            component
+        );
+    }
+
+    let request = CatRequest {
+        scheme: "http".to_owned(),
+        path: "/dir/file".to_owned(),
+        ..Default::default()
+    };
+    assert_eq!(request.uri_component(UriComponent::Port), "80");
+    assert_eq!(request.uri_component(UriComponent::Query), "");
+    assert_eq!(request.uri_component(UriComponent::Stem), "file");
+    assert_eq!(request.uri_component(UriComponent::Extension), "");
+
+    let request = CatRequest {
+        scheme: "https".to_owned(),
+        port: Some(8443),
+        ..Default::default()
+    };
+    assert_eq!(request.uri_component(UriComponent::Port), "8443");
+    assert_eq!(request.uri_component(UriComponent::Filename), "");
+}
+
+fn request() -> CatRequest {
+    CatRequest {
+        method: "GET".to_owned(),
+        scheme: "https".to_owned(),
+        host: "cdn.example.com".to_owned(),
+        port: None,
+        path: "/video/seg1.ts".to_owned(),
+        query: None,
+        headers: vec![
+            ("Accept".to_owned(), "*/*".to_owned()),
+            ("user-agent".to_owned(), "player/1.0".to_owned()),
+        ],
+        alpn: Some("h2".to_owned()),
+        geo_iso3166: Some("GB-LND".to_owned()),
+    }
+}
+
+#[test]
+fn test_check_request() {
+    use iana::CwtClaimName as N;
+    let mismatch = |name| Err(CatError::Mismatch(ClaimName::Assigned(name)));
+
+    // No request-matching claims place no restrictions.
+    let claims = CatClaimsBuilder::new()
+        .replay(Replay::Permitted)
+        .tls_public_key(vec![1; 4])
+        .build();
+    assert_eq!(claims.check_request(&request()), Ok(()));
+
+    let tests = vec![
+        (
+            CatClaimsBuilder::new()
+                .uri_match(
+                    UriComponent::Host,
+                    vec![
+                        Match::Suffix(".example.com".to_owned()),
+                        Match::Prefix("cdn.".to_owned()),
+                    ],
+                )
+                .uri_match(UriComponent::Port, vec![Match::Exact("443".to_owned())])
+                .uri_match(
+                    UriComponent::Extension,
+                    vec![Match::Exact(".ts".to_owned())],
+                )
+                .build(),
+            Ok(()),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .uri_match(
+                    UriComponent::Host,
+                    vec![
+                        Match::Suffix(".example.com".to_owned()),
+                        Match::Prefix("www.".to_owned()),
+                    ],
+                )
+                .build(),
+            mismatch(N::CatUri),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .uri_match(
+                    UriComponent::ParentPath,
+                    vec![Match::Contains("audio".to_owned())],
+                )
+                .build(),
+            mismatch(N::CatUri),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .method("HEAD".to_owned())
+                .method("GET".to_owned())
+                .build(),
+            Ok(()),
+        ),
+        (
+            CatClaimsBuilder::new().method("get".to_owned()).build(),
+            mismatch(N::CatMethod),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .alpn("http/1.1".to_owned())
+                .alpn("h2".to_owned())
+                .build(),
+            Ok(()),
+        ),
+        (
+            CatClaimsBuilder::new().alpn("h3".to_owned()).build(),
+            mismatch(N::CatAlpn),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .header_match(
+                    "User-Agent".to_owned(),
+                    vec![Match::Prefix("player/".to_owned())],
+                )
+                .build(),
+            Ok(()),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .header_match(
+                    "User-Agent".to_owned(),
+                    vec![Match::Prefix("browser/".to_owned())],
+                )
+                .build(),
+            mismatch(N::CatHeader),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .header_match("Referer".to_owned(), vec![Match::Contains("".to_owned())])
+                .build(),
+            mismatch(N::CatHeader),
+        ),
+        (
+            CatClaimsBuilder::new().geo_iso3166("GB".to_owned()).build(),
+            Ok(()),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .geo_iso3166("US".to_owned())
+                .geo_iso3166("gb-lnd".to_owned())
+                .build(),
+            Ok(()),
+        ),
+        (
+            CatClaimsBuilder::new().geo_iso3166("G".to_owned()).build(),
+            mismatch(N::CatGeoIso3166),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .geo_iso3166("GB-MAN".to_owned())
+                .build(),
+            mismatch(N::CatGeoIso3166),
+        ),
+        (
+            CatClaimsBuilder::new()
+                .uri_match(
+                    UriComponent::Path,
+                    vec![Match::Regex(Value::Text(".*".to_owned()))],
+                )
+                .build(),
+            Err(CatError::Unsupported(ClaimName::Assigned(N::CatUri))),
+        ),
+    ];
+    for (i, (claims, want)) in tests.into_iter().enumerate() {
+        assert_eq!(claims.check_request(&request()), want, "case {}", i);
+    }
+
+    // Request-matching claims fail for a request lacking the relevant information.
+    let mut req = request();
+    req.alpn = None;
+    req.geo_iso3166 = None;
+    let claims = CatClaimsBuilder::new().alpn("h2".to_owned()).build();
+    assert_eq!(claims.check_request(&req), mismatch(N::CatAlpn));
+    let claims = CatClaimsBuilder::new().geo_iso3166("GB".to_owned()).build();
+    assert_eq!(claims.check_request(&req), mismatch(N::CatGeoIso3166));
+
+    assert_eq!(
+        CatError::Mismatch(ClaimName::Assigned(N::CatMethod)).to_string(),
+        "request does not match claim Assigned(CatMethod)"
+    );
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn test_check_request_hash() {
+    use iana::CwtClaimName as N;
+    // SHA-256 and SHA-512/256 of "seg1.ts".
+    let sha256 = crate::crypto::sha256(b"seg1.ts");
+    let sha512_256 = {
+        use sha2::Digest;
+        sha2::Sha512_256::digest(b"seg1.ts").to_vec()
+    };
+    let claims = CatClaimsBuilder::new()
+        .uri_match(
+            UriComponent::Filename,
+            vec![Match::Sha256(sha256), Match::Sha512_256(sha512_256.clone())],
+        )
+        .build();
+    assert_eq!(claims.check_request(&request()), Ok(()));
+
+    let claims = CatClaimsBuilder::new()
+        .header_match("Accept".to_owned(), vec![Match::Sha512_256(sha512_256)])
+        .build();
+    assert_eq!(
+        claims.check_request(&request()),
+        Err(CatError::Mismatch(ClaimName::Assigned(N::CatHeader)))
+    );
+}
+
+#[cfg(not(feature = "sha2"))]
+#[test]
+fn test_check_request_hash() {
+    use iana::CwtClaimName as N;
+    let claims = CatClaimsBuilder::new()
+        .uri_match(UriComponent::Filename, vec![Match::Sha256(vec![1; 32])])
+        .build();
+    assert_eq!(
+        claims.check_request(&request()),
+        Err(CatError::Unsupported(ClaimName::Assigned(N::CatUri)))
+    );
+}
@@ -0,0 +1,181 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Elliptic curve point handling for EC2 keys, built on the RustCrypto curve crates.
+
+use crate::{cbor::value::Value, iana, iana::EnumI64, CoseKey, Label, ToSec1OctetStringError};
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use elliptic_curve::{
+    sec1::{EncodedPoint, FromEncodedPoint, ModulusSize, ToEncodedPoint},
+    AffinePoint, CurveArithmetic, FieldBytesSize,
+};
+
+/// Error type for failures in handling EC2 key points.
+pub enum EcError {
+    /// The [`CoseKey`] does not hold an EC2 public key point.
+    InvalidKey(ToSec1OctetStringError),
+    /// The [`CoseKey`] does not hold a recognized elliptic curve identifier.
+    InvalidCurve,
+    /// The elliptic curve is not supported by the enabled crate features.
+    UnsupportedCurve(iana::EllipticCurve),
+    /// The coordinates do not describe a point on the curve.
+    InvalidPoint,
+}
+
+impl core::error::Error for EcError {}
+
+impl core::fmt::Debug for EcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl core::fmt::Display for EcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl EcError {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            EcError::InvalidKey(e) => write!(f, "invalid EC2 key: {e}"),
+            EcError::InvalidCurve => write!(f, "missing or unrecognized curve"),
+            EcError::UnsupportedCurve(crv) => write!(f, "unsupported curve {crv:?}"),
+            EcError::InvalidPoint => write!(f, "point not on curve"),
+        }
+    }
+}
+
+impl From<ToSec1OctetStringError> for EcError {
+    fn from(e: ToSec1OctetStringError) -> Self {
+        EcError::InvalidKey(e)
+    }
+}
+
+/// Decode a SEC1-encoded point on curve `C`, checking that it is on the curve, and return its
+/// uncompressed SEC1 encoding.
+fn uncompressed<C>(sec1: &[u8]) -> Result<Vec<u8>, EcError>
+where
+    C: CurveArithmetic,
+    FieldBytesSize<C>: ModulusSize,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+{
+    let point = EncodedPoint::<C>::from_bytes(sec1).map_err(|_e| EcError::InvalidPoint)?;
+    let point: Option<AffinePoint<C>> = AffinePoint::<C>::from_encoded_point(&point).into();
+    let point = point.ok_or(EcError::InvalidPoint)?;
+    Ok(point.to_encoded_point(false).as_bytes().to_vec())
+}
+
+/// Derive the public key point for private key `d` on curve `C`, and return its uncompressed SEC1
+/// encoding.
+fn public_point<C>(d: &[u8]) -> Option<Vec<u8>>
+where
+    C: CurveArithmetic,
+    FieldBytesSize<C>: ModulusSize,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+{
+    let secret = elliptic_curve::SecretKey::<C>::from_slice(d).ok()?;
+    Some(
+        secret
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec(),
+    )
+}
+
+/// Return the curve of an EC2 key.
+fn curve(key: &CoseKey) -> Result<iana::EllipticCurve, EcError> {
+    key.param(iana::Ec2KeyParameter::Crv as i64)
+        .and_then(Value::as_integer)
+        .and_then(|crv| i64::try_from(crv).ok())
+        .and_then(iana::EllipticCurve::from_i64)
+        .ok_or(EcError::InvalidCurve)
+}
+
+/// Return the uncompressed SEC1 encoding of the public key point of an EC2 key, decompressing the
+/// point if the key holds just the sign of the y-coordinate.  The point is checked to be on the
+/// key's curve.
+///
+/// The P-256, P-384, P-521 and secp256k1 curves are supported, when the corresponding `p256`,
+/// `p384`, `p521` or `k256` feature is enabled.
+pub fn ec2_uncompressed_point(key: &CoseKey) -> Result<Vec<u8>, EcError> {
+    let sec1 = key.to_sec1_octet_string()?;
+    match curve(key)? {
+        #[cfg(feature = "p256")]
+        iana::EllipticCurve::P_256 => uncompressed::<p256::NistP256>(&sec1),
+        #[cfg(feature = "p384")]
+        iana::EllipticCurve::P_384 => uncompressed::<p384::NistP384>(&sec1),
+        #[cfg(feature = "p521")]
+        iana::EllipticCurve::P_521 => uncompressed::<p521::NistP521>(&sec1),
+        #[cfg(feature = "k256")]
+        iana::EllipticCurve::Secp256k1 => uncompressed::<k256::Secp256k1>(&sec1),
+        crv => Err(EcError::UnsupportedCurve(crv)),
+    }
+}
+
+/// Derive the public key point of an EC2 key from its private key, and return its uncompressed SEC1
+/// encoding.  Returns `None` if the key has no valid private key on a supported curve.
+pub(crate) fn ec2_derive_public_point(key: &CoseKey) -> Option<Vec<u8>> {
+    let d = key
+        .param(iana::Ec2KeyParameter::D as i64)
+        .and_then(Value::as_bytes)?;
+    match curve(key).ok()? {
+        #[cfg(feature = "p256")]
+        iana::EllipticCurve::P_256 => public_point::<p256::NistP256>(d),
+        #[cfg(feature = "p384")]
+        iana::EllipticCurve::P_384 => public_point::<p384::NistP384>(d),
+        #[cfg(feature = "p521")]
+        iana::EllipticCurve::P_521 => public_point::<p521::NistP521>(d),
+        #[cfg(feature = "k256")]
+        iana::EllipticCurve::Secp256k1 => public_point::<k256::Secp256k1>(d),
+        _ => None,
+    }
+}
+
+/// Check that the public key point of an EC2 key is on the key's curve.
+pub fn ec2_check_point(key: &CoseKey) -> Result<(), EcError> {
+    ec2_uncompressed_point(key).map(|_| ())
+}
+
+/// Return a copy of an EC2 key in which the public key point is held in uncompressed form, with
+/// the y-coordinate decompressed if the key holds just its sign.  The point is checked to be on
+/// the key's curve.
+pub fn ec2_decompress(key: &CoseKey) -> Result<CoseKey, EcError> {
+    let point = ec2_uncompressed_point(key)?;
+    let len = (point.len() - 1) / 2;
+    Ok(with_y(key, Value::Bytes(point[1 + len..].to_vec())))
+}
+
+/// Return a copy of an EC2 key in which the public key point is held in compressed form, with just
+/// the sign of the y-coordinate.  The point is checked to be on the key's curve.
+pub fn ec2_compress(key: &CoseKey) -> Result<CoseKey, EcError> {
+    let point = ec2_uncompressed_point(key)?;
+    let y_sign = point.last().is_some_and(|b| b & 0x01 != 0);
+    Ok(with_y(key, Value::Bool(y_sign)))
+}
+
+/// Return a copy of `key` with the (first) y-coordinate parameter replaced.
+fn with_y(key: &CoseKey, y: Value) -> CoseKey {
+    let mut key = key.clone();
+    let label = Label::Int(iana::Ec2KeyParameter::Y as i64);
+    if let Some((_, value)) = key.params.iter_mut().find(|(l, _)| *l == label) {
+        *value = y;
+    }
+    key
+}
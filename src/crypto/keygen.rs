@@ -0,0 +1,315 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Key generation.
+
+use crate::{cbor::value::Value, iana, util::wipe, CoseKey, CoseKeyBuilder, ThumbprintError};
+use alloc::vec;
+use rand_core::CryptoRngCore;
+
+/// Source of the key identifier for a generated key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyIdSource {
+    /// Random key identifier of the given length.
+    Random(usize),
+    /// RFC 9679 COSE Key Thumbprint of the key, using SHA-256.  Not available for symmetric keys,
+    /// as the thumbprint of a symmetric key is a hash of the secret key itself.
+    Thumbprint,
+}
+
+/// Error type for failures in key generation.
+pub enum KeyGenError {
+    /// The algorithm is not supported for the requested key type.
+    UnsupportedAlgorithm(iana::Algorithm),
+    /// The elliptic curve is not supported for the requested key type, or by the enabled crate
+    /// features.
+    UnsupportedCurve(iana::EllipticCurve),
+    /// The key identifier could not be derived.
+    Thumbprint(ThumbprintError),
+    /// A thumbprint key identifier was requested for a symmetric key, which would reveal a hash of
+    /// the secret key.
+    SymmetricThumbprint,
+    /// RSA key generation failed.
+    #[cfg(feature = "rsa")]
+    Rsa(super::RsaError),
+}
+
+impl core::error::Error for KeyGenError {}
+
+impl core::fmt::Debug for KeyGenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl core::fmt::Display for KeyGenError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl KeyGenError {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeyGenError::UnsupportedAlgorithm(alg) => write!(f, "unsupported algorithm {alg:?}"),
+            KeyGenError::UnsupportedCurve(crv) => write!(f, "unsupported curve {crv:?}"),
+            KeyGenError::Thumbprint(e) => write!(f, "failed to derive key ID: {e}"),
+            KeyGenError::SymmetricThumbprint => {
+                write!(f, "thumbprint key ID not allowed for symmetric key")
+            }
+            #[cfg(feature = "rsa")]
+            KeyGenError::Rsa(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Return the key operations that a generated key for `alg` permits.
+fn key_ops(alg: iana::Algorithm) -> Result<[iana::KeyOperation; 2], KeyGenError> {
+    use iana::{Algorithm::*, KeyOperation::*};
+    match alg {
+        ES256 | ES384 | ES512 | ES256K | ESP256 | ESP384 | ESP512 | EdDSA | Ed25519 | Ed448
+        | PS256 | PS384 | PS512 | RS256 | RS384 | RS512 | ML_DSA_44 | ML_DSA_65 | ML_DSA_87 => {
+            Ok([Sign, Verify])
+        }
+        ECDH_ES_HKDF_256 | ECDH_ES_HKDF_512 | ECDH_SS_HKDF_256 | ECDH_SS_HKDF_512
+        | ECDH_ES_A128KW | ECDH_ES_A192KW | ECDH_ES_A256KW | ECDH_SS_A128KW | ECDH_SS_A192KW
+        | ECDH_SS_A256KW => Ok([DeriveKey, DeriveBits]),
+        A128GCM | A192GCM | A256GCM | ChaCha20Poly1305 | AES_CCM_16_64_128 | AES_CCM_16_64_256
+        | AES_CCM_64_64_128 | AES_CCM_64_64_256 | AES_CCM_16_128_128 | AES_CCM_16_128_256
+        | AES_CCM_64_128_128 | AES_CCM_64_128_256 => Ok([Encrypt, Decrypt]),
+        HMAC_256_64 | HMAC_256_256 | HMAC_384_384 | HMAC_512_512 | AES_MAC_128_64
+        | AES_MAC_256_64 | AES_MAC_128_128 | AES_MAC_256_128 => Ok([MacCreate, MacVerify]),
+        A128KW
+        | A192KW
+        | A256KW
+        | RSAES_OAEP_RFC_8017_default
+        | RSAES_OAEP_SHA_256
+        | RSAES_OAEP_SHA_512 => Ok([WrapKey, UnwrapKey]),
+        alg => Err(KeyGenError::UnsupportedAlgorithm(alg)),
+    }
+}
+
+/// Return whether `alg` is an ECDH key agreement algorithm.
+fn is_ecdh(alg: iana::Algorithm) -> bool {
+    key_ops(alg).is_ok_and(|ops| ops[0] == iana::KeyOperation::DeriveKey)
+}
+
+/// Set the algorithm, key operations and key identifier of a generated key.
+fn finish<R: CryptoRngCore>(
+    rng: &mut R,
+    builder: CoseKeyBuilder,
+    alg: iana::Algorithm,
+    kid: KeyIdSource,
+) -> Result<CoseKey, KeyGenError> {
+    let [op1, op2] = key_ops(alg)?;
+    let mut key = builder
+        .algorithm(alg)
+        .add_key_op(op1)
+        .add_key_op(op2)
+        .build();
+    key.key_id = match kid {
+        KeyIdSource::Random(len) => {
+            let mut key_id = vec![0; len];
+            rng.fill_bytes(&mut key_id);
+            key_id
+        }
+        KeyIdSource::Thumbprint => key.thumbprint_sha256().map_err(KeyGenError::Thumbprint)?,
+    };
+    Ok(key)
+}
+
+/// Generate an EC2 key pair on curve `crv` for use with `alg`, which must be an ECDSA or ECDH
+/// algorithm compatible with the curve: `ES256`/`ESP256` for P-256, `ES384`/`ESP384` for P-384,
+/// `ES512`/`ESP512` for P-521, `ES256K` for secp256k1, or an ECDH algorithm for one of the NIST
+/// curves.
+///
+/// The P-256, P-384, P-521 and secp256k1 curves are supported, when the corresponding `p256`,
+/// `p384`, `p521` or `k256` feature is enabled.
+pub fn generate_ec2_key<R: CryptoRngCore>(
+    rng: &mut R,
+    crv: iana::EllipticCurve,
+    alg: iana::Algorithm,
+    kid: KeyIdSource,
+) -> Result<CoseKey, KeyGenError> {
+    use iana::{Algorithm::*, EllipticCurve::*};
+    let ok = match (alg, crv) {
+        (ES256 | ESP256, P_256) | (ES384 | ESP384, P_384) | (ES512 | ESP512, P_521) => true,
+        (ES256K, Secp256k1) => true,
+        (alg, P_256 | P_384 | P_521) => is_ecdh(alg),
+        (_, Secp256k1) => false,
+        (_, crv) => return Err(KeyGenError::UnsupportedCurve(crv)),
+    };
+    if !ok {
+        return Err(KeyGenError::UnsupportedAlgorithm(alg));
+    }
+    let builder = match crv {
+        #[cfg(feature = "p256")]
+        P_256 => Ok(ec2_key_pair::<p256::NistP256, R>(rng, crv)),
+        #[cfg(feature = "p384")]
+        P_384 => Ok(ec2_key_pair::<p384::NistP384, R>(rng, crv)),
+        #[cfg(feature = "p521")]
+        P_521 => Ok(ec2_key_pair::<p521::NistP521, R>(rng, crv)),
+        #[cfg(feature = "k256")]
+        Secp256k1 => Ok(ec2_key_pair::<k256::Secp256k1, R>(rng, crv)),
+        crv => Err(KeyGenError::UnsupportedCurve(crv)),
+    };
+    finish(rng, builder?, alg, kid)
+}
+
+/// Generate a random key pair on curve `C`.
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521", feature = "k256"))]
+fn ec2_key_pair<C, R>(rng: &mut R, crv: iana::EllipticCurve) -> CoseKeyBuilder
+where
+    C: elliptic_curve::CurveArithmetic,
+    elliptic_curve::FieldBytesSize<C>: elliptic_curve::sec1::ModulusSize,
+    elliptic_curve::AffinePoint<C>:
+        elliptic_curve::sec1::FromEncodedPoint<C> + elliptic_curve::sec1::ToEncodedPoint<C>,
+    R: CryptoRngCore,
+{
+    use elliptic_curve::sec1::ToEncodedPoint;
+    let secret = elliptic_curve::SecretKey::<C>::random(rng);
+    let point = secret.public_key().to_encoded_point(false);
+    let point = &point.as_bytes()[1..];
+    let (x, y) = point.split_at(point.len() / 2);
+    CoseKeyBuilder::new_ec2_priv_key(crv, x.to_vec(), y.to_vec(), secret.to_bytes().to_vec())
+}
+
+/// Generate an OKP key pair on curve `crv` for use with `alg`: an `EdDSA` or `Ed25519` signing key
+/// for Ed25519 (with the `ed25519` feature), or an ECDH key for X25519 (with the `x25519`
+/// feature).
+pub fn generate_okp_key<R: CryptoRngCore>(
+    rng: &mut R,
+    crv: iana::EllipticCurve,
+    alg: iana::Algorithm,
+    kid: KeyIdSource,
+) -> Result<CoseKey, KeyGenError> {
+    let ok = match crv {
+        iana::EllipticCurve::Ed25519 => {
+            matches!(alg, iana::Algorithm::EdDSA | iana::Algorithm::Ed25519)
+        }
+        iana::EllipticCurve::Ed448 => {
+            matches!(alg, iana::Algorithm::EdDSA | iana::Algorithm::Ed448)
+        }
+        iana::EllipticCurve::X25519 | iana::EllipticCurve::X448 => is_ecdh(alg),
+        crv => return Err(KeyGenError::UnsupportedCurve(crv)),
+    };
+    if !ok {
+        return Err(KeyGenError::UnsupportedAlgorithm(alg));
+    }
+    let key_pair: Result<([u8; 32], [u8; 32]), KeyGenError> = match crv {
+        #[cfg(feature = "ed25519")]
+        iana::EllipticCurve::Ed25519 => {
+            let secret = ed25519_dalek::SigningKey::generate(rng);
+            Ok((secret.verifying_key().to_bytes(), secret.to_bytes()))
+        }
+        #[cfg(feature = "x25519")]
+        iana::EllipticCurve::X25519 => {
+            let secret = x25519_dalek::StaticSecret::random_from_rng(&mut *rng);
+            Ok((
+                x25519_dalek::PublicKey::from(&secret).to_bytes(),
+                secret.to_bytes(),
+            ))
+        }
+        crv => Err(KeyGenError::UnsupportedCurve(crv)),
+    };
+    let (x, mut d) = key_pair?;
+    let builder = CoseKeyBuilder::new_okp_key()
+        .param(iana::OkpKeyParameter::Crv as i64, Value::from(crv as i64))
+        .param(iana::OkpKeyParameter::X as i64, Value::Bytes(x.to_vec()))
+        .param(iana::OkpKeyParameter::D as i64, Value::Bytes(d.to_vec()));
+    wipe(&mut d);
+    finish(rng, builder, alg, kid)
+}
+
+/// Return the key length in bytes for a symmetric algorithm.
+fn symmetric_key_len(alg: iana::Algorithm) -> Option<usize> {
+    use iana::Algorithm::*;
+    match alg {
+        A128GCM | AES_CCM_16_64_128 | AES_CCM_64_64_128 | AES_CCM_16_128_128
+        | AES_CCM_64_128_128 | AES_MAC_128_64 | AES_MAC_128_128 | A128KW => Some(16),
+        A192GCM | A192KW => Some(24),
+        A256GCM | ChaCha20Poly1305 | AES_CCM_16_64_256 | AES_CCM_64_64_256 | AES_CCM_16_128_256
+        | AES_CCM_64_128_256 | AES_MAC_256_64 | AES_MAC_256_128 | A256KW | HMAC_256_64
+        | HMAC_256_256 => Some(32),
+        HMAC_384_384 => Some(48),
+        HMAC_512_512 => Some(64),
+        _ => None,
+    }
+}
+
+/// Generate a symmetric key for use with `alg`, which must be an AEAD, MAC or key wrap algorithm.
+/// The key length is determined by the algorithm.  The key identifier cannot be a
+/// [`KeyIdSource::Thumbprint`].
+pub fn generate_symmetric_key<R: CryptoRngCore>(
+    rng: &mut R,
+    alg: iana::Algorithm,
+    kid: KeyIdSource,
+) -> Result<CoseKey, KeyGenError> {
+    let len = symmetric_key_len(alg).ok_or(KeyGenError::UnsupportedAlgorithm(alg))?;
+    if kid == KeyIdSource::Thumbprint {
+        return Err(KeyGenError::SymmetricThumbprint);
+    }
+    let mut k = vec![0; len];
+    rng.fill_bytes(&mut k);
+    finish(rng, CoseKeyBuilder::new_symmetric_key(k), alg, kid)
+}
+
+/// Generate an RSA key pair with a modulus of `bits` bits for use with `alg`, which must be an
+/// RSASSA-PSS, RSASSA-PKCS1-v1_5 or RSAES-OAEP algorithm.
+#[cfg(feature = "rsa")]
+pub fn generate_rsa_key<R: CryptoRngCore>(
+    rng: &mut R,
+    bits: usize,
+    alg: iana::Algorithm,
+    kid: KeyIdSource,
+) -> Result<CoseKey, KeyGenError> {
+    use iana::Algorithm::*;
+    if !matches!(
+        alg,
+        PS256
+            | PS384
+            | PS512
+            | RS256
+            | RS384
+            | RS512
+            | RSAES_OAEP_RFC_8017_default
+            | RSAES_OAEP_SHA_256
+            | RSAES_OAEP_SHA_512
+    ) {
+        return Err(KeyGenError::UnsupportedAlgorithm(alg));
+    }
+    let key = ::rsa::RsaPrivateKey::new(rng, bits)
+        .map_err(|e| KeyGenError::Rsa(super::RsaError::Rsa(e)))?;
+    finish(rng, CoseKeyBuilder::from(&key), alg, kid)
+}
+
+/// Generate an ML-DSA key pair for `variant`.  The private key is held as a 32-byte seed.
+#[cfg(feature = "mldsa")]
+pub fn generate_mldsa_key<R: CryptoRngCore>(
+    rng: &mut R,
+    variant: crate::MlDsaVariant,
+    kid: KeyIdSource,
+) -> Result<CoseKey, KeyGenError> {
+    let mut seed = [0u8; 32];
+    rng.fill_bytes(&mut seed);
+    let public = super::mldsa_public_key(variant, &seed);
+    let builder = CoseKeyBuilder::new_mldsa_pub_key(variant, public).param(
+        iana::AkpKeyParameter::Priv as i64,
+        Value::Bytes(seed.to_vec()),
+    );
+    wipe(&mut seed);
+    finish(rng, builder, variant.into(), kid)
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! ML-DSA key derivation from a private key seed, built on the `ml-dsa` crate.
+//!
+//! Only key material is derived; signing and verification are left to the caller.
+
+use crate::{util::wipe, MlDsaVariant};
+use alloc::vec::Vec;
+use ml_dsa::{
+    ExpandedSigningKey, Keypair, MlDsa44, MlDsa65, MlDsa87, MlDsaParams, Seed, SigningKey,
+};
+
+/// Derive the encoded ML-DSA public key from a 32-byte private key seed.
+pub fn mldsa_public_key(variant: MlDsaVariant, seed: &[u8; 32]) -> Vec<u8> {
+    fn public<P: MlDsaParams>(seed: &Seed) -> Vec<u8> {
+        SigningKey::<P>::from_seed(seed)
+            .verifying_key()
+            .encode()
+            .to_vec()
+    }
+    let mut seed = Seed::from(*seed);
+    let public = match variant {
+        MlDsaVariant::MlDsa44 => public::<MlDsa44>(&seed),
+        MlDsaVariant::MlDsa65 => public::<MlDsa65>(&seed),
+        MlDsaVariant::MlDsa87 => public::<MlDsa87>(&seed),
+    };
+    wipe(&mut seed);
+    public
+}
+
+/// Derive the encoded ML-DSA expanded private key (the `skEncode` form of FIPS 204) from a 32-byte
+/// private key seed.
+///
+/// The expanded form is only needed to check keys held in that form against their seed (as in
+/// the `both` form of an RFC 9881 `ML-DSA-PrivateKey`); the seed is the preferred form.
+pub fn mldsa_expanded_private_key(variant: MlDsaVariant, seed: &[u8; 32]) -> Vec<u8> {
+    // `to_expanded()` is deprecated to steer callers towards seeds, but is the only way to get the
+    // encoded expanded key.
+    #[allow(deprecated)]
+    fn expanded<P: MlDsaParams>(seed: &Seed) -> Vec<u8> {
+        let mut bytes = ExpandedSigningKey::<P>::from_seed(seed).to_expanded();
+        let expanded = bytes.to_vec();
+        wipe(bytes.as_mut_slice());
+        expanded
+    }
+    let mut seed = Seed::from(*seed);
+    let expanded = match variant {
+        MlDsaVariant::MlDsa44 => expanded::<MlDsa44>(&seed),
+        MlDsaVariant::MlDsa65 => expanded::<MlDsa65>(&seed),
+        MlDsaVariant::MlDsa87 => expanded::<MlDsa87>(&seed),
+    };
+    wipe(&mut seed);
+    expanded
+}
@@ -0,0 +1,77 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Optional cryptographic backends.
+//!
+//! The core types in this crate do not perform any cryptographic operations themselves; instead,
+//! the caller provides closures that do the work.  This module holds implementations of those
+//! operations that are built on external crates, each enabled by a crate feature:
+//!
+//! - `rsa`: RSASSA-PSS (`PS256`/`PS384`/`PS512`), RSASSA-PKCS1-v1_5 (`RS256`/`RS384`/`RS512`) and
+//!   RSAES-OAEP key transport, as per RFC 8230.  RSAES-OAEP decryption also needs the `rsa-decrypt`
+//!   feature, as the `rsa` crate is affected by RUSTSEC-2023-0071.
+//! - `sha2`: SHA-256 hashing, as used for RFC 9679 COSE Key Thumbprints.
+//! - `hkdf`: HKDF-SHA-256 key derivation, as used for OSCORE security contexts.
+//! - `p256`, `p384`, `p521`, `k256`: EC2 public key point decompression, validation and derivation,
+//!   for the P-256, P-384, P-521 and secp256k1 curves respectively.
+//! - `ed25519`, `x25519`: OKP public key derivation, for the Ed25519 and X25519 curves.
+//! - `mldsa`: ML-DSA public and expanded private key derivation from a private key seed.
+//! - `keygen`: generation of keys of all types, using the backends enabled by the other features.
+
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521", feature = "k256"))]
+mod ec;
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521", feature = "k256"))]
+pub use self::ec::*;
+#[cfg(feature = "keygen")]
+mod keygen;
+#[cfg(feature = "keygen")]
+pub use self::keygen::*;
+#[cfg(feature = "mldsa")]
+mod mldsa;
+#[cfg(feature = "mldsa")]
+pub use self::mldsa::*;
+mod public;
+pub(crate) use self::public::derive_public_params;
+#[cfg(feature = "rsa")]
+mod rsa;
+#[cfg(feature = "rsa")]
+pub use self::rsa::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Calculate the SHA-256 hash of `data`.
+#[cfg(feature = "sha2")]
+pub fn sha256(data: &[u8]) -> alloc::vec::Vec<u8> {
+    use sha2::Digest;
+    sha2::Sha256::digest(data).to_vec()
+}
+
+/// Derive `len` bytes of output keying material from `salt`, `ikm` and `info` using HKDF-SHA-256
+/// (RFC 5869), or return `None` if `len` is too large.
+#[cfg(feature = "hkdf")]
+pub fn hkdf_sha256(
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Option<alloc::vec::Vec<u8>> {
+    let mut okm = alloc::vec![0; len];
+    hkdf::Hkdf::<sha2::Sha256>::new(Some(salt), ikm)
+        .expand(info, &mut okm)
+        .ok()?;
+    Some(okm)
+}
@@ -0,0 +1,107 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Derivation of public key parameters from private keys, for [`CoseKey::to_public_key`].
+
+use crate::{cbor::value::Value, CoseKey, Label};
+use alloc::vec::Vec;
+
+/// Derive the public key parameters of `key` from its private key material, as far as the enabled
+/// crate features allow.  Returns an empty list if nothing can be derived.
+pub(crate) fn derive_public_params(key: &CoseKey) -> Vec<(Label, Value)> {
+    let derived = match key.kty {
+        #[cfg(any(feature = "p256", feature = "p384", feature = "p521", feature = "k256"))]
+        crate::KeyType::Assigned(crate::iana::KeyType::EC2) => ec2(key),
+        #[cfg(any(feature = "ed25519", feature = "x25519"))]
+        crate::KeyType::Assigned(crate::iana::KeyType::OKP) => okp(key),
+        #[cfg(feature = "mldsa")]
+        crate::KeyType::Assigned(crate::iana::KeyType::AKP) => akp(key),
+        _ => None,
+    };
+    derived.unwrap_or_default()
+}
+
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521", feature = "k256"))]
+fn ec2(key: &CoseKey) -> Option<Vec<(Label, Value)>> {
+    use crate::iana::Ec2KeyParameter;
+    let point = super::ec::ec2_derive_public_point(key)?;
+    let (x, y) = point[1..].split_at((point.len() - 1) / 2);
+    Some(alloc::vec![
+        (
+            Label::Int(Ec2KeyParameter::X as i64),
+            Value::Bytes(x.to_vec())
+        ),
+        (
+            Label::Int(Ec2KeyParameter::Y as i64),
+            Value::Bytes(y.to_vec())
+        ),
+    ])
+}
+
+#[cfg(any(feature = "ed25519", feature = "x25519"))]
+fn okp(key: &CoseKey) -> Option<Vec<(Label, Value)>> {
+    use crate::{iana, iana::EnumI64, util::wipe};
+    use core::convert::{TryFrom, TryInto};
+    let crv = key
+        .param(iana::OkpKeyParameter::Crv as i64)
+        .and_then(Value::as_integer)
+        .and_then(|crv| i64::try_from(crv).ok())
+        .and_then(iana::EllipticCurve::from_i64)?;
+    let mut d: [u8; 32] = key
+        .param(iana::OkpKeyParameter::D as i64)
+        .and_then(Value::as_bytes)?
+        .as_slice()
+        .try_into()
+        .ok()?;
+    let x = match crv {
+        #[cfg(feature = "ed25519")]
+        iana::EllipticCurve::Ed25519 => Some(
+            ed25519_dalek::SigningKey::from_bytes(&d)
+                .verifying_key()
+                .to_bytes(),
+        ),
+        #[cfg(feature = "x25519")]
+        iana::EllipticCurve::X25519 => {
+            Some(x25519_dalek::PublicKey::from(&x25519_dalek::StaticSecret::from(d)).to_bytes())
+        }
+        _ => None,
+    };
+    wipe(&mut d);
+    Some(alloc::vec![(
+        Label::Int(iana::OkpKeyParameter::X as i64),
+        Value::Bytes(x?.to_vec()),
+    )])
+}
+
+#[cfg(feature = "mldsa")]
+fn akp(key: &CoseKey) -> Option<Vec<(Label, Value)>> {
+    use crate::{iana, Algorithm, MlDsaVariant};
+    use core::convert::{TryFrom, TryInto};
+    let variant = match key.alg {
+        Some(Algorithm::Assigned(alg)) => MlDsaVariant::try_from(alg).ok()?,
+        _ => return None,
+    };
+    let seed: &[u8; 32] = key
+        .param(iana::AkpKeyParameter::Priv as i64)
+        .and_then(Value::as_bytes)?
+        .as_slice()
+        .try_into()
+        .ok()?;
+    Some(alloc::vec![(
+        Label::Int(iana::AkpKeyParameter::Pub as i64),
+        Value::Bytes(super::mldsa_public_key(variant, seed)),
+    )])
+}
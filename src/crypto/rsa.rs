@@ -0,0 +1,365 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! RSA backend, built on the `rsa` crate.
+
+use crate::{
+    cbor::value::Value, iana, util::wipe, Algorithm, CoseKey, CoseKeyBuilder, RsaKeyError,
+    RsaOtherPrimeInfo, RsaPrivateComponents,
+};
+use ::rsa::{
+    rand_core::CryptoRngCore,
+    traits::{PrivateKeyParts, PublicKeyParts},
+    BigUint, Oaep, Pkcs1v15Sign, Pss, RsaPrivateKey, RsaPublicKey,
+};
+use alloc::{vec, vec::Vec};
+use core::convert::TryFrom;
+use sha2::Digest;
+
+/// Error type for failures in the RSA backend.
+pub enum RsaError {
+    /// The [`CoseKey`] does not hold a valid RSA key.
+    InvalidKey(RsaKeyError),
+    /// The algorithm is not supported for the requested operation.
+    UnsupportedAlgorithm(iana::Algorithm),
+    /// The [`CoseKey`] is restricted to a different algorithm.
+    AlgorithmMismatch,
+    /// The [`CoseKey`] does not hold private key material.
+    NotPrivateKey,
+    /// The underlying RSA operation failed.
+    Rsa(::rsa::Error),
+}
+
+impl core::error::Error for RsaError {}
+
+impl core::fmt::Debug for RsaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl core::fmt::Display for RsaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl RsaError {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RsaError::InvalidKey(e) => write!(f, "invalid RSA key: {e}"),
+            RsaError::UnsupportedAlgorithm(alg) => write!(f, "unsupported algorithm {alg:?}"),
+            RsaError::AlgorithmMismatch => write!(f, "key restricted to different algorithm"),
+            RsaError::NotPrivateKey => write!(f, "not an RSA private key"),
+            RsaError::Rsa(e) => write!(f, "RSA failure: {e}"),
+        }
+    }
+}
+
+impl From<RsaKeyError> for RsaError {
+    fn from(e: RsaKeyError) -> Self {
+        RsaError::InvalidKey(e)
+    }
+}
+
+impl From<::rsa::Error> for RsaError {
+    fn from(e: ::rsa::Error) -> Self {
+        RsaError::Rsa(e)
+    }
+}
+
+/// Hash functions used by the RSA signature algorithms.
+#[derive(Clone, Copy)]
+enum RsaHash {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl RsaHash {
+    fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            RsaHash::Sha256 => sha2::Sha256::digest(data).to_vec(),
+            RsaHash::Sha384 => sha2::Sha384::digest(data).to_vec(),
+            RsaHash::Sha512 => sha2::Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+/// RSA signature schemes.
+enum SignatureScheme {
+    Pss(RsaHash),
+    Pkcs1v15(RsaHash),
+}
+
+impl SignatureScheme {
+    fn new(alg: iana::Algorithm) -> Result<Self, RsaError> {
+        match alg {
+            iana::Algorithm::PS256 => Ok(SignatureScheme::Pss(RsaHash::Sha256)),
+            iana::Algorithm::PS384 => Ok(SignatureScheme::Pss(RsaHash::Sha384)),
+            iana::Algorithm::PS512 => Ok(SignatureScheme::Pss(RsaHash::Sha512)),
+            iana::Algorithm::RS256 => Ok(SignatureScheme::Pkcs1v15(RsaHash::Sha256)),
+            iana::Algorithm::RS384 => Ok(SignatureScheme::Pkcs1v15(RsaHash::Sha384)),
+            iana::Algorithm::RS512 => Ok(SignatureScheme::Pkcs1v15(RsaHash::Sha512)),
+            alg => Err(RsaError::UnsupportedAlgorithm(alg)),
+        }
+    }
+
+    fn hash(&self) -> RsaHash {
+        match self {
+            SignatureScheme::Pss(h) | SignatureScheme::Pkcs1v15(h) => *h,
+        }
+    }
+
+    /// PSS salt length is the hash length, as per RFC 8230 section 2.
+    fn pss(hash: RsaHash) -> Pss {
+        match hash {
+            RsaHash::Sha256 => Pss::new::<sha2::Sha256>(),
+            RsaHash::Sha384 => Pss::new::<sha2::Sha384>(),
+            RsaHash::Sha512 => Pss::new::<sha2::Sha512>(),
+        }
+    }
+
+    fn pkcs1v15(hash: RsaHash) -> Pkcs1v15Sign {
+        match hash {
+            RsaHash::Sha256 => Pkcs1v15Sign::new::<sha2::Sha256>(),
+            RsaHash::Sha384 => Pkcs1v15Sign::new::<sha2::Sha384>(),
+            RsaHash::Sha512 => Pkcs1v15Sign::new::<sha2::Sha512>(),
+        }
+    }
+}
+
+/// Return the OAEP padding scheme for a key transport algorithm, as per RFC 8230 section 3.
+fn oaep(alg: iana::Algorithm) -> Result<Oaep, RsaError> {
+    match alg {
+        iana::Algorithm::RSAES_OAEP_RFC_8017_default => Ok(Oaep::new::<sha1::Sha1>()),
+        iana::Algorithm::RSAES_OAEP_SHA_256 => Ok(Oaep::new::<sha2::Sha256>()),
+        iana::Algorithm::RSAES_OAEP_SHA_512 => Ok(Oaep::new::<sha2::Sha512>()),
+        alg => Err(RsaError::UnsupportedAlgorithm(alg)),
+    }
+}
+
+/// Check that `key` is not restricted to an algorithm other than `alg`.
+fn check_alg(key: &CoseKey, alg: iana::Algorithm) -> Result<(), RsaError> {
+    match &key.alg {
+        Some(key_alg) if *key_alg != Algorithm::Assigned(alg) => Err(RsaError::AlgorithmMismatch),
+        _ => Ok(()),
+    }
+}
+
+/// Return the (already-checked) integer parameter `param` from `key`.
+fn uint(key: &CoseKey, param: iana::RsaKeyParameter) -> Result<BigUint, RsaError> {
+    key.param(param as i64)
+        .and_then(Value::as_bytes)
+        .map(|b| BigUint::from_bytes_be(b))
+        .ok_or(RsaError::InvalidKey(RsaKeyError::MissingParameter(param)))
+}
+
+/// Convert an RSA [`CoseKey`] (public or private) to an [`RsaPublicKey`].
+pub fn rsa_public_key(key: &CoseKey) -> Result<RsaPublicKey, RsaError> {
+    key.check_rsa_key()?;
+    Ok(RsaPublicKey::new(
+        uint(key, iana::RsaKeyParameter::N)?,
+        uint(key, iana::RsaKeyParameter::E)?,
+    )?)
+}
+
+/// Convert an RSA private [`CoseKey`] to an [`RsaPrivateKey`].
+pub fn rsa_private_key(key: &CoseKey) -> Result<RsaPrivateKey, RsaError> {
+    key.check_rsa_key()?;
+    if key.param(iana::RsaKeyParameter::D as i64).is_none() {
+        return Err(RsaError::NotPrivateKey);
+    }
+    let mut primes = vec![
+        uint(key, iana::RsaKeyParameter::P)?,
+        uint(key, iana::RsaKeyParameter::Q)?,
+    ];
+    if let Some(Value::Array(infos)) = key.param(iana::RsaKeyParameter::Other as i64) {
+        for info in infos {
+            let r = info
+                .as_map()
+                .into_iter()
+                .flatten()
+                .find(|(l, _)| *l == Value::from(iana::RsaKeyParameter::RI as i64))
+                .and_then(|(_, v)| v.as_bytes())
+                .ok_or(RsaError::InvalidKey(RsaKeyError::MissingParameter(
+                    iana::RsaKeyParameter::RI,
+                )))?;
+            primes.push(BigUint::from_bytes_be(r));
+        }
+    }
+    let mut private = RsaPrivateKey::from_components(
+        uint(key, iana::RsaKeyParameter::N)?,
+        uint(key, iana::RsaKeyParameter::E)?,
+        uint(key, iana::RsaKeyParameter::D)?,
+        primes,
+    )?;
+    private.validate()?;
+    private.precompute()?;
+    Ok(private)
+}
+
+impl From<&RsaPublicKey> for CoseKeyBuilder {
+    fn from(key: &RsaPublicKey) -> Self {
+        CoseKeyBuilder::new_rsa_pub_key(key.n().to_bytes_be(), key.e().to_bytes_be())
+    }
+}
+
+impl From<&RsaPrivateKey> for CoseKeyBuilder {
+    fn from(key: &RsaPrivateKey) -> Self {
+        let d = key.d();
+        let primes = key.primes();
+        let one = BigUint::from(1u8);
+        let two = BigUint::from(2u8);
+        // Encode a derived secret value, wiping the intermediate.
+        let bytes = |mut x: BigUint| {
+            let b = x.to_bytes_be();
+            wipe(&mut x);
+            b
+        };
+        // CRT exponent d mod (r-1) for a prime factor r.
+        let crt_exp = |r: &BigUint| {
+            let mut r1 = r - &one;
+            let result = bytes(d % &r1);
+            wipe(&mut r1);
+            result
+        };
+        // All of the factors are prime, so modular inverses can be calculated as x^(r-2) mod r.
+        let inv = |x: &BigUint, r: &BigUint| {
+            let mut reduced = x % r;
+            let mut exp = r - &two;
+            let result = bytes(reduced.modpow(&exp, r));
+            wipe(&mut reduced);
+            wipe(&mut exp);
+            result
+        };
+        let mut other = Vec::new();
+        let mut product = &primes[0] * &primes[1];
+        for r in &primes[2..] {
+            other.push(RsaOtherPrimeInfo {
+                r: r.to_bytes_be(),
+                d: crt_exp(r),
+                t: inv(&product, r),
+            });
+            product *= r;
+        }
+        wipe(&mut product);
+        let (p, q) = (&primes[0], &primes[1]);
+        CoseKeyBuilder::new_rsa_priv_key(
+            key.n().to_bytes_be(),
+            key.e().to_bytes_be(),
+            RsaPrivateComponents {
+                d: d.to_bytes_be(),
+                p: p.to_bytes_be(),
+                q: q.to_bytes_be(),
+                dp: crt_exp(p),
+                dq: crt_exp(q),
+                qinv: inv(q, p),
+                other,
+            },
+        )
+    }
+}
+
+/// Sign `data` with the RSA private key held in `key`, using the signature algorithm `alg` (one of
+/// `PS256`, `PS384`, `PS512`, `RS256`, `RS384` or `RS512`).
+///
+/// The randomness needed for RSASSA-PSS salts (and for blinding) comes from `rng`.
+pub fn rsa_sign<R: CryptoRngCore>(
+    alg: iana::Algorithm,
+    key: &CoseKey,
+    rng: &mut R,
+    data: &[u8],
+) -> Result<Vec<u8>, RsaError> {
+    let scheme = SignatureScheme::new(alg)?;
+    check_alg(key, alg)?;
+    let private = rsa_private_key(key)?;
+    let hashed = scheme.hash().digest(data);
+    Ok(match scheme {
+        SignatureScheme::Pss(h) => private.sign_with_rng(rng, SignatureScheme::pss(h), &hashed)?,
+        SignatureScheme::Pkcs1v15(h) => {
+            private.sign_with_rng(rng, SignatureScheme::pkcs1v15(h), &hashed)?
+        }
+    })
+}
+
+/// Verify the `signature` over `data` with the RSA public key held in `key`, using the signature
+/// algorithm `alg` (one of `PS256`, `PS384`, `PS512`, `RS256`, `RS384` or `RS512`).
+pub fn rsa_verify(
+    alg: iana::Algorithm,
+    key: &CoseKey,
+    signature: &[u8],
+    data: &[u8],
+) -> Result<(), RsaError> {
+    let scheme = SignatureScheme::new(alg)?;
+    check_alg(key, alg)?;
+    let public = rsa_public_key(key)?;
+    let hashed = scheme.hash().digest(data);
+    match scheme {
+        SignatureScheme::Pss(h) => public.verify(SignatureScheme::pss(h), &hashed, signature)?,
+        SignatureScheme::Pkcs1v15(h) => {
+            public.verify(SignatureScheme::pkcs1v15(h), &hashed, signature)?
+        }
+    }
+    Ok(())
+}
+
+/// Encrypt a content encryption key `cek` with the RSA public key held in `key`, using the key
+/// transport algorithm `alg` (one of the `RSAES_OAEP_*` algorithms).  Suitable for use with
+/// [`CoseRecipientBuilder::try_create_key_transport`](crate::CoseRecipientBuilder::try_create_key_transport).
+pub fn rsa_oaep_encrypt<R: CryptoRngCore>(
+    alg: iana::Algorithm,
+    key: &CoseKey,
+    rng: &mut R,
+    cek: &[u8],
+) -> Result<Vec<u8>, RsaError> {
+    let padding = oaep(alg)?;
+    check_alg(key, alg)?;
+    Ok(rsa_public_key(key)?.encrypt(rng, padding, cek)?)
+}
+
+/// Decrypt a content encryption key from `ciphertext` with the RSA private key held in `key`,
+/// using the key transport algorithm `alg` (one of the `RSAES_OAEP_*` algorithms).  Suitable for
+/// use with [`CoseRecipient::decrypt_key_transport`](crate::CoseRecipient::decrypt_key_transport).
+///
+/// Only available with the `rsa-decrypt` feature: the `rsa` crate is affected by
+/// [RUSTSEC-2023-0071](https://rustsec.org/advisories/RUSTSEC-2023-0071), a timing side channel
+/// that may allow recovery of the private key by an attacker who can submit ciphertexts for
+/// decryption and observe how long they take.  There is no fixed version of the crate.
+#[cfg(feature = "rsa-decrypt")]
+pub fn rsa_oaep_decrypt(
+    alg: iana::Algorithm,
+    key: &CoseKey,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, RsaError> {
+    let padding = oaep(alg)?;
+    check_alg(key, alg)?;
+    Ok(rsa_private_key(key)?.decrypt(padding, ciphertext)?)
+}
+
+impl TryFrom<&CoseKey> for RsaPublicKey {
+    type Error = RsaError;
+    fn try_from(key: &CoseKey) -> Result<Self, Self::Error> {
+        rsa_public_key(key)
+    }
+}
+
+impl TryFrom<&CoseKey> for RsaPrivateKey {
+    type Error = RsaError;
+    fn try_from(key: &CoseKey) -> Result<Self, Self::Error> {
+        rsa_private_key(key)
+    }
+}
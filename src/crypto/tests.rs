@@ -0,0 +1,80 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521", feature = "k256"))]
+mod ec;
+#[cfg(feature = "keygen")]
+mod keygen;
+#[cfg(feature = "mldsa")]
+mod mldsa;
+#[cfg(any(
+    feature = "p256",
+    feature = "ed25519",
+    feature = "x25519",
+    feature = "mldsa"
+))]
+mod public;
+#[cfg(feature = "rsa")]
+mod rsa;
+
+/// Deterministic (and insecure) random number generator for tests.
+#[cfg(any(feature = "keygen", feature = "rsa"))]
+struct TestRng(u64);
+
+#[cfg(any(feature = "keygen", feature = "rsa"))]
+impl rand_core::RngCore for TestRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+    fn next_u64(&mut self) -> u64 {
+        // xorshift64
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for b in dest.iter_mut() {
+            *b = self.next_u64() as u8;
+        }
+    }
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "keygen", feature = "rsa"))]
+impl rand_core::CryptoRng for TestRng {}
+
+#[cfg(feature = "hkdf")]
+#[test]
+fn test_hkdf_sha256() {
+    // RFC 5869 Appendix A.1.
+    let okm = super::hkdf_sha256(
+        &hex::decode("000102030405060708090a0b0c").unwrap(),
+        &[0x0b; 22],
+        &hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap(),
+        42,
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(okm),
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+    );
+    assert!(super::hkdf_sha256(b"", b"secret", b"", 255 * 32).is_some());
+    assert!(super::hkdf_sha256(b"", b"secret", b"", 255 * 32 + 1).is_none());
+}
@@ -0,0 +1,135 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::{
+    cbor::value::Value, crypto::*, iana, util::expect_err, CoseKey, CoseKeyBuilder, KeyType,
+    ToSec1OctetStringError,
+};
+use alloc::{vec, vec::Vec};
+
+/// Public key points generated with OpenSSL, as (curve, x, y).
+fn points() -> Vec<(iana::EllipticCurve, &'static str, &'static str)> {
+    vec![
+        #[cfg(feature = "p256")]
+        (
+            iana::EllipticCurve::P_256,
+            "b1f7772927f25d6000282641d34428b1a3dec7eb9d48fd90dc9ee39829bbcc0e",
+            "a5c9a7a45172726defdf0e8aad8eea203a3d1d476f11ad34a780e1b5917b5d9c",
+        ),
+        #[cfg(feature = "p384")]
+        (
+            iana::EllipticCurve::P_384,
+            concat!(
+                "16ea1f234b4e0e0a4b789fe023b0fe287e2579e72773239b",
+                "a41ee149ed3dc041c974abe3bc5bfc4daf56a5a70ba272dd",
+            ),
+            concat!(
+                "ed7ac0e6d8de08e57127503c4c6b47c404ae30135e473e4e",
+                "149e10a180b25b527a0837db86844751bc7542f40aceba46",
+            ),
+        ),
+        #[cfg(feature = "p521")]
+        (
+            iana::EllipticCurve::P_521,
+            concat!(
+                "008d4b40b56b37b20fcae088ce7d4f0c4e82ff6c90996e199c277da997b5e5dbbb",
+                "8fb665845b316bfbcd26df478935c93e7053b2da2b486bf8b3f52cc2d1a5b71f87",
+            ),
+            concat!(
+                "005c12052b7511dd4e6ef7243ff601f0d19c3de96100dcfc985309c2f37a4347c4",
+                "03a493078ea97e166b9b68678860b3061ba84df1ddf74114ad5b308d26bf80677b",
+            ),
+        ),
+        #[cfg(feature = "k256")]
+        (
+            iana::EllipticCurve::Secp256k1,
+            "824d1391539670314bfd2b188e0aed24bda0326f880c2d1376e0393f6ae31995",
+            "0b216eef79a8da6ee4660a4c98e414c1b28a153c3fa0fa0c664fc5a27c17b5d5",
+        ),
+    ]
+}
+
+#[test]
+fn test_ec2_decompress() {
+    for (crv, x, y) in points() {
+        let x = hex::decode(x).unwrap();
+        let y = hex::decode(y).unwrap();
+        let y_sign = y.last().unwrap() & 0x01 != 0;
+        let uncompressed = CoseKeyBuilder::new_ec2_pub_key(crv, x.clone(), y.clone()).build();
+        let compressed = CoseKeyBuilder::new_ec2_pub_key_y_sign(crv, x.clone(), y_sign).build();
+        let want_point = [&[0x04], x.as_slice(), y.as_slice()].concat();
+
+        for key in [&uncompressed, &compressed] {
+            assert!(ec2_check_point(key).is_ok(), "for {:?}", crv);
+            assert_eq!(
+                ec2_uncompressed_point(key).unwrap(),
+                want_point,
+                "for {crv:?}"
+            );
+            assert_eq!(ec2_decompress(key).unwrap(), uncompressed, "for {crv:?}");
+            assert_eq!(ec2_compress(key).unwrap(), compressed, "for {crv:?}");
+        }
+
+        // Other parameters are preserved.
+        let private = CoseKeyBuilder::new_ec2_priv_key(crv, x.clone(), y.clone(), vec![1, 2, 3])
+            .key_id(vec![4])
+            .build();
+        let got = ec2_compress(&private).unwrap();
+        assert_eq!(got.key_id, vec![4]);
+        assert_eq!(got.params.len(), private.params.len());
+        assert_eq!(ec2_decompress(&got).unwrap(), private);
+    }
+}
+
+#[test]
+fn test_ec2_decompress_fail() {
+    for (crv, x, y) in points() {
+        let x = hex::decode(x).unwrap();
+        let mut y = hex::decode(y).unwrap();
+        *y.last_mut().unwrap() ^= 0x01;
+        let key = CoseKeyBuilder::new_ec2_pub_key(crv, x.clone(), y).build();
+        expect_err(ec2_check_point(&key), "point not on curve");
+        expect_err(ec2_decompress(&key), "point not on curve");
+        expect_err(ec2_compress(&key), "point not on curve");
+
+        // Wrong length for the curve.
+        let key = CoseKeyBuilder::new_ec2_pub_key_y_sign(crv, x[1..].to_vec(), true).build();
+        expect_err(ec2_check_point(&key), "point not on curve");
+    }
+
+    let okp = CoseKeyBuilder::new_okp_key().build();
+    assert!(matches!(
+        ec2_check_point(&okp),
+        Err(EcError::InvalidKey(ToSec1OctetStringError::NotEcKey))
+    ));
+    let mut key =
+        CoseKeyBuilder::new_ec2_pub_key_y_sign(iana::EllipticCurve::P_256, vec![1], true).build();
+    key.params.retain(|(l, _)| *l != crate::Label::Int(-1));
+    expect_err(ec2_check_point(&key), "missing or unrecognized curve");
+    key.params
+        .push((crate::Label::Int(-1), Value::Text("P-256".into())));
+    expect_err(ec2_check_point(&key), "missing or unrecognized curve");
+
+    let key =
+        CoseKeyBuilder::new_ec2_pub_key_y_sign(iana::EllipticCurve::BrainpoolP256R1, vec![1], true)
+            .build();
+    expect_err(ec2_check_point(&key), "unsupported curve BrainpoolP256R1");
+    let key = CoseKey {
+        kty: KeyType::Assigned(iana::KeyType::EC2),
+        ..Default::default()
+    };
+    expect_err(ec2_decompress(&key), "invalid EC2 key");
+}
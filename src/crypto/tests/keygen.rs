@@ -0,0 +1,393 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::TestRng;
+use crate::{crypto::*, iana, util::expect_err, Algorithm, CoseKey, KeyOperation, KeyType, Label};
+use alloc::{collections::BTreeSet, vec};
+#[cfg(feature = "ed25519")]
+use core::convert::TryInto;
+
+/// Check the common fields of a generated key.
+fn check_key(
+    key: &CoseKey,
+    kty: iana::KeyType,
+    alg: iana::Algorithm,
+    ops: [iana::KeyOperation; 2],
+) {
+    assert_eq!(key.kty, KeyType::Assigned(kty));
+    assert_eq!(key.alg, Some(Algorithm::Assigned(alg)));
+    let want: BTreeSet<_> = ops.iter().map(|op| KeyOperation::Assigned(*op)).collect();
+    assert_eq!(key.key_ops, want);
+}
+
+/// Return the length of the (bstr) parameter `label` in `key`.
+fn param_len(key: &CoseKey, label: i64) -> usize {
+    key.params
+        .iter()
+        .find(|(l, _)| *l == Label::Int(label))
+        .and_then(|(_, v)| v.as_bytes())
+        .unwrap()
+        .len()
+}
+
+#[test]
+fn test_generate_symmetric_key() {
+    let tests = vec![
+        (iana::Algorithm::A128GCM, 16, iana::KeyOperation::Encrypt),
+        (iana::Algorithm::A192GCM, 24, iana::KeyOperation::Encrypt),
+        (
+            iana::Algorithm::ChaCha20Poly1305,
+            32,
+            iana::KeyOperation::Encrypt,
+        ),
+        (
+            iana::Algorithm::AES_CCM_64_128_256,
+            32,
+            iana::KeyOperation::Encrypt,
+        ),
+        (
+            iana::Algorithm::HMAC_256_64,
+            32,
+            iana::KeyOperation::MacCreate,
+        ),
+        (
+            iana::Algorithm::HMAC_384_384,
+            48,
+            iana::KeyOperation::MacCreate,
+        ),
+        (
+            iana::Algorithm::HMAC_512_512,
+            64,
+            iana::KeyOperation::MacCreate,
+        ),
+        (
+            iana::Algorithm::AES_MAC_128_64,
+            16,
+            iana::KeyOperation::MacCreate,
+        ),
+        (iana::Algorithm::A256KW, 32, iana::KeyOperation::WrapKey),
+    ];
+    let mut rng = TestRng(1);
+    for (alg, len, op) in tests {
+        let key = generate_symmetric_key(&mut rng, alg, KeyIdSource::Random(8)).unwrap();
+        let other_op = match op {
+            iana::KeyOperation::Encrypt => iana::KeyOperation::Decrypt,
+            iana::KeyOperation::MacCreate => iana::KeyOperation::MacVerify,
+            _ => iana::KeyOperation::UnwrapKey,
+        };
+        check_key(&key, iana::KeyType::Symmetric, alg, [op, other_op]);
+        assert_eq!(key.key_id.len(), 8);
+        assert_eq!(param_len(&key, iana::SymmetricKeyParameter::K as i64), len);
+    }
+
+    // Each key is different.
+    let key1 = generate_symmetric_key(&mut rng, iana::Algorithm::A128GCM, KeyIdSource::Random(4));
+    let key2 = generate_symmetric_key(&mut rng, iana::Algorithm::A128GCM, KeyIdSource::Random(4));
+    assert_ne!(key1.unwrap(), key2.unwrap());
+
+    // Key ID cannot be the thumbprint, as that is a hash of the secret key.
+    expect_err(
+        generate_symmetric_key(&mut rng, iana::Algorithm::A128GCM, KeyIdSource::Thumbprint),
+        "thumbprint key ID not allowed",
+    );
+
+    for alg in [
+        iana::Algorithm::ES256,
+        iana::Algorithm::Direct,
+        iana::Algorithm::PS256,
+    ] {
+        expect_err(
+            generate_symmetric_key(&mut rng, alg, KeyIdSource::Random(8)),
+            "unsupported algorithm",
+        );
+    }
+}
+
+#[cfg(any(feature = "p256", feature = "p384", feature = "p521", feature = "k256"))]
+#[test]
+fn test_generate_ec2_key() {
+    let tests = vec![
+        #[cfg(feature = "p256")]
+        (iana::EllipticCurve::P_256, iana::Algorithm::ES256, 32),
+        #[cfg(feature = "p256")]
+        (iana::EllipticCurve::P_256, iana::Algorithm::ESP256, 32),
+        #[cfg(feature = "p256")]
+        (
+            iana::EllipticCurve::P_256,
+            iana::Algorithm::ECDH_ES_HKDF_256,
+            32,
+        ),
+        #[cfg(feature = "p384")]
+        (iana::EllipticCurve::P_384, iana::Algorithm::ES384, 48),
+        #[cfg(feature = "p521")]
+        (iana::EllipticCurve::P_521, iana::Algorithm::ESP512, 66),
+        #[cfg(feature = "k256")]
+        (iana::EllipticCurve::Secp256k1, iana::Algorithm::ES256K, 32),
+    ];
+    let mut rng = TestRng(2);
+    for (crv, alg, len) in tests {
+        let key = generate_ec2_key(&mut rng, crv, alg, KeyIdSource::Thumbprint).unwrap();
+        let ops = if alg == iana::Algorithm::ECDH_ES_HKDF_256 {
+            [
+                iana::KeyOperation::DeriveKey,
+                iana::KeyOperation::DeriveBits,
+            ]
+        } else {
+            [iana::KeyOperation::Sign, iana::KeyOperation::Verify]
+        };
+        check_key(&key, iana::KeyType::EC2, alg, ops);
+        assert_eq!(key.key_id, key.thumbprint_sha256().unwrap());
+        assert_eq!(param_len(&key, iana::Ec2KeyParameter::X as i64), len);
+        assert_eq!(param_len(&key, iana::Ec2KeyParameter::Y as i64), len);
+        assert_eq!(param_len(&key, iana::Ec2KeyParameter::D as i64), len);
+        assert!(ec2_check_point(&key).is_ok(), "for {:?}", crv);
+    }
+
+    let tests = vec![
+        (
+            iana::EllipticCurve::P_256,
+            iana::Algorithm::ESP384,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::P_256,
+            iana::Algorithm::ES256K,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::P_256,
+            iana::Algorithm::EdDSA,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::P_256,
+            iana::Algorithm::A128GCM,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::P_384,
+            iana::Algorithm::ES256,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::P_521,
+            iana::Algorithm::ES384,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::P_256,
+            iana::Algorithm::ES512,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::Secp256k1,
+            iana::Algorithm::ES256,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::Secp256k1,
+            iana::Algorithm::ECDH_ES_HKDF_256,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::BrainpoolP256R1,
+            iana::Algorithm::ECDH_ES_HKDF_256,
+            "unsupported curve",
+        ),
+        (
+            iana::EllipticCurve::X25519,
+            iana::Algorithm::ECDH_ES_HKDF_256,
+            "unsupported curve",
+        ),
+        (
+            iana::EllipticCurve::Ed25519,
+            iana::Algorithm::ES256,
+            "unsupported curve",
+        ),
+    ];
+    for (crv, alg, err_msg) in tests {
+        expect_err(
+            generate_ec2_key(&mut rng, crv, alg, KeyIdSource::Random(8)),
+            err_msg,
+        );
+    }
+}
+
+#[cfg(any(feature = "ed25519", feature = "x25519"))]
+#[test]
+fn test_generate_okp_key() {
+    let tests = vec![
+        #[cfg(feature = "ed25519")]
+        (
+            iana::EllipticCurve::Ed25519,
+            iana::Algorithm::EdDSA,
+            iana::KeyOperation::Sign,
+        ),
+        #[cfg(feature = "ed25519")]
+        (
+            iana::EllipticCurve::Ed25519,
+            iana::Algorithm::Ed25519,
+            iana::KeyOperation::Sign,
+        ),
+        #[cfg(feature = "x25519")]
+        (
+            iana::EllipticCurve::X25519,
+            iana::Algorithm::ECDH_SS_A128KW,
+            iana::KeyOperation::DeriveKey,
+        ),
+    ];
+    let mut rng = TestRng(3);
+    for (crv, alg, op) in tests {
+        let key = generate_okp_key(&mut rng, crv, alg, KeyIdSource::Random(16)).unwrap();
+        let other_op = if op == iana::KeyOperation::Sign {
+            iana::KeyOperation::Verify
+        } else {
+            iana::KeyOperation::DeriveBits
+        };
+        check_key(&key, iana::KeyType::OKP, alg, [op, other_op]);
+        assert_eq!(key.key_id.len(), 16);
+        assert_eq!(param_len(&key, iana::OkpKeyParameter::X as i64), 32);
+        assert_eq!(param_len(&key, iana::OkpKeyParameter::D as i64), 32);
+    }
+
+    let tests = vec![
+        (
+            iana::EllipticCurve::Ed25519,
+            iana::Algorithm::Ed448,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::X25519,
+            iana::Algorithm::EdDSA,
+            "unsupported algorithm",
+        ),
+        (
+            iana::EllipticCurve::Ed448,
+            iana::Algorithm::Ed448,
+            "unsupported curve",
+        ),
+        (
+            iana::EllipticCurve::P_256,
+            iana::Algorithm::ES256,
+            "unsupported curve",
+        ),
+    ];
+    for (crv, alg, err_msg) in tests {
+        expect_err(
+            generate_okp_key(&mut rng, crv, alg, KeyIdSource::Random(8)),
+            err_msg,
+        );
+    }
+}
+
+#[cfg(feature = "ed25519")]
+#[test]
+fn test_generate_ed25519_key_consistent() {
+    use ed25519_dalek::Signer;
+    let mut rng = TestRng(4);
+    let key = generate_okp_key(
+        &mut rng,
+        iana::EllipticCurve::Ed25519,
+        iana::Algorithm::EdDSA,
+        KeyIdSource::Thumbprint,
+    )
+    .unwrap();
+    let bytes = |label: iana::OkpKeyParameter| -> [u8; 32] {
+        let value = key
+            .params
+            .iter()
+            .find(|(l, _)| *l == Label::Int(label as i64))
+            .and_then(|(_, v)| v.as_bytes())
+            .unwrap();
+        value.as_slice().try_into().unwrap()
+    };
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&bytes(iana::OkpKeyParameter::D));
+    let verifying_key =
+        ed25519_dalek::VerifyingKey::from_bytes(&bytes(iana::OkpKeyParameter::X)).unwrap();
+    let sig = signing_key.sign(b"data");
+    assert!(verifying_key.verify_strict(b"data", &sig).is_ok());
+}
+
+#[cfg(feature = "rsa")]
+#[test]
+fn test_generate_rsa_key() {
+    let mut rng = TestRng(5);
+    let key = generate_rsa_key(
+        &mut rng,
+        512,
+        iana::Algorithm::PS256,
+        KeyIdSource::Random(8),
+    )
+    .unwrap();
+    check_key(
+        &key,
+        iana::KeyType::RSA,
+        iana::Algorithm::PS256,
+        [iana::KeyOperation::Sign, iana::KeyOperation::Verify],
+    );
+    assert!(key.check_rsa_key().is_ok());
+    assert!(rsa_private_key(&key).is_ok());
+    assert_eq!(param_len(&key, iana::RsaKeyParameter::N as i64), 64);
+
+    let key = generate_rsa_key(
+        &mut rng,
+        512,
+        iana::Algorithm::RSAES_OAEP_SHA_256,
+        KeyIdSource::Thumbprint,
+    )
+    .unwrap();
+    assert!(key
+        .key_ops
+        .contains(&KeyOperation::Assigned(iana::KeyOperation::WrapKey)));
+    assert_eq!(key.key_id, key.thumbprint_sha256().unwrap());
+
+    expect_err(
+        generate_rsa_key(
+            &mut rng,
+            512,
+            iana::Algorithm::ES256,
+            KeyIdSource::Random(8),
+        ),
+        "unsupported algorithm",
+    );
+    expect_err(
+        generate_rsa_key(&mut rng, 0, iana::Algorithm::PS256, KeyIdSource::Random(8)),
+        "RSA failure",
+    );
+}
+
+#[cfg(feature = "mldsa")]
+#[test]
+fn test_generate_mldsa_key() {
+    let tests = vec![
+        (crate::MlDsaVariant::MlDsa44, 1312),
+        (crate::MlDsaVariant::MlDsa65, 1952),
+        (crate::MlDsaVariant::MlDsa87, 2592),
+    ];
+    let mut rng = TestRng(6);
+    for (variant, len) in tests {
+        let key = generate_mldsa_key(&mut rng, variant, KeyIdSource::Thumbprint).unwrap();
+        check_key(
+            &key,
+            iana::KeyType::AKP,
+            variant.into(),
+            [iana::KeyOperation::Sign, iana::KeyOperation::Verify],
+        );
+        assert_eq!(key.key_id, key.thumbprint_sha256().unwrap());
+        assert_eq!(param_len(&key, iana::AkpKeyParameter::Pub as i64), len);
+        assert_eq!(param_len(&key, iana::AkpKeyParameter::Priv as i64), 32);
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::{crypto::*, MlDsaVariant};
+use alloc::vec;
+use sha3::Digest;
+
+#[test]
+fn test_mldsa_public_key() {
+    // Public keys generated by OpenSSL from the same seed, as (variant, length, prefix,
+    // SHA3-256(public key)).
+    let tests = vec![
+        (
+            MlDsaVariant::MlDsa44,
+            1312,
+            "d7b2b47254aae0db",
+            "373c7bf2cac5bd2a6c35933bab0fa1c951f22247e1333383fcb618822080373f",
+        ),
+        (
+            MlDsaVariant::MlDsa65,
+            1952,
+            "48683d91978e31eb",
+            "1800725067e388d837d911fe4f66101cc1961b1bb755030dc574272cfb00013f",
+        ),
+        (
+            MlDsaVariant::MlDsa87,
+            2592,
+            "9792bcec2f243068",
+            "e6cf50a9c2fa5234f59949ff61f8161db4d629532127f4aefa8bb10811ecfb1e",
+        ),
+    ];
+    let mut seed = [0u8; 32];
+    for (i, b) in seed.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    for (variant, len, prefix, digest) in tests {
+        let public = mldsa_public_key(variant, &seed);
+        assert_eq!(public.len(), len, "for {variant:?}");
+        assert_eq!(hex::encode(&public[..8]), prefix, "for {variant:?}");
+        assert_eq!(
+            hex::encode(sha3::Sha3_256::digest(&public)),
+            digest,
+            "for {variant:?}"
+        );
+    }
+}
+
+#[test]
+fn test_mldsa_expanded_private_key() {
+    // Expanded private keys generated by OpenSSL from the same seed, as (variant, length, prefix,
+    // SHA3-256(expanded private key)).
+    let tests = vec![
+        (
+            MlDsaVariant::MlDsa44,
+            2560,
+            "d7b2b47254aae0db",
+            "da0665a6d807669d06aeb7010507aa6bd4640641055462e38e37344a274f36a6",
+        ),
+        (
+            MlDsaVariant::MlDsa65,
+            4032,
+            "48683d91978e31eb",
+            "4ecfbd119980b1090a7feda39c225539ffa3ef3ff673b239b60189bfd4541170",
+        ),
+        (
+            MlDsaVariant::MlDsa87,
+            4896,
+            "9792bcec2f243068",
+            "db6218a2eeae53e567d25222055251eab0ba85d1c69aeb8a62253d2096fd96a2",
+        ),
+    ];
+    let mut seed = [0u8; 32];
+    for (i, b) in seed.iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    for (variant, len, prefix, digest) in tests {
+        let expanded = mldsa_expanded_private_key(variant, &seed);
+        assert_eq!(expanded.len(), len, "for {variant:?}");
+        assert_eq!(hex::encode(&expanded[..8]), prefix, "for {variant:?}");
+        assert_eq!(
+            hex::encode(sha3::Sha3_256::digest(&expanded)),
+            digest,
+            "for {variant:?}"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::{cbor::value::Value, iana, CoseKeyBuilder};
+use alloc::vec;
+
+#[cfg(feature = "p256")]
+#[test]
+fn test_to_public_key_ec2() {
+    // Key from RFC 6979 section A.2.5.
+    let d =
+        hex::decode("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721").unwrap();
+    let x =
+        hex::decode("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6").unwrap();
+    let y =
+        hex::decode("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299").unwrap();
+    let private = CoseKeyBuilder::new()
+        .key_type(iana::KeyType::EC2)
+        .param(
+            iana::Ec2KeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::P_256 as u64),
+        )
+        .param(iana::Ec2KeyParameter::D as i64, Value::Bytes(d))
+        .build();
+    assert_eq!(
+        private.to_public_key().unwrap(),
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_256, x, y).build()
+    );
+
+    // An invalid private key gives no public key.
+    let private = CoseKeyBuilder::new()
+        .key_type(iana::KeyType::EC2)
+        .param(
+            iana::Ec2KeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::P_256 as u64),
+        )
+        .param(iana::Ec2KeyParameter::D as i64, Value::Bytes(vec![0; 32]))
+        .build();
+    assert!(private.to_public_key().is_err());
+}
+
+#[cfg(any(feature = "ed25519", feature = "x25519"))]
+#[test]
+fn test_to_public_key_okp() {
+    let tests = vec![
+        // Key from RFC 8032 section 7.1 (test 1).
+        #[cfg(feature = "ed25519")]
+        (
+            iana::EllipticCurve::Ed25519,
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        ),
+        // Key from RFC 7748 section 6.1 (Alice).
+        #[cfg(feature = "x25519")]
+        (
+            iana::EllipticCurve::X25519,
+            "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+            "8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a",
+        ),
+    ];
+    for (crv, d, x) in tests {
+        let private = CoseKeyBuilder::new_okp_key()
+            .param(iana::OkpKeyParameter::Crv as i64, Value::from(crv as u64))
+            .param(
+                iana::OkpKeyParameter::D as i64,
+                Value::Bytes(hex::decode(d).unwrap()),
+            )
+            .build();
+        let want = CoseKeyBuilder::new_okp_key()
+            .param(iana::OkpKeyParameter::Crv as i64, Value::from(crv as u64))
+            .param(
+                iana::OkpKeyParameter::X as i64,
+                Value::Bytes(hex::decode(x).unwrap()),
+            )
+            .build();
+        assert_eq!(private.to_public_key().unwrap(), want, "for {:?}", crv);
+    }
+}
+
+#[cfg(feature = "mldsa")]
+#[test]
+fn test_to_public_key_akp() {
+    use crate::{crypto::mldsa_public_key, MlDsaVariant};
+    let seed = [0x42; 32];
+    let private = CoseKeyBuilder::new()
+        .key_type(iana::KeyType::AKP)
+        .algorithm(iana::Algorithm::ML_DSA_65)
+        .param(
+            iana::AkpKeyParameter::Priv as i64,
+            Value::Bytes(seed.to_vec()),
+        )
+        .build();
+    assert_eq!(
+        private.to_public_key().unwrap(),
+        CoseKeyBuilder::new_mldsa_pub_key(
+            MlDsaVariant::MlDsa65,
+            mldsa_public_key(MlDsaVariant::MlDsa65, &seed)
+        )
+        .build()
+    );
+
+    // The algorithm identifies the parameter set.
+    let private = CoseKeyBuilder::new()
+        .key_type(iana::KeyType::AKP)
+        .param(
+            iana::AkpKeyParameter::Priv as i64,
+            Value::Bytes(seed.to_vec()),
+        )
+        .build();
+    assert!(private.to_public_key().is_err());
+
+    // The seed must be 32 bytes.
+    let private = CoseKeyBuilder::new()
+        .key_type(iana::KeyType::AKP)
+        .algorithm(iana::Algorithm::ML_DSA_65)
+        .param(
+            iana::AkpKeyParameter::Priv as i64,
+            Value::Bytes(vec![0x42; 31]),
+        )
+        .build();
+    assert!(private.to_public_key().is_err());
+}
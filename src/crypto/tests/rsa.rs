@@ -0,0 +1,355 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::TestRng;
+use crate::crypto::*;
+use crate::{
+    iana, util::expect_err, CoseKey, CoseKeyBuilder, CoseRecipientBuilder, CoseSign1Builder,
+    HeaderBuilder, RsaOtherPrimeInfo, RsaPrivateComponents,
+};
+use alloc::vec;
+use core::convert::TryFrom;
+
+const RSA_2048_N: &str = concat!(
+    "9e2bbcf77f951faa37bbd3ad639e2a158576831ad4718d3bc2d5b1b91c12ed4a9dcb512e8e61a2c18809e4b7",
+    "7492afbffbf3b44d223188d6660c2cee060e56c44b868aea4dd07ca8a47b216e059967545587f578addc5b30",
+    "17f3864927f98218dba8b16b83e8d17f82f91178830f458b839d84291ffa07d4452324f96e9fa9346b4d656a",
+    "9f30ef972cbe4d5541b93ea92f5676334d3e1e17c3057d8249e38c9d890e1964812669ce009f8f55a9a0a464",
+    "0013be1c405964d28315020ae0ddf9e758d4c987050c2837f4d08a88f0ca04a10f9838b6075779ba8689402b",
+    "9c34e957b88e3e43affe1b5153519070b18a5baf404f5462fad35b82e1d475f88ec52265",
+);
+
+fn rsa_2048_private_components() -> RsaPrivateComponents {
+    RsaPrivateComponents {
+        d: hex::decode(concat!(
+            "04d8f14f227d56fd97fcad2594b67db2b026ec28afd7150d7e509f443d8c84f232f8ceb19f1275f2f970",
+            "d82e1927ffb3e20c5b7bbbaf09f30c28965deb18986726ab66697cde055c03c2744953699aa5968376ad",
+            "eea220b74e1c9c0274f3ac1d87e9d91a22ee49315fb9b363de9c62b4be615ef205b55b7511735cc83a30",
+            "3176c9ed97b8f131a85f07d81669cbe3db024226a2afb2f9e4aab481a44384664911744b3f5107a52b34",
+            "1592c490d43675469fefa9959661e87a7b2d259734adbd3c79724232d7e425323dbdb3469b9dd11e1245",
+            "6294dc5c2781ad4203a87ddc6e014f1d5ff53f6c99714fbb45d2d8ec7ed41d3f9bffc9a41323ca8dd514",
+            "4ca82701",
+        ))
+        .unwrap(),
+        p: hex::decode(concat!(
+            "d9dcee60996cb05779a133099d2a3f6db14c785cea054a995abe9b1a8f009b181796d6bcb7cec5ac1062",
+            "6ae9badc7d3f43e5c0e9fe2537cdf24d40c7f4a34259569ada230b9ac72d236637f408c2109c997277ec",
+            "91ffb4644d579e008d44790cb9a51a680b867d4ef34f326fa304d1660faf6376ad1d936908147012e26e",
+            "6ce5",
+        ))
+        .unwrap(),
+        q: hex::decode(concat!(
+            "b9dbd4558d5e973c1a77a1089ff3622021d623033b7176906bac8fcabe8b51e9b4ede8f402ed9d78f509",
+            "91528871c0a80dc7b37e5afeb98e0274efcd6f84b7a7ab1430619adeca76f971d729a9f7ed6762245ead",
+            "2b8ae60d27f670960ea16e7a651bdbf4b3f9116f2374b0b591e3309ae18225e525e59b40b35e906d732e",
+            "0781",
+        ))
+        .unwrap(),
+        dp: hex::decode(concat!(
+            "a394b787e0ab335cfafe868bd96e3761ed963569ce6d5aadfaecd31905f6b67d48790193818fc7ec8200",
+            "e3b5cb90729a6628c4c8ed0294fc15cadbb14786adf2d68c3eedc170ddcc8e9fd9b36a400ca908ae624d",
+            "46f270483faa62009811a9b25c521399bbcda798f827a6ff1bb02a6a2facc949fa821284f284260287f1",
+            "d1e1",
+        ))
+        .unwrap(),
+        dq: hex::decode(concat!(
+            "20b20c5b74802468bd5b3827f543728aa6892e259e6a6a6a2396fac0b6de796d7c97b1b8635f7ba950a9",
+            "fb37a2ab283228226e7016ffe29299da0a698069bf892e6e61c879df218ce67ae15801ff8c72f86fdf7b",
+            "e5420d10596c6007f5d0ee4777cbfbd8c364586dcdb5f4742c92e3f50b20c31bd99bd7c0fd0cf0a9606e",
+            "c381",
+        ))
+        .unwrap(),
+        qinv: hex::decode(concat!(
+            "d5dec71db33954172644880c919d5bf6f194efe8ba731f410f570fb2121cc4ce0272ae0258665910dda6",
+            "3c5d407fb6af7ff20c266d4c0004987e1510a629f332e80846546cbd7f78525a85bd7e9b71b72a38965f",
+            "46798f2eee87a570c27ef85108008b5ff5971fc572bfd542bb02632d22a50d988e73cd09771838b3ed37",
+            "1d91",
+        ))
+        .unwrap(),
+        other: vec![],
+    }
+}
+
+fn rsa_2048_private_key() -> CoseKey {
+    CoseKeyBuilder::new_rsa_priv_key(
+        hex::decode(RSA_2048_N).unwrap(),
+        vec![0x01, 0x00, 0x01],
+        rsa_2048_private_components(),
+    )
+    .build()
+}
+
+fn rsa_2048_public_key() -> CoseKey {
+    CoseKeyBuilder::new_rsa_pub_key(hex::decode(RSA_2048_N).unwrap(), vec![0x01, 0x00, 0x01])
+        .build()
+}
+
+#[test]
+fn test_rsa_sign_verify() {
+    let mut rng = TestRng(42);
+    let private = rsa_2048_private_key();
+    let public = rsa_2048_public_key();
+    let data = b"This is the content";
+    for alg in [
+        iana::Algorithm::PS256,
+        iana::Algorithm::PS384,
+        iana::Algorithm::PS512,
+        iana::Algorithm::RS256,
+        iana::Algorithm::RS384,
+        iana::Algorithm::RS512,
+    ] {
+        let sig = rsa_sign(alg, &private, &mut rng, data).unwrap();
+        assert_eq!(sig.len(), 256);
+        rsa_verify(alg, &public, &sig, data).unwrap();
+        // A private key also holds the public key.
+        rsa_verify(alg, &private, &sig, data).unwrap();
+        assert!(rsa_verify(alg, &public, &sig, b"other data").is_err());
+    }
+    // RSASSA-PKCS1-v1_5 signatures are deterministic.
+    let sig1 = rsa_sign(iana::Algorithm::RS256, &private, &mut rng, data).unwrap();
+    let sig2 = rsa_sign(iana::Algorithm::RS256, &private, &mut rng, data).unwrap();
+    assert_eq!(sig1, sig2);
+
+    // Use as closures with `CoseSign1`.
+    let sign1 = CoseSign1Builder::new()
+        .protected(
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::PS256)
+                .build(),
+        )
+        .payload(data.to_vec())
+        .try_create_signature(b"", |tbs| {
+            rsa_sign(iana::Algorithm::PS256, &private, &mut rng, tbs)
+        })
+        .unwrap()
+        .build();
+    sign1
+        .verify_signature(b"", |sig, tbs| {
+            rsa_verify(iana::Algorithm::PS256, &public, sig, tbs)
+        })
+        .unwrap();
+}
+
+#[test]
+fn test_rsa_sign_verify_fail() {
+    let mut rng = TestRng(42);
+    let private = rsa_2048_private_key();
+    let public = rsa_2048_public_key();
+    let data = b"This is the content";
+
+    expect_err(
+        rsa_sign(iana::Algorithm::ES256, &private, &mut rng, data),
+        "unsupported algorithm",
+    );
+    expect_err(
+        rsa_verify(iana::Algorithm::RSAES_OAEP_SHA_256, &public, &[], data),
+        "unsupported algorithm",
+    );
+    expect_err(
+        rsa_sign(iana::Algorithm::PS256, &public, &mut rng, data),
+        "not an RSA private key",
+    );
+    let mut restricted = private.clone();
+    restricted.alg = Some(iana::Algorithm::RS256.into());
+    expect_err(
+        rsa_sign(iana::Algorithm::PS256, &restricted, &mut rng, data),
+        "different algorithm",
+    );
+    assert!(rsa_sign(iana::Algorithm::RS256, &restricted, &mut rng, data).is_ok());
+    let mut broken = private.clone();
+    broken.params.truncate(4);
+    expect_err(
+        rsa_sign(iana::Algorithm::PS256, &broken, &mut rng, data),
+        "missing RSA parameter",
+    );
+    let sym = CoseKeyBuilder::new_symmetric_key(vec![1, 2, 3]).build();
+    expect_err(
+        rsa_verify(iana::Algorithm::PS256, &sym, &[], data),
+        "not an RSA key",
+    );
+    let sig = rsa_sign(iana::Algorithm::PS256, &private, &mut rng, data).unwrap();
+    expect_err(
+        rsa_verify(iana::Algorithm::PS384, &public, &sig, data),
+        "RSA failure",
+    );
+}
+
+#[test]
+fn test_rsa_oaep_encrypt() {
+    let mut rng = TestRng(7);
+    let public = rsa_2048_public_key();
+    let cek = [0x42; 16];
+    for alg in [
+        iana::Algorithm::RSAES_OAEP_RFC_8017_default,
+        iana::Algorithm::RSAES_OAEP_SHA_256,
+        iana::Algorithm::RSAES_OAEP_SHA_512,
+    ] {
+        let recipient = CoseRecipientBuilder::new()
+            .protected(HeaderBuilder::new().algorithm(alg).build())
+            .try_create_key_transport(&cek, |cek| rsa_oaep_encrypt(alg, &public, &mut rng, cek))
+            .unwrap()
+            .build();
+        assert_eq!(recipient.ciphertext.unwrap().len(), 256);
+    }
+    expect_err(
+        rsa_oaep_encrypt(iana::Algorithm::PS256, &public, &mut rng, &cek),
+        "unsupported algorithm",
+    );
+}
+
+#[test]
+#[cfg(feature = "rsa-decrypt")]
+fn test_rsa_oaep_key_transport() {
+    use crate::{CborSerializable, CoseRecipient};
+    let mut rng = TestRng(7);
+    let private = rsa_2048_private_key();
+    let public = rsa_2048_public_key();
+    let cek = [0x42; 16];
+    for alg in [
+        iana::Algorithm::RSAES_OAEP_RFC_8017_default,
+        iana::Algorithm::RSAES_OAEP_SHA_256,
+        iana::Algorithm::RSAES_OAEP_SHA_512,
+    ] {
+        let recipient = CoseRecipientBuilder::new()
+            .protected(HeaderBuilder::new().algorithm(alg).build())
+            .try_create_key_transport(&cek, |cek| rsa_oaep_encrypt(alg, &public, &mut rng, cek))
+            .unwrap()
+            .build();
+        let data = recipient.to_vec().unwrap();
+
+        let recipient = CoseRecipient::from_slice(&data).unwrap();
+        let got = recipient
+            .decrypt_key_transport(
+                || RsaError::NotPrivateKey,
+                |ct| rsa_oaep_decrypt(alg, &private, ct),
+            )
+            .unwrap();
+        assert_eq!(got, cek);
+    }
+    expect_err(
+        rsa_oaep_decrypt(iana::Algorithm::RSAES_OAEP_SHA_256, &public, &[0; 256]),
+        "not an RSA private key",
+    );
+    expect_err(
+        rsa_oaep_decrypt(iana::Algorithm::RSAES_OAEP_SHA_256, &private, &[0; 256]),
+        "RSA failure",
+    );
+}
+
+#[test]
+fn test_rsa_key_convert() {
+    let private = rsa_2048_private_key();
+    let rsa_private = ::rsa::RsaPrivateKey::try_from(&private).unwrap();
+    let got = CoseKeyBuilder::from(&rsa_private).build();
+    assert_eq!(got, private);
+
+    let rsa_public = ::rsa::RsaPublicKey::try_from(&private).unwrap();
+    let got = CoseKeyBuilder::from(&rsa_public).build();
+    assert_eq!(got, rsa_2048_public_key());
+    assert_eq!(rsa_public, rsa_private.to_public_key());
+}
+
+#[test]
+fn test_rsa_multi_prime_key_convert() {
+    let key = CoseKeyBuilder::new_rsa_priv_key(
+        hex::decode(concat!(
+            "94ae6ea20b394c49c4e10636e80e3a8751eab8315ab617ff8afd1e9d6aff4e453516a4782aeb554c84cd",
+            "46e6a7e1ca05bbe46074383b5c9f43b4992a3e4dba8df17e51e7c8a1d9a4507d576ab09d8eb1a2c35fc6",
+            "60c945832f7db757999a1dd479fbeb819455189fc0432b103949f1587f12e376cd27e1608c1747742836",
+            "3a46e100225290a4d3086f343cc4f639e3031a7a5a60a572199a14cd192ffe795f26cbfcdec19d937fa4",
+            "413084adf9d6872bba7d7d2311e36ff4866ba297c3c4e93a14fbbb550a9837ba73d07634559c6af5316a",
+            "c5dc7d47bd3fad284d21de9f43166f2cd729cfc9fc5c5b2ea89669a890cab6d192056d797f290c0c0570",
+            "8e2e83a3",
+        ))
+        .unwrap(),
+        vec![0x01, 0x00, 0x01],
+        RsaPrivateComponents {
+            d: hex::decode(concat!(
+                "3376f18a9bb1f8ab2c3febe3c38d714af7daf468f90a40e24e9cf124f22d6f4d5b63fc08306b073a",
+                "96596bea594039aaa47017c702d3e16aab87eee085726859cbb6bae92d3480de7491fe594acb2ee2",
+                "640196bc571263ba52674237aeb4494dee68267e7c6e0eb84e25d4e35926fcccb138524263ab8d40",
+                "41fb617703dfeb0074e662f66a3d5ab5cfd35895a8ffb3bbace952445684aadd56b2515c1faa1b0b",
+                "0a10605c4d465802fd7c3d927bb54df0b8fc88cee0d1363dbaa490dfc2960a79f14e1f5290f7cd8f",
+                "eacbfa0ae14a64787c9c6f65aa0ff7bbd21f7d92842c414d454f06375f7840e0cb772f434ee3bed1",
+                "78ec51f48455b645655f04c359f90941",
+            ))
+            .unwrap(),
+            p: hex::decode(concat!(
+                "079335a4f85174c5a369377877837be31787ecd747b77e1d99a6cfde285ca3a606cf04b485952356",
+                "a37de745a8054abb8139a21a73655322b8b4226d6dab1dda6fe13389e45703005b11581aa6e113ed",
+                "04b994263455",
+            ))
+            .unwrap(),
+            q: hex::decode(concat!(
+                "068ac1483668e60c3e1182f6ac5436f7cc8e042bb3aca5fb256259f7cb7c0021ae61f58303afd8e3",
+                "c239d12c7706ada28788c75e7bea75b880334b024f0f4423a8fae7b27936da1d4e94f9154eaebfc0",
+                "d43fa35225af",
+            ))
+            .unwrap(),
+            dp: hex::decode(concat!(
+                "04211da736aec0e754737107bcaef2841339c7c47b95edac192cb43838ec77517478b9e9e6c02c29",
+                "78aa93be871f8b63b7c8413406200037f7544b4837f68629a3bfb226c3512393b81136642100a5aa",
+                "195f4901a299",
+            ))
+            .unwrap(),
+            dq: hex::decode(concat!(
+                "05ae6ceadcadbed374f7da47852361d553e525c41a36deb2bc25acc2610a20471fdd5cccd9dfd638",
+                "fe0d1e16428c1f311e09703322e45c35cdc83de51469caa0397924ccbf2e1ec002e5a76794bc7834",
+                "eb5f14e41b6b",
+            ))
+            .unwrap(),
+            qinv: hex::decode(concat!(
+                "c97a2b0653fff5417586b853ff350313eca49817b03ee5705ad6d3456e2936d72e2b7f88b281fff8",
+                "aa48b224792aefc656c8fcf0f0339827065010a7c3c36612a9a74a250505217e1e4589af36006929",
+                "2361cd73f0",
+            ))
+            .unwrap(),
+            other: vec![RsaOtherPrimeInfo {
+                r: hex::decode(concat!(
+                    "030011c193293b051b60670652f6540e13077230b6429befb78f42c6594346ee9108524bc244",
+                    "a902dada22ff46fbd9e9f1879b5f87c83b134b06b12e38e47bfeb15355bd1d9df9e01ba84f13",
+                    "5121004956dfb7ddbe19",
+                ))
+                .unwrap(),
+                d: hex::decode(concat!(
+                    "37e112d4420ba93374b00a25bf061e8003eaaae097b80098ba33817abd0a4c110129fb9906a5",
+                    "c52f66ef64e3252f95759d0810cb30261c21393b6ac178de0c899a2682bbc4e2890b7a1346b4",
+                    "80522503cd02f37f01",
+                ))
+                .unwrap(),
+                t: hex::decode(concat!(
+                    "02537ef22acf4878377f32b0234d04b6a7b39d4ccb9bdb4834b3cd1c0ed6f433d9267f59a17e",
+                    "e94d1a1ab36cb1e68129524c448422a8c14444b0cd622920613c8e989ecd00171fdd62cea8d7",
+                    "adc380675d896659bb4e",
+                ))
+                .unwrap(),
+            }],
+        },
+    )
+    .build();
+    key.check_rsa_key().unwrap();
+    let rsa_private = ::rsa::RsaPrivateKey::try_from(&key).unwrap();
+    assert_eq!(
+        ::rsa::traits::PrivateKeyParts::primes(&rsa_private).len(),
+        3
+    );
+    let got = CoseKeyBuilder::from(&rsa_private).build();
+    assert_eq!(got, key);
+
+    let mut rng = TestRng(3);
+    let sig = rsa_sign(iana::Algorithm::PS256, &key, &mut rng, b"data").unwrap();
+    rsa_verify(iana::Algorithm::PS256, &key, &sig, b"data").unwrap();
+}
@@ -0,0 +1,184 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Proof-of-possession key confirmation (`cnf`) claim support, as per RFC 8747.
+
+use super::{ClaimName, ClaimsSet, ClaimsSetBuilder, CwtError};
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    iana,
+    util::{cbor_type_error, ValueTryAs},
+    CborSerializable, CoseEncrypt, CoseEncrypt0, CoseError, CoseKey, Label,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+
+const CNF: ClaimName = ClaimName::Assigned(iana::CwtClaimName::Cnf);
+
+/// An encrypted COSE_Key, as held in a `cnf` claim.
+///
+/// ```cddl
+///  Encrypted_COSE_Key = COSE_Encrypt0 / COSE_Encrypt
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum EncryptedCoseKey {
+    /// Key encrypted with a known key.
+    Encrypt0(CoseEncrypt0),
+    /// Key encrypted with a content encryption key held for one or more recipients.
+    Encrypt(CoseEncrypt),
+}
+
+impl AsCborValue for EncryptedCoseKey {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        // Accept the tagged forms as well as the (expected) untagged forms.
+        let value = match value {
+            Value::Tag(t, v) if t == iana::CborTag::CoseEncrypt0 as u64 => {
+                return Ok(EncryptedCoseKey::Encrypt0(CoseEncrypt0::from_cbor_value(
+                    *v,
+                )?))
+            }
+            Value::Tag(t, v) if t == iana::CborTag::CoseEncrypt as u64 => {
+                return Ok(EncryptedCoseKey::Encrypt(CoseEncrypt::from_cbor_value(*v)?))
+            }
+            v => v,
+        };
+        match &value {
+            Value::Array(a) if a.len() == 3 => Ok(EncryptedCoseKey::Encrypt0(
+                CoseEncrypt0::from_cbor_value(value)?,
+            )),
+            Value::Array(a) if a.len() == 4 => Ok(EncryptedCoseKey::Encrypt(
+                CoseEncrypt::from_cbor_value(value)?,
+            )),
+            v => cbor_type_error(v, "COSE_Encrypt0/COSE_Encrypt"),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        match self {
+            EncryptedCoseKey::Encrypt0(e) => e.to_cbor_value(),
+            EncryptedCoseKey::Encrypt(e) => e.to_cbor_value(),
+        }
+    }
+}
+
+impl EncryptedCoseKey {
+    /// Decrypt the key with an AEAD, using `cipher` to decrypt the cipher text and combined AAD.
+    /// For a `COSE_Encrypt`, the `cipher` is responsible for determining the content encryption
+    /// key from the recipients.
+    pub fn decrypt_key<F, E>(&self, external_aad: &[u8], cipher: F) -> Result<CoseKey, CwtError<E>>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<Vec<u8>, E>,
+    {
+        let cipher = |ct: &[u8], aad: &[u8]| cipher(ct, aad).map_err(CwtError::Crypto);
+        let data = match self {
+            EncryptedCoseKey::Encrypt0(e) => {
+                e.decrypt_ciphertext(external_aad, || CwtError::MissingPayload, cipher)?
+            }
+            EncryptedCoseKey::Encrypt(e) => {
+                e.decrypt_ciphertext(external_aad, || CwtError::MissingPayload, cipher)?
+            }
+        };
+        Ok(CoseKey::from_slice(&data)?)
+    }
+}
+
+/// Proof-of-possession key confirmation, as held in a `cnf` claim.
+///
+/// ```cddl
+///  cnf = {
+///      ( 1 => COSE_Key ) //
+///      ( 2 => Encrypted_COSE_Key ) //
+///      ( 3 => kid ) //
+///      ( label => any )
+///  }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Confirmation {
+    /// The proof-of-possession key itself.
+    CoseKey(CoseKey),
+    /// The proof-of-possession key, encrypted.
+    EncryptedCoseKey(Box<EncryptedCoseKey>),
+    /// The key identifier of the proof-of-possession key.
+    KeyId(Vec<u8>),
+    /// Another confirmation method.
+    Other(Label, Value),
+}
+
+impl crate::CborSerializable for Confirmation {}
+
+const COSE_KEY: Label = Label::Int(iana::CwtConfirmationMethod::CoseKey as i64);
+const ENCRYPTED_COSE_KEY: Label = Label::Int(iana::CwtConfirmationMethod::EncryptedCoseKey as i64);
+const KID: Label = Label::Int(iana::CwtConfirmationMethod::Kid as i64);
+
+impl AsCborValue for Confirmation {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut m = match value {
+            Value::Map(m) => m,
+            v => return cbor_type_error(&v, "map"),
+        };
+        // Exactly one confirmation method must be present.
+        if m.len() != 1 {
+            return Err(CoseError::UnexpectedItem("map", "single-entry map"));
+        }
+        let (method, value) = m.remove(0);
+        Ok(match Label::from_cbor_value(method)? {
+            x if x == COSE_KEY => Confirmation::CoseKey(CoseKey::from_cbor_value(value)?),
+            x if x == ENCRYPTED_COSE_KEY => {
+                Confirmation::EncryptedCoseKey(Box::new(EncryptedCoseKey::from_cbor_value(value)?))
+            }
+            x if x == KID => Confirmation::KeyId(value.try_as_bytes()?),
+            method => Confirmation::Other(method, value),
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let (method, value) = match self {
+            Confirmation::CoseKey(key) => (COSE_KEY, key.to_cbor_value()?),
+            Confirmation::EncryptedCoseKey(key) => (ENCRYPTED_COSE_KEY, key.to_cbor_value()?),
+            Confirmation::KeyId(kid) => (KID, Value::Bytes(kid)),
+            Confirmation::Other(method, value) => (method, value),
+        };
+        Ok(Value::Map(vec![(method.to_cbor_value()?, value)]))
+    }
+}
+
+impl ClaimsSet {
+    /// Return the proof-of-possession key confirmation held in the `cnf` claim, if present.
+    pub fn confirmation(&self) -> Result<Option<Confirmation>, CoseError> {
+        self.rest
+            .iter()
+            .find(|(name, _)| *name == CNF)
+            .map(|(_, value)| Confirmation::from_cbor_value(value.clone()))
+            .transpose()
+    }
+}
+
+impl ClaimsSetBuilder {
+    /// Set the `cnf` claim to hold a proof-of-possession key confirmation, replacing any
+    /// confirmation already set.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the confirmation cannot be CBOR-encoded (for example, because
+    /// a key holds duplicate parameters).
+    #[must_use]
+    pub fn confirmation(mut self, cnf: Confirmation) -> Self {
+        let value = cnf.to_cbor_value().expect("failed to encode confirmation"); // safe: documented
+        self.0.rest.retain(|(label, _)| *label != CNF);
+        self.0.rest.push((CNF, value));
+        self
+    }
+}
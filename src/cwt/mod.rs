@@ -27,6 +27,13 @@ use crate::{
 use alloc::{collections::BTreeSet, string::String, vec::Vec};
 use core::convert::TryInto;
 
+mod cnf;
+pub use cnf::*;
+mod token;
+pub use token::*;
+mod validate;
+pub use validate::*;
+
 #[cfg(test)]
 mod tests;
 
@@ -79,6 +86,22 @@ pub struct ClaimsSet {
 
 impl crate::CborSerializable for ClaimsSet {}
 
+impl ClaimsSet {
+    /// Indicate whether the claims set holds a claim with the given `name`.
+    pub fn has_claim(&self, name: &ClaimName) -> bool {
+        match name {
+            x if *x == ISS => self.issuer.is_some(),
+            x if *x == SUB => self.subject.is_some(),
+            x if *x == AUD => self.audience.is_some(),
+            x if *x == EXP => self.expiration_time.is_some(),
+            x if *x == NBF => self.not_before.is_some(),
+            x if *x == IAT => self.issued_at.is_some(),
+            x if *x == CTI => self.cwt_id.is_some(),
+            name => self.rest.iter().any(|(n, _)| n == name),
+        }
+    }
+}
+
 const ISS: ClaimName = ClaimName::Assigned(iana::CwtClaimName::Iss);
 const SUB: ClaimName = ClaimName::Assigned(iana::CwtClaimName::Sub);
 const AUD: ClaimName = ClaimName::Assigned(iana::CwtClaimName::Aud);
@@ -15,8 +15,18 @@
 ////////////////////////////////////////////////////////////////////////////////
 
 use super::*;
-use crate::{cbor::value::Value, iana, iana::WithPrivateRange, util::expect_err, CborSerializable};
-use alloc::{borrow::ToOwned, vec};
+use crate::{
+    cbor::value::Value, iana, iana::WithPrivateRange, util::expect_err, CborSerializable,
+    CoseError, CoseHeaders, Label,
+};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 
 #[test]
 fn test_cwt_encode() {
@@ -233,3 +243,702 @@ fn test_cwt_dup_claim() {
     let result = ClaimsSet::from_slice(&data);
     expect_err(result, "duplicate map key");
 }
+
+#[test]
+fn test_has_claim() {
+    let claims = ClaimsSetBuilder::new()
+        .issuer("iss".to_owned())
+        .not_before(Timestamp::WholeSeconds(1))
+        .claim(iana::CwtClaimName::Nonce, Value::Bytes(vec![1]))
+        .text_claim("x".to_owned(), Value::Null)
+        .build();
+    assert!(claims.has_claim(&ClaimName::Assigned(iana::CwtClaimName::Iss)));
+    assert!(claims.has_claim(&ClaimName::Assigned(iana::CwtClaimName::Nbf)));
+    assert!(claims.has_claim(&ClaimName::Assigned(iana::CwtClaimName::Nonce)));
+    assert!(claims.has_claim(&ClaimName::Text("x".to_owned())));
+    assert!(!claims.has_claim(&ClaimName::Assigned(iana::CwtClaimName::Sub)));
+    assert!(!claims.has_claim(&ClaimName::Assigned(iana::CwtClaimName::Exp)));
+    assert!(!claims.has_claim(&ClaimName::Text("y".to_owned())));
+    assert!(!claims.has_claim(&ClaimName::PrivateUse(-70_000)));
+}
+
+#[test]
+fn test_claims_validate_time() {
+    let claims = ClaimsSetBuilder::new()
+        .not_before(Timestamp::WholeSeconds(1000))
+        .expiration_time(Timestamp::WholeSeconds(2000))
+        .build();
+    let tests = [
+        (
+            Timestamp::WholeSeconds(999),
+            0,
+            Err(ClaimsError::NotYetValid),
+        ),
+        (Timestamp::WholeSeconds(1000), 0, Ok(())),
+        (Timestamp::WholeSeconds(1999), 0, Ok(())),
+        (Timestamp::WholeSeconds(2000), 0, Err(ClaimsError::Expired)),
+        (Timestamp::WholeSeconds(990), 10, Ok(())),
+        (
+            Timestamp::WholeSeconds(989),
+            10,
+            Err(ClaimsError::NotYetValid),
+        ),
+        (Timestamp::WholeSeconds(2009), 10, Ok(())),
+        (Timestamp::WholeSeconds(2010), 10, Err(ClaimsError::Expired)),
+        (
+            Timestamp::FractionalSeconds(999.5),
+            0,
+            Err(ClaimsError::NotYetValid),
+        ),
+        (Timestamp::FractionalSeconds(1999.5), 0, Ok(())),
+        (Timestamp::FractionalSeconds(2000.5), 1, Ok(())),
+        (
+            Timestamp::FractionalSeconds(2001.0),
+            1,
+            Err(ClaimsError::Expired),
+        ),
+        (
+            Timestamp::WholeSeconds(i64::MIN),
+            0,
+            Err(ClaimsError::NotYetValid),
+        ),
+        (
+            Timestamp::WholeSeconds(i64::MAX),
+            u32::MAX,
+            Err(ClaimsError::Expired),
+        ),
+        (
+            Timestamp::FractionalSeconds(f64::NAN),
+            0,
+            Err(ClaimsError::InvalidTimestamp),
+        ),
+        (
+            Timestamp::FractionalSeconds(f64::INFINITY),
+            0,
+            Err(ClaimsError::InvalidTimestamp),
+        ),
+    ];
+    for (now, leeway, want) in tests {
+        let validator = ClaimsValidator::new(now.clone()).leeway(leeway);
+        assert_eq!(
+            validator.validate(&claims),
+            want,
+            "for {:?}, {}",
+            now,
+            leeway
+        );
+    }
+
+    // Fractional claim values.
+    let claims = ClaimsSetBuilder::new()
+        .not_before(Timestamp::FractionalSeconds(1000.25))
+        .expiration_time(Timestamp::FractionalSeconds(2000.75))
+        .build();
+    let tests = [
+        (Timestamp::WholeSeconds(1000), Err(ClaimsError::NotYetValid)),
+        (Timestamp::FractionalSeconds(1000.25), Ok(())),
+        (Timestamp::WholeSeconds(2000), Ok(())),
+        (
+            Timestamp::FractionalSeconds(2000.75),
+            Err(ClaimsError::Expired),
+        ),
+    ];
+    for (now, want) in tests {
+        let validator = ClaimsValidator::new(now.clone());
+        assert_eq!(validator.validate(&claims), want, "for {:?}", now);
+    }
+
+    let claims = ClaimsSetBuilder::new()
+        .expiration_time(Timestamp::FractionalSeconds(f64::NAN))
+        .build();
+    let validator = ClaimsValidator::new(Timestamp::WholeSeconds(0));
+    assert_eq!(
+        validator.validate(&claims),
+        Err(ClaimsError::InvalidTimestamp)
+    );
+
+    // No time-based claims means no time constraints.
+    let validator = ClaimsValidator::new(|| Timestamp::WholeSeconds(0));
+    assert!(validator.validate(&ClaimsSet::default()).is_ok());
+}
+
+#[test]
+fn test_claims_validate_policy() {
+    let claims = ClaimsSetBuilder::new()
+        .issuer("iss".to_owned())
+        .audience("aud".to_owned())
+        .issued_at(Timestamp::WholeSeconds(10))
+        .build();
+    let clock = || Timestamp::WholeSeconds(100);
+    let tests = vec![
+        (ClaimsValidator::new(clock), Ok(())),
+        (
+            ClaimsValidator::new(clock)
+                .issuer("other".to_owned())
+                .issuer("iss".to_owned()),
+            Ok(()),
+        ),
+        (
+            ClaimsValidator::new(clock).issuer("other".to_owned()),
+            Err(ClaimsError::WrongIssuer("iss".to_owned())),
+        ),
+        (
+            ClaimsValidator::new(clock).audience("aud".to_owned()),
+            Ok(()),
+        ),
+        (
+            ClaimsValidator::new(clock).audience("other".to_owned()),
+            Err(ClaimsError::WrongAudience("aud".to_owned())),
+        ),
+        (
+            ClaimsValidator::new(clock)
+                .require(iana::CwtClaimName::Iat)
+                .require(iana::CwtClaimName::Iss),
+            Ok(()),
+        ),
+        (
+            ClaimsValidator::new(clock).require(iana::CwtClaimName::Exp),
+            Err(ClaimsError::MissingClaim(ClaimName::Assigned(
+                iana::CwtClaimName::Exp,
+            ))),
+        ),
+        (
+            ClaimsValidator::new(clock).require_claim(ClaimName::Text("x".to_owned())),
+            Err(ClaimsError::MissingClaim(ClaimName::Text("x".to_owned()))),
+        ),
+    ];
+    for (i, (validator, want)) in tests.into_iter().enumerate() {
+        assert_eq!(validator.validate(&claims), want, "case {}", i);
+    }
+
+    // Configured issuer and audience policies require the corresponding claims.
+    let validator = ClaimsValidator::new(clock).issuer("iss".to_owned());
+    assert_eq!(
+        validator.validate(&ClaimsSet::default()),
+        Err(ClaimsError::MissingClaim(ClaimName::Assigned(
+            iana::CwtClaimName::Iss
+        )))
+    );
+    let validator = ClaimsValidator::new(clock).audience("aud".to_owned());
+    assert_eq!(
+        validator.validate(&ClaimsSet::default()),
+        Err(ClaimsError::MissingClaim(ClaimName::Assigned(
+            iana::CwtClaimName::Aud
+        )))
+    );
+}
+
+#[test]
+fn test_claims_error_display() {
+    let tests = [
+        (
+            ClaimsError::MissingClaim(ClaimName::Assigned(iana::CwtClaimName::Exp)),
+            "missing claim Assigned(Exp)",
+        ),
+        (
+            ClaimsError::WrongIssuer("a".to_owned()),
+            "unexpected issuer \"a\"",
+        ),
+        (
+            ClaimsError::WrongAudience("b".to_owned()),
+            "unexpected audience \"b\"",
+        ),
+        (ClaimsError::Expired, "expired"),
+        (ClaimsError::NotYetValid, "not yet valid"),
+        (ClaimsError::InvalidTimestamp, "invalid timestamp"),
+    ];
+    for (err, want) in tests {
+        assert_eq!(err.to_string(), want);
+    }
+}
+
+/// Insecure stand-in for a signature or MAC algorithm.
+fn fake_tag(data: &[u8]) -> Vec<u8> {
+    vec![
+        data.len() as u8,
+        data.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)),
+    ]
+}
+
+fn fake_verify(tag: &[u8], data: &[u8]) -> Result<(), String> {
+    if tag == fake_tag(data) {
+        Ok(())
+    } else {
+        Err("bad tag".to_owned())
+    }
+}
+
+/// Insecure stand-in for an AEAD.
+fn fake_encrypt(plaintext: &[u8], aad: &[u8]) -> Vec<u8> {
+    let mut ct: Vec<u8> = plaintext.iter().map(|b| b ^ 0x5a).collect();
+    ct.extend(fake_tag(aad));
+    ct
+}
+
+fn fake_decrypt(ct: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    let (ct, tag) = ct.split_at(ct.len() - 2);
+    fake_verify(tag, aad)?;
+    Ok(ct.iter().map(|b| b ^ 0x5a).collect())
+}
+
+fn cwt_claims() -> ClaimsSet {
+    // Claims from RFC 8392 section A.1.
+    ClaimsSetBuilder::new()
+        .issuer("coap://as.example.com".to_owned())
+        .subject("erikw".to_owned())
+        .audience("coap://light.example.com".to_owned())
+        .expiration_time(Timestamp::WholeSeconds(1444064944))
+        .not_before(Timestamp::WholeSeconds(1443944944))
+        .issued_at(Timestamp::WholeSeconds(1443944944))
+        .cwt_id(vec![0x0b, 0x71])
+        .build()
+}
+
+#[test]
+fn test_cwt_sign_verify() {
+    let tests = [
+        (CwtTag::Untagged, "84"),
+        (CwtTag::Cose, "d284"),
+        (CwtTag::Cwt, "d83dd284"),
+    ];
+    for (tag, prefix) in tests {
+        let builder = crate::CoseSign1Builder::new().protected(
+            crate::HeaderBuilder::new()
+                .algorithm(iana::Algorithm::ES256)
+                .build(),
+        );
+        let data = sign_cwt(
+            CwtContent::Claims(cwt_claims()),
+            builder,
+            b"aad",
+            tag,
+            fake_tag,
+        )
+        .unwrap();
+        assert!(hex::encode(&data).starts_with(prefix), "for {:?}", tag);
+
+        let claims = verify_signed_cwt(&data, b"aad", fake_verify).unwrap();
+        assert_eq!(claims, cwt_claims());
+        let result = verify_signed_cwt(&data, b"other", fake_verify);
+        assert!(matches!(result, Err(CwtError::Crypto(_))));
+
+        // The wrong message type is rejected.
+        let result = verify_maced_cwt(&data, b"aad", fake_verify);
+        if tag == CwtTag::Untagged {
+            // An untagged COSE_Sign1 parses as a COSE_Mac0, but fails verification.
+            assert!(matches!(result, Err(CwtError::Crypto(_))));
+        } else {
+            assert!(matches!(
+                result,
+                Err(CwtError::UnexpectedMessageType(CwtMessageType::Sign1))
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_cwt_mac_verify() {
+    for tag in [CwtTag::Untagged, CwtTag::Cose, CwtTag::Cwt] {
+        let builder = crate::CoseMac0Builder::new().protected(
+            crate::HeaderBuilder::new()
+                .algorithm(iana::Algorithm::HMAC_256_64)
+                .build(),
+        );
+        let data = mac_cwt(
+            CwtContent::Claims(cwt_claims()),
+            builder,
+            b"",
+            tag,
+            fake_tag,
+        )
+        .unwrap();
+        let claims = verify_maced_cwt(&data, b"", fake_verify).unwrap();
+        assert_eq!(claims, cwt_claims());
+        let result = verify_maced_cwt(&data, b"", |_tag, _data| Err("no".to_owned()));
+        assert!(matches!(result, Err(CwtError::Crypto(_))));
+    }
+}
+
+#[test]
+fn test_cwt_encrypt_decrypt() {
+    for tag in [CwtTag::Untagged, CwtTag::Cose, CwtTag::Cwt] {
+        let builder = crate::CoseEncrypt0Builder::new().protected(
+            crate::HeaderBuilder::new()
+                .algorithm(iana::Algorithm::A128GCM)
+                .build(),
+        );
+        let data = encrypt_cwt(
+            CwtContent::Claims(cwt_claims()),
+            builder,
+            b"",
+            tag,
+            fake_encrypt,
+        )
+        .unwrap();
+        let claims = decrypt_cwt(&data, b"", fake_decrypt).unwrap();
+        assert_eq!(claims, cwt_claims());
+        let result = decrypt_cwt(&data, b"x", fake_decrypt);
+        assert!(matches!(result, Err(CwtError::Crypto(_))));
+    }
+}
+
+#[test]
+fn test_cwt_nested() {
+    // Sign, then encrypt (as per RFC 8392 section A.6).
+    let inner = sign_cwt(
+        CwtContent::Claims(cwt_claims()),
+        crate::CoseSign1Builder::new(),
+        b"",
+        CwtTag::Cose,
+        fake_tag,
+    )
+    .unwrap();
+    let outer = encrypt_cwt(
+        CwtContent::Nested(inner.clone()),
+        crate::CoseEncrypt0Builder::new(),
+        b"",
+        CwtTag::Cwt,
+        fake_encrypt,
+    )
+    .unwrap();
+
+    let msg = CwtMessage::from_slice(&outer, None).unwrap();
+    assert_eq!(msg.message_type(), CwtMessageType::Encrypt0);
+    assert!(msg.is_nested());
+    assert_eq!(
+        msg.protected_header().content_type,
+        Some(crate::ContentType::Assigned(iana::CoapContentFormat::Cwt))
+    );
+    let msg = CwtMessage::from_slice(&inner, None).unwrap();
+    assert_eq!(msg.message_type(), CwtMessageType::Sign1);
+    assert!(!msg.is_nested());
+
+    let mut layers = Vec::new();
+    let claims = verify_cwt(&outer, None, |msg| {
+        layers.push(msg.message_type());
+        match msg {
+            CwtMessage::Sign1(_) => msg.verify_signature(b"", fake_verify),
+            CwtMessage::Encrypt0(_) => msg.decrypt(b"", fake_decrypt),
+            CwtMessage::Mac0(_) => Err(CwtError::UnexpectedMessageType(CwtMessageType::Mac0)),
+        }
+    })
+    .unwrap();
+    assert_eq!(claims, cwt_claims());
+    assert_eq!(
+        layers,
+        vec![CwtMessageType::Encrypt0, CwtMessageType::Sign1]
+    );
+
+    // The single-type helpers reject the inner message.
+    let result = decrypt_cwt(&outer, b"", fake_decrypt);
+    assert!(matches!(
+        result,
+        Err(CwtError::UnexpectedMessageType(CwtMessageType::Sign1))
+    ));
+
+    // An existing content type (here in text form) is preserved.
+    let outer = sign_cwt(
+        CwtContent::Nested(inner.clone()),
+        crate::CoseSign1Builder::new().unprotected(
+            crate::HeaderBuilder::new()
+                .content_type("application/cwt".to_owned())
+                .build(),
+        ),
+        b"",
+        CwtTag::Untagged,
+        fake_tag,
+    )
+    .unwrap();
+    let msg = CwtMessage::from_slice(&outer, Some(CwtMessageType::Sign1)).unwrap();
+    assert!(msg.is_nested());
+    assert!(msg.protected_header().content_type.is_none());
+    let claims = verify_signed_cwt(&outer, b"", fake_verify).unwrap();
+    assert_eq!(claims, cwt_claims());
+
+    // Too many layers are rejected.
+    let mut data = inner;
+    for _ in 0..8 {
+        data = sign_cwt(
+            CwtContent::Nested(data),
+            crate::CoseSign1Builder::new(),
+            b"",
+            CwtTag::Cose,
+            fake_tag,
+        )
+        .unwrap();
+    }
+    let result = verify_signed_cwt(&data, b"", fake_verify);
+    assert!(matches!(result, Err(CwtError::NestingTooDeep)));
+}
+
+#[test]
+fn test_cwt_message_fail() {
+    let tests = [
+        // Untagged with no default type.
+        ("8440a0f640", None),
+        // CWT tag without a COSE tag.
+        ("d83d8440a0f640", Some(CwtMessageType::Sign1)),
+        // Unsupported COSE tag.
+        ("d8628440a0f640", Some(CwtMessageType::Sign1)),
+        // Not a COSE_Sign1.
+        ("d28340a0f6", None),
+    ];
+    for (data, default_type) in tests {
+        let result = CwtMessage::from_slice(&hex::decode(data).unwrap(), default_type);
+        assert!(result.is_err(), "for {}", data);
+    }
+    let msg = CwtMessage::from_slice(
+        &hex::decode("8440a0f640").unwrap(),
+        Some(CwtMessageType::Mac0),
+    )
+    .unwrap();
+    assert_eq!(msg.message_type(), CwtMessageType::Mac0);
+
+    // Detached payloads are rejected.
+    let result = verify_signed_cwt(&hex::decode("d28440a0f640").unwrap(), b"", fake_verify);
+    assert!(matches!(result, Err(CwtError::MissingPayload)));
+    let result = verify_maced_cwt(&hex::decode("d18440a0f640").unwrap(), b"", fake_verify);
+    assert!(matches!(result, Err(CwtError::MissingPayload)));
+    let result = decrypt_cwt(&hex::decode("d08340a0f6").unwrap(), b"", fake_decrypt);
+    assert!(matches!(result, Err(CwtError::MissingPayload)));
+
+    // The payload must be a claims set.
+    let data = sign_cwt(
+        CwtContent::Nested(vec![0x01]),
+        crate::CoseSign1Builder::new().protected(
+            crate::HeaderBuilder::new()
+                .content_format(iana::CoapContentFormat::Cbor)
+                .build(),
+        ),
+        b"",
+        CwtTag::Cose,
+        fake_tag,
+    )
+    .unwrap();
+    let result = verify_signed_cwt(&data, b"", fake_verify);
+    assert!(matches!(result, Err(CwtError::Cose(_))));
+}
+
+#[test]
+fn test_cwt_error_display() {
+    let tests: [(CwtError<String>, &str); 5] = [
+        (
+            CwtError::Cose(CoseError::ExtraneousData),
+            "invalid CWT: extraneous data in CBOR input",
+        ),
+        (
+            CwtError::UnexpectedMessageType(CwtMessageType::Mac0),
+            "unexpected message type Mac0",
+        ),
+        (CwtError::MissingPayload, "missing payload"),
+        (CwtError::NestingTooDeep, "CWT nested too deeply"),
+        (
+            CwtError::Crypto("bad".to_owned()),
+            "verification failed: bad",
+        ),
+    ];
+    for (err, want) in tests {
+        assert_eq!(err.to_string(), want);
+        assert_eq!(format!("{err:?}"), want);
+    }
+}
+
+#[test]
+fn test_confirmation_encode() {
+    let tests = vec![
+        (
+            // Example from RFC 8747 section 3.4.
+            Confirmation::KeyId(hex::decode("dfd1aa976d8d4575a0fe34b96de2bfad").unwrap()),
+            concat!(
+                "a1", // 1-map
+                "03",
+                "50",
+                "dfd1aa976d8d4575a0fe34b96de2bfad" // 3 (kid) => 16-bstr
+            ),
+        ),
+        (
+            // Example from RFC 8747 section 3.2.
+            Confirmation::CoseKey(
+                crate::CoseKeyBuilder::new_ec2_pub_key(
+                    iana::EllipticCurve::P_256,
+                    hex::decode("d7cc072de2205bdc1537a543d53c60a6acb62eccd890c7fa27c9e354089bbe13")
+                        .unwrap(),
+                    hex::decode("f95e1d4b851a2cc80fff87d8e23f22afb725d535e515d020731e79a3b4e47120")
+                        .unwrap(),
+                )
+                .build(),
+            ),
+            concat!(
+                "a1", // 1-map
+                "01", // 1 (COSE_Key) =>
+                "a4", // 4-map
+                "01",
+                "02", // 1 (kty) => EC2
+                "20",
+                "01", // -1 (crv) => P-256
+                "21",
+                "5820",
+                "d7cc072de2205bdc1537a543d53c60a6acb62eccd890c7fa27c9e354089bbe13",
+                "22",
+                "5820",
+                "f95e1d4b851a2cc80fff87d8e23f22afb725d535e515d020731e79a3b4e47120",
+            ),
+        ),
+        (
+            Confirmation::EncryptedCoseKey(Box::new(EncryptedCoseKey::Encrypt0(
+                crate::CoseEncrypt0Builder::new()
+                    .ciphertext(vec![1])
+                    .build(),
+            ))),
+            concat!(
+                "a1", // 1-map
+                "02", // 2 (Encrypted_COSE_Key) =>
+                "83", "40", "a0", "4101", // [h'', {}, h'01']
+            ),
+        ),
+        (
+            Confirmation::EncryptedCoseKey(Box::new(EncryptedCoseKey::Encrypt(
+                crate::CoseEncryptBuilder::new().ciphertext(vec![1]).build(),
+            ))),
+            concat!(
+                "a1", // 1-map
+                "02", // 2 (Encrypted_COSE_Key) =>
+                "84", "40", "a0", "4101", "80", // [h'', {}, h'01', []]
+            ),
+        ),
+        (
+            Confirmation::Other(Label::Int(4), Value::Map(vec![])),
+            concat!(
+                "a1", // 1-map
+                "04", "a0", // 4 (osc) => {}
+            ),
+        ),
+        (
+            Confirmation::Other(Label::Text("x".to_owned()), Value::Null),
+            concat!(
+                "a1", // 1-map
+                "6178", "f6", // "x" => null
+            ),
+        ),
+    ];
+    for (i, (cnf, cnf_data)) in tests.into_iter().enumerate() {
+        let got = cnf.clone().to_vec().unwrap();
+        assert_eq!(hex::encode(&got), cnf_data, "case {}", i);
+        let got = Confirmation::from_slice(&got).unwrap();
+        // Decoding retains the original protected header data, so compare re-encoded forms.
+        assert_eq!(hex::encode(got.to_vec().unwrap()), cnf_data, "case {}", i);
+    }
+
+    // Tagged forms of an encrypted key are accepted.
+    let got = Confirmation::from_slice(&hex::decode("a102d08340a04101").unwrap()).unwrap();
+    assert!(matches!(
+        got,
+        Confirmation::EncryptedCoseKey(k) if matches!(*k, EncryptedCoseKey::Encrypt0(_))
+    ));
+    let got = Confirmation::from_slice(&hex::decode("a102d8608440a0410180").unwrap()).unwrap();
+    assert!(matches!(
+        got,
+        Confirmation::EncryptedCoseKey(k) if matches!(*k, EncryptedCoseKey::Encrypt(_))
+    ));
+}
+
+#[test]
+fn test_confirmation_decode_fail() {
+    let tests = [
+        ("a0", "expected single-entry map"),
+        ("a2034101044102", "expected single-entry map"),
+        ("8103", "expected map"),
+        ("a10301", "expected bstr"),
+        ("a10201", "expected COSE_Encrypt0/COSE_Encrypt"),
+        ("a1028240a0", "expected COSE_Encrypt0/COSE_Encrypt"),
+        ("a101a0", "mandatory kty label"),
+        ("a1f6a0", "expected int/tstr"),
+    ];
+    for (cnf_data, err_msg) in tests {
+        let data = hex::decode(cnf_data).unwrap();
+        let result = Confirmation::from_slice(&data);
+        expect_err(result, err_msg);
+    }
+}
+
+#[test]
+fn test_claims_confirmation() {
+    let cnf = Confirmation::KeyId(vec![1, 2, 3]);
+    let claims = ClaimsSetBuilder::new()
+        .issuer("iss".to_owned())
+        .confirmation(cnf.clone())
+        .build();
+    assert_eq!(claims.confirmation().unwrap(), Some(cnf));
+    let claims = ClaimsSet::from_slice(&claims.to_vec().unwrap()).unwrap();
+    assert_eq!(
+        claims.confirmation().unwrap(),
+        Some(Confirmation::KeyId(vec![1, 2, 3]))
+    );
+
+    assert_eq!(ClaimsSet::default().confirmation().unwrap(), None);
+    let claims = ClaimsSetBuilder::new()
+        .claim(iana::CwtClaimName::Cnf, Value::Null)
+        .build();
+    expect_err(claims.confirmation(), "expected map");
+
+    // Setting the confirmation again replaces the existing `cnf` claim.
+    let claims = ClaimsSetBuilder::new()
+        .claim(iana::CwtClaimName::Cnf, Value::Null)
+        .confirmation(Confirmation::KeyId(vec![1]))
+        .confirmation(Confirmation::KeyId(vec![2]))
+        .build();
+    assert_eq!(claims.rest.len(), 1);
+    assert_eq!(
+        claims.confirmation().unwrap(),
+        Some(Confirmation::KeyId(vec![2]))
+    );
+    let claims = ClaimsSet::from_slice(&claims.to_vec().unwrap()).unwrap();
+    assert_eq!(
+        claims.confirmation().unwrap(),
+        Some(Confirmation::KeyId(vec![2]))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_claims_confirmation_panic() {
+    // A key with duplicate parameters can't be encoded.
+    let key = crate::CoseKeyBuilder::new_symmetric_key(vec![1])
+        .param(-1, Value::Null)
+        .build();
+    let _claims = ClaimsSetBuilder::new()
+        .confirmation(Confirmation::CoseKey(key))
+        .build();
+}
+
+#[test]
+fn test_encrypted_cose_key_decrypt() {
+    let key = crate::CoseKeyBuilder::new_symmetric_key(vec![1, 2, 3])
+        .key_id(b"pop".to_vec())
+        .build();
+    let encrypt0 = crate::CoseEncrypt0Builder::new()
+        .create_ciphertext(&key.clone().to_vec().unwrap(), b"", fake_encrypt)
+        .build();
+    let encrypted = EncryptedCoseKey::Encrypt0(encrypt0);
+    assert_eq!(encrypted.decrypt_key(b"", fake_decrypt).unwrap(), key);
+    let result = encrypted.decrypt_key(b"x", fake_decrypt);
+    assert!(matches!(result, Err(CwtError::Crypto(_))));
+
+    let encrypt = crate::CoseEncryptBuilder::new()
+        .create_ciphertext(&key.clone().to_vec().unwrap(), b"", fake_encrypt)
+        .build();
+    let encrypted = EncryptedCoseKey::Encrypt(encrypt);
+    assert_eq!(encrypted.decrypt_key(b"", fake_decrypt).unwrap(), key);
+
+    // Missing or invalid content.
+    let encrypted = EncryptedCoseKey::Encrypt0(crate::CoseEncrypt0Builder::new().build());
+    let result = encrypted.decrypt_key(b"", fake_decrypt);
+    assert!(matches!(result, Err(CwtError::MissingPayload)));
+    let encrypted = EncryptedCoseKey::Encrypt0(
+        crate::CoseEncrypt0Builder::new()
+            .create_ciphertext(&[0x01], b"", fake_encrypt)
+            .build(),
+    );
+    let result = encrypted.decrypt_key(b"", fake_decrypt);
+    assert!(matches!(result, Err(CwtError::Cose(_))));
+}
@@ -0,0 +1,453 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Issuance and verification of complete CWTs, as per RFC 8392 section 7.
+
+use super::ClaimsSet;
+use crate::{
+    cbor::value::Value, common::AsCborValue, enc_structure_data, header::ContentType, iana,
+    mac_structure_data, CborSerializable, CoseEncrypt0, CoseEncrypt0Builder, CoseError,
+    CoseHeaders, CoseMac0, CoseMac0Builder, CoseSign1, CoseSign1Builder, EncryptionContext, Header,
+    MacContext, TaggedCborSerializable,
+};
+use alloc::{boxed::Box, vec::Vec};
+
+/// Maximum number of COSE message layers (the outermost CWT and any nested CWTs) accepted when
+/// verifying.
+const MAX_LAYERS: usize = 8;
+
+/// The COSE message types that can protect a CWT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CwtMessageType {
+    /// A signed CWT, held in a `COSE_Sign1`.
+    Sign1,
+    /// A MACed CWT, held in a `COSE_Mac0`.
+    Mac0,
+    /// An encrypted CWT, held in a `COSE_Encrypt0`.
+    Encrypt0,
+}
+
+impl CwtMessageType {
+    /// Return the COSE CBOR tag for the message type.
+    fn tag(self) -> u64 {
+        match self {
+            CwtMessageType::Sign1 => CoseSign1::TAG,
+            CwtMessageType::Mac0 => CoseMac0::TAG,
+            CwtMessageType::Encrypt0 => CoseEncrypt0::TAG,
+        }
+    }
+
+    /// Return the message type for a COSE CBOR tag, if it is one that can protect a CWT.
+    fn from_tag(tag: u64) -> Option<Self> {
+        [
+            CwtMessageType::Sign1,
+            CwtMessageType::Mac0,
+            CwtMessageType::Encrypt0,
+        ]
+        .iter()
+        .find(|t| t.tag() == tag)
+        .copied()
+    }
+}
+
+/// The CBOR tags to include in an encoded CWT.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CwtTag {
+    /// No tags: the COSE message type must be known to the recipient from context.
+    Untagged,
+    /// The COSE CBOR tag for the message type.
+    Cose,
+    /// The CWT CBOR tag (61), wrapping the COSE CBOR tag for the message type.
+    Cwt,
+}
+
+/// The content of a CWT.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CwtContent {
+    /// A CWT Claims Set.
+    Claims(ClaimsSet),
+    /// An encoded nested CWT.  The content type of the enclosing message is set to
+    /// `application/cwt` (if no content type is already set).
+    Nested(Vec<u8>),
+}
+
+impl CwtContent {
+    /// Return the encoded payload, marking the `protected` header as holding a nested CWT if
+    /// necessary.
+    fn into_payload(
+        self,
+        protected: &mut Header,
+        unprotected: &Header,
+    ) -> Result<Vec<u8>, CoseError> {
+        match self {
+            CwtContent::Claims(claims) => claims.to_vec(),
+            CwtContent::Nested(cwt) => {
+                if protected.content_type.is_none() && unprotected.content_type.is_none() {
+                    protected.content_type =
+                        Some(ContentType::Assigned(iana::CoapContentFormat::Cwt));
+                }
+                Ok(cwt)
+            }
+        }
+    }
+}
+
+/// Encode a COSE message `value` of type `msg_type` as a CWT, with the given tags.
+fn encode(value: Value, msg_type: CwtMessageType, tag: CwtTag) -> Result<Vec<u8>, CoseError> {
+    let value = match tag {
+        CwtTag::Untagged => value,
+        CwtTag::Cose => Value::Tag(msg_type.tag(), Box::new(value)),
+        CwtTag::Cwt => Value::Tag(
+            iana::CborTag::Cwt as u64,
+            Box::new(Value::Tag(msg_type.tag(), Box::new(value))),
+        ),
+    };
+    value.to_vec()
+}
+
+/// Create a signed CWT holding `content`, using `builder` (which should already hold any header
+/// values) and `signer` to generate the signature bytes.
+pub fn sign_cwt<F>(
+    content: CwtContent,
+    builder: CoseSign1Builder,
+    external_aad: &[u8],
+    tag: CwtTag,
+    signer: F,
+) -> Result<Vec<u8>, CoseError>
+where
+    F: FnOnce(&[u8]) -> Vec<u8>,
+{
+    let mut sign1 = builder.build();
+    let payload = content.into_payload(&mut sign1.protected.header, &sign1.unprotected)?;
+    sign1.payload = Some(payload);
+    sign1.signature = signer(&sign1.tbs_data(external_aad));
+    encode(sign1.to_cbor_value()?, CwtMessageType::Sign1, tag)
+}
+
+/// Create a MACed CWT holding `content`, using `builder` (which should already hold any header
+/// values) and `create` to generate the tag bytes.
+pub fn mac_cwt<F>(
+    content: CwtContent,
+    builder: CoseMac0Builder,
+    external_aad: &[u8],
+    tag: CwtTag,
+    create: F,
+) -> Result<Vec<u8>, CoseError>
+where
+    F: FnOnce(&[u8]) -> Vec<u8>,
+{
+    let mut mac0 = builder.build();
+    let payload = content.into_payload(&mut mac0.protected.header, &mac0.unprotected)?;
+    mac0.tag = create(&mac_structure_data(
+        MacContext::CoseMac0,
+        mac0.protected.clone(),
+        external_aad,
+        &payload,
+    ));
+    mac0.payload = Some(payload);
+    encode(mac0.to_cbor_value()?, CwtMessageType::Mac0, tag)
+}
+
+/// Create an encrypted CWT holding `content`, using `builder` (which should already hold any
+/// header values) and `cipher` to generate the encrypted bytes from the plaintext and combined AAD
+/// (in that order).
+pub fn encrypt_cwt<F>(
+    content: CwtContent,
+    builder: CoseEncrypt0Builder,
+    external_aad: &[u8],
+    tag: CwtTag,
+    cipher: F,
+) -> Result<Vec<u8>, CoseError>
+where
+    F: FnOnce(&[u8], &[u8]) -> Vec<u8>,
+{
+    let mut encrypt0 = builder.build();
+    let payload = content.into_payload(&mut encrypt0.protected.header, &encrypt0.unprotected)?;
+    let aad = enc_structure_data(
+        EncryptionContext::CoseEncrypt0,
+        encrypt0.protected.clone(),
+        external_aad,
+    );
+    encrypt0.ciphertext = Some(cipher(&payload, &aad));
+    encode(encrypt0.to_cbor_value()?, CwtMessageType::Encrypt0, tag)
+}
+
+/// A COSE message that protects a CWT.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CwtMessage {
+    /// A signed CWT.
+    Sign1(CoseSign1),
+    /// A MACed CWT.
+    Mac0(CoseMac0),
+    /// An encrypted CWT.
+    Encrypt0(CoseEncrypt0),
+}
+
+impl CwtMessage {
+    /// Parse an encoded CWT, with or without the CWT and COSE CBOR tags.  The COSE message type is
+    /// taken from the COSE CBOR tag if present, and is otherwise `default_type`.
+    pub fn from_slice(
+        data: &[u8],
+        default_type: Option<CwtMessageType>,
+    ) -> Result<Self, CoseError> {
+        let mut value = Value::from_slice(data)?;
+        let mut cwt_tagged = false;
+        if let Value::Tag(tag, inner) = value {
+            if tag == iana::CborTag::Cwt as u64 {
+                cwt_tagged = true;
+                value = *inner;
+            } else {
+                value = Value::Tag(tag, inner);
+            }
+        }
+        let (msg_type, value) = match value {
+            Value::Tag(tag, inner) => match CwtMessageType::from_tag(tag) {
+                Some(msg_type) => (msg_type, *inner),
+                None => return Err(CoseError::UnexpectedItem("other tag", "COSE tag")),
+            },
+            // The CWT tag must be followed by a COSE tag.
+            _ if cwt_tagged => return Err(CoseError::UnexpectedItem("untagged", "COSE tag")),
+            value => match default_type {
+                Some(msg_type) => (msg_type, value),
+                None => return Err(CoseError::UnexpectedItem("untagged", "COSE tag")),
+            },
+        };
+        Ok(match msg_type {
+            CwtMessageType::Sign1 => CwtMessage::Sign1(CoseSign1::from_cbor_value(value)?),
+            CwtMessageType::Mac0 => CwtMessage::Mac0(CoseMac0::from_cbor_value(value)?),
+            CwtMessageType::Encrypt0 => CwtMessage::Encrypt0(CoseEncrypt0::from_cbor_value(value)?),
+        })
+    }
+
+    /// Return the type of the message.
+    pub fn message_type(&self) -> CwtMessageType {
+        match self {
+            CwtMessage::Sign1(_) => CwtMessageType::Sign1,
+            CwtMessage::Mac0(_) => CwtMessageType::Mac0,
+            CwtMessage::Encrypt0(_) => CwtMessageType::Encrypt0,
+        }
+    }
+
+    /// Indicate whether the message holds a nested CWT, as indicated by a content type of
+    /// `application/cwt`.
+    pub fn is_nested(&self) -> bool {
+        self.protected_header()
+            .content_type
+            .as_ref()
+            .or(self.unprotected_header().content_type.as_ref())
+            .is_some_and(|ct| match ct {
+                ContentType::Assigned(ct) => *ct == iana::CoapContentFormat::Cwt,
+                ContentType::Text(ct) => ct == "application/cwt",
+            })
+    }
+}
+
+impl CoseHeaders for CwtMessage {
+    fn protected_header(&self) -> &Header {
+        match self {
+            CwtMessage::Sign1(m) => m.protected_header(),
+            CwtMessage::Mac0(m) => m.protected_header(),
+            CwtMessage::Encrypt0(m) => m.protected_header(),
+        }
+    }
+    fn unprotected_header(&self) -> &Header {
+        match self {
+            CwtMessage::Sign1(m) => m.unprotected_header(),
+            CwtMessage::Mac0(m) => m.unprotected_header(),
+            CwtMessage::Encrypt0(m) => m.unprotected_header(),
+        }
+    }
+}
+
+/// The error type returned when a CWT fails verification.
+pub enum CwtError<E> {
+    /// The CWT or its claims could not be parsed.
+    Cose(CoseError),
+    /// The COSE message type is not accepted.
+    UnexpectedMessageType(CwtMessageType),
+    /// The COSE message has no payload or ciphertext.
+    MissingPayload,
+    /// The CWT is nested too deeply.
+    NestingTooDeep,
+    /// The cryptographic verification or decryption failed.
+    Crypto(E),
+}
+
+impl<E: core::fmt::Display> core::error::Error for CwtError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Debug for CwtError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for CwtError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> CwtError<E> {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CwtError::Cose(e) => write!(f, "invalid CWT: {e}"),
+            CwtError::UnexpectedMessageType(t) => write!(f, "unexpected message type {t:?}"),
+            CwtError::MissingPayload => write!(f, "missing payload"),
+            CwtError::NestingTooDeep => write!(f, "CWT nested too deeply"),
+            CwtError::Crypto(e) => write!(f, "verification failed: {e}"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for CwtError<E> {
+    fn from(e: CoseError) -> Self {
+        CwtError::Cose(e)
+    }
+}
+
+impl CwtMessage {
+    /// Verify the signature of a signed CWT, using `verifier` on the signature value and
+    /// serialized data (in that order), and return the payload.
+    pub fn verify_signature<F, E>(
+        &self,
+        external_aad: &[u8],
+        verifier: F,
+    ) -> Result<Vec<u8>, CwtError<E>>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+    {
+        let sign1 = match self {
+            CwtMessage::Sign1(sign1) => sign1,
+            msg => return Err(CwtError::UnexpectedMessageType(msg.message_type())),
+        };
+        let payload = sign1.payload.as_ref().ok_or(CwtError::MissingPayload)?;
+        sign1
+            .verify_signature(external_aad, verifier)
+            .map_err(CwtError::Crypto)?;
+        Ok(payload.clone())
+    }
+
+    /// Verify the tag of a MACed CWT, using `verify` on the tag value and the combined
+    /// to-be-MACed data (in that order), and return the payload.
+    pub fn verify_tag<F, E>(&self, external_aad: &[u8], verify: F) -> Result<Vec<u8>, CwtError<E>>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+    {
+        let mac0 = match self {
+            CwtMessage::Mac0(mac0) => mac0,
+            msg => return Err(CwtError::UnexpectedMessageType(msg.message_type())),
+        };
+        mac0.verify_payload_tag(
+            external_aad,
+            || CwtError::MissingPayload,
+            |tag, data| verify(tag, data).map_err(CwtError::Crypto),
+        )?;
+        Ok(mac0.payload.clone().unwrap_or_default())
+    }
+
+    /// Decrypt an encrypted CWT, using `cipher` to decrypt the cipher text and combined AAD, and
+    /// return the plaintext payload.
+    pub fn decrypt<F, E>(&self, external_aad: &[u8], cipher: F) -> Result<Vec<u8>, CwtError<E>>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<Vec<u8>, E>,
+    {
+        let encrypt0 = match self {
+            CwtMessage::Encrypt0(encrypt0) => encrypt0,
+            msg => return Err(CwtError::UnexpectedMessageType(msg.message_type())),
+        };
+        encrypt0.decrypt_ciphertext(
+            external_aad,
+            || CwtError::MissingPayload,
+            |ct, aad| cipher(ct, aad).map_err(CwtError::Crypto),
+        )
+    }
+}
+
+/// Verify a CWT and any CWTs nested inside it, and return the innermost claims set.
+///
+/// The COSE message type of each layer is taken from its COSE CBOR tag if present, and is
+/// otherwise `default_type`.  The `open` function is used on each layer (outermost first) to
+/// verify or decrypt it and return its payload, typically by using one of the
+/// [`CwtMessage::verify_signature`], [`CwtMessage::verify_tag`] or [`CwtMessage::decrypt`]
+/// methods.  A layer whose content type is `application/cwt` holds a nested CWT.
+///
+/// Note that the claims are not validated; use a [`ClaimsValidator`](super::ClaimsValidator) to
+/// do so.
+pub fn verify_cwt<F, E>(
+    data: &[u8],
+    default_type: Option<CwtMessageType>,
+    mut open: F,
+) -> Result<ClaimsSet, CwtError<E>>
+where
+    F: FnMut(&CwtMessage) -> Result<Vec<u8>, CwtError<E>>,
+{
+    let mut msg = CwtMessage::from_slice(data, default_type)?;
+    for _ in 0..MAX_LAYERS {
+        let payload = open(&msg)?;
+        if !msg.is_nested() {
+            return Ok(ClaimsSet::from_slice(&payload)?);
+        }
+        msg = CwtMessage::from_slice(&payload, default_type)?;
+    }
+    Err(CwtError::NestingTooDeep)
+}
+
+/// Verify a signed CWT (which may be untagged), using `verifier` on the signature value and
+/// serialized data (in that order), and return its claims set.  Nested CWTs are accepted if they
+/// are also signed.
+pub fn verify_signed_cwt<F, E>(
+    data: &[u8],
+    external_aad: &[u8],
+    mut verifier: F,
+) -> Result<ClaimsSet, CwtError<E>>
+where
+    F: FnMut(&[u8], &[u8]) -> Result<(), E>,
+{
+    verify_cwt(data, Some(CwtMessageType::Sign1), |msg| {
+        msg.verify_signature(external_aad, &mut verifier)
+    })
+}
+
+/// Verify a MACed CWT (which may be untagged), using `verify` on the tag value and the combined
+/// to-be-MACed data (in that order), and return its claims set.  Nested CWTs are accepted if they
+/// are also MACed.
+pub fn verify_maced_cwt<F, E>(
+    data: &[u8],
+    external_aad: &[u8],
+    mut verify: F,
+) -> Result<ClaimsSet, CwtError<E>>
+where
+    F: FnMut(&[u8], &[u8]) -> Result<(), E>,
+{
+    verify_cwt(data, Some(CwtMessageType::Mac0), |msg| {
+        msg.verify_tag(external_aad, &mut verify)
+    })
+}
+
+/// Decrypt an encrypted CWT (which may be untagged), using `cipher` to decrypt the cipher text and
+/// combined AAD, and return its claims set.  Nested CWTs are accepted if they are also encrypted.
+pub fn decrypt_cwt<F, E>(
+    data: &[u8],
+    external_aad: &[u8],
+    mut cipher: F,
+) -> Result<ClaimsSet, CwtError<E>>
+where
+    F: FnMut(&[u8], &[u8]) -> Result<Vec<u8>, E>,
+{
+    verify_cwt(data, Some(CwtMessageType::Encrypt0), |msg| {
+        msg.decrypt(external_aad, &mut cipher)
+    })
+}
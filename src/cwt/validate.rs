@@ -0,0 +1,204 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Validation of CWT claims against a time source and an issuer/audience policy.
+
+use super::{ClaimName, ClaimsSet, Timestamp, AUD, ISS};
+use crate::iana;
+use alloc::{string::String, vec::Vec};
+
+/// Source of the current time, for checking time-based claims.
+///
+/// Implemented for closures returning a [`Timestamp`], and for [`Timestamp`] itself (as a fixed
+/// time).
+pub trait Clock {
+    /// Return the current time.
+    fn now(&self) -> Timestamp;
+}
+
+impl<F: Fn() -> Timestamp> Clock for F {
+    fn now(&self) -> Timestamp {
+        self()
+    }
+}
+
+impl Clock for Timestamp {
+    fn now(&self) -> Timestamp {
+        self.clone()
+    }
+}
+
+/// The error type returned when a [`ClaimsSet`] fails validation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClaimsError {
+    /// A required claim is not present.
+    MissingClaim(ClaimName),
+    /// The issuer is not one of the expected issuers.
+    WrongIssuer(String),
+    /// The audience is not the expected audience.
+    WrongAudience(String),
+    /// The expiration time has passed.
+    Expired,
+    /// The not-before time has not yet been reached.
+    NotYetValid,
+    /// A time-based claim (or the current time) is not a finite number of seconds.
+    InvalidTimestamp,
+}
+
+impl core::error::Error for ClaimsError {}
+
+impl core::fmt::Display for ClaimsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ClaimsError::MissingClaim(name) => write!(f, "missing claim {name:?}"),
+            ClaimsError::WrongIssuer(iss) => write!(f, "unexpected issuer {iss:?}"),
+            ClaimsError::WrongAudience(aud) => write!(f, "unexpected audience {aud:?}"),
+            ClaimsError::Expired => write!(f, "expired"),
+            ClaimsError::NotYetValid => write!(f, "not yet valid"),
+            ClaimsError::InvalidTimestamp => write!(f, "invalid timestamp"),
+        }
+    }
+}
+
+/// Return the value of a timestamp in seconds, failing for non-finite values.
+fn seconds(t: &Timestamp) -> Result<f64, ClaimsError> {
+    match t {
+        Timestamp::WholeSeconds(t) => Ok(*t as f64),
+        Timestamp::FractionalSeconds(t) if t.is_finite() => Ok(*t),
+        Timestamp::FractionalSeconds(_) => Err(ClaimsError::InvalidTimestamp),
+    }
+}
+
+/// Indicate whether `a + offset` is strictly before `b`.  Whole-second timestamps are compared
+/// exactly; otherwise the comparison is done in floating point.
+fn before(a: &Timestamp, offset: i64, b: &Timestamp) -> Result<bool, ClaimsError> {
+    match (a, b) {
+        (Timestamp::WholeSeconds(a), Timestamp::WholeSeconds(b)) => {
+            Ok(i128::from(*a) + i128::from(offset) < i128::from(*b))
+        }
+        _ => Ok(seconds(a)? + (offset as f64) < seconds(b)?),
+    }
+}
+
+/// Validator for the claims in a [`ClaimsSet`].
+///
+/// The validator checks that:
+///   - all required claims are present
+///   - the issuer (`iss`) is one of the expected issuers, if any are configured
+///   - the audience (`aud`) matches the expected audience, if configured
+///   - the current time is before the expiration time (`exp`), if present
+///   - the current time is at or after the not-before time (`nbf`), if present
+///
+/// Time comparisons allow for a configurable leeway, to cope with clock skew.
+#[derive(Clone, Debug)]
+pub struct ClaimsValidator<C: Clock> {
+    clock: C,
+    leeway: i64,
+    issuers: Vec<String>,
+    audience: Option<String>,
+    required: Vec<ClaimName>,
+}
+
+impl<C: Clock> ClaimsValidator<C> {
+    /// Create a validator that uses `clock` as its source of the current time, with no leeway and
+    /// no issuer, audience or required claim policy.
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            leeway: 0,
+            issuers: Vec::new(),
+            audience: None,
+            required: Vec::new(),
+        }
+    }
+
+    /// Set the leeway (in seconds) allowed when checking time-based claims.
+    #[must_use]
+    pub fn leeway(mut self, seconds: u32) -> Self {
+        self.leeway = i64::from(seconds);
+        self
+    }
+
+    /// Add an expected issuer.  If any issuers are configured, the `iss` claim is required and must
+    /// match one of them.
+    #[must_use]
+    pub fn issuer(mut self, issuer: String) -> Self {
+        self.issuers.push(issuer);
+        self
+    }
+
+    /// Set the expected audience.  If configured, the `aud` claim is required and must match it.
+    #[must_use]
+    pub fn audience(mut self, audience: String) -> Self {
+        self.audience = Some(audience);
+        self
+    }
+
+    /// Add a claim that must be present.
+    #[must_use]
+    pub fn require(mut self, name: iana::CwtClaimName) -> Self {
+        self.required.push(ClaimName::Assigned(name));
+        self
+    }
+
+    /// Add a claim, identified by an arbitrary [`ClaimName`], that must be present.
+    #[must_use]
+    pub fn require_claim(mut self, name: ClaimName) -> Self {
+        self.required.push(name);
+        self
+    }
+
+    /// Validate `claims` against the configured policy and the current time.
+    pub fn validate(&self, claims: &ClaimsSet) -> Result<(), ClaimsError> {
+        if let Some(name) = self.required.iter().find(|name| !claims.has_claim(name)) {
+            return Err(ClaimsError::MissingClaim(name.clone()));
+        }
+        if !self.issuers.is_empty() {
+            let iss = claims
+                .issuer
+                .as_ref()
+                .ok_or(ClaimsError::MissingClaim(ISS))?;
+            if !self.issuers.contains(iss) {
+                return Err(ClaimsError::WrongIssuer(iss.clone()));
+            }
+        }
+        if let Some(expected) = &self.audience {
+            let aud = claims
+                .audience
+                .as_ref()
+                .ok_or(ClaimsError::MissingClaim(AUD))?;
+            if aud != expected {
+                return Err(ClaimsError::WrongAudience(aud.clone()));
+            }
+        }
+
+        let now = self.clock.now();
+        seconds(&now)?;
+        if let Some(exp) = &claims.expiration_time {
+            // Valid while now < exp + leeway.
+            if !before(&now, -self.leeway, exp)? {
+                return Err(ClaimsError::Expired);
+            }
+        }
+        if let Some(nbf) = &claims.not_before {
+            // Valid once now >= nbf - leeway.
+            if before(&now, self.leeway, nbf)? {
+                return Err(ClaimsError::NotYetValid);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,510 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Open Profile for DICE functionality.
+//!
+//! A DICE chain (as sent by Android and Pigweed devices) is a CBOR array holding the root public
+//! key, followed by a `COSE_Sign1` certificate for each DICE layer.  The payload of each
+//! certificate is a CWT claims set, whose DICE-specific claims are held in typed form by
+//! [`DiceClaims`].  [`DiceChain::verify`] checks the signature of each certificate against the
+//! subject public key of the previous one, along with issuer/subject continuity.
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    cwt::{ClaimName, ClaimsSet},
+    util::{cbor_type_error, set_once, ValueTryAs},
+    CborSerializable, CoseError, CoseKey, CoseSign1, Label, TaggedCborSerializable,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::convert::TryInto;
+
+#[cfg(test)]
+mod tests;
+
+/// Claim label for the code hash.
+pub const CODE_HASH: i64 = -4670545;
+/// Claim label for the code descriptor.
+pub const CODE_DESCRIPTOR: i64 = -4670546;
+/// Claim label for the configuration hash.
+pub const CONFIG_HASH: i64 = -4670547;
+/// Claim label for the configuration descriptor.
+pub const CONFIG_DESCRIPTOR: i64 = -4670548;
+/// Claim label for the authority hash.
+pub const AUTHORITY_HASH: i64 = -4670549;
+/// Claim label for the authority descriptor.
+pub const AUTHORITY_DESCRIPTOR: i64 = -4670550;
+/// Claim label for the DICE mode.
+pub const MODE: i64 = -4670551;
+/// Claim label for the subject public key.
+pub const SUBJECT_PUBLIC_KEY: i64 = -4670552;
+/// Claim label for the key usage.
+pub const KEY_USAGE: i64 = -4670553;
+/// Claim label for the DICE profile name.
+pub const PROFILE_NAME: i64 = -4670554;
+
+/// Key usage bit (in the first byte of a key usage claim) for signing certificates.
+pub const KEY_USAGE_CERT_SIGN: u8 = 0x20;
+
+/// DICE mode of a layer, as held in a mode claim.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiceMode {
+    /// The mode has not been configured.
+    NotConfigured = 0,
+    /// Normal operation.
+    Normal = 1,
+    /// Debug mode.
+    Debug = 2,
+    /// Recovery or maintenance mode.
+    Recovery = 3,
+}
+
+impl AsCborValue for DiceMode {
+    /// Parse a DICE mode, which is normally encoded as a single-byte bstr but is also accepted as
+    /// an integer.
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mode: u8 = match value {
+            Value::Bytes(b) if b.len() == 1 => b[0],
+            Value::Bytes(_) => {
+                return Err(CoseError::UnexpectedItem("bstr", "single-byte bstr"));
+            }
+            v => v.try_as_integer()?.try_into()?,
+        };
+        match mode {
+            x if x == DiceMode::NotConfigured as u8 => Ok(DiceMode::NotConfigured),
+            x if x == DiceMode::Normal as u8 => Ok(DiceMode::Normal),
+            x if x == DiceMode::Debug as u8 => Ok(DiceMode::Debug),
+            x if x == DiceMode::Recovery as u8 => Ok(DiceMode::Recovery),
+            _ => Err(CoseError::OutOfRangeIntegerValue),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::Bytes(vec![self as u8]))
+    }
+}
+
+const COMPONENT_NAME: Label = Label::Int(-70002);
+const COMPONENT_VERSION: Label = Label::Int(-70003);
+const RESETTABLE: Label = Label::Int(-70004);
+const SECURITY_VERSION: Label = Label::Int(-70005);
+const RKP_VM_MARKER: Label = Label::Int(-70006);
+
+/// Configuration descriptor of a layer, as held (serialized) in a configuration descriptor claim.
+///
+/// ```cddl
+///  ConfigurationDescriptor = {
+///      ? -70002 : tstr,        ; Component name
+///      ? -70003 : int / tstr,  ; Component version
+///      ? -70004 : null,        ; Resettable
+///      ? -70005 : uint,        ; Security version
+///      ? -70006 : null,        ; RKP VM marker
+///  }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigDescriptor {
+    /// Name of the component.
+    pub component_name: Option<String>,
+    /// Version of the component, as an integer or text.
+    pub component_version: Option<Value>,
+    /// Whether the layer's secrets change on factory reset.
+    pub resettable: bool,
+    /// Security version of the component.
+    pub security_version: Option<u64>,
+    /// Whether the component is an RKP VM.
+    pub rkp_vm_marker: bool,
+    /// Any additional entries.
+    pub rest: Vec<(Label, Value)>,
+}
+
+impl CborSerializable for ConfigDescriptor {}
+
+impl AsCborValue for ConfigDescriptor {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut desc = Self::default();
+        let mut seen: Vec<Label> = Vec::new();
+        for (k, v) in value.try_as_map()? {
+            let k = Label::from_cbor_value(k)?;
+            if seen.contains(&k) {
+                return Err(CoseError::DuplicateMapKey);
+            }
+            seen.push(k.clone());
+            match k {
+                x if x == COMPONENT_NAME => desc.component_name = Some(v.try_as_string()?),
+                x if x == COMPONENT_VERSION => match v {
+                    Value::Integer(_) | Value::Text(_) => desc.component_version = Some(v),
+                    v => return cbor_type_error(&v, "int or tstr"),
+                },
+                x if x == RESETTABLE => match v {
+                    Value::Null => desc.resettable = true,
+                    v => return cbor_type_error(&v, "null"),
+                },
+                x if x == SECURITY_VERSION => {
+                    desc.security_version = Some(v.try_as_integer()?.try_into()?)
+                }
+                x if x == RKP_VM_MARKER => match v {
+                    Value::Null => desc.rkp_vm_marker = true,
+                    v => return cbor_type_error(&v, "null"),
+                },
+                k => desc.rest.push((k, v)),
+            }
+        }
+        Ok(desc)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut map = Vec::new();
+        if let Some(name) = self.component_name {
+            map.push((COMPONENT_NAME.to_cbor_value()?, Value::Text(name)));
+        }
+        if let Some(version) = self.component_version {
+            map.push((COMPONENT_VERSION.to_cbor_value()?, version));
+        }
+        if self.resettable {
+            map.push((RESETTABLE.to_cbor_value()?, Value::Null));
+        }
+        if let Some(version) = self.security_version {
+            map.push((SECURITY_VERSION.to_cbor_value()?, Value::from(version)));
+        }
+        if self.rkp_vm_marker {
+            map.push((RKP_VM_MARKER.to_cbor_value()?, Value::Null));
+        }
+        for (k, v) in self.rest {
+            map.push((k.to_cbor_value()?, v));
+        }
+        Ok(Value::Map(map))
+    }
+}
+
+/// Structure representing the claims of a DICE certificate.
+///
+/// DICE claims that are not represented here, along with the standard CWT claims (including the
+/// `iss` and `sub` claims that link certificates in a chain), are held in [`DiceClaims::claims`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiceClaims {
+    /// Hash of the code of the layer.
+    pub code_hash: Option<Vec<u8>>,
+    /// Implementation-specific description of the code.
+    pub code_descriptor: Option<Vec<u8>>,
+    /// Hash of the configuration of the layer.
+    pub config_hash: Option<Vec<u8>>,
+    /// Serialized configuration descriptor, which [`DiceClaims::config_descriptor`] parses.
+    pub config_descriptor: Option<Vec<u8>>,
+    /// Hash of the authority that verified the code.
+    pub authority_hash: Option<Vec<u8>>,
+    /// Implementation-specific description of the authority.
+    pub authority_descriptor: Option<Vec<u8>>,
+    /// DICE mode.
+    pub mode: Option<DiceMode>,
+    /// Public key of the subject (the next layer).
+    pub subject_public_key: Option<CoseKey>,
+    /// Key usage bitmask, in little-endian byte order.
+    pub key_usage: Option<Vec<u8>>,
+    /// Name of the DICE profile.
+    pub profile_name: Option<String>,
+    /// Any other claims.
+    pub claims: ClaimsSet,
+}
+
+impl CborSerializable for DiceClaims {}
+
+impl DiceClaims {
+    /// Extract the DICE claims from a claims set, leaving any other claims in
+    /// [`DiceClaims::claims`].
+    pub fn from_claims_set(mut claims: ClaimsSet) -> Result<Self, CoseError> {
+        let rest = core::mem::take(&mut claims.rest);
+        let mut dice = Self {
+            claims,
+            ..Default::default()
+        };
+        for (name, value) in rest {
+            let label = match name {
+                ClaimName::PrivateUse(label) => label,
+                name => {
+                    dice.claims.rest.push((name, value));
+                    continue;
+                }
+            };
+            match label {
+                CODE_HASH => set_once(&mut dice.code_hash, value.try_as_bytes()?)?,
+                CODE_DESCRIPTOR => set_once(&mut dice.code_descriptor, value.try_as_bytes()?)?,
+                CONFIG_HASH => set_once(&mut dice.config_hash, value.try_as_bytes()?)?,
+                CONFIG_DESCRIPTOR => set_once(&mut dice.config_descriptor, value.try_as_bytes()?)?,
+                AUTHORITY_HASH => set_once(&mut dice.authority_hash, value.try_as_bytes()?)?,
+                AUTHORITY_DESCRIPTOR => {
+                    set_once(&mut dice.authority_descriptor, value.try_as_bytes()?)?
+                }
+                MODE => set_once(&mut dice.mode, DiceMode::from_cbor_value(value)?)?,
+                SUBJECT_PUBLIC_KEY => set_once(
+                    &mut dice.subject_public_key,
+                    CoseKey::from_slice(&value.try_as_bytes()?)?,
+                )?,
+                KEY_USAGE => set_once(&mut dice.key_usage, value.try_as_bytes()?)?,
+                PROFILE_NAME => set_once(&mut dice.profile_name, value.try_as_string()?)?,
+                label => dice.claims.rest.push((ClaimName::PrivateUse(label), value)),
+            }
+        }
+        Ok(dice)
+    }
+
+    /// Convert to a claims set, with the DICE claims following any other claims.
+    pub fn to_claims_set(self) -> Result<ClaimsSet, CoseError> {
+        let mut claims = self.claims;
+        let mut push = |label: i64, value: Value| {
+            claims.rest.push((ClaimName::PrivateUse(label), value));
+        };
+        let bytes = [
+            (CODE_HASH, self.code_hash),
+            (CODE_DESCRIPTOR, self.code_descriptor),
+            (CONFIG_HASH, self.config_hash),
+            (CONFIG_DESCRIPTOR, self.config_descriptor),
+            (AUTHORITY_HASH, self.authority_hash),
+            (AUTHORITY_DESCRIPTOR, self.authority_descriptor),
+        ];
+        for (label, value) in bytes {
+            if let Some(value) = value {
+                push(label, Value::Bytes(value));
+            }
+        }
+        if let Some(mode) = self.mode {
+            push(MODE, mode.to_cbor_value()?);
+        }
+        if let Some(key) = self.subject_public_key {
+            push(SUBJECT_PUBLIC_KEY, Value::Bytes(key.to_vec()?));
+        }
+        if let Some(key_usage) = self.key_usage {
+            push(KEY_USAGE, Value::Bytes(key_usage));
+        }
+        if let Some(profile_name) = self.profile_name {
+            push(PROFILE_NAME, Value::Text(profile_name));
+        }
+        Ok(claims)
+    }
+
+    /// Parse the configuration descriptor, if present.
+    pub fn config_descriptor(&self) -> Result<Option<ConfigDescriptor>, CoseError> {
+        self.config_descriptor
+            .as_deref()
+            .map(ConfigDescriptor::from_slice)
+            .transpose()
+    }
+
+    /// Indicate whether the key usage allows the subject key to sign certificates.
+    pub fn allows_cert_sign(&self) -> bool {
+        self.key_usage
+            .as_ref()
+            .and_then(|k| k.first())
+            .is_some_and(|b| b & KEY_USAGE_CERT_SIGN != 0)
+    }
+}
+
+impl AsCborValue for DiceClaims {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        Self::from_claims_set(ClaimsSet::from_cbor_value(value)?)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        self.to_claims_set()?.to_cbor_value()
+    }
+}
+
+/// Builder for [`DiceClaims`] objects.
+#[derive(Default)]
+pub struct DiceClaimsBuilder(DiceClaims);
+
+impl DiceClaimsBuilder {
+    builder! {DiceClaims}
+    builder_set_optional! {code_hash: Vec<u8>}
+    builder_set_optional! {code_descriptor: Vec<u8>}
+    builder_set_optional! {config_hash: Vec<u8>}
+    builder_set_optional! {authority_hash: Vec<u8>}
+    builder_set_optional! {authority_descriptor: Vec<u8>}
+    builder_set_optional! {mode: DiceMode}
+    builder_set_optional! {subject_public_key: CoseKey}
+    builder_set_optional! {key_usage: Vec<u8>}
+    builder_set_optional! {profile_name: String}
+    builder_set! {claims: ClaimsSet}
+
+    /// Set the configuration descriptor.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the descriptor cannot be CBOR-encoded.
+    #[must_use]
+    pub fn config_descriptor(mut self, desc: ConfigDescriptor) -> Self {
+        let data = desc.to_vec().expect("failed to encode descriptor"); // safe: documented
+        self.0.config_descriptor = Some(data);
+        self
+    }
+}
+
+/// The error type returned when a DICE chain fails verification.
+pub enum DiceError<E> {
+    /// A certificate or its claims could not be parsed.
+    Cose(CoseError),
+    /// The certificate at the given index has no subject public key.
+    MissingSubjectPublicKey(usize),
+    /// The issuer of the certificate at the given index is not the subject of its predecessor.
+    IssuerMismatch(usize),
+    /// The key usage of the certificate at the given index does not allow it to sign the next
+    /// certificate.
+    KeyUsage(usize),
+    /// The signature of the certificate at the given index failed verification.
+    Crypto(usize, E),
+}
+
+impl<E: core::fmt::Display> core::error::Error for DiceError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Debug for DiceError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for DiceError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> DiceError<E> {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DiceError::Cose(e) => write!(f, "invalid DICE chain: {e}"),
+            DiceError::MissingSubjectPublicKey(i) => {
+                write!(f, "certificate {i} has no subject public key")
+            }
+            DiceError::IssuerMismatch(i) => {
+                write!(f, "certificate {i} issuer does not match previous subject")
+            }
+            DiceError::KeyUsage(i) => {
+                write!(
+                    f,
+                    "certificate {i} key usage does not allow certificate signing"
+                )
+            }
+            DiceError::Crypto(i, e) => write!(f, "certificate {i} verification failed: {e}"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for DiceError<E> {
+    fn from(e: CoseError) -> Self {
+        DiceError::Cose(e)
+    }
+}
+
+/// DICE chain, holding the root public key and the certificates of the DICE layers (in order from
+/// the root).
+///
+/// ```cddl
+///  DiceCertChain = [
+///      COSE_Key,             ; Root public key
+///      + COSE_Sign1,         ; Certificate for each layer
+///  ]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiceChain {
+    /// Root public key, which signs the first certificate.
+    pub root_key: CoseKey,
+    /// Certificates of the DICE layers.
+    pub certs: Vec<CoseSign1>,
+}
+
+impl CborSerializable for DiceChain {}
+
+impl AsCborValue for DiceChain {
+    /// Parse a DICE chain, whose certificates may be tagged or untagged.
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.len() < 2 {
+            return Err(CoseError::UnexpectedItem(
+                "array",
+                "array with root key and certificates",
+            ));
+        }
+        let certs = a
+            .split_off(1)
+            .into_iter()
+            .map(|v| match v {
+                Value::Tag(tag, inner) if tag == CoseSign1::TAG => {
+                    CoseSign1::from_cbor_value(*inner)
+                }
+                v => CoseSign1::from_cbor_value(v),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            root_key: CoseKey::from_cbor_value(a.remove(0))?,
+            certs,
+        })
+    }
+
+    /// Encode a DICE chain, with untagged certificates.
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut a = vec![self.root_key.to_cbor_value()?];
+        for cert in self.certs {
+            a.push(cert.to_cbor_value()?);
+        }
+        Ok(Value::Array(a))
+    }
+}
+
+impl DiceChain {
+    /// Parse the claims of each certificate, without verification.
+    pub fn claims(&self) -> Result<Vec<DiceClaims>, CoseError> {
+        self.certs
+            .iter()
+            .map(|cert| DiceClaims::from_slice(cert.payload.as_deref().unwrap_or_default()))
+            .collect()
+    }
+
+    /// Verify the chain and return the claims of each certificate.  The signature of each
+    /// certificate is checked by `verifier`, which is given the public key of the previous layer
+    /// (or the root key), the signature value and the serialized data (in that order).  Each
+    /// certificate must hold a subject public key, the key usage of each certificate other than
+    /// the last must allow certificate signing, and the issuer of each certificate must match the
+    /// subject of its predecessor.
+    ///
+    /// The public key of the final layer is the `subject_public_key` of the last returned claims.
+    pub fn verify<F, E>(&self, mut verifier: F) -> Result<Vec<DiceClaims>, DiceError<E>>
+    where
+        F: FnMut(&CoseKey, &[u8], &[u8]) -> Result<(), E>,
+    {
+        let mut result: Vec<DiceClaims> = Vec::with_capacity(self.certs.len());
+        let mut key = self.root_key.clone();
+        for (i, cert) in self.certs.iter().enumerate() {
+            if let Some(prev) = result.last() {
+                if !prev.allows_cert_sign() {
+                    return Err(DiceError::KeyUsage(i - 1));
+                }
+            }
+            cert.verify_signature(b"", |sig, data| verifier(&key, sig, data))
+                .map_err(|e| DiceError::Crypto(i, e))?;
+            let claims = DiceClaims::from_slice(cert.payload.as_deref().unwrap_or_default())?;
+            if let Some(prev) = result.last() {
+                if claims.claims.issuer != prev.claims.subject {
+                    return Err(DiceError::IssuerMismatch(i));
+                }
+            }
+            key = claims
+                .subject_public_key
+                .clone()
+                .ok_or(DiceError::MissingSubjectPublicKey(i))?;
+            result.push(claims);
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{
+    cwt::ClaimsSetBuilder,
+    iana,
+    util::{
+        expect_err,
+        test_crypto::{self, public_key},
+    },
+    CoseSign1Builder, HeaderBuilder,
+};
+use alloc::{borrow::ToOwned, boxed::Box, format, vec};
+
+/// Return the claims of a certificate for layer `n`, issued by layer `n - 1`.
+fn layer_claims(n: u8) -> DiceClaims {
+    DiceClaimsBuilder::new()
+        .claims(
+            ClaimsSetBuilder::new()
+                .issuer(format!("layer{}", n - 1))
+                .subject(format!("layer{}", n))
+                .build(),
+        )
+        .code_hash(vec![n; 32])
+        .config_descriptor(ConfigDescriptor {
+            component_name: Some(format!("component{}", n)),
+            ..Default::default()
+        })
+        .authority_hash(vec![0xaa; 32])
+        .mode(DiceMode::Normal)
+        .subject_public_key(public_key(n))
+        .key_usage(vec![KEY_USAGE_CERT_SIGN])
+        .build()
+}
+
+/// Return a certificate holding `claims`, signed by the key with the given value.
+fn cert(claims: DiceClaims, signer: u8) -> CoseSign1 {
+    CoseSign1Builder::new()
+        .protected(
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::EdDSA)
+                .build(),
+        )
+        .payload(claims.to_vec().unwrap())
+        .create_signature(b"", |data| test_crypto::sign(signer, data))
+        .build()
+}
+
+/// Return a chain with a root key and certificates for layers 1 to `n`.
+fn chain(n: u8) -> DiceChain {
+    DiceChain {
+        root_key: public_key(0),
+        certs: (1..=n).map(|i| cert(layer_claims(i), i - 1)).collect(),
+    }
+}
+
+#[test]
+fn test_dice_mode() {
+    let tests = [
+        (Value::Bytes(vec![0]), DiceMode::NotConfigured),
+        (Value::Bytes(vec![1]), DiceMode::Normal),
+        (Value::Bytes(vec![2]), DiceMode::Debug),
+        (Value::Bytes(vec![3]), DiceMode::Recovery),
+        (Value::from(2), DiceMode::Debug),
+    ];
+    for (value, want) in tests {
+        let got = DiceMode::from_cbor_value(value.clone()).unwrap();
+        assert_eq!(got, want, "for {:?}", value);
+    }
+    assert_eq!(
+        DiceMode::Recovery.to_cbor_value().unwrap(),
+        Value::Bytes(vec![3])
+    );
+
+    let tests = [
+        (Value::Bytes(vec![4]), "out of range"),
+        (Value::Bytes(vec![1, 1]), "single-byte bstr"),
+        (Value::Bytes(vec![]), "single-byte bstr"),
+        (Value::from(-1), "out of range"),
+        (Value::Text("normal".to_owned()), "int"),
+    ];
+    for (value, err_msg) in tests {
+        expect_err(DiceMode::from_cbor_value(value), err_msg);
+    }
+}
+
+#[test]
+fn test_config_descriptor() {
+    let desc = ConfigDescriptor {
+        component_name: Some("vm".to_owned()),
+        component_version: Some(Value::from(12)),
+        resettable: true,
+        security_version: Some(3),
+        rkp_vm_marker: true,
+        rest: vec![(Label::Int(-71000), Value::Bool(true))],
+    };
+    let data = desc.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "a6",         // 6-map
+            "3a00011171", // -70002
+            "62766d",     // "vm"
+            "3a00011172", // -70003
+            "0c",         // 12
+            "3a00011173", // -70004
+            "f6",         // null
+            "3a00011174", // -70005
+            "03",         // 3
+            "3a00011175", // -70006
+            "f6",         // null
+            "3a00011557", // -71000
+            "f5",         // true
+        )
+    );
+    assert_eq!(ConfigDescriptor::from_slice(&data).unwrap(), desc);
+
+    let tests = [
+        // {-70002: "a", -70002: "b"}
+        ("a23a0001117161613a000111716162", "duplicate map key"),
+        // {-70003: 1.5}
+        ("a13a00011172f93e00", "int or tstr"),
+        // {-70004: true}
+        ("a13a00011173f5", "null"),
+        // {-70005: -1}
+        ("a13a0001117420", "out of range"),
+        // {-70006: 0}
+        ("a13a0001117500", "null"),
+        // ["vm"]
+        ("8162766d", "map"),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(ConfigDescriptor::from_slice(&data), err_msg);
+    }
+}
+
+#[test]
+fn test_dice_claims() {
+    let claims = layer_claims(1);
+    let claims_set = claims.clone().to_claims_set().unwrap();
+    assert_eq!(claims_set.issuer.as_deref(), Some("layer0"));
+    assert_eq!(claims_set.rest.len(), 6);
+    assert_eq!(DiceClaims::from_claims_set(claims_set).unwrap(), claims);
+    let data = claims.clone().to_vec().unwrap();
+    assert_eq!(DiceClaims::from_slice(&data).unwrap(), claims);
+    assert_eq!(
+        claims.config_descriptor().unwrap().unwrap().component_name,
+        Some("component1".to_owned())
+    );
+    assert!(claims.allows_cert_sign());
+    assert!(!DiceClaims::default().allows_cert_sign());
+    assert_eq!(DiceClaims::default().config_descriptor().unwrap(), None);
+
+    // {1: "iss", -4670551: h'02', -4670554: "android.16", -65537: 0}
+    let data = hex::decode(concat!(
+        "a4",
+        "01",
+        "63697373",
+        "3a00474456",
+        "4102",
+        "3a00474459",
+        "6a616e64726f69642e3136",
+        "3a00010000",
+        "00",
+    ))
+    .unwrap();
+    let got = DiceClaims::from_slice(&data).unwrap();
+    let want = DiceClaims {
+        mode: Some(DiceMode::Debug),
+        profile_name: Some("android.16".to_owned()),
+        claims: ClaimsSetBuilder::new()
+            .issuer("iss".to_owned())
+            .private_claim(-65537, Value::from(0))
+            .build(),
+        ..Default::default()
+    };
+    assert_eq!(got, want);
+
+    let tests = [
+        // {-4670551: h'01', -4670551: h'01'}
+        ("a23a0047445641013a004744564101", "duplicate map key"),
+        // {-4670545: "hash"}
+        ("a13a004744506468617368", "bstr"),
+        // {-4670552: h'01'}
+        ("a13a004744574101", "map"),
+        // {-4670554: h'01'}
+        ("a13a004744594101", "tstr"),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(DiceClaims::from_slice(&data), err_msg);
+    }
+}
+
+#[test]
+fn test_dice_chain() {
+    let data = chain(3).to_vec().unwrap();
+    let chain = DiceChain::from_slice(&data).unwrap();
+    assert_eq!(chain.certs.len(), 3);
+    assert_eq!(chain.clone().to_vec().unwrap(), data);
+
+    let claims = chain.verify(test_crypto::verify_with_key).unwrap();
+    assert_eq!(
+        claims,
+        vec![layer_claims(1), layer_claims(2), layer_claims(3)]
+    );
+    assert_eq!(claims, chain.claims().unwrap());
+    assert_eq!(claims[2].subject_public_key, Some(public_key(3)));
+
+    // The key usage of the last certificate is not checked.
+    let mut leaf_only = chain.clone();
+    let mut claims = layer_claims(3);
+    claims.key_usage = Some(vec![0x80]); // digitalSignature
+    leaf_only.certs[2] = cert(claims, 2);
+    leaf_only.verify(test_crypto::verify_with_key).unwrap();
+
+    // Tagged certificates are also accepted.
+    let mut value = chain.clone().to_cbor_value().unwrap();
+    if let Value::Array(a) = &mut value {
+        let cert = a.remove(1);
+        a.insert(1, Value::Tag(CoseSign1::TAG, Box::new(cert)));
+    }
+    assert_eq!(DiceChain::from_cbor_value(value).unwrap(), chain);
+
+    let tests = [
+        // [root key]
+        (
+            Value::Array(vec![public_key(0).to_cbor_value().unwrap()]),
+            "array with root key",
+        ),
+        (Value::Array(vec![]), "array with root key"),
+        (
+            Value::Array(vec![
+                Value::from(1),
+                cert(layer_claims(1), 0).to_cbor_value().unwrap(),
+            ]),
+            "map",
+        ),
+        (
+            Value::Array(vec![public_key(0).to_cbor_value().unwrap(), Value::from(1)]),
+            "array",
+        ),
+        (Value::Map(vec![]), "array"),
+    ];
+    for (value, err_msg) in tests {
+        expect_err(DiceChain::from_cbor_value(value), err_msg);
+    }
+}
+
+#[test]
+fn test_dice_chain_verify_fail() {
+    let mut wrong_signer = chain(3);
+    wrong_signer.certs[1] = cert(layer_claims(2), 7);
+
+    let mut wrong_issuer = chain(3);
+    let mut claims = layer_claims(3);
+    claims.claims.issuer = Some("layer9".to_owned());
+    wrong_issuer.certs[2] = cert(claims, 2);
+
+    let mut no_issuer = chain(2);
+    let mut claims = layer_claims(2);
+    claims.claims.issuer = None;
+    no_issuer.certs[1] = cert(claims, 1);
+
+    let mut no_subject_key = chain(2);
+    let mut claims = layer_claims(2);
+    claims.subject_public_key = None;
+    no_subject_key.certs[1] = cert(claims, 1);
+
+    let mut no_cert_sign = chain(3);
+    let mut claims = layer_claims(2);
+    claims.key_usage = Some(vec![0x80]); // digitalSignature
+    no_cert_sign.certs[1] = cert(claims, 1);
+
+    let mut no_key_usage = chain(2);
+    let mut claims = layer_claims(1);
+    claims.key_usage = None;
+    no_key_usage.certs[0] = cert(claims, 0);
+
+    let mut bad_payload = chain(2);
+    bad_payload.certs[0] = CoseSign1Builder::new()
+        .payload(vec![0x80])
+        .create_signature(b"", |data| test_crypto::sign(0, data))
+        .build();
+
+    let tests = [
+        (
+            wrong_signer,
+            "certificate 1 verification failed: bad signature",
+        ),
+        (
+            wrong_issuer,
+            "certificate 2 issuer does not match previous subject",
+        ),
+        (
+            no_issuer,
+            "certificate 1 issuer does not match previous subject",
+        ),
+        (no_subject_key, "certificate 1 has no subject public key"),
+        (
+            no_cert_sign,
+            "certificate 1 key usage does not allow certificate signing",
+        ),
+        (
+            no_key_usage,
+            "certificate 0 key usage does not allow certificate signing",
+        ),
+        (bad_payload, "invalid DICE chain: "),
+    ];
+    for (chain, err_msg) in tests {
+        expect_err(chain.verify(test_crypto::verify_with_key), err_msg);
+    }
+}
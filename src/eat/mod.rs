@@ -0,0 +1,417 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Entity Attestation Token (EAT) functionality, as per RFC 9711.
+//!
+//! An EAT is a CWT whose claims describe an entity. [`EatClaims`] holds the EAT claims of a claims
+//! set in typed form, with any other claims held in a [`ClaimsSet`]; use the [`crate::cwt`]
+//! functions to sign, MAC or encrypt the encoded claims.
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    cwt::{ClaimName, ClaimsSet},
+    iana,
+    util::{cbor_type_error, set_once, set_once_vec, ValueTryAs},
+    CoseError,
+};
+use alloc::{string::String, vec, vec::Vec};
+use types::{check_size, try_as_nonempty_array, try_as_nonempty_map, try_as_uint};
+
+mod psa;
+pub use psa::*;
+mod submod;
+pub use submod::*;
+mod types;
+pub use types::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Structure representing the claims of an EAT.
+///
+/// Claims that can hold multiple entries are represented as a [`Vec`], which is empty if the claim
+/// is absent.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EatClaims {
+    /// Nonces (`eat_nonce`), each of 8 to 64 bytes.
+    pub nonce: Vec<Vec<u8>>,
+    /// Universal Entity ID (`ueid`).
+    pub ueid: Option<Ueid>,
+    /// Semi-permanent UEIDs, keyed by name (`sueids`).
+    pub sueids: Vec<(String, Ueid)>,
+    /// Hardware OEM ID (`oemid`).
+    pub oemid: Option<OemId>,
+    /// Hardware model identifier, of 1 to 32 bytes (`hwmodel`).
+    pub hardware_model: Option<Vec<u8>>,
+    /// Hardware version (`hwversion`).
+    pub hardware_version: Option<Version>,
+    /// Seconds since boot (`uptime`).
+    pub uptime: Option<u64>,
+    /// Whether the software booted was OEM-authorized (`oemboot`).
+    pub oem_boot: Option<bool>,
+    /// Status of debug facilities (`dbgstat`).
+    pub debug_status: Option<DebugStatus>,
+    /// Geographic location (`location`).
+    pub location: Option<Location>,
+    /// EAT profile (`eat_profile`).
+    pub profile: Option<Profile>,
+    /// Submodules, keyed by name (`submods`).
+    pub submodules: Vec<(String, Submodule)>,
+    /// Number of times the entity has booted (`bootcount`).
+    pub boot_count: Option<u64>,
+    /// Identifier of the boot cycle, of 32 to 64 bytes (`bootseed`).
+    pub boot_seed: Option<Vec<u8>>,
+    /// Digital Letters of Approval (`dloas`).
+    pub dloas: Vec<Dloa>,
+    /// Software name (`swname`).
+    pub software_name: Option<String>,
+    /// Software version (`swversion`).
+    pub software_version: Option<Version>,
+    /// Software manifests (`manifests`).
+    pub manifests: Vec<FormattedContent>,
+    /// Software measurements (`measurements`).
+    pub measurements: Vec<FormattedContent>,
+    /// Results of comparing measurements against reference values (`measres`).
+    pub measurement_results: Vec<MeasurementResults>,
+    /// Intended use of the EAT (`intuse`).
+    pub intended_use: Option<IntendedUse>,
+    /// Any other claims, including the core CWT claims.
+    pub claims: ClaimsSet,
+}
+
+impl crate::CborSerializable for EatClaims {}
+
+/// Convert a non-empty map with text keys, checking for duplicate keys.
+fn named_from_cbor<T, F>(value: Value, f: F) -> Result<Vec<(String, T)>, CoseError>
+where
+    F: Fn(Value) -> Result<T, CoseError>,
+{
+    let mut result: Vec<(String, T)> = Vec::new();
+    for (name, value) in try_as_nonempty_map(value)? {
+        let name = name.try_as_string()?;
+        if result.iter().any(|(n, _)| *n == name) {
+            return Err(CoseError::DuplicateMapKey);
+        }
+        result.push((name, f(value)?));
+    }
+    Ok(result)
+}
+
+/// Convert to a map with text keys.
+fn named_to_cbor<T: AsCborValue>(named: Vec<(String, T)>) -> Result<Value, CoseError> {
+    Ok(Value::Map(
+        named
+            .into_iter()
+            .map(|(name, v)| Ok((Value::Text(name), v.to_cbor_value()?)))
+            .collect::<Result<Vec<_>, CoseError>>()?,
+    ))
+}
+
+/// Convert a non-empty array.
+fn array_from_cbor<T: AsCborValue>(value: Value) -> Result<Vec<T>, CoseError> {
+    try_as_nonempty_array(value)?
+        .into_iter()
+        .map(T::from_cbor_value)
+        .collect()
+}
+
+fn nonce_from_cbor(value: Value) -> Result<Vec<Vec<u8>>, CoseError> {
+    let nonces = match value {
+        Value::Bytes(b) => vec![b],
+        Value::Array(a) if a.len() >= 2 => a
+            .into_iter()
+            .map(|v| v.try_as_bytes())
+            .collect::<Result<Vec<_>, CoseError>>()?,
+        Value::Array(_) => {
+            return Err(CoseError::UnexpectedItem("array", "array with 2+ items"));
+        }
+        v => return cbor_type_error(&v, "bstr/array"),
+    };
+    for nonce in &nonces {
+        check_size(nonce, 8, 64, "bstr of 8-64 bytes")?;
+    }
+    Ok(nonces)
+}
+
+fn nonce_to_cbor(mut nonces: Vec<Vec<u8>>) -> Result<Value, CoseError> {
+    for nonce in &nonces {
+        check_size(nonce, 8, 64, "bstr of 8-64 bytes")?;
+    }
+    Ok(match nonces.len() {
+        1 => Value::Bytes(nonces.remove(0)),
+        _ => Value::Array(nonces.into_iter().map(Value::Bytes).collect()),
+    })
+}
+
+fn hardware_model_from_cbor(value: Value) -> Result<Vec<u8>, CoseError> {
+    let model = value.try_as_bytes()?;
+    check_size(&model, 1, 32, "bstr of 1-32 bytes")?;
+    Ok(model)
+}
+
+fn boot_seed_from_cbor(value: Value) -> Result<Vec<u8>, CoseError> {
+    let seed = value.try_as_bytes()?;
+    check_size(&seed, 32, 64, "bstr of 32-64 bytes")?;
+    Ok(seed)
+}
+
+impl EatClaims {
+    /// Extract the EAT claims from a claims set, leaving any other claims in
+    /// [`EatClaims::claims`].
+    pub fn from_claims_set(mut claims: ClaimsSet) -> Result<Self, CoseError> {
+        use iana::CwtClaimName as N;
+        let rest = core::mem::take(&mut claims.rest);
+        let mut eat = Self {
+            claims,
+            ..Default::default()
+        };
+        for (name, value) in rest {
+            let name = match name {
+                ClaimName::Assigned(name) => name,
+                name => {
+                    eat.claims.rest.push((name, value));
+                    continue;
+                }
+            };
+            match name {
+                N::Nonce => set_once_vec(&mut eat.nonce, nonce_from_cbor(value)?)?,
+                N::UEID => set_once(&mut eat.ueid, Ueid::from_cbor_value(value)?)?,
+                N::SUEIDs => set_once_vec(
+                    &mut eat.sueids,
+                    named_from_cbor(value, Ueid::from_cbor_value)?,
+                )?,
+                N::HardwareOemid => set_once(&mut eat.oemid, OemId::from_cbor_value(value)?)?,
+                N::HardwareModel => {
+                    set_once(&mut eat.hardware_model, hardware_model_from_cbor(value)?)?
+                }
+                N::HardwareVersion => {
+                    set_once(&mut eat.hardware_version, Version::from_cbor_value(value)?)?
+                }
+                N::Uptime => set_once(&mut eat.uptime, try_as_uint(value)?)?,
+                N::OemAuthorizedBoot => match value {
+                    Value::Bool(b) => set_once(&mut eat.oem_boot, b)?,
+                    v => return cbor_type_error(&v, "bool"),
+                },
+                N::DebugStatus => {
+                    set_once(&mut eat.debug_status, DebugStatus::from_cbor_value(value)?)?
+                }
+                N::Location => set_once(&mut eat.location, Location::from_cbor_value(value)?)?,
+                N::EatProfile => set_once(&mut eat.profile, Profile::from_cbor_value(value)?)?,
+                N::SubmodulesSection => set_once_vec(
+                    &mut eat.submodules,
+                    named_from_cbor(value, Submodule::from_cbor_value)?,
+                )?,
+                N::BootCount => set_once(&mut eat.boot_count, try_as_uint(value)?)?,
+                N::BootSeed => set_once(&mut eat.boot_seed, boot_seed_from_cbor(value)?)?,
+                N::DLoAs => set_once_vec(&mut eat.dloas, array_from_cbor(value)?)?,
+                N::SoftwareName => set_once(&mut eat.software_name, value.try_as_string()?)?,
+                N::SoftwareVersion => {
+                    set_once(&mut eat.software_version, Version::from_cbor_value(value)?)?
+                }
+                N::SoftwareManifests => set_once_vec(&mut eat.manifests, array_from_cbor(value)?)?,
+                N::Measurements => set_once_vec(&mut eat.measurements, array_from_cbor(value)?)?,
+                N::SoftwareMeasurementResults => {
+                    set_once_vec(&mut eat.measurement_results, array_from_cbor(value)?)?
+                }
+                N::IntendedUse => {
+                    set_once(&mut eat.intended_use, IntendedUse::from_cbor_value(value)?)?
+                }
+                name => eat.claims.rest.push((ClaimName::Assigned(name), value)),
+            }
+        }
+        Ok(eat)
+    }
+
+    /// Convert to a claims set, with the EAT claims preceding any other claims.
+    pub fn to_claims_set(self) -> Result<ClaimsSet, CoseError> {
+        use iana::CwtClaimName as N;
+        let mut eat = Vec::new();
+        if !self.nonce.is_empty() {
+            eat.push((N::Nonce, nonce_to_cbor(self.nonce)?));
+        }
+        if let Some(ueid) = self.ueid {
+            eat.push((N::UEID, ueid.to_cbor_value()?));
+        }
+        if !self.sueids.is_empty() {
+            eat.push((N::SUEIDs, named_to_cbor(self.sueids)?));
+        }
+        if let Some(oemid) = self.oemid {
+            eat.push((N::HardwareOemid, oemid.to_cbor_value()?));
+        }
+        if let Some(model) = self.hardware_model {
+            check_size(&model, 1, 32, "bstr of 1-32 bytes")?;
+            eat.push((N::HardwareModel, Value::Bytes(model)));
+        }
+        if let Some(version) = self.hardware_version {
+            eat.push((N::HardwareVersion, version.to_cbor_value()?));
+        }
+        if let Some(uptime) = self.uptime {
+            eat.push((N::Uptime, Value::from(uptime)));
+        }
+        if let Some(oem_boot) = self.oem_boot {
+            eat.push((N::OemAuthorizedBoot, Value::Bool(oem_boot)));
+        }
+        if let Some(status) = self.debug_status {
+            eat.push((N::DebugStatus, status.to_cbor_value()?));
+        }
+        if let Some(location) = self.location {
+            eat.push((N::Location, location.to_cbor_value()?));
+        }
+        if let Some(profile) = self.profile {
+            eat.push((N::EatProfile, profile.to_cbor_value()?));
+        }
+        if !self.submodules.is_empty() {
+            eat.push((N::SubmodulesSection, named_to_cbor(self.submodules)?));
+        }
+        if let Some(count) = self.boot_count {
+            eat.push((N::BootCount, Value::from(count)));
+        }
+        if let Some(seed) = self.boot_seed {
+            check_size(&seed, 32, 64, "bstr of 32-64 bytes")?;
+            eat.push((N::BootSeed, Value::Bytes(seed)));
+        }
+        if !self.dloas.is_empty() {
+            eat.push((N::DLoAs, crate::util::to_cbor_array(self.dloas)?));
+        }
+        if let Some(name) = self.software_name {
+            eat.push((N::SoftwareName, Value::Text(name)));
+        }
+        if let Some(version) = self.software_version {
+            eat.push((N::SoftwareVersion, version.to_cbor_value()?));
+        }
+        if !self.manifests.is_empty() {
+            eat.push((
+                N::SoftwareManifests,
+                crate::util::to_cbor_array(self.manifests)?,
+            ));
+        }
+        if !self.measurements.is_empty() {
+            eat.push((
+                N::Measurements,
+                crate::util::to_cbor_array(self.measurements)?,
+            ));
+        }
+        if !self.measurement_results.is_empty() {
+            eat.push((
+                N::SoftwareMeasurementResults,
+                crate::util::to_cbor_array(self.measurement_results)?,
+            ));
+        }
+        if let Some(intended_use) = self.intended_use {
+            eat.push((N::IntendedUse, intended_use.to_cbor_value()?));
+        }
+
+        let mut claims = self.claims;
+        let rest = core::mem::take(&mut claims.rest);
+        claims.rest = eat
+            .into_iter()
+            .map(|(name, value)| (ClaimName::Assigned(name), value))
+            .chain(rest)
+            .collect();
+        Ok(claims)
+    }
+
+    /// Return the submodule with the given name.
+    pub fn submodule(&self, name: &str) -> Option<&Submodule> {
+        self.submodules
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, submod)| submod)
+    }
+}
+
+impl AsCborValue for EatClaims {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        Self::from_claims_set(ClaimsSet::from_cbor_value(value)?)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        self.to_claims_set()?.to_cbor_value()
+    }
+}
+
+/// Builder for [`EatClaims`] objects.
+#[derive(Default)]
+pub struct EatClaimsBuilder(EatClaims);
+
+impl EatClaimsBuilder {
+    builder! {EatClaims}
+    builder_set_optional! {ueid: Ueid}
+    builder_set_optional! {oemid: OemId}
+    builder_set_optional! {hardware_model: Vec<u8>}
+    builder_set_optional! {hardware_version: Version}
+    builder_set_optional! {uptime: u64}
+    builder_set_optional! {oem_boot: bool}
+    builder_set_optional! {debug_status: DebugStatus}
+    builder_set_optional! {location: Location}
+    builder_set_optional! {profile: Profile}
+    builder_set_optional! {boot_count: u64}
+    builder_set_optional! {boot_seed: Vec<u8>}
+    builder_set_optional! {software_name: String}
+    builder_set_optional! {software_version: Version}
+    builder_set_optional! {intended_use: IntendedUse}
+    builder_set! {claims: ClaimsSet}
+
+    /// Add a nonce.
+    #[must_use]
+    pub fn nonce(mut self, nonce: Vec<u8>) -> Self {
+        self.0.nonce.push(nonce);
+        self
+    }
+
+    /// Add a named semi-permanent UEID.
+    #[must_use]
+    pub fn sueid(mut self, name: String, ueid: Ueid) -> Self {
+        self.0.sueids.push((name, ueid));
+        self
+    }
+
+    /// Add a named submodule.
+    #[must_use]
+    pub fn submodule(mut self, name: String, submodule: Submodule) -> Self {
+        self.0.submodules.push((name, submodule));
+        self
+    }
+
+    /// Add a Digital Letter of Approval.
+    #[must_use]
+    pub fn dloa(mut self, dloa: Dloa) -> Self {
+        self.0.dloas.push(dloa);
+        self
+    }
+
+    /// Add a software manifest.
+    #[must_use]
+    pub fn manifest(mut self, manifest: FormattedContent) -> Self {
+        self.0.manifests.push(manifest);
+        self
+    }
+
+    /// Add a software measurement.
+    #[must_use]
+    pub fn measurement(mut self, measurement: FormattedContent) -> Self {
+        self.0.measurements.push(measurement);
+        self
+    }
+
+    /// Add the results from a measurement system.
+    #[must_use]
+    pub fn measurement_results(mut self, results: MeasurementResults) -> Self {
+        self.0.measurement_results.push(results);
+        self
+    }
+}
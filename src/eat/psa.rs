@@ -0,0 +1,469 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Arm Platform Security Architecture (PSA) attestation token profile, as per RFC 9783.
+
+use super::{types::try_as_uint, Profile, Ueid, UeidType};
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    cwt::{ClaimName, ClaimsSet},
+    iana,
+    util::{set_once, set_once_vec, ValueTryAs},
+    CoseError,
+};
+use alloc::{string::String, vec::Vec};
+use core::convert::TryInto;
+
+/// EAT profile identifier for PSA attestation tokens.
+pub const PSA_PROFILE: &str = "tag:psacertified.org,2023:psa#tfm";
+
+/// EAT profile identifier used by PSA attestation tokens that predate RFC 9783.
+pub const PSA_PROFILE_LEGACY: &str = "http://arm.com/psa/2.0.0";
+
+/// Security lifecycle states, as held in the upper byte of a `psa-security-lifecycle` claim.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum LifecycleState {
+    /// Unknown state.
+    Unknown = 0x00,
+    /// Assembly and test.
+    AssemblyAndTest = 0x10,
+    /// PSA Root of Trust provisioning.
+    PsaRotProvisioning = 0x20,
+    /// Secured.
+    Secured = 0x30,
+    /// Non-PSA Root of Trust debug.
+    NonPsaRotDebug = 0x40,
+    /// Recoverable PSA Root of Trust debug.
+    RecoverablePsaRotDebug = 0x50,
+    /// Decommissioned.
+    Decommissioned = 0x60,
+}
+
+/// Security lifecycle, as held in a `psa-security-lifecycle` claim.
+///
+/// Each state covers a range of 256 values, where the lower byte is implementation defined.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SecurityLifecycle {
+    /// The lifecycle state.
+    pub state: LifecycleState,
+    /// Implementation defined detail of the state.
+    pub detail: u8,
+}
+
+impl SecurityLifecycle {
+    /// Create a security lifecycle from its encoded value, failing if the value is not in the
+    /// range of a known state.
+    pub fn from_u16(value: u16) -> Result<Self, CoseError> {
+        let state = match (value >> 8) as u8 {
+            x if x == LifecycleState::Unknown as u8 => LifecycleState::Unknown,
+            x if x == LifecycleState::AssemblyAndTest as u8 => LifecycleState::AssemblyAndTest,
+            x if x == LifecycleState::PsaRotProvisioning as u8 => {
+                LifecycleState::PsaRotProvisioning
+            }
+            x if x == LifecycleState::Secured as u8 => LifecycleState::Secured,
+            x if x == LifecycleState::NonPsaRotDebug as u8 => LifecycleState::NonPsaRotDebug,
+            x if x == LifecycleState::RecoverablePsaRotDebug as u8 => {
+                LifecycleState::RecoverablePsaRotDebug
+            }
+            x if x == LifecycleState::Decommissioned as u8 => LifecycleState::Decommissioned,
+            _ => return Err(CoseError::OutOfRangeIntegerValue),
+        };
+        Ok(Self {
+            state,
+            detail: value as u8,
+        })
+    }
+
+    /// Return the encoded value of the security lifecycle.
+    pub fn to_u16(self) -> u16 {
+        ((self.state as u16) << 8) | u16::from(self.detail)
+    }
+}
+
+impl From<LifecycleState> for SecurityLifecycle {
+    fn from(state: LifecycleState) -> Self {
+        Self { state, detail: 0 }
+    }
+}
+
+impl AsCborValue for SecurityLifecycle {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        Self::from_u16(try_as_uint(value)?.try_into()?)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::from(self.to_u16()))
+    }
+}
+
+/// Measured software component, as held in a `psa-software-components` claim.
+///
+/// ```cddl
+///  psa-software-component = {
+///    ? &(measurement-type: 1) => text
+///      &(measurement-value: 2) => psa-hash-type
+///    ? &(version: 4) => text
+///      &(signer-id: 5) => psa-hash-type
+///    ? &(measurement-desc: 6) => text
+///  }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SoftwareComponent {
+    /// Role of the component, such as "BL" or "PRoT".
+    pub measurement_type: Option<String>,
+    /// Hash of the component.
+    pub measurement_value: Vec<u8>,
+    /// Version of the component.
+    pub version: Option<String>,
+    /// Hash of the signing authority public key for the component.
+    pub signer_id: Vec<u8>,
+    /// Name of the hash algorithm used for the measurement, such as "sha-256".
+    pub measurement_desc: Option<String>,
+}
+
+const MEASUREMENT_TYPE: i64 = 1;
+const MEASUREMENT_VALUE: i64 = 2;
+const VERSION: i64 = 4;
+const SIGNER_ID: i64 = 5;
+const MEASUREMENT_DESC: i64 = 6;
+
+impl AsCborValue for SoftwareComponent {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut measurement_value = None;
+        let mut signer_id = None;
+        let mut component = Self::default();
+        for (k, v) in value.try_as_map()? {
+            let k: i64 = k.try_as_integer()?.try_into()?;
+            match k {
+                MEASUREMENT_TYPE => set_once(&mut component.measurement_type, v.try_as_string()?)?,
+                MEASUREMENT_VALUE => set_once(&mut measurement_value, v.try_as_bytes()?)?,
+                VERSION => set_once(&mut component.version, v.try_as_string()?)?,
+                SIGNER_ID => set_once(&mut signer_id, v.try_as_bytes()?)?,
+                MEASUREMENT_DESC => set_once(&mut component.measurement_desc, v.try_as_string()?)?,
+                _ => return Err(CoseError::UnexpectedItem("int", "software component field")),
+            }
+        }
+        match (measurement_value, signer_id) {
+            (Some(measurement_value), Some(signer_id)) => {
+                component.measurement_value = measurement_value;
+                component.signer_id = signer_id;
+                Ok(component)
+            }
+            _ => Err(CoseError::UnexpectedItem(
+                "map",
+                "map with measurement value and signer ID",
+            )),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut map = Vec::new();
+        if let Some(measurement_type) = self.measurement_type {
+            map.push((Value::from(MEASUREMENT_TYPE), Value::Text(measurement_type)));
+        }
+        map.push((
+            Value::from(MEASUREMENT_VALUE),
+            Value::Bytes(self.measurement_value),
+        ));
+        if let Some(version) = self.version {
+            map.push((Value::from(VERSION), Value::Text(version)));
+        }
+        map.push((Value::from(SIGNER_ID), Value::Bytes(self.signer_id)));
+        if let Some(desc) = self.measurement_desc {
+            map.push((Value::from(MEASUREMENT_DESC), Value::Text(desc)));
+        }
+        Ok(Value::Map(map))
+    }
+}
+
+/// The error type returned when PSA claims do not meet the requirements of the PSA profile.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PsaError {
+    /// A claim required by the profile is not present.
+    MissingClaim(ClaimName),
+    /// A claim holds a value that is not permitted by the profile.
+    InvalidClaim(ClaimName),
+}
+
+impl core::error::Error for PsaError {}
+
+impl core::fmt::Display for PsaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PsaError::MissingClaim(name) => write!(f, "missing claim {name:?}"),
+            PsaError::InvalidClaim(name) => write!(f, "invalid claim {name:?}"),
+        }
+    }
+}
+
+/// Structure representing the claims of a PSA attestation token.
+///
+/// Decoding only checks the types of the PSA claims; use [`PsaClaims::check`] to check the
+/// claims against the requirements of the PSA profile.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PsaClaims {
+    /// Nonce (`eat_nonce`).
+    pub nonce: Option<Vec<u8>>,
+    /// Instance ID (`ueid`).
+    pub instance_id: Option<Ueid>,
+    /// EAT profile (`eat_profile`).
+    pub profile: Option<Profile>,
+    /// Boot seed (`bootseed`).
+    pub boot_seed: Option<Vec<u8>>,
+    /// Client ID of the caller (`psa-client-id`), which is negative for non-secure callers.
+    pub client_id: Option<i64>,
+    /// Security lifecycle (`psa-security-lifecycle`).
+    pub security_lifecycle: Option<SecurityLifecycle>,
+    /// Implementation ID (`psa-implementation-id`).
+    pub implementation_id: Option<Vec<u8>>,
+    /// Certification reference (`psa-certification-reference`).
+    pub certification_reference: Option<String>,
+    /// Software components (`psa-software-components`).
+    pub software_components: Vec<SoftwareComponent>,
+    /// Verification service indicator (`psa-verification-service-indicator`).
+    pub verification_service_indicator: Option<String>,
+    /// Any other claims.
+    pub claims: ClaimsSet,
+}
+
+impl crate::CborSerializable for PsaClaims {}
+
+/// Indicate whether `data` has the size of a PSA hash.
+fn is_hash_size(data: &[u8]) -> bool {
+    matches!(data.len(), 32 | 48 | 64)
+}
+
+/// Indicate whether `reference` is an EAN-13, optionally followed by a dash and 5 digits.
+fn is_certification_reference(reference: &str) -> bool {
+    let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    match reference.split_once('-') {
+        None => reference.len() == 13 && all_digits(reference),
+        Some((ean, suffix)) => {
+            ean.len() == 13 && all_digits(ean) && suffix.len() == 5 && all_digits(suffix)
+        }
+    }
+}
+
+impl PsaClaims {
+    /// Extract the PSA claims from a claims set, leaving any other claims in
+    /// [`PsaClaims::claims`].
+    pub fn from_claims_set(mut claims: ClaimsSet) -> Result<Self, CoseError> {
+        use iana::CwtClaimName as N;
+        let rest = core::mem::take(&mut claims.rest);
+        let mut psa = Self {
+            claims,
+            ..Default::default()
+        };
+        for (name, value) in rest {
+            let name = match name {
+                ClaimName::Assigned(name) => name,
+                name => {
+                    psa.claims.rest.push((name, value));
+                    continue;
+                }
+            };
+            match name {
+                N::Nonce => set_once(&mut psa.nonce, value.try_as_bytes()?)?,
+                N::UEID => set_once(&mut psa.instance_id, Ueid::from_cbor_value(value)?)?,
+                N::EatProfile => set_once(&mut psa.profile, Profile::from_cbor_value(value)?)?,
+                N::BootSeed => set_once(&mut psa.boot_seed, value.try_as_bytes()?)?,
+                N::PsaClientId => {
+                    set_once(&mut psa.client_id, value.try_as_integer()?.try_into()?)?
+                }
+                N::PsaSecurityLifecycle => set_once(
+                    &mut psa.security_lifecycle,
+                    SecurityLifecycle::from_cbor_value(value)?,
+                )?,
+                N::PsaImplementationId => {
+                    set_once(&mut psa.implementation_id, value.try_as_bytes()?)?
+                }
+                N::PsaCertificationReference => {
+                    set_once(&mut psa.certification_reference, value.try_as_string()?)?
+                }
+                N::PsaSoftwareComponents => {
+                    set_once_vec(&mut psa.software_components, super::array_from_cbor(value)?)?
+                }
+                N::PsaVerificationServiceIndicator => set_once(
+                    &mut psa.verification_service_indicator,
+                    value.try_as_string()?,
+                )?,
+                name => psa.claims.rest.push((ClaimName::Assigned(name), value)),
+            }
+        }
+        Ok(psa)
+    }
+
+    /// Convert to a claims set, with the PSA claims preceding any other claims.  No checks are
+    /// made against the requirements of the PSA profile, so this can be used to build invalid
+    /// tokens for testing.
+    pub fn to_claims_set(self) -> Result<ClaimsSet, CoseError> {
+        use iana::CwtClaimName as N;
+        let mut psa = Vec::new();
+        if let Some(nonce) = self.nonce {
+            psa.push((N::Nonce, Value::Bytes(nonce)));
+        }
+        if let Some(instance_id) = self.instance_id {
+            psa.push((N::UEID, instance_id.to_cbor_value()?));
+        }
+        if let Some(profile) = self.profile {
+            psa.push((N::EatProfile, profile.to_cbor_value()?));
+        }
+        if let Some(seed) = self.boot_seed {
+            psa.push((N::BootSeed, Value::Bytes(seed)));
+        }
+        if let Some(client_id) = self.client_id {
+            psa.push((N::PsaClientId, Value::from(client_id)));
+        }
+        if let Some(lifecycle) = self.security_lifecycle {
+            psa.push((N::PsaSecurityLifecycle, lifecycle.to_cbor_value()?));
+        }
+        if let Some(implementation_id) = self.implementation_id {
+            psa.push((N::PsaImplementationId, Value::Bytes(implementation_id)));
+        }
+        if let Some(reference) = self.certification_reference {
+            psa.push((N::PsaCertificationReference, Value::Text(reference)));
+        }
+        if !self.software_components.is_empty() {
+            psa.push((
+                N::PsaSoftwareComponents,
+                crate::util::to_cbor_array(self.software_components)?,
+            ));
+        }
+        if let Some(indicator) = self.verification_service_indicator {
+            psa.push((N::PsaVerificationServiceIndicator, Value::Text(indicator)));
+        }
+
+        let mut claims = self.claims;
+        let rest = core::mem::take(&mut claims.rest);
+        claims.rest = psa
+            .into_iter()
+            .map(|(name, value)| (ClaimName::Assigned(name), value))
+            .chain(rest)
+            .collect();
+        Ok(claims)
+    }
+
+    /// Check the claims against the requirements of the PSA profile.  Both the RFC 9783 profile
+    /// ([`PSA_PROFILE`]) and the earlier [`PSA_PROFILE_LEGACY`] profile are accepted.
+    pub fn check(&self) -> Result<(), PsaError> {
+        use iana::CwtClaimName as N;
+        let missing = |name| PsaError::MissingClaim(ClaimName::Assigned(name));
+        let invalid = |name| PsaError::InvalidClaim(ClaimName::Assigned(name));
+
+        match &self.profile {
+            None => return Err(missing(N::EatProfile)),
+            Some(Profile::Uri(p)) if p == PSA_PROFILE || p == PSA_PROFILE_LEGACY => {}
+            Some(_) => return Err(invalid(N::EatProfile)),
+        }
+        match &self.nonce {
+            None => return Err(missing(N::Nonce)),
+            Some(nonce) if !is_hash_size(nonce) => return Err(invalid(N::Nonce)),
+            Some(_) => {}
+        }
+        match &self.instance_id {
+            None => return Err(missing(N::UEID)),
+            Some(ueid) if ueid.ueid_type() != UeidType::Rand || ueid.id().len() != 32 => {
+                return Err(invalid(N::UEID))
+            }
+            Some(_) => {}
+        }
+        if let Some(seed) = &self.boot_seed {
+            if seed.len() < 8 || seed.len() > 32 {
+                return Err(invalid(N::BootSeed));
+            }
+        }
+        if self.client_id.is_none() {
+            return Err(missing(N::PsaClientId));
+        }
+        if self.security_lifecycle.is_none() {
+            return Err(missing(N::PsaSecurityLifecycle));
+        }
+        match &self.implementation_id {
+            None => return Err(missing(N::PsaImplementationId)),
+            Some(id) if id.len() != 32 => return Err(invalid(N::PsaImplementationId)),
+            Some(_) => {}
+        }
+        if let Some(reference) = &self.certification_reference {
+            if !is_certification_reference(reference) {
+                return Err(invalid(N::PsaCertificationReference));
+            }
+        }
+        if self.software_components.is_empty() {
+            return Err(missing(N::PsaSoftwareComponents));
+        }
+        if self
+            .software_components
+            .iter()
+            .any(|c| !is_hash_size(&c.measurement_value) || !is_hash_size(&c.signer_id))
+        {
+            return Err(invalid(N::PsaSoftwareComponents));
+        }
+        Ok(())
+    }
+}
+
+impl AsCborValue for PsaClaims {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        Self::from_claims_set(ClaimsSet::from_cbor_value(value)?)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        self.to_claims_set()?.to_cbor_value()
+    }
+}
+
+/// Builder for [`PsaClaims`] objects.
+pub struct PsaClaimsBuilder(PsaClaims);
+
+impl Default for PsaClaimsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PsaClaimsBuilder {
+    /// Constructor for builder, with the profile set to [`PSA_PROFILE`].
+    pub fn new() -> Self {
+        Self(PsaClaims {
+            profile: Some(Profile::Uri(String::from(PSA_PROFILE))),
+            ..Default::default()
+        })
+    }
+
+    /// Build the completed object.
+    pub fn build(self) -> PsaClaims {
+        self.0
+    }
+
+    builder_set_optional! {nonce: Vec<u8>}
+    builder_set_optional! {instance_id: Ueid}
+    builder_set_optional! {profile: Profile}
+    builder_set_optional! {boot_seed: Vec<u8>}
+    builder_set_optional! {client_id: i64}
+    builder_set_optional! {security_lifecycle: SecurityLifecycle}
+    builder_set_optional! {implementation_id: Vec<u8>}
+    builder_set_optional! {certification_reference: String}
+    builder_set_optional! {verification_service_indicator: String}
+    builder_set! {claims: ClaimsSet}
+
+    /// Add a software component.
+    #[must_use]
+    pub fn software_component(mut self, component: SoftwareComponent) -> Self {
+        self.0.software_components.push(component);
+        self
+    }
+}
@@ -0,0 +1,282 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Submodules, nested tokens and detached EAT bundles.
+
+use super::{types::try_as_nonempty_map, EatClaims};
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    iana,
+    util::{cbor_type_error, ValueTryAs},
+    CborSerializable, CoseError, Label, TaggedCborSerializable,
+};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+
+/// Token nested inside an EAT, either as a submodule or as the main token of a detached EAT
+/// bundle.
+///
+/// ```cddl
+///  Nested-Token = CBOR-Nested-Token
+///  CBOR-Nested-Token =
+///      JSON-Token-Inside-CBOR-Token /
+///      CBOR-Token-Inside-CBOR-Token
+///  CBOR-Token-Inside-CBOR-Token = bstr .cbor $CBOR-Tagged-Token
+///  JSON-Token-Inside-CBOR-Token = tstr
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NestedToken {
+    /// Encoded tagged CBOR token, either a tagged CWT or a tagged detached EAT bundle.
+    Cbor(Vec<u8>),
+    /// JSON token.
+    Json(String),
+}
+
+impl NestedToken {
+    /// Create a nested token holding a detached EAT bundle.
+    pub fn from_bundle(bundle: DetachedEatBundle) -> Result<Self, CoseError> {
+        Ok(NestedToken::Cbor(bundle.to_tagged_vec()?))
+    }
+
+    /// Return the detached EAT bundle held in the nested token, or `None` if the nested token
+    /// holds something else.
+    pub fn bundle(&self) -> Result<Option<DetachedEatBundle>, CoseError> {
+        match self {
+            NestedToken::Cbor(data) => match Value::from_slice(data)? {
+                Value::Tag(t, v) if t == DetachedEatBundle::TAG => {
+                    Ok(Some(DetachedEatBundle::from_cbor_value(*v)?))
+                }
+                _ => Ok(None),
+            },
+            NestedToken::Json(_) => Ok(None),
+        }
+    }
+}
+
+impl AsCborValue for NestedToken {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        match value {
+            Value::Bytes(b) => Ok(NestedToken::Cbor(b)),
+            Value::Text(t) => Ok(NestedToken::Json(t)),
+            v => cbor_type_error(&v, "bstr/tstr"),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(match self {
+            NestedToken::Cbor(b) => Value::Bytes(b),
+            NestedToken::Json(t) => Value::Text(t),
+        })
+    }
+}
+
+/// Digest of a submodule's claims set, where the claims set itself is conveyed separately in a
+/// detached EAT bundle.
+///
+/// ```cddl
+///  Detached-Submodule-Digest = [
+///     hash-algorithm : text / int,
+///     digest         : binary-data
+///  ]
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DetachedDigest {
+    /// Hash algorithm, from the IANA Named Information Hash Algorithm registry.
+    pub algorithm: Label,
+    /// Digest of the encoded claims set.
+    pub digest: Vec<u8>,
+}
+
+impl AsCborValue for DetachedDigest {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.len() != 2 {
+            return Err(CoseError::UnexpectedItem("array", "array with 2 items"));
+        }
+        let digest = a.remove(1).try_as_bytes()?;
+        Ok(Self {
+            algorithm: Label::from_cbor_value(a.remove(0))?,
+            digest,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::Array(vec![
+            self.algorithm.to_cbor_value()?,
+            Value::Bytes(self.digest),
+        ]))
+    }
+}
+
+/// Submodule of an entity, as held in a `submods` claim.
+///
+/// ```cddl
+///  Submodule = Claims-Set / CBOR-Nested-Token /
+///              Detached-Submodule-Digest
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Submodule {
+    /// Claims about the submodule.
+    Claims(Box<EatClaims>),
+    /// Separately-secured token about the submodule.
+    Token(NestedToken),
+    /// Digest of claims about the submodule, held in a detached EAT bundle.
+    Digest(DetachedDigest),
+}
+
+impl AsCborValue for Submodule {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        match value {
+            Value::Map(_) => Ok(Submodule::Claims(Box::new(EatClaims::from_cbor_value(
+                value,
+            )?))),
+            Value::Bytes(_) | Value::Text(_) => {
+                Ok(Submodule::Token(NestedToken::from_cbor_value(value)?))
+            }
+            Value::Array(_) => Ok(Submodule::Digest(DetachedDigest::from_cbor_value(value)?)),
+            v => cbor_type_error(&v, "map/bstr/tstr/array"),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        match self {
+            Submodule::Claims(claims) => claims.to_cbor_value(),
+            Submodule::Token(token) => token.to_cbor_value(),
+            Submodule::Digest(digest) => digest.to_cbor_value(),
+        }
+    }
+}
+
+/// Claims set held in a detached EAT bundle.
+///
+/// ```cddl
+///  cbor-wrapped-claims-set = bstr .cbor Claims-Set
+///  json-wrapped-claims-set = tstr .b64u JSON-Claims-Set
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DetachedClaimsSet {
+    /// Encoded CBOR claims set.
+    Cbor(Vec<u8>),
+    /// Base64url-encoded JSON claims set.
+    Json(String),
+}
+
+impl DetachedClaimsSet {
+    /// Create a detached claims set holding encoded CBOR claims.
+    pub fn from_claims(claims: EatClaims) -> Result<Self, CoseError> {
+        Ok(DetachedClaimsSet::Cbor(claims.to_vec()?))
+    }
+
+    /// Return the claims held in a CBOR detached claims set, or `None` for a JSON claims set.
+    pub fn claims(&self) -> Result<Option<EatClaims>, CoseError> {
+        match self {
+            DetachedClaimsSet::Cbor(data) => Ok(Some(EatClaims::from_slice(data)?)),
+            DetachedClaimsSet::Json(_) => Ok(None),
+        }
+    }
+}
+
+impl AsCborValue for DetachedClaimsSet {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        match value {
+            Value::Bytes(b) => Ok(DetachedClaimsSet::Cbor(b)),
+            Value::Text(t) => Ok(DetachedClaimsSet::Json(t)),
+            v => cbor_type_error(&v, "bstr/tstr"),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(match self {
+            DetachedClaimsSet::Cbor(b) => Value::Bytes(b),
+            DetachedClaimsSet::Json(t) => Value::Text(t),
+        })
+    }
+}
+
+/// Detached EAT bundle, holding a main token together with claims sets whose digests appear in
+/// that token.
+///
+/// ```cddl
+///  BUNDLE-Tagged-Message   = #6.602(BUNDLE-Untagged-Message)
+///  BUNDLE-Untagged-Message = Detached-EAT-Bundle
+///
+///  Detached-EAT-Bundle = [
+///      main-token : Nested-Token,
+///      detached-claims-sets: {
+///          + tstr => JC<json-wrapped-claims-set,
+///                       cbor-wrapped-claims-set>
+///      }
+///  ]
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DetachedEatBundle {
+    /// Main token, which holds digests of the detached claims sets.
+    pub main_token: NestedToken,
+    /// Detached claims sets, keyed by submodule name.
+    pub detached_claims: Vec<(String, DetachedClaimsSet)>,
+}
+
+impl DetachedEatBundle {
+    /// Return the detached claims set for the submodule with the given name.
+    pub fn detached_claims_set(&self, name: &str) -> Option<&DetachedClaimsSet> {
+        self.detached_claims
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, claims)| claims)
+    }
+}
+
+impl CborSerializable for DetachedEatBundle {}
+
+impl TaggedCborSerializable for DetachedEatBundle {
+    const TAG: u64 = iana::CborTag::DetachedEatBundle as u64;
+}
+
+impl AsCborValue for DetachedEatBundle {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.len() != 2 {
+            return Err(CoseError::UnexpectedItem("array", "array with 2 items"));
+        }
+        let mut detached_claims = Vec::new();
+        for (name, claims) in try_as_nonempty_map(a.remove(1))? {
+            let name = name.try_as_string()?;
+            if detached_claims.iter().any(|(n, _)| *n == name) {
+                return Err(CoseError::DuplicateMapKey);
+            }
+            detached_claims.push((name, DetachedClaimsSet::from_cbor_value(claims)?));
+        }
+        Ok(Self {
+            main_token: NestedToken::from_cbor_value(a.remove(0))?,
+            detached_claims,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        if self.detached_claims.is_empty() {
+            return Err(CoseError::UnexpectedItem("empty map", "non-empty map"));
+        }
+        let detached_claims = self
+            .detached_claims
+            .into_iter()
+            .map(|(name, claims)| Ok((Value::Text(name), claims.to_cbor_value()?)))
+            .collect::<Result<Vec<_>, CoseError>>()?;
+        Ok(Value::Array(vec![
+            self.main_token.to_cbor_value()?,
+            Value::Map(detached_claims),
+        ]))
+    }
+}
@@ -0,0 +1,509 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+mod psa;
+
+use super::*;
+use crate::{
+    cwt::ClaimsSetBuilder, util::expect_err, CborSerializable, Label, TaggedCborSerializable,
+};
+use alloc::{borrow::ToOwned, boxed::Box, vec};
+
+#[test]
+fn test_ueid() {
+    let tests = vec![
+        (UeidType::Rand, vec![0x02; 16]),
+        (UeidType::Rand, vec![0x02; 24]),
+        (UeidType::Rand, vec![0x02; 32]),
+        (UeidType::IeeeEui, vec![0x02; 6]),
+        (UeidType::IeeeEui, vec![0x02; 8]),
+        (UeidType::Imei, vec![0x02; 8]),
+    ];
+    for (ueid_type, id) in tests {
+        let ueid = Ueid::from_id(ueid_type, &id).unwrap();
+        assert_eq!(ueid.ueid_type(), ueid_type);
+        assert_eq!(ueid.id(), id.as_slice());
+        assert_eq!(ueid.as_bytes()[0], ueid_type as u8);
+        assert_eq!(Ueid::new(ueid.as_bytes().to_vec()).unwrap(), ueid);
+    }
+
+    let tests = vec![
+        (vec![0x01; 7], "UEID of valid size for type"),
+        (vec![0x02; 8], "UEID of valid size for type"),
+        (vec![0x03; 7], "UEID of valid size for type"),
+        (vec![0x04; 7], "UEID type"),
+        (vec![0x00; 7], "UEID type"),
+        (vec![0x01; 6], "bstr of 7-33 bytes"),
+        (vec![0x01; 34], "bstr of 7-33 bytes"),
+    ];
+    for (data, err_msg) in tests {
+        expect_err(Ueid::new(data), err_msg);
+    }
+}
+
+#[test]
+fn test_eat_claims_encode() {
+    let tests = vec![
+        (EatClaimsBuilder::new().build(), "a0"),
+        (
+            EatClaimsBuilder::new()
+                .claims(ClaimsSetBuilder::new().issuer("x".to_owned()).build())
+                .nonce(vec![0x01; 8])
+                .ueid(Ueid::from_id(UeidType::Rand, &[0x02; 16]).unwrap())
+                .oem_boot(true)
+                .debug_status(DebugStatus::Disabled)
+                .software_name("fw".to_owned())
+                .intended_use(IntendedUse::Generic)
+                .build(),
+            concat!(
+                "a7", // 7-map
+                "01",
+                "6178", // 1 (iss) => "x"
+                "0a",
+                "48",
+                "0101010101010101", // 10 (eat_nonce) => 8-bstr
+                "190100",
+                "51",
+                "01",
+                "02020202020202020202020202020202", // 256 (ueid) => 17-bstr
+                "190106",
+                "f5", // 262 (oemboot) => true
+                "190107",
+                "01", // 263 (dbgstat) => disabled
+                "19010e",
+                "626677", // 270 (swname) => "fw"
+                "190113",
+                "01", // 275 (intuse) => generic
+            ),
+        ),
+        (
+            EatClaimsBuilder::new()
+                .nonce(vec![0x01; 8])
+                .nonce(vec![0x02; 8])
+                .sueid(
+                    "a".to_owned(),
+                    Ueid::from_id(UeidType::IeeeEui, &[1, 2, 3, 4, 5, 6]).unwrap(),
+                )
+                .oemid(OemId::Ieee(vec![1, 2, 3]))
+                .hardware_model(vec![0x42])
+                .hardware_version(Version {
+                    version: "1.2".to_owned(),
+                    scheme: Some(Label::Int(1)),
+                })
+                .uptime(10)
+                .profile(Profile::Uri("u".to_owned()))
+                .boot_count(3)
+                .dloa(Dloa {
+                    registrar: "r".to_owned(),
+                    platform_label: "p".to_owned(),
+                    application_label: None,
+                })
+                .software_version(Version {
+                    version: "3".to_owned(),
+                    scheme: None,
+                })
+                .claims(
+                    ClaimsSetBuilder::new()
+                        .claim(iana::CwtClaimName::Exi, Value::from(1))
+                        .build(),
+                )
+                .build(),
+            concat!(
+                "ab", // 11-map
+                "0a",
+                "82",
+                "48",
+                "0101010101010101",
+                "48",
+                "0202020202020202", // 10 (eat_nonce)
+                "190101",
+                "a1",
+                "6161",
+                "47",
+                "02010203040506", // 257 (sueids) => {"a": ueid}
+                "190102",
+                "43",
+                "010203", // 258 (oemid) => 3-bstr
+                "190103",
+                "41",
+                "42", // 259 (hwmodel) => 1-bstr
+                "190104",
+                "82",
+                "63312e32",
+                "01", // 260 (hwversion) => ["1.2", 1]
+                "190105",
+                "0a", // 261 (uptime) => 10
+                "190109",
+                "6175", // 265 (eat_profile) => "u"
+                "19010b",
+                "03", // 267 (bootcount) => 3
+                "19010d",
+                "81",
+                "82",
+                "6172",
+                "6170", // 269 (dloas) => [["r", "p"]]
+                "19010f",
+                "81",
+                "6133", // 271 (swversion) => ["3"]
+                "1828",
+                "01", // 40 (exi) => 1
+            ),
+        ),
+    ];
+    for (i, (claims, claims_data)) in tests.into_iter().enumerate() {
+        let got = claims.clone().to_vec().unwrap();
+        assert_eq!(hex::encode(&got), claims_data, "case {}", i);
+
+        let got = EatClaims::from_slice(&got).unwrap();
+        assert_eq!(got, claims, "case {}", i);
+    }
+}
+
+fn full_claims() -> EatClaims {
+    EatClaimsBuilder::new()
+        .claims(ClaimsSetBuilder::new().issuer("x".to_owned()).build())
+        .nonce(vec![0x01; 64])
+        .ueid(Ueid::from_id(UeidType::Imei, &[0x02; 8]).unwrap())
+        .sueid(
+            "a".to_owned(),
+            Ueid::from_id(UeidType::Rand, &[0x03; 32]).unwrap(),
+        )
+        .sueid(
+            "b".to_owned(),
+            Ueid::from_id(UeidType::IeeeEui, &[0x04; 8]).unwrap(),
+        )
+        .oemid(OemId::Random(vec![0x05; 16]))
+        .hardware_model(vec![0x06; 32])
+        .hardware_version(Version {
+            version: "1.0".to_owned(),
+            scheme: Some(Label::Text("semver".to_owned())),
+        })
+        .uptime(1000)
+        .oem_boot(false)
+        .debug_status(DebugStatus::DisabledFullyAndPermanently)
+        .location(
+            LocationBuilder::new(51.5, -0.125)
+                .altitude(10.5)
+                .accuracy(2.0)
+                .altitude_accuracy(4.0)
+                .heading(90.0)
+                .speed(1.5)
+                .timestamp(1_700_000_000)
+                .age(5)
+                .build(),
+        )
+        .profile(Profile::Oid(vec![0x2b, 0x06, 0x01]))
+        .submodule(
+            "sub".to_owned(),
+            Submodule::Claims(Box::new(
+                EatClaimsBuilder::new()
+                    .software_name("sub-fw".to_owned())
+                    .build(),
+            )),
+        )
+        .submodule(
+            "token".to_owned(),
+            Submodule::Token(NestedToken::Cbor(vec![0xd8, 0x3d, 0xf6])),
+        )
+        .submodule(
+            "json".to_owned(),
+            Submodule::Token(NestedToken::Json("e30".to_owned())),
+        )
+        .submodule(
+            "digest".to_owned(),
+            Submodule::Digest(DetachedDigest {
+                algorithm: Label::Int(1),
+                digest: vec![0x07; 32],
+            }),
+        )
+        .boot_count(7)
+        .boot_seed(vec![0x08; 32])
+        .dloa(Dloa {
+            registrar: "https://example.com".to_owned(),
+            platform_label: "platform".to_owned(),
+            application_label: Some("app".to_owned()),
+        })
+        .software_name("fw".to_owned())
+        .software_version(Version {
+            version: "2.0".to_owned(),
+            scheme: Some(Label::Int(16384)),
+        })
+        .manifest(FormattedContent {
+            content_type: 258,
+            content: Value::Bytes(vec![0x09]),
+        })
+        .measurement(FormattedContent {
+            content_type: 258,
+            content: Value::Bytes(vec![0x0a]),
+        })
+        .measurement_results(MeasurementResults {
+            measurement_system: "verifier".to_owned(),
+            results: vec![
+                (ResultId::Text("fw".to_owned()), ComparisonResult::Success),
+                (ResultId::Bytes(vec![0x0b]), ComparisonResult::Absent),
+            ],
+        })
+        .intended_use(IntendedUse::ProofOfPossession)
+        .build()
+}
+
+#[test]
+fn test_eat_claims_roundtrip() {
+    let claims = full_claims();
+    let data = claims.clone().to_vec().unwrap();
+    let got = EatClaims::from_slice(&data).unwrap();
+    assert_eq!(got, claims);
+
+    // The EAT claims are also visible as generic claims.
+    let claims_set = crate::cwt::ClaimsSet::from_slice(&data).unwrap();
+    assert_eq!(claims_set.issuer, Some("x".to_owned()));
+    assert_eq!(claims_set.rest.len(), 21);
+    let got = EatClaims::from_claims_set(claims_set).unwrap();
+    assert_eq!(got, claims);
+    assert!(got.claims.rest.is_empty());
+
+    assert_eq!(
+        got.submodule("sub"),
+        Some(&Submodule::Claims(Box::new(
+            EatClaimsBuilder::new()
+                .software_name("sub-fw".to_owned())
+                .build()
+        )))
+    );
+    assert_eq!(got.submodule("missing"), None);
+}
+
+#[test]
+fn test_eat_claims_decode_fail() {
+    let tests = [
+        ("a10a4701010101010101", "bstr of 8-64 bytes"),
+        ("a10a81480101010101010101", "array with 2+ items"),
+        ("a10a01", "expected bstr/array"),
+        ("a10a82480101010101010101f6", "expected bstr"),
+        ("a119010047040102030405 06", "UEID type"),
+        ("a1190100480101020304050607", "UEID of valid size for type"),
+        ("a11901004101", "bstr of 7-33 bytes"),
+        ("a1190101a0", "non-empty map"),
+        (
+            "a1190101a2616147020102030405066161470201020304050 6",
+            "duplicate map key",
+        ),
+        ("a1190101a101470201020304050 6", "expected tstr"),
+        ("a119010244 01020304", "bstr of 3 or 16 bytes"),
+        ("a1190102f6", "expected int/bstr"),
+        ("a119010340", "bstr of 1-32 bytes"),
+        ("a119010480", "array with 1 or 2 items"),
+        ("a11901048101", "expected tstr"),
+        ("a119010520", "out of range integer value"),
+        ("a119010601", "expected bool"),
+        ("a119010705", "out of range integer value"),
+        ("a1190108a101f93c00", "map with latitude and longitude"),
+        ("a1190108a10a01", "location field"),
+        ("a1190108a20101 0101", "duplicate map key"),
+        ("a1190108a3010102010920", "out of range integer value"),
+        ("a1190108a30101020108f93c00", "expected int"),
+        ("a1190108a20101 0260", "expected int/float"),
+        ("a119010901", "expected tstr/bstr"),
+        ("a119010aa1616101", "expected map/bstr/tstr/array"),
+        ("a119010aa161618101", "array with 2 items"),
+        ("a119010c4100", "bstr of 32-64 bytes"),
+        ("a119010d80", "non-empty array"),
+        ("a119010d81816161", "array with 2 or 3 items"),
+        ("a119010e01", "expected tstr"),
+        ("a119011081 8101", "array with 2 items"),
+        ("a119011181 821a0001000040", "out of range integer value"),
+        ("a119011281 82616180", "non-empty array"),
+        ("a119011281 826161818261610 5", "out of range integer value"),
+        ("a119011281 8261618181 6161", "array with 2 items"),
+        ("a119011281 826161818201 01", "expected tstr/bstr"),
+        ("a119011300", "out of range integer value"),
+    ];
+    for (claims_data, err_msg) in tests.iter() {
+        let data = hex::decode(claims_data.replace(' ', "")).unwrap();
+        let result = EatClaims::from_slice(&data);
+        expect_err(result, err_msg);
+    }
+
+    // Duplicate claims in a claims set built by hand.
+    let claims = ClaimsSetBuilder::new()
+        .claim(iana::CwtClaimName::Uptime, Value::from(1))
+        .claim(iana::CwtClaimName::Uptime, Value::from(2))
+        .build();
+    expect_err(EatClaims::from_claims_set(claims), "duplicate map key");
+    let claims = ClaimsSetBuilder::new()
+        .claim(iana::CwtClaimName::DLoAs, Value::Array(vec![]))
+        .build();
+    expect_err(EatClaims::from_claims_set(claims), "non-empty array");
+}
+
+#[test]
+fn test_eat_claims_encode_fail() {
+    let tests = vec![
+        (
+            EatClaimsBuilder::new().nonce(vec![1; 7]).build(),
+            "bstr of 8-64 bytes",
+        ),
+        (
+            EatClaimsBuilder::new().nonce(vec![1; 65]).build(),
+            "bstr of 8-64 bytes",
+        ),
+        (
+            EatClaimsBuilder::new().hardware_model(vec![]).build(),
+            "bstr of 1-32 bytes",
+        ),
+        (
+            EatClaimsBuilder::new().boot_seed(vec![1; 31]).build(),
+            "bstr of 32-64 bytes",
+        ),
+        (
+            EatClaimsBuilder::new()
+                .oemid(OemId::Random(vec![1; 3]))
+                .build(),
+            "bstr of 16 bytes",
+        ),
+        (
+            EatClaimsBuilder::new()
+                .oemid(OemId::Ieee(vec![1; 16]))
+                .build(),
+            "bstr of 3 bytes",
+        ),
+        (
+            EatClaimsBuilder::new()
+                .measurement_results(MeasurementResults {
+                    measurement_system: "x".to_owned(),
+                    results: vec![],
+                })
+                .build(),
+            "non-empty array",
+        ),
+    ];
+    for (claims, err_msg) in tests {
+        expect_err(claims.to_vec(), err_msg);
+    }
+}
+
+#[test]
+fn test_location() {
+    // Integer values are accepted for numbers.
+    let data = hex::decode(concat!(
+        "a3", // 3-map
+        "01",
+        "18",
+        "33", // 1 (latitude) => 51
+        "02",
+        "20", // 2 (longitude) => -1
+        "03",
+        "fb",
+        "4025000000000000", // 3 (altitude) => 10.5
+    ))
+    .unwrap();
+    let got = Location::from_cbor_value(Value::from_slice(&data).unwrap()).unwrap();
+    assert_eq!(got, LocationBuilder::new(51.0, -1.0).altitude(10.5).build());
+
+    let got = LocationBuilder::new(1.5, 2.5).age(3).build();
+    let got = got.to_cbor_value().unwrap().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(got),
+        concat!(
+            "a3", // 3-map
+            "01", "f93e00", // 1 (latitude) => 1.5
+            "02", "f94100", // 2 (longitude) => 2.5
+            "09", "03", // 9 (age) => 3
+        )
+    );
+}
+
+#[test]
+fn test_detached_eat_bundle() {
+    let detached = EatClaimsBuilder::new()
+        .software_name("detached".to_owned())
+        .build();
+    let main = EatClaimsBuilder::new()
+        .submodule(
+            "sub".to_owned(),
+            Submodule::Digest(DetachedDigest {
+                algorithm: Label::Int(1),
+                digest: vec![0x01; 32],
+            }),
+        )
+        .build();
+    let bundle = DetachedEatBundle {
+        main_token: NestedToken::Cbor(main.to_vec().unwrap()),
+        detached_claims: vec![
+            (
+                "sub".to_owned(),
+                DetachedClaimsSet::from_claims(detached.clone()).unwrap(),
+            ),
+            ("json".to_owned(), DetachedClaimsSet::Json("e30".to_owned())),
+        ],
+    };
+
+    let data = bundle.clone().to_tagged_vec().unwrap();
+    assert_eq!(hex::encode(&data[..3]), "d9025a");
+    let got = DetachedEatBundle::from_tagged_slice(&data).unwrap();
+    assert_eq!(got, bundle);
+    assert_eq!(
+        got.detached_claims_set("sub").unwrap().claims().unwrap(),
+        Some(detached)
+    );
+    assert_eq!(
+        got.detached_claims_set("json").unwrap().claims().unwrap(),
+        None
+    );
+    assert!(got.detached_claims_set("missing").is_none());
+
+    // A bundle nested as a submodule token.
+    let token = NestedToken::from_bundle(bundle.clone()).unwrap();
+    assert_eq!(token, NestedToken::Cbor(data));
+    let outer = EatClaimsBuilder::new()
+        .submodule("bundle".to_owned(), Submodule::Token(token))
+        .build();
+    let outer = EatClaims::from_slice(&outer.to_vec().unwrap()).unwrap();
+    let token = match outer.submodule("bundle") {
+        Some(Submodule::Token(token)) => token,
+        _ => panic!("expected nested token"),
+    };
+    assert_eq!(token.bundle().unwrap(), Some(bundle));
+
+    // Nested tokens that are not bundles.
+    assert_eq!(
+        NestedToken::Cbor(vec![0xd8, 0x3d, 0xf6]).bundle().unwrap(),
+        None
+    );
+    assert_eq!(NestedToken::Json("e30".to_owned()).bundle().unwrap(), None);
+    expect_err(
+        NestedToken::Cbor(vec![0xd9, 0x02, 0x5a, 0x80]).bundle(),
+        "array with 2 items",
+    );
+
+    let tests = [
+        ("82 40 a0", "non-empty map"),
+        ("82 40 a2 6161 40 6161 40", "duplicate map key"),
+        ("82 40 a1 01 40", "expected tstr"),
+        ("82 40 a1 6161 01", "expected bstr/tstr"),
+        ("82 01 a1 6161 40", "expected bstr/tstr"),
+        ("81 40", "array with 2 items"),
+    ];
+    for (data, err_msg) in tests.iter() {
+        let data = hex::decode(data.replace(' ', "")).unwrap();
+        expect_err(DetachedEatBundle::from_slice(&data), err_msg);
+    }
+    let empty = DetachedEatBundle {
+        main_token: NestedToken::Json("e30".to_owned()),
+        detached_claims: vec![],
+    };
+    expect_err(empty.to_vec(), "non-empty map");
+}
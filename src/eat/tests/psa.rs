@@ -0,0 +1,330 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    cwt,
+    cwt::{ClaimName, ClaimsSetBuilder},
+    eat::*,
+    iana,
+    util::expect_err,
+    CborSerializable, CoseSign1Builder, HeaderBuilder,
+};
+use alloc::{borrow::ToOwned, string::ToString, vec, vec::Vec};
+
+#[test]
+fn test_security_lifecycle() {
+    let tests = [
+        (0x0000, LifecycleState::Unknown, 0x00),
+        (0x00ff, LifecycleState::Unknown, 0xff),
+        (0x1000, LifecycleState::AssemblyAndTest, 0x00),
+        (0x2001, LifecycleState::PsaRotProvisioning, 0x01),
+        (0x3000, LifecycleState::Secured, 0x00),
+        (0x30a5, LifecycleState::Secured, 0xa5),
+        (0x4000, LifecycleState::NonPsaRotDebug, 0x00),
+        (0x5000, LifecycleState::RecoverablePsaRotDebug, 0x00),
+        (0x60ff, LifecycleState::Decommissioned, 0xff),
+    ];
+    for (value, state, detail) in tests {
+        let lifecycle = SecurityLifecycle::from_u16(value).unwrap();
+        assert_eq!(lifecycle.state, state, "for {:#06x}", value);
+        assert_eq!(lifecycle.detail, detail, "for {:#06x}", value);
+        assert_eq!(lifecycle.to_u16(), value);
+        let got = SecurityLifecycle::from_cbor_value(Value::from(value)).unwrap();
+        assert_eq!(got, lifecycle);
+    }
+    assert_eq!(
+        SecurityLifecycle::from(LifecycleState::Secured).to_u16(),
+        0x3000
+    );
+
+    for value in [0x0100, 0x0fff, 0x7000, 0xffff] {
+        expect_err(
+            SecurityLifecycle::from_u16(value),
+            "out of range integer value",
+        );
+    }
+    expect_err(
+        SecurityLifecycle::from_cbor_value(Value::from(0x10000)),
+        "out of range integer value",
+    );
+    expect_err(
+        SecurityLifecycle::from_cbor_value(Value::from(-1)),
+        "out of range integer value",
+    );
+}
+
+fn psa_claims() -> PsaClaims {
+    PsaClaimsBuilder::new()
+        .nonce(vec![0x01; 32])
+        .instance_id(Ueid::from_id(UeidType::Rand, &[0x02; 32]).unwrap())
+        .client_id(-1)
+        .security_lifecycle(LifecycleState::Secured.into())
+        .implementation_id(vec![0x03; 32])
+        .software_component(SoftwareComponent {
+            measurement_value: vec![0x04; 32],
+            signer_id: vec![0x05; 32],
+            ..Default::default()
+        })
+        .build()
+}
+
+#[test]
+fn test_psa_claims_encode() {
+    let claims = psa_claims();
+    assert!(claims.check().is_ok());
+    let data = claims.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "a7", // 7-map
+            "0a",
+            "5820",
+            "0101010101010101010101010101010101010101010101010101010101010101",
+            "190100",
+            "5821",
+            "01",
+            "0202020202020202020202020202020202020202020202020202020202020202",
+            "190109",
+            "7821", // 265 (eat_profile) => 33-tstr
+            "7461673a7073616365727469666965642e6f72672c323032333a7073612374666d",
+            "19095a",
+            "20", // 2394 (client ID) => -1
+            "19095b",
+            "193000", // 2395 (security lifecycle) => secured
+            "19095c",
+            "5820",
+            "0303030303030303030303030303030303030303030303030303030303030303",
+            "19095f",
+            "81",
+            "a2", // 2399 (software components) => [{2 => hash, 5 => hash}]
+            "02",
+            "5820",
+            "0404040404040404040404040404040404040404040404040404040404040404",
+            "05",
+            "5820",
+            "0505050505050505050505050505050505050505050505050505050505050505",
+        )
+    );
+    let got = PsaClaims::from_slice(&data).unwrap();
+    assert_eq!(got, claims);
+
+    // All claims, along with some non-PSA claims.
+    let claims = PsaClaimsBuilder::new()
+        .nonce(vec![0x01; 64])
+        .instance_id(Ueid::from_id(UeidType::Rand, &[0x02; 32]).unwrap())
+        .boot_seed(vec![0x06; 8])
+        .client_id(1)
+        .security_lifecycle(SecurityLifecycle::from_u16(0x2001).unwrap())
+        .implementation_id(vec![0x03; 32])
+        .certification_reference("1234567890123-12345".to_owned())
+        .software_component(SoftwareComponent {
+            measurement_type: Some("BL".to_owned()),
+            measurement_value: vec![0x04; 48],
+            version: Some("1.2.3".to_owned()),
+            signer_id: vec![0x05; 64],
+            measurement_desc: Some("sha-256".to_owned()),
+        })
+        .software_component(SoftwareComponent {
+            measurement_value: vec![0x07; 32],
+            signer_id: vec![0x08; 32],
+            ..Default::default()
+        })
+        .verification_service_indicator("https://verifier.example".to_owned())
+        .claims(
+            ClaimsSetBuilder::new()
+                .issuer("iss".to_owned())
+                .claim(iana::CwtClaimName::Uptime, Value::from(10))
+                .build(),
+        )
+        .build();
+    assert!(claims.check().is_ok());
+    let data = claims.clone().to_vec().unwrap();
+    let got = PsaClaims::from_slice(&data).unwrap();
+    assert_eq!(got, claims);
+    assert_eq!(got.claims.rest.len(), 1);
+
+    // The legacy profile is also accepted.
+    let mut claims = psa_claims();
+    claims.profile = Some(Profile::Uri(PSA_PROFILE_LEGACY.to_owned()));
+    assert!(claims.check().is_ok());
+}
+
+#[test]
+fn test_psa_claims_decode_fail() {
+    let tests = [
+        ("a10a01", "expected bstr"),
+        ("a119010041 01", "bstr of 7-33 bytes"),
+        ("a119010901", "expected tstr/bstr"),
+        ("a119010c01", "expected bstr"),
+        ("a119095a40", "expected int"),
+        ("a119095b190100", "out of range integer value"),
+        ("a119095b197000", "out of range integer value"),
+        ("a119095c01", "expected bstr"),
+        ("a119095e01", "expected tstr"),
+        ("a119095f80", "non-empty array"),
+        ("a119095f8101", "expected map"),
+        (
+            "a119095f81a10240",
+            "map with measurement value and signer ID",
+        ),
+        (
+            "a119095f81a10540",
+            "map with measurement value and signer ID",
+        ),
+        ("a119095f81a3024005400340", "software component field"),
+        ("a119095f81a3024005400240", "duplicate map key"),
+        ("a119095f81a202010540", "expected bstr"),
+        ("a119095f81a20240", "decode CBOR failure"),
+        ("a119095f81a30240054001 01", "expected tstr"),
+        ("a119095f81a2024060 40", "expected int"),
+        ("a119096001", "expected tstr"),
+    ];
+    for (claims_data, err_msg) in tests.iter() {
+        let data = hex::decode(claims_data.replace(' ', "")).unwrap();
+        let result = PsaClaims::from_slice(&data);
+        expect_err(result, err_msg);
+    }
+
+    let claims = ClaimsSetBuilder::new()
+        .claim(iana::CwtClaimName::PsaClientId, Value::from(1))
+        .claim(iana::CwtClaimName::PsaClientId, Value::from(2))
+        .build();
+    expect_err(PsaClaims::from_claims_set(claims), "duplicate map key");
+}
+
+#[test]
+fn test_psa_claims_check() {
+    use iana::CwtClaimName as N;
+    let missing = |name| PsaError::MissingClaim(ClaimName::Assigned(name));
+    let invalid = |name| PsaError::InvalidClaim(ClaimName::Assigned(name));
+
+    type Mutator = fn(&mut PsaClaims);
+    let tests: Vec<(Mutator, PsaError)> = vec![
+        (|c| c.profile = None, missing(N::EatProfile)),
+        (
+            |c| c.profile = Some(Profile::Uri("other".to_owned())),
+            invalid(N::EatProfile),
+        ),
+        (
+            |c| c.profile = Some(Profile::Oid(vec![1])),
+            invalid(N::EatProfile),
+        ),
+        (|c| c.nonce = None, missing(N::Nonce)),
+        (|c| c.nonce = Some(vec![1; 16]), invalid(N::Nonce)),
+        (|c| c.instance_id = None, missing(N::UEID)),
+        (
+            |c| c.instance_id = Some(Ueid::from_id(UeidType::Rand, &[1; 16]).unwrap()),
+            invalid(N::UEID),
+        ),
+        (
+            |c| c.instance_id = Some(Ueid::from_id(UeidType::IeeeEui, &[1; 6]).unwrap()),
+            invalid(N::UEID),
+        ),
+        (|c| c.boot_seed = Some(vec![1; 7]), invalid(N::BootSeed)),
+        (|c| c.boot_seed = Some(vec![1; 33]), invalid(N::BootSeed)),
+        (|c| c.client_id = None, missing(N::PsaClientId)),
+        (
+            |c| c.security_lifecycle = None,
+            missing(N::PsaSecurityLifecycle),
+        ),
+        (
+            |c| c.implementation_id = None,
+            missing(N::PsaImplementationId),
+        ),
+        (
+            |c| c.implementation_id = Some(vec![1; 31]),
+            invalid(N::PsaImplementationId),
+        ),
+        (
+            |c| c.certification_reference = Some("123456789012".to_owned()),
+            invalid(N::PsaCertificationReference),
+        ),
+        (
+            |c| c.certification_reference = Some("1234567890123-1234".to_owned()),
+            invalid(N::PsaCertificationReference),
+        ),
+        (
+            |c| c.certification_reference = Some("123456789012a".to_owned()),
+            invalid(N::PsaCertificationReference),
+        ),
+        (
+            |c| c.certification_reference = Some("1234567890123-1234a".to_owned()),
+            invalid(N::PsaCertificationReference),
+        ),
+        (
+            |c| c.software_components.clear(),
+            missing(N::PsaSoftwareComponents),
+        ),
+        (
+            |c| c.software_components[0].measurement_value = vec![1; 20],
+            invalid(N::PsaSoftwareComponents),
+        ),
+        (
+            |c| c.software_components[0].signer_id = vec![],
+            invalid(N::PsaSoftwareComponents),
+        ),
+    ];
+    for (i, (mutate, want)) in tests.into_iter().enumerate() {
+        let mut claims = psa_claims();
+        mutate(&mut claims);
+        assert_eq!(claims.check(), Err(want), "case {}", i);
+    }
+
+    let mut claims = psa_claims();
+    claims.certification_reference = Some("1234567890123".to_owned());
+    claims.boot_seed = Some(vec![1; 32]);
+    assert!(claims.check().is_ok());
+
+    assert_eq!(
+        missing(N::Nonce).to_string(),
+        "missing claim Assigned(Nonce)"
+    );
+    assert_eq!(invalid(N::UEID).to_string(), "invalid claim Assigned(UEID)");
+}
+
+#[test]
+fn test_psa_token() {
+    // Produce a (fake-signed) token, as for a test fixture.
+    let claims = psa_claims();
+    let builder = CoseSign1Builder::new().protected(
+        HeaderBuilder::new()
+            .algorithm(iana::Algorithm::ES256)
+            .build(),
+    );
+    let token = cwt::sign_cwt(
+        cwt::CwtContent::Claims(claims.clone().to_claims_set().unwrap()),
+        builder,
+        b"",
+        cwt::CwtTag::Cose,
+        |data| data.to_vec(),
+    )
+    .unwrap();
+    assert_eq!(token[0], 0xd2); // tag 18 (COSE_Sign1)
+
+    let got = cwt::verify_signed_cwt(&token, b"", |sig, data| {
+        if sig == data {
+            Ok(())
+        } else {
+            Err("bad signature")
+        }
+    })
+    .unwrap();
+    let got = PsaClaims::from_claims_set(got).unwrap();
+    assert_eq!(got, claims);
+    assert!(got.check().is_ok());
+}
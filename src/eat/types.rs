@@ -0,0 +1,677 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Types for the values of individual EAT claims.
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    util::{cbor_type_error, ValueTryAs},
+    CoseError, Label,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::convert::TryInto;
+
+/// Check that the length of a byte string is within the range `[min, max]`.
+pub(crate) fn check_size(
+    data: &[u8],
+    min: usize,
+    max: usize,
+    want: &'static str,
+) -> Result<(), CoseError> {
+    if data.len() < min || data.len() > max {
+        return Err(CoseError::UnexpectedItem("wrong-size bstr", want));
+    }
+    Ok(())
+}
+
+/// Extract an unsigned integer.
+pub(crate) fn try_as_uint(value: Value) -> Result<u64, CoseError> {
+    Ok(value.try_as_integer()?.try_into()?)
+}
+
+/// Extract a number, which may be encoded as an integer or a float.
+fn try_as_number(value: Value) -> Result<f64, CoseError> {
+    match value {
+        Value::Integer(i) => Ok(i128::from(i) as f64),
+        Value::Float(f) => Ok(f),
+        v => cbor_type_error(&v, "int/float"),
+    }
+}
+
+/// Extract a non-empty array.
+pub(crate) fn try_as_nonempty_array(value: Value) -> Result<Vec<Value>, CoseError> {
+    let a = value.try_as_array()?;
+    if a.is_empty() {
+        return Err(CoseError::UnexpectedItem("empty array", "non-empty array"));
+    }
+    Ok(a)
+}
+
+/// Extract a non-empty map.
+pub(crate) fn try_as_nonempty_map(value: Value) -> Result<Vec<(Value, Value)>, CoseError> {
+    let m = value.try_as_map()?;
+    if m.is_empty() {
+        return Err(CoseError::UnexpectedItem("empty map", "non-empty map"));
+    }
+    Ok(m)
+}
+
+/// Define an enum for a claim that holds one of a fixed set of unsigned integer values.
+macro_rules! uint_enum {
+    ( $(#[$attr:meta])* $enum_name:ident {$($(#[$fattr:meta])* $name:ident: $val:expr,)* } ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+        pub enum $enum_name {
+            $($(#[$fattr])* $name = $val,)*
+        }
+        impl AsCborValue for $enum_name {
+            fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+                match try_as_uint(value)? {
+                    $(x if x == Self::$name as u64 => Ok(Self::$name),)*
+                    _ => Err(CoseError::OutOfRangeIntegerValue),
+                }
+            }
+            fn to_cbor_value(self) -> Result<Value, CoseError> {
+                Ok(Value::from(self as u64))
+            }
+        }
+    }
+}
+
+/// Types of Universal Entity ID, from RFC 9711 section 4.2.1.1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UeidType {
+    /// Random number of 16, 24 or 32 bytes.
+    Rand = 0x01,
+    /// IEEE EUI-48 or EUI-64, of 6 or 8 bytes.
+    IeeeEui = 0x02,
+    /// IMEI, encoded as a 64-bit binary integer.
+    Imei = 0x03,
+}
+
+impl UeidType {
+    /// Indicate whether an identifier of length `len` is valid for this type.
+    fn valid_len(self, len: usize) -> bool {
+        match self {
+            UeidType::Rand => matches!(len, 16 | 24 | 32),
+            UeidType::IeeeEui => matches!(len, 6 | 8),
+            UeidType::Imei => len == 8,
+        }
+    }
+}
+
+/// Universal Entity ID, as held in `ueid` and `sueids` claims.
+///
+/// The encoded form is a type byte followed by an identifier whose length depends on the type.
+///
+/// ```cddl
+///  ueid-type = bstr .size (7..33)
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ueid {
+    ueid_type: UeidType,
+    data: Vec<u8>,
+}
+
+impl Ueid {
+    /// Create a UEID from its encoded form, checking that the type is recognized and that the
+    /// identifier has a length that is valid for the type.
+    pub fn new(data: Vec<u8>) -> Result<Self, CoseError> {
+        check_size(&data, 7, 33, "bstr of 7-33 bytes")?;
+        let ueid_type = match data[0] {
+            x if x == UeidType::Rand as u8 => UeidType::Rand,
+            x if x == UeidType::IeeeEui as u8 => UeidType::IeeeEui,
+            x if x == UeidType::Imei as u8 => UeidType::Imei,
+            _ => return Err(CoseError::UnexpectedItem("unknown UEID type", "UEID type")),
+        };
+        if !ueid_type.valid_len(data.len() - 1) {
+            return Err(CoseError::UnexpectedItem(
+                "wrong-size bstr",
+                "UEID of valid size for type",
+            ));
+        }
+        Ok(Self { ueid_type, data })
+    }
+
+    /// Create a UEID of the given type from an identifier.
+    pub fn from_id(ueid_type: UeidType, id: &[u8]) -> Result<Self, CoseError> {
+        let mut data = vec![ueid_type as u8];
+        data.extend_from_slice(id);
+        Self::new(data)
+    }
+
+    /// Return the type of the UEID.
+    pub fn ueid_type(&self) -> UeidType {
+        self.ueid_type
+    }
+
+    /// Return the identifier, without the leading type byte.
+    pub fn id(&self) -> &[u8] {
+        &self.data[1..]
+    }
+
+    /// Return the encoded form of the UEID.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl AsCborValue for Ueid {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        Self::new(value.try_as_bytes()?)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::Bytes(self.data))
+    }
+}
+
+/// Hardware OEM identifier, as held in an `oemid` claim.
+///
+/// ```cddl
+///  oemid-pen = int
+///  oemid-ieee = bstr .size 3
+///  oemid-random = bstr .size 16
+///  oemid => oemid-random / oemid-ieee / oemid-pen
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OemId {
+    /// Random identifier of 16 bytes.
+    Random(Vec<u8>),
+    /// IEEE OUI of 3 bytes.
+    Ieee(Vec<u8>),
+    /// IANA Private Enterprise Number.
+    Pen(i64),
+}
+
+impl AsCborValue for OemId {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        match value {
+            Value::Integer(i) => Ok(OemId::Pen(i.try_into()?)),
+            Value::Bytes(b) if b.len() == 16 => Ok(OemId::Random(b)),
+            Value::Bytes(b) if b.len() == 3 => Ok(OemId::Ieee(b)),
+            Value::Bytes(_) => Err(CoseError::UnexpectedItem(
+                "wrong-size bstr",
+                "bstr of 3 or 16 bytes",
+            )),
+            v => cbor_type_error(&v, "int/bstr"),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(match self {
+            OemId::Random(b) => {
+                check_size(&b, 16, 16, "bstr of 16 bytes")?;
+                Value::Bytes(b)
+            }
+            OemId::Ieee(b) => {
+                check_size(&b, 3, 3, "bstr of 3 bytes")?;
+                Value::Bytes(b)
+            }
+            OemId::Pen(pen) => Value::from(pen),
+        })
+    }
+}
+
+/// Version of hardware or software, as held in `hwversion` and `swversion` claims.
+///
+/// ```cddl
+///  version-type = [
+///      version: tstr,
+///      ? scheme: $version-scheme
+///  ]
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Version {
+    /// Version string.
+    pub version: String,
+    /// Version scheme, as per the CoSWID version scheme registry.
+    pub scheme: Option<Label>,
+}
+
+impl AsCborValue for Version {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.is_empty() || a.len() > 2 {
+            return Err(CoseError::UnexpectedItem(
+                "array",
+                "array with 1 or 2 items",
+            ));
+        }
+        let scheme = match a.len() {
+            2 => Some(Label::from_cbor_value(a.remove(1))?),
+            _ => None,
+        };
+        Ok(Self {
+            version: a.remove(0).try_as_string()?,
+            scheme,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut a = vec![Value::Text(self.version)];
+        if let Some(scheme) = self.scheme {
+            a.push(scheme.to_cbor_value()?);
+        }
+        Ok(Value::Array(a))
+    }
+}
+
+uint_enum! {
+    /// Status of debug facilities, as held in a `dbgstat` claim.
+    DebugStatus {
+        /// Debug is enabled.
+        Enabled: 0,
+        /// Debug is disabled.
+        Disabled: 1,
+        /// Debug has been disabled since boot.
+        DisabledSinceBoot: 2,
+        /// Debug is permanently disabled.
+        DisabledPermanently: 3,
+        /// Debug is permanently disabled for the entity and all its submodules.
+        DisabledFullyAndPermanently: 4,
+    }
+}
+
+/// Geographic location, as held in a `location` claim.
+///
+/// ```cddl
+///  location-type = {
+///      latitude => number,
+///      longitude => number,
+///      ? altitude => number,
+///      ? accuracy => number,
+///      ? altitude-accuracy => number,
+///      ? heading => number,
+///      ? speed => number,
+///      ? timestamp => ~time-int,
+///      ? age => uint
+///  }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Location {
+    /// Latitude, in degrees.
+    pub latitude: f64,
+    /// Longitude, in degrees.
+    pub longitude: f64,
+    /// Altitude, in meters.
+    pub altitude: Option<f64>,
+    /// Accuracy of latitude and longitude, in meters.
+    pub accuracy: Option<f64>,
+    /// Accuracy of altitude, in meters.
+    pub altitude_accuracy: Option<f64>,
+    /// Heading, in degrees relative to true north.
+    pub heading: Option<f64>,
+    /// Speed, in meters per second.
+    pub speed: Option<f64>,
+    /// Time the location was obtained, as seconds since UNIX epoch.
+    pub timestamp: Option<i64>,
+    /// Number of seconds between the location being obtained and the token being created.
+    pub age: Option<u64>,
+}
+
+const LATITUDE: i64 = 1;
+const LONGITUDE: i64 = 2;
+const ALTITUDE: i64 = 3;
+const ACCURACY: i64 = 4;
+const ALTITUDE_ACCURACY: i64 = 5;
+const HEADING: i64 = 6;
+const SPEED: i64 = 7;
+const TIMESTAMP: i64 = 8;
+const AGE: i64 = 9;
+
+impl AsCborValue for Location {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut latitude = None;
+        let mut longitude = None;
+        let mut loc = Self::default();
+        for (k, v) in value.try_as_map()? {
+            let k: i64 = k.try_as_integer()?.try_into()?;
+            let field = match k {
+                LATITUDE => &mut latitude,
+                LONGITUDE => &mut longitude,
+                ALTITUDE => &mut loc.altitude,
+                ACCURACY => &mut loc.accuracy,
+                ALTITUDE_ACCURACY => &mut loc.altitude_accuracy,
+                HEADING => &mut loc.heading,
+                SPEED => &mut loc.speed,
+                TIMESTAMP => {
+                    if loc.timestamp.is_some() {
+                        return Err(CoseError::DuplicateMapKey);
+                    }
+                    loc.timestamp = Some(v.try_as_integer()?.try_into()?);
+                    continue;
+                }
+                AGE => {
+                    if loc.age.is_some() {
+                        return Err(CoseError::DuplicateMapKey);
+                    }
+                    loc.age = Some(try_as_uint(v)?);
+                    continue;
+                }
+                _ => return Err(CoseError::UnexpectedItem("int", "location field")),
+            };
+            if field.is_some() {
+                return Err(CoseError::DuplicateMapKey);
+            }
+            *field = Some(try_as_number(v)?);
+        }
+        match (latitude, longitude) {
+            (Some(latitude), Some(longitude)) => {
+                loc.latitude = latitude;
+                loc.longitude = longitude;
+                Ok(loc)
+            }
+            _ => Err(CoseError::UnexpectedItem(
+                "map",
+                "map with latitude and longitude",
+            )),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut map = vec![
+            (Value::from(LATITUDE), Value::Float(self.latitude)),
+            (Value::from(LONGITUDE), Value::Float(self.longitude)),
+        ];
+        for (k, v) in [
+            (ALTITUDE, self.altitude),
+            (ACCURACY, self.accuracy),
+            (ALTITUDE_ACCURACY, self.altitude_accuracy),
+            (HEADING, self.heading),
+            (SPEED, self.speed),
+        ] {
+            if let Some(v) = v {
+                map.push((Value::from(k), Value::Float(v)));
+            }
+        }
+        if let Some(timestamp) = self.timestamp {
+            map.push((Value::from(TIMESTAMP), Value::from(timestamp)));
+        }
+        if let Some(age) = self.age {
+            map.push((Value::from(AGE), Value::from(age)));
+        }
+        Ok(Value::Map(map))
+    }
+}
+
+/// Builder for [`Location`] objects.
+pub struct LocationBuilder(Location);
+
+impl LocationBuilder {
+    /// Constructor for builder, from the mandatory latitude and longitude.
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self(Location {
+            latitude,
+            longitude,
+            ..Default::default()
+        })
+    }
+
+    /// Build the completed object.
+    pub fn build(self) -> Location {
+        self.0
+    }
+
+    builder_set_optional! {altitude: f64}
+    builder_set_optional! {accuracy: f64}
+    builder_set_optional! {altitude_accuracy: f64}
+    builder_set_optional! {heading: f64}
+    builder_set_optional! {speed: f64}
+    builder_set_optional! {timestamp: i64}
+    builder_set_optional! {age: u64}
+}
+
+/// EAT profile, as held in an `eat_profile` claim.
+///
+/// ```cddl
+///  profile-type = ~uri / ~oid
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Profile {
+    /// Profile identified by URI.
+    Uri(String),
+    /// Profile identified by OID, in its DER-encoded form (without tag and length).
+    Oid(Vec<u8>),
+}
+
+impl AsCborValue for Profile {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        match value {
+            Value::Text(t) => Ok(Profile::Uri(t)),
+            Value::Bytes(b) => Ok(Profile::Oid(b)),
+            v => cbor_type_error(&v, "tstr/bstr"),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(match self {
+            Profile::Uri(t) => Value::Text(t),
+            Profile::Oid(b) => Value::Bytes(b),
+        })
+    }
+}
+
+/// Digital Letter of Approval, as held in a `dloas` claim.
+///
+/// ```cddl
+///  dloa-type = [
+///      dloa_registrar: ~uri
+///      dloa_platform_label: text
+///      ? dloa_application_label: text
+///  ]
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Dloa {
+    /// URI of the registrar that issued the DLoA.
+    pub registrar: String,
+    /// Label of the certified platform.
+    pub platform_label: String,
+    /// Label of the certified application, if any.
+    pub application_label: Option<String>,
+}
+
+impl AsCborValue for Dloa {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.len() < 2 || a.len() > 3 {
+            return Err(CoseError::UnexpectedItem(
+                "array",
+                "array with 2 or 3 items",
+            ));
+        }
+        let application_label = match a.len() {
+            3 => Some(a.remove(2).try_as_string()?),
+            _ => None,
+        };
+        Ok(Self {
+            platform_label: a.remove(1).try_as_string()?,
+            registrar: a.remove(0).try_as_string()?,
+            application_label,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut a = vec![
+            Value::Text(self.registrar),
+            Value::Text(self.platform_label),
+        ];
+        if let Some(label) = self.application_label {
+            a.push(Value::Text(label));
+        }
+        Ok(Value::Array(a))
+    }
+}
+
+/// Content of a given CoAP content format, as held in `manifests` and `measurements` claims.
+///
+/// ```cddl
+///  manifest-format = [
+///      content-type:   coap-content-format,
+///      content-format: JC< $manifest-body-json,
+///                          $manifest-body-cbor >
+///  ]
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormattedContent {
+    /// CoAP content format of the content.
+    pub content_type: u16,
+    /// The content, typically a bstr holding the encoded content.
+    pub content: Value,
+}
+
+impl AsCborValue for FormattedContent {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.len() != 2 {
+            return Err(CoseError::UnexpectedItem("array", "array with 2 items"));
+        }
+        let content = a.remove(1);
+        Ok(Self {
+            content_type: a.remove(0).try_as_integer()?.try_into()?,
+            content,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::Array(vec![
+            Value::from(self.content_type),
+            self.content,
+        ]))
+    }
+}
+
+uint_enum! {
+    /// Result of comparing a measurement against reference values.
+    ComparisonResult {
+        /// Comparison succeeded.
+        Success: 1,
+        /// Comparison failed.
+        Fail: 2,
+        /// Comparison was not run.
+        NotRun: 3,
+        /// Measurement was absent.
+        Absent: 4,
+    }
+}
+
+/// Identifier of an individual measurement result.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ResultId {
+    /// Text identifier.
+    Text(String),
+    /// Binary identifier.
+    Bytes(Vec<u8>),
+}
+
+impl AsCborValue for ResultId {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        match value {
+            Value::Text(t) => Ok(ResultId::Text(t)),
+            Value::Bytes(b) => Ok(ResultId::Bytes(b)),
+            v => cbor_type_error(&v, "tstr/bstr"),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(match self {
+            ResultId::Text(t) => Value::Text(t),
+            ResultId::Bytes(b) => Value::Bytes(b),
+        })
+    }
+}
+
+/// Results from a single measurement system, as held in a `measres` claim.
+///
+/// ```cddl
+///  measurement-results-group = [
+///      measurement-system: tstr,
+///      measurement-results: [ + individual-result ]
+///  ]
+///
+///  individual-result = [
+///      result-id:  tstr / binary-data,
+///      result:     result-type,
+///  ]
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MeasurementResults {
+    /// Name of the measurement system that produced the results.
+    pub measurement_system: String,
+    /// Individual results.
+    pub results: Vec<(ResultId, ComparisonResult)>,
+}
+
+impl AsCborValue for MeasurementResults {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.len() != 2 {
+            return Err(CoseError::UnexpectedItem("array", "array with 2 items"));
+        }
+        let results = try_as_nonempty_array(a.remove(1))?
+            .into_iter()
+            .map(|r| {
+                let mut r = r.try_as_array()?;
+                if r.len() != 2 {
+                    return Err(CoseError::UnexpectedItem("array", "array with 2 items"));
+                }
+                let result = ComparisonResult::from_cbor_value(r.remove(1))?;
+                Ok((ResultId::from_cbor_value(r.remove(0))?, result))
+            })
+            .collect::<Result<Vec<_>, CoseError>>()?;
+        Ok(Self {
+            measurement_system: a.remove(0).try_as_string()?,
+            results,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        if self.results.is_empty() {
+            return Err(CoseError::UnexpectedItem("empty array", "non-empty array"));
+        }
+        let results = self
+            .results
+            .into_iter()
+            .map(|(id, result)| {
+                Ok(Value::Array(vec![
+                    id.to_cbor_value()?,
+                    result.to_cbor_value()?,
+                ]))
+            })
+            .collect::<Result<Vec<_>, CoseError>>()?;
+        Ok(Value::Array(vec![
+            Value::Text(self.measurement_system),
+            Value::Array(results),
+        ]))
+    }
+}
+
+uint_enum! {
+    /// Intended use of the EAT, as held in an `intuse` claim.
+    IntendedUse {
+        /// General-purpose attestation.
+        Generic: 1,
+        /// Registration of the entity with a service.
+        Registration: 2,
+        /// Provisioning of keys or secrets to the entity.
+        Provisioning: 3,
+        /// Certificate issuance, as part of a certificate signing request.
+        CertificateIssuance: 4,
+        /// Proof of possession of a key.
+        ProofOfPossession: 5,
+    }
+}
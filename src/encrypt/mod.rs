@@ -154,6 +154,26 @@ impl CoseRecipient {
         let aad = enc_structure_data(context, self.protected.clone(), external_aad);
         cipher(ct, &aad)
     }
+
+    /// Decrypt a content encryption key that was transported by a key transport algorithm (for
+    /// example RSAES-OAEP, RFC 8230 section 3), using `decrypt` on the `ciphertext`.  No AAD is
+    /// involved in key transport.  Returns `missing_ciphertext_error()` if the `ciphertext` is not
+    /// set.
+    pub fn decrypt_key_transport<F, E, G>(
+        &self,
+        missing_ciphertext_error: G,
+        decrypt: F,
+    ) -> Result<Vec<u8>, E>
+    where
+        F: FnOnce(&[u8]) -> Result<Vec<u8>, E>,
+        G: FnOnce() -> E,
+    {
+        let ct = self
+            .ciphertext
+            .as_ref()
+            .ok_or_else(missing_ciphertext_error)?;
+        decrypt(ct)
+    }
 }
 
 /// Builder for [`CoseRecipient`] objects.
@@ -216,6 +236,27 @@ impl CoseRecipientBuilder {
         Ok(self.ciphertext(cipher(plaintext, &aad)?))
     }
 
+    /// Encrypt a content encryption key `cek` for transport to this recipient, using `encrypt` to
+    /// generate the encrypted bytes (for example with RSAES-OAEP, RFC 8230 section 3).  No AAD is
+    /// involved in key transport.
+    #[must_use]
+    pub fn create_key_transport<F>(self, cek: &[u8], encrypt: F) -> Self
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        self.ciphertext(encrypt(cek))
+    }
+
+    /// Encrypt a content encryption key `cek` for transport to this recipient, using `encrypt` to
+    /// generate the encrypted bytes (for example with RSAES-OAEP, RFC 8230 section 3).  No AAD is
+    /// involved in key transport.
+    pub fn try_create_key_transport<F, E>(self, cek: &[u8], encrypt: F) -> Result<Self, E>
+    where
+        F: FnOnce(&[u8]) -> Result<Vec<u8>, E>,
+    {
+        Ok(self.ciphertext(encrypt(cek)?))
+    }
+
     /// Construct the combined AAD data needed for encryption with an AEAD. Any protected header
     /// values should be set before using this method.
     ///
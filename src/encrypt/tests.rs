@@ -808,6 +808,44 @@ fn test_cose_recipient_result() {
     expect_err(status, "failed");
 }
 
+#[test]
+fn test_cose_recipient_key_transport() {
+    let cek = b"content encryption key";
+    let cipher = FakeCipher {};
+
+    let protected = HeaderBuilder::new()
+        .algorithm(iana::Algorithm::RSAES_OAEP_SHA_256)
+        .build();
+    let recipient = CoseRecipientBuilder::new()
+        .protected(protected.clone())
+        .unprotected(HeaderBuilder::new().key_id(b"11".to_vec()).build())
+        .create_key_transport(cek, |cek| cipher.encrypt(cek, b"").unwrap())
+        .build();
+    let recovered_cek = recipient
+        .decrypt_key_transport(no_ct_err, |ct| cipher.decrypt(ct, b""))
+        .unwrap();
+    assert_eq!(&cek[..], recovered_cek);
+
+    let recipient = CoseRecipientBuilder::new()
+        .protected(protected.clone())
+        .try_create_key_transport(cek, |cek| cipher.encrypt(cek, b""))
+        .unwrap()
+        .build();
+    let recovered_cek = recipient
+        .decrypt_key_transport(no_ct_err, |ct| cipher.decrypt(ct, b""))
+        .unwrap();
+    assert_eq!(&cek[..], recovered_cek);
+
+    let status = CoseRecipientBuilder::new()
+        .protected(protected)
+        .try_create_key_transport(cek, |cek| cipher.fail_encrypt(cek, b""));
+    expect_err(status, "failed");
+
+    let result =
+        CoseRecipient::default().decrypt_key_transport(no_ct_err, |ct| cipher.decrypt(ct, b""));
+    assert_eq!(result, Err(no_ct_err()));
+}
+
 #[test]
 fn test_cose_recipient_missing_ciphertext() {
     let external_aad = b"This is the external aad";
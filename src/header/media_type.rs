@@ -0,0 +1,393 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Media type functionality.
+
+use crate::{iana, CoseError, Result};
+use alloc::{string::String, vec::Vec};
+
+/// Structure representing a media type, as per RFC 9110 section 8.3.1.
+///
+/// ```text
+///   media-type = type "/" subtype parameters
+///   parameters = *( OWS ";" OWS [ parameter ] )
+///   parameter  = parameter-name "=" parameter-value
+/// ```
+///
+/// The type, subtype and parameter names are case-insensitive, and are held in lower case.
+/// Parameter values are held with any quoting removed.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MediaType {
+    /// Top-level type, such as "application".
+    pub type_name: String,
+    /// Subtype, such as "cose".
+    pub subtype: String,
+    /// Parameters, as name=value pairs.
+    pub params: Vec<(String, String)>,
+}
+
+/// Indicate whether `c` is a `tchar`, as per RFC 9110 section 5.6.2.
+fn is_tchar(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&c)
+}
+
+/// Simple parser over the bytes of a media type string.
+struct Parser<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.data.get(self.pos).copied()
+    }
+
+    fn skip_ows(&mut self) {
+        while let Some(b' ' | b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn token(&mut self) -> Option<String> {
+        let start = self.pos;
+        while self.peek().is_some_and(is_tchar) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return None;
+        }
+        // All `tchar`s are ASCII.
+        Some(
+            self.data[start..self.pos]
+                .iter()
+                .map(|c| *c as char)
+                .collect(),
+        )
+    }
+
+    fn quoted_string(&mut self) -> Option<String> {
+        self.pos += 1; // opening DQUOTE
+        let mut value = String::new();
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(value);
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    match self.peek()? {
+                        c @ (b'\t' | b' '..=b'~') => value.push(c as char),
+                        _ => return None,
+                    }
+                }
+                c @ (b'\t' | b' '..=b'~') => value.push(c as char),
+                _ => return None,
+            }
+            self.pos += 1;
+        }
+    }
+}
+
+impl MediaType {
+    /// Parse a media type from its text form, such as `application/foo; charset=utf-8`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let syntax_err = || {
+            Err(CoseError::UnexpectedItem(
+                "arbitrary text",
+                "text of form type/subtype",
+            ))
+        };
+        let param_err = || {
+            Err(CoseError::UnexpectedItem(
+                "invalid parameter",
+                "media type parameter of form name=value",
+            ))
+        };
+        let mut p = Parser {
+            data: text.as_bytes(),
+            pos: 0,
+        };
+        let Some(type_name) = p.token() else {
+            return syntax_err();
+        };
+        if p.peek() != Some(b'/') {
+            return syntax_err();
+        }
+        p.pos += 1;
+        let Some(subtype) = p.token() else {
+            return syntax_err();
+        };
+        let mut media_type = Self {
+            type_name: type_name.to_ascii_lowercase(),
+            subtype: subtype.to_ascii_lowercase(),
+            params: Vec::new(),
+        };
+
+        loop {
+            p.skip_ows();
+            match p.peek() {
+                None => break,
+                Some(b';') => p.pos += 1,
+                Some(_) => return param_err(),
+            }
+            p.skip_ows();
+            if matches!(p.peek(), None | Some(b';')) {
+                // Empty parameter.
+                continue;
+            }
+            let Some(name) = p.token() else {
+                return param_err();
+            };
+            if p.peek() != Some(b'=') {
+                return param_err();
+            }
+            p.pos += 1;
+            let value = match p.peek() {
+                Some(b'"') => p.quoted_string(),
+                _ => p.token(),
+            };
+            let Some(value) = value else {
+                return param_err();
+            };
+            let name = name.to_ascii_lowercase();
+            if media_type.params.iter().any(|(n, _)| *n == name) {
+                return Err(CoseError::UnexpectedItem(
+                    "repeated parameter",
+                    "unique media type parameters",
+                ));
+            }
+            media_type.params.push((name, value));
+        }
+        Ok(media_type)
+    }
+
+    /// Return the value of the parameter with the given (case-insensitive) name, if present.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Indicate whether this media type is equivalent to `other`, ignoring the order of
+    /// parameters.
+    pub fn is_equivalent(&self, other: &MediaType) -> bool {
+        self.type_name == other.type_name
+            && self.subtype == other.subtype
+            && self.params.len() == other.params.len()
+            && self
+                .params
+                .iter()
+                .all(|(n, v)| other.param(n) == Some(v.as_str()))
+    }
+}
+
+impl core::fmt::Display for MediaType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}/{}", self.type_name, self.subtype)?;
+        for (name, value) in &self.params {
+            if !value.is_empty() && value.bytes().all(is_tchar) {
+                write!(f, "; {name}={value}")?;
+            } else {
+                write!(f, "; {name}=\"")?;
+                for c in value.chars() {
+                    if c == '"' || c == '\\' {
+                        write!(f, "\\")?;
+                    }
+                    write!(f, "{c}")?;
+                }
+                write!(f, "\"")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Media types (and content codings, where not identity) for CoAP content formats, from the IANA
+/// registry.
+const CONTENT_FORMATS: &[(iana::CoapContentFormat, &str, Option<&str>)] = {
+    use iana::CoapContentFormat as F;
+    &[
+        (F::TextPlainUtf8, "text/plain; charset=utf-8", None),
+        (
+            F::CoseEncrypt0,
+            "application/cose; cose-type=\"cose-encrypt0\"",
+            None,
+        ),
+        (
+            F::CoseMac0,
+            "application/cose; cose-type=\"cose-mac0\"",
+            None,
+        ),
+        (
+            F::CoseSign1,
+            "application/cose; cose-type=\"cose-sign1\"",
+            None,
+        ),
+        (F::AceCbor, "application/ace+cbor", None),
+        (F::ImageGif, "image/gif", None),
+        (F::ImageJpeg, "image/jpeg", None),
+        (F::ImagePng, "image/png", None),
+        (F::LinkFormat, "application/link-format", None),
+        (F::Xml, "application/xml", None),
+        (F::OctetStream, "application/octet-stream", None),
+        (F::Exi, "application/exi", None),
+        (F::Json, "application/json", None),
+        (F::JsonPatchJson, "application/json-patch+json", None),
+        (F::MergePatchJson, "application/merge-patch+json", None),
+        (F::Cbor, "application/cbor", None),
+        (F::Cwt, "application/cwt", None),
+        (F::MultipartCore, "application/multipart-core", None),
+        (F::CborSeq, "application/cbor-seq", None),
+        (F::EdhocCborSeq, "application/edhoc+cbor-seq", None),
+        (F::CidEdhocCborSeq, "application/cid-edhoc+cbor-seq", None),
+        (
+            F::CoseEncrypt,
+            "application/cose; cose-type=\"cose-encrypt\"",
+            None,
+        ),
+        (F::CoseMac, "application/cose; cose-type=\"cose-mac\"", None),
+        (
+            F::CoseSign,
+            "application/cose; cose-type=\"cose-sign\"",
+            None,
+        ),
+        (F::CoseKey, "application/cose-key", None),
+        (F::CoseKeySet, "application/cose-key-set", None),
+        (F::SenmlJson, "application/senml+json", None),
+        (F::SensmlJson, "application/sensml+json", None),
+        (F::SenmlCbor, "application/senml+cbor", None),
+        (F::SensmlCbor, "application/sensml+cbor", None),
+        (F::SenmlExi, "application/senml-exi", None),
+        (F::SensmlExi, "application/sensml-exi", None),
+        (F::YangData, "application/yang-data+cbor; id=sid", None),
+        (F::CoapGroupJson, "application/coap-group+json", None),
+        (
+            F::ConciseProblemDetailsCbor,
+            "application/concise-problem-details+cbor",
+            None,
+        ),
+        (F::SwidCbor, "application/swid+cbor", None),
+        (F::PkixCmp, "application/pkixcmp", None),
+        (F::YangSidJson, "application/yang-sid+json", None),
+        (F::AceGroupcommCbor, "application/ace-groupcomm+cbor", None),
+        (F::AceTrlCbor, "application/ace-trl+cbor", None),
+        (F::EatCwt, "application/eat+cwt", None),
+        (F::EatJwt, "application/eat+jwt", None),
+        (F::EatBunCbor, "application/eat-bun+cbor", None),
+        (F::EatBunJson, "application/eat-bun+json", None),
+        (F::EatUcsCbor, "application/eat-ucs+cbor", None),
+        (F::EatUcsJson, "application/eat-ucs+json", None),
+        (F::DotsCbor, "application/dots+cbor", None),
+        (
+            F::MissingBlocksCborSeq,
+            "application/missing-blocks+cbor-seq",
+            None,
+        ),
+        (
+            F::Pkcs7MimeSmimeTypeServerGeneratedKey,
+            "application/pkcs7-mime; smime-type=server-generated-key",
+            None,
+        ),
+        (
+            F::Pkcs7MimeSmimeTypeCertsOnly,
+            "application/pkcs7-mime; smime-type=certs-only",
+            None,
+        ),
+        (
+            F::Pkcs7MimeSmimeTypeCmcRequest,
+            "application/pkcs7-mime; smime-type=CMC-Request",
+            None,
+        ),
+        (
+            F::Pkcs7MimeSmimeTypeCmcResponse,
+            "application/pkcs7-mime; smime-type=CMC-Response",
+            None,
+        ),
+        (F::Pkcs8, "application/pkcs8", None),
+        (F::Csrattrs, "application/csrattrs", None),
+        (F::Pkcs10, "application/pkcs10", None),
+        (F::PkixCert, "application/pkix-cert", None),
+        (F::AifCbor, "application/aif+cbor", None),
+        (F::AifJson, "application/aif+json", None),
+        (F::SenmlXml, "application/senml+xml", None),
+        (F::SensmlXml, "application/sensml+xml", None),
+        (F::SenmlEtchJson, "application/senml-etch+json", None),
+        (F::SenmlEtchCbor, "application/senml-etch+cbor", None),
+        (F::YangDataCbor, "application/yang-data+cbor", None),
+        (
+            F::YangDataCborName,
+            "application/yang-data+cbor; id=name",
+            None,
+        ),
+        (F::TdJson, "application/td+json", None),
+        (F::TmJson, "application/tm+json", None),
+        (F::UccsCbor, "application/uccs+cbor", None),
+        (F::VndOcfCbor, "application/vnd.ocf+cbor", None),
+        (F::Oscore, "application/oscore", None),
+        (F::Javascript, "application/javascript", None),
+        (
+            F::EatCwtTfm,
+            "application/eat+cwt; eat_profile=\"tag:psacertified.org,2023:psa#tfm\"",
+            None,
+        ),
+        (
+            F::EatCwtLegacy,
+            "application/eat+cwt; eat_profile=\"tag:psacertified.org,2019:psa#legacy\"",
+            None,
+        ),
+        (F::JsonDeflate, "application/json", Some("deflate")),
+        (F::CborDeflate, "application/cbor", Some("deflate")),
+        (F::VndOmaLwm2mTlv, "application/vnd.oma.lwm2m+tlv", None),
+        (F::VndOmaLwm2mJson, "application/vnd.oma.lwm2m+json", None),
+        (F::VndOmaLwm2mCbor, "application/vnd.oma.lwm2m+cbor", None),
+        (F::TextCss, "text/css", None),
+        (F::ImageSvgXml, "image/svg+xml", None),
+    ]
+};
+
+impl iana::CoapContentFormat {
+    /// Return the media type for the content format, as it appears in the IANA registry.
+    pub fn media_type(&self) -> Option<&'static str> {
+        CONTENT_FORMATS
+            .iter()
+            .find(|(f, _, _)| f == self)
+            .map(|(_, media_type, _)| *media_type)
+    }
+
+    /// Return the content coding for the content format, or `None` for the identity coding.
+    pub fn content_coding(&self) -> Option<&'static str> {
+        CONTENT_FORMATS
+            .iter()
+            .find(|(f, _, _)| f == self)
+            .and_then(|(_, _, coding)| *coding)
+    }
+
+    /// Return the content format (with identity content coding) for a media type, if there is
+    /// one.  Parameter order, quoting and the case of names are not significant.
+    pub fn from_media_type(media_type: &MediaType) -> Option<Self> {
+        CONTENT_FORMATS
+            .iter()
+            .filter(|(_, _, coding)| coding.is_none())
+            .find(|(_, text, _)| {
+                MediaType::parse(text)
+                    .map(|m| m.is_equivalent(media_type))
+                    .unwrap_or(false)
+            })
+            .map(|(f, _, _)| *f)
+    }
+}
@@ -19,13 +19,22 @@
 use crate::{
     cbor::value::Value,
     common::AsCborValue,
+    cwt::ClaimsSet,
     iana,
     iana::EnumI64,
     util::{cbor_type_error, to_cbor_array, ValueTryAs},
-    Algorithm, CborSerializable, CoseError, CoseSignature, Label, RegisteredLabelWithPrivate,
-    Result,
+    Algorithm, CborSerializable, CoseError, CoseSign1, CoseSignature, Label,
+    RegisteredLabelWithPrivate, Result, TaggedCborSerializable,
 };
-use alloc::{collections::BTreeSet, string::String, vec, vec::Vec};
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+mod media_type;
+pub use media_type::*;
 
 #[cfg(test)]
 mod tests;
@@ -84,6 +93,43 @@ impl Header {
             && self.counter_signatures.is_empty()
             && self.rest.is_empty()
     }
+
+    /// Return the CWT claims (RFC 9597) held in the header, if present.
+    pub fn cwt_claims(&self) -> Result<Option<ClaimsSet>> {
+        self.rest
+            .iter()
+            .find(|(label, _)| *label == CWT_CLAIMS)
+            .map(|(_, value)| ClaimsSet::from_cbor_value(value.clone()))
+            .transpose()
+    }
+
+    /// Return the type of the complete COSE object (the `typ` header parameter of RFC 9596), if
+    /// present.  A text value is checked to be a valid media type.
+    pub fn typ(&self) -> Result<Option<ContentType>> {
+        let Some((_, value)) = self.rest.iter().find(|(label, _)| *label == TYP) else {
+            return Ok(None);
+        };
+        let typ = ContentType::from_cbor_value(value.clone())?;
+        if let ContentType::Text(text) = &typ {
+            MediaType::parse(text)?;
+        }
+        Ok(Some(typ))
+    }
+
+    /// Return the receipts (the `receipts` header parameter of draft-ietf-cose-merkle-tree-proofs)
+    /// held in the header, in priority order.  Each receipt is a CBOR-encoded `COSE_Sign1`, which
+    /// may be tagged.
+    pub fn receipts(&self) -> Result<Vec<CoseSign1>> {
+        let Some((_, value)) = self.rest.iter().find(|(label, _)| *label == RECEIPTS) else {
+            return Ok(Vec::new());
+        };
+        value
+            .clone()
+            .try_as_array_then_convert(|v| match Value::from_slice(&v.try_as_bytes()?)? {
+                Value::Tag(t, inner) if t == CoseSign1::TAG => CoseSign1::from_cbor_value(*inner),
+                v => CoseSign1::from_cbor_value(v),
+            })
+    }
 }
 
 impl crate::CborSerializable for Header {}
@@ -95,6 +141,9 @@ const KID: Label = Label::Int(iana::HeaderParameter::Kid as i64);
 const IV: Label = Label::Int(iana::HeaderParameter::Iv as i64);
 const PARTIAL_IV: Label = Label::Int(iana::HeaderParameter::PartialIv as i64);
 const COUNTER_SIG: Label = Label::Int(iana::HeaderParameter::CounterSignature as i64);
+const CWT_CLAIMS: Label = Label::Int(iana::HeaderParameter::CwtClaims as i64);
+const TYP: Label = Label::Int(iana::HeaderParameter::ObjectContentType as i64);
+const RECEIPTS: Label = Label::Int(iana::HeaderParameter::Receipts as i64);
 
 impl AsCborValue for Header {
     fn from_cbor_value(value: Value) -> Result<Self> {
@@ -327,6 +376,60 @@ impl HeaderBuilder {
         self
     }
 
+    /// Set the CWT claims (RFC 9597), replacing any CWT claims already set.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the claims cannot be CBOR-encoded.
+    #[must_use]
+    pub fn cwt_claims(mut self, claims: ClaimsSet) -> Self {
+        let value = claims.to_cbor_value().expect("failed to encode claims"); // safe: documented
+        self.0.rest.retain(|(label, _)| *label != CWT_CLAIMS);
+        self.0.rest.push((CWT_CLAIMS, value));
+        self
+    }
+
+    /// Set the type of the complete COSE object (the `typ` header parameter of RFC 9596) to a
+    /// numeric value, replacing any type already set.
+    #[must_use]
+    pub fn typ_format(self, typ: iana::CoapContentFormat) -> Self {
+        self.set_typ(Value::from(typ as i64))
+    }
+
+    /// Set the type of the complete COSE object (the `typ` header parameter of RFC 9596) to a
+    /// media type, replacing any type already set.
+    #[must_use]
+    pub fn typ_media_type(self, typ: MediaType) -> Self {
+        self.set_typ(Value::Text(typ.to_string()))
+    }
+
+    fn set_typ(mut self, value: Value) -> Self {
+        self.0.rest.retain(|(label, _)| *label != TYP);
+        self.0.rest.push((TYP, value));
+        self
+    }
+
+    /// Add a receipt (the `receipts` header parameter of draft-ietf-cose-merkle-tree-proofs),
+    /// after any receipts already added.  The receipt is encoded as a tagged `COSE_Sign1`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the receipt cannot be CBOR-encoded, or if the header already
+    /// holds a `receipts` value that is not an array.
+    #[must_use]
+    pub fn add_receipt(mut self, receipt: CoseSign1) -> Self {
+        let data = receipt.to_tagged_vec().expect("failed to encode receipt"); // safe: documented
+        match self.0.rest.iter_mut().find(|(label, _)| *label == RECEIPTS) {
+            Some((_, Value::Array(receipts))) => receipts.push(Value::Bytes(data)),
+            Some(_) => panic!("receipts header value is not an array"), // safe: documented
+            None => self
+                .0
+                .rest
+                .push((RECEIPTS, Value::Array(vec![Value::Bytes(data)]))),
+        }
+        self
+    }
+
     /// Set a header label:value pair. If duplicate labels are added to a [`Header`],
     /// subsequent attempts to CBOR-encode the header will fail.
     ///
@@ -414,3 +517,55 @@ impl AsCborValue for ProtectedHeader {
         self.header.to_cbor_value()
     }
 }
+
+/// Trait for COSE structures that hold a protected and an unprotected header.
+pub trait CoseHeaders {
+    /// Return the protected header.
+    fn protected_header(&self) -> &Header;
+    /// Return the unprotected header.
+    fn unprotected_header(&self) -> &Header;
+
+    /// Return the CWT claims (RFC 9597) held in the protected header, if present.  Fails if CWT
+    /// claims are present in the unprotected header, as profiles that carry claims in the header
+    /// require them to be integrity protected.
+    fn protected_cwt_claims(&self) -> Result<Option<ClaimsSet>> {
+        if self
+            .unprotected_header()
+            .rest
+            .iter()
+            .any(|(label, _)| *label == CWT_CLAIMS)
+        {
+            return Err(CoseError::UnexpectedItem(
+                "unprotected CWT claims",
+                "protected CWT claims",
+            ));
+        }
+        self.protected_header().cwt_claims()
+    }
+}
+
+macro_rules! impl_cose_headers {
+    { $($type:ty),* } => {
+        $(
+            impl CoseHeaders for $type {
+                fn protected_header(&self) -> &Header {
+                    &self.protected.header
+                }
+                fn unprotected_header(&self) -> &Header {
+                    &self.unprotected
+                }
+            }
+        )*
+    };
+}
+
+impl_cose_headers! {
+    crate::CoseEncrypt,
+    crate::CoseEncrypt0,
+    crate::CoseMac,
+    crate::CoseMac0,
+    crate::CoseRecipient,
+    crate::CoseSign,
+    crate::CoseSign1,
+    crate::CoseSignature
+}
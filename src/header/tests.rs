@@ -16,7 +16,7 @@
 
 use super::*;
 use crate::{cbor::value::Value, iana, util::expect_err, CborSerializable, Label};
-use alloc::{borrow::ToOwned, vec};
+use alloc::{borrow::ToOwned, string::ToString, vec, vec::Vec};
 
 #[test]
 fn test_header_encode() {
@@ -526,3 +526,296 @@ fn test_header_builder_core_param_panic() {
     // Attempting to set a core header parameter (in range [1,7]) via `.param()` panics.
     let _hdr = HeaderBuilder::new().value(1, Value::Null).build();
 }
+
+#[test]
+fn test_header_cwt_claims() {
+    let claims = crate::cwt::ClaimsSetBuilder::new()
+        .issuer("iss".to_owned())
+        .subject("sub".to_owned())
+        .build();
+    let header = HeaderBuilder::new()
+        .algorithm(iana::Algorithm::ES256)
+        .cwt_claims(crate::cwt::ClaimsSetBuilder::new().build())
+        .cwt_claims(claims.clone())
+        .build();
+    assert_eq!(header.rest.len(), 1);
+    let data = header.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "a2", // 2-map
+            "01", "26", // 1 (alg) => ES256
+            "0f", "a2", // 15 (CWT claims) => 2-map
+            "01", "63", "697373", // 1 (iss) => "iss"
+            "02", "63", "737562", // 2 (sub) => "sub"
+        )
+    );
+    let got = Header::from_slice(&data).unwrap();
+    assert_eq!(got.cwt_claims().unwrap(), Some(claims));
+
+    assert_eq!(Header::default().cwt_claims().unwrap(), None);
+    let header = HeaderBuilder::new().value(15, Value::from(1)).build();
+    expect_err(header.cwt_claims(), "expected map");
+}
+
+#[test]
+fn test_protected_cwt_claims() {
+    let claims = crate::cwt::ClaimsSetBuilder::new()
+        .issuer("iss".to_owned())
+        .build();
+    let sign1 = crate::CoseSign1Builder::new()
+        .protected(HeaderBuilder::new().cwt_claims(claims.clone()).build())
+        .build();
+    assert_eq!(sign1.protected_cwt_claims().unwrap(), Some(claims.clone()));
+
+    let sign1 = crate::CoseSign1Builder::new().build();
+    assert_eq!(sign1.protected_cwt_claims().unwrap(), None);
+
+    let sign1 = crate::CoseSign1Builder::new()
+        .unprotected(HeaderBuilder::new().cwt_claims(claims.clone()).build())
+        .build();
+    expect_err(sign1.protected_cwt_claims(), "unprotected CWT claims");
+
+    let sign1 = crate::CoseSign1Builder::new()
+        .protected(HeaderBuilder::new().cwt_claims(claims.clone()).build())
+        .unprotected(HeaderBuilder::new().cwt_claims(claims).build())
+        .build();
+    expect_err(sign1.protected_cwt_claims(), "unprotected CWT claims");
+}
+
+#[test]
+fn test_media_type_parse() {
+    let tests = [
+        ("a/b", ("a", "b", vec![]), "a/b"),
+        ("Text/Plain", ("text", "plain", vec![]), "text/plain"),
+        (
+            "application/foo; charset=utf-8",
+            ("application", "foo", vec![("charset", "utf-8")]),
+            "application/foo; charset=utf-8",
+        ),
+        (
+            "application/cose;cose-type=\"cose-sign1\"",
+            ("application", "cose", vec![("cose-type", "cose-sign1")]),
+            "application/cose; cose-type=cose-sign1",
+        ),
+        (
+            "a/b ;  X=1\t; y=\"two words\" ;; ",
+            ("a", "b", vec![("x", "1"), ("y", "two words")]),
+            "a/b; x=1; y=\"two words\"",
+        ),
+        (
+            "a/b; x=\"q\\\"uo\\\\te\"; y=\"\"",
+            ("a", "b", vec![("x", "q\"uo\\te"), ("y", "")]),
+            "a/b; x=\"q\\\"uo\\\\te\"; y=\"\"",
+        ),
+    ];
+    for (text, (type_name, subtype, params), display) in tests {
+        let got = MediaType::parse(text).unwrap();
+        assert_eq!(got.type_name, type_name, "for {:?}", text);
+        assert_eq!(got.subtype, subtype, "for {:?}", text);
+        let params: Vec<_> = params
+            .into_iter()
+            .map(|(n, v)| (n.to_owned(), v.to_owned()))
+            .collect();
+        assert_eq!(got.params, params, "for {:?}", text);
+        assert_eq!(got.to_string(), display, "for {:?}", text);
+        assert_eq!(MediaType::parse(display).unwrap(), got, "for {:?}", text);
+    }
+
+    let got = MediaType::parse("a/b; Charset=UTF-8").unwrap();
+    assert_eq!(got.param("charset"), Some("UTF-8"));
+    assert_eq!(got.param("CHARSET"), Some("UTF-8"));
+    assert_eq!(got.param("other"), None);
+
+    let tests = [
+        ("", "text of form type/subtype"),
+        ("a", "text of form type/subtype"),
+        ("a/", "text of form type/subtype"),
+        ("/b", "text of form type/subtype"),
+        (" a/b", "text of form type/subtype"),
+        ("a/b/c", "name=value"),
+        ("a/b c", "name=value"),
+        ("a/b; x", "name=value"),
+        ("a/b; x=", "name=value"),
+        ("a/b; =1", "name=value"),
+        ("a/b; x y=1", "name=value"),
+        ("a/b; x=\"unterminated", "name=value"),
+        ("a/b; x=\"a\"b", "name=value"),
+        ("a/b; x=\"caf\u{e9}\"", "name=value"),
+        ("a/b; x=1; X=2", "unique media type parameters"),
+    ];
+    for (text, err_msg) in tests {
+        expect_err(MediaType::parse(text), err_msg);
+    }
+}
+
+#[test]
+fn test_content_format_media_type() {
+    use iana::CoapContentFormat as F;
+    // Every content format has a media type, which maps back to the same content format unless a
+    // content coding is involved.
+    for i in 0..=65535 {
+        let Some(format) = <F as iana::EnumI64>::from_i64(i) else {
+            continue;
+        };
+        let text = format.media_type().unwrap();
+        let media_type = MediaType::parse(text).unwrap();
+        match format.content_coding() {
+            None => assert_eq!(
+                F::from_media_type(&media_type),
+                Some(format),
+                "for {:?}",
+                format
+            ),
+            Some(_) => assert_ne!(
+                F::from_media_type(&media_type),
+                Some(format),
+                "for {:?}",
+                format
+            ),
+        }
+    }
+
+    assert_eq!(F::Json.content_coding(), None);
+    assert_eq!(F::JsonDeflate.content_coding(), Some("deflate"));
+    assert_eq!(F::JsonDeflate.media_type(), Some("application/json"));
+
+    let tests = [
+        ("application/cose; cose-type=cose-sign1", Some(F::CoseSign1)),
+        (
+            "Application/COSE;  COSE-Type=\"cose-mac\"",
+            Some(F::CoseMac),
+        ),
+        ("text/plain;charset=utf-8", Some(F::TextPlainUtf8)),
+        ("application/yang-data+cbor", Some(F::YangDataCbor)),
+        ("application/yang-data+cbor; id=sid", Some(F::YangData)),
+        ("application/cbor", Some(F::Cbor)),
+        ("application/cose", None),
+        ("application/cose; cose-type=cose-sign1; x=1", None),
+        ("text/plain", None),
+        ("application/unknown", None),
+    ];
+    for (text, want) in tests {
+        let media_type = MediaType::parse(text).unwrap();
+        assert_eq!(F::from_media_type(&media_type), want, "for {:?}", text);
+    }
+}
+
+#[test]
+fn test_header_typ() {
+    let header = HeaderBuilder::new()
+        .typ_media_type(MediaType::parse("application/example+cbor").unwrap())
+        .typ_format(iana::CoapContentFormat::Cwt)
+        .build();
+    assert_eq!(header.rest.len(), 1);
+    let data = header.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "a1", // 1-map
+            "10", "183d", // 16 (typ) => 61 (application/cwt)
+        )
+    );
+    let got = Header::from_slice(&data).unwrap();
+    assert_eq!(
+        got.typ().unwrap(),
+        Some(ContentType::Assigned(iana::CoapContentFormat::Cwt))
+    );
+
+    let header = HeaderBuilder::new()
+        .typ_media_type(MediaType::parse("Application/Foo;Charset=\"utf-8\"").unwrap())
+        .build();
+    let data = header.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "a1", // 1-map
+            "10",
+            "781e", // 16 (typ) => 30-tstr
+            "6170706c69636174696f6e2f666f6f3b20636861727365743d7574662d38",
+        )
+    );
+    let got = Header::from_slice(&data).unwrap();
+    assert_eq!(
+        got.typ().unwrap(),
+        Some(ContentType::Text(
+            "application/foo; charset=utf-8".to_owned()
+        ))
+    );
+
+    assert_eq!(Header::default().typ().unwrap(), None);
+    let tests = [
+        (Value::Bytes(vec![1]), "expected int/tstr"),
+        (Value::Text("a".to_owned()), "text of form type/subtype"),
+        (Value::Text("a/b; x".to_owned()), "name=value"),
+    ];
+    for (value, err_msg) in tests {
+        let header = HeaderBuilder::new().value(16, value).build();
+        expect_err(header.typ(), err_msg);
+    }
+}
+
+#[test]
+fn test_header_receipts() {
+    let receipt1 = crate::CoseSign1Builder::new().build();
+    let receipt2 = crate::CoseSign1Builder::new().signature(vec![1]).build();
+    let header = HeaderBuilder::new()
+        .add_receipt(receipt1.clone())
+        .add_receipt(receipt2.clone())
+        .build();
+    assert_eq!(header.rest.len(), 1);
+    let data = header.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "a1", // 1-map
+            "19018a",
+            "82", // 394 (receipts) => 2-arr
+            "46",
+            "d28440a0f640", // 6-bstr => 18(COSE_Sign1)
+            "47",
+            "d28440a0f64101", // 7-bstr => 18(COSE_Sign1)
+        )
+    );
+    let got = Header::from_slice(&data).unwrap();
+    let receipts = got.receipts().unwrap();
+    assert_eq!(receipts.len(), 2);
+    assert_eq!(receipts[0].signature, receipt1.signature);
+    assert_eq!(receipts[1].signature, receipt2.signature);
+
+    // Untagged receipts are also accepted.
+    let header = HeaderBuilder::new()
+        .value(
+            394,
+            Value::Array(vec![Value::Bytes(hex::decode("8440a0f640").unwrap())]),
+        )
+        .build();
+    let receipts = header.receipts().unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(
+        receipts[0].clone().to_vec().unwrap(),
+        receipt1.to_vec().unwrap()
+    );
+
+    assert_eq!(Header::default().receipts().unwrap(), vec![]);
+    let tests = [
+        (Value::Bytes(vec![1]), "expected array"),
+        (Value::Array(vec![Value::from(1)]), "expected bstr"),
+        (
+            Value::Array(vec![Value::Bytes(hex::decode("d18440a0f640").unwrap())]),
+            "expected array",
+        ),
+    ];
+    for (value, err_msg) in tests {
+        let header = HeaderBuilder::new().value(394, value).build();
+        expect_err(header.receipts(), err_msg);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_header_receipts_not_array() {
+    let _header = HeaderBuilder::new()
+        .value(394, Value::from(1))
+        .add_receipt(crate::CoseSign1Builder::new().build());
+}
@@ -125,7 +125,7 @@ iana_registry! {
         ///
         /// Associated value of type map
         CwtClaims: 15,
-        /// Content type of complete COSE object.
+        /// Content type of complete COSE object (`typ`, RFC 9596).
         ///
         /// Associated value of type uint/tstr
         ObjectContentType: 16,
@@ -153,6 +153,19 @@ iana_registry! {
         ///
         /// Associated value of type array
         CuphOwnerPubKey: 257,
+        /// Priority ordered sequence of CBOR encoded Receipts
+        ///
+        /// Associated value of type array
+        Receipts: 394,
+        /// Algorithm identifier for verifiable data structures, used to produce verifiable data
+        /// structure proofs
+        ///
+        /// Associated value of type int
+        Vds: 395,
+        /// Location for verifiable data structure proofs in COSE Header Parameters
+        ///
+        /// Associated value of type map
+        Vdp: 396,
     }
 }
 
@@ -165,6 +178,35 @@ impl WithPrivateRange for HeaderParameter {
     }
 }
 
+iana_registry! {
+    /// IANA-registered COSE verifiable data structure algorithms.
+    ///
+    /// From IANA registry <https://www.iana.org/assignments/cose/cose.xhtml#verifiable-data-structure-algorithms>
+    /// as of 2025-11-06.
+    VerifiableDataStructure {
+        /// SHA256 Binary Merkle Tree (RFC 9162)
+        RFC9162_SHA256: 1,
+    }
+}
+
+iana_registry! {
+    /// IANA-registered COSE verifiable data structure proof types for
+    /// [`VerifiableDataStructure::RFC9162_SHA256`].
+    ///
+    /// From IANA registry <https://www.iana.org/assignments/cose/cose.xhtml#verifiable-data-structure-proofs>
+    /// as of 2025-11-06.
+    Rfc9162Sha256ProofType {
+        /// Proof of inclusion
+        ///
+        /// Associated value of type array of bstr
+        InclusionProof: -1,
+        /// Proof of append only property
+        ///
+        /// Associated value of type array of bstr
+        ConsistencyProof: -2,
+    }
+}
+
 iana_registry! {
     /// IANA-registered COSE header algorithm parameters.
     ///
@@ -718,6 +760,8 @@ iana_registry! {
     /// From IANA registry <https://www.iana.org/assignments/cbor-tags/cbor-tags.xhtml>
     /// as of 2025-09-13.
     CborTag {
+        /// Standard date/time string
+        DateTimeString: 0,
         /// COSE Single Recipient Encrypted Data Object
         CoseEncrypt0: 16,
         /// COSE Mac w/o Recipients Object
@@ -726,6 +770,8 @@ iana_registry! {
         CoseSign1: 18,
         /// COSE standalone V2 countersignature
         CoseCountersignature: 19,
+        /// Encoded CBOR data item
+        EncodedCborData: 24,
         /// CBOR Web Token (CWT)
         Cwt: 61,
         /// COSE Encrypted Data Object
@@ -734,6 +780,8 @@ iana_registry! {
         CoseMac: 97,
         /// COSE Signed Data Object
         CoseSign: 98,
+        /// Detached EAT Bundle (DEB)
+        DetachedEatBundle: 602,
     }
 }
 
@@ -807,7 +855,7 @@ iana_registry! {
         SenmlExi: 114,
         /// application/sensml-exi
         SensmlExi: 115,
-        /// application/yang-data+cbor
+        /// application/yang-data+cbor; id=sid
         YangData: 140,
         /// application/coap-group+json
         CoapGroupJson: 256,
@@ -883,9 +931,9 @@ iana_registry! {
         Oscore: 10001,
         /// application/javascript
         Javascript: 10002,
-        /// application/eat+cwt
+        /// application/eat+cwt; eat_profile="tag:psacertified.org,2023:psa#tfm"
         EatCwtTfm: 10003,
-        /// application/eat+cwt
+        /// application/eat+cwt; eat_profile="tag:psacertified.org,2019:psa#legacy"
         EatCwtLegacy: 10004,
         /// application/json deflate
         JsonDeflate: 11050,
@@ -1055,6 +1103,23 @@ iana_registry! {
 /// Integer values for CWT claims below this value are reserved for private use.
 pub const CWT_CLAIM_PRIVATE_USE_MAX: i64 = -65536;
 
+iana_registry! {
+    /// CWT Confirmation Methods
+    /// From IANA registry <https://www.iana.org/assignments/cwt/cwt.xhtml#confirmation-methods>
+    /// as of 2025-09-13.
+    CwtConfirmationMethod {
+        /// COSE_Key representing public key ("COSE_Key": COSE_Key)
+        CoseKey: 1,
+        /// Encrypted COSE_Key ("Encrypted_COSE_Key": COSE_Encrypt or COSE_Encrypt0)
+        EncryptedCoseKey: 2,
+        /// Key Identifier ("kid": bstr)
+        Kid: 3,
+        /// OSCORE_Input_Material carrying the parameters for using OSCORE per-message security
+        /// with implicit key confirmation ("osc": map)
+        Osc: 4,
+    }
+}
+
 impl WithPrivateRange for CwtClaimName {
     fn is_private(i: i64) -> bool {
         i < CWT_CLAIM_PRIVATE_USE_MAX
@@ -0,0 +1,638 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Conversion between COSE_Key and JSON Web Key (JWK) formats, as per RFC 7517.
+//!
+//! Conversion is lossless in both directions: if anything in the input has no equivalent in the
+//! output format, the conversion fails with an error that lists all such items.  In particular:
+//!
+//! - Key types `OKP`, `EC2`, `RSA`, `Symmetric` and `AKP` are supported, as JWK `kty` values
+//!   `OKP`, `EC`, `RSA`, `oct` and `AKP`.
+//! - Binary parameters are encoded as unpadded base64url.
+//! - The `kid` is a byte string in COSE and a text string in JWK; only key identifiers that are
+//!   valid UTF-8 can be converted.
+//! - Registered algorithms, curves and key operations are mapped to their JOSE names.  Text values
+//!   are carried across unchanged, unless they would collide with a registered name.
+//! - The COSE `base_iv` parameter, the `MacCreate`/`MacVerify` key operations, and JWK members such
+//!   as `use` or `x5c` have no equivalent in the other format.
+
+use crate::{
+    cbor::value::Value, iana, iana::EnumI64, util::Base64, Algorithm, CoseKey, CoseKeySet, KeyType,
+    Label, RegisteredLabel,
+};
+use alloc::{borrow::ToOwned, collections::BTreeSet, string::String, vec, vec::Vec};
+use core::convert::TryFrom;
+
+mod json;
+use json::Json;
+
+#[cfg(test)]
+mod tests;
+
+/// Error type for failures in JWK conversion.
+#[derive(Clone, PartialEq, Eq)]
+pub enum JwkError {
+    /// The input is not well-formed JSON; holds the byte offset of the problem.
+    InvalidJson(usize),
+    /// The JSON input is not an object.
+    NotAnObject,
+    /// A required JWK member is missing.
+    MissingMember(&'static str),
+    /// A JWK member appears more than once.
+    DuplicateMember(String),
+    /// A JWK member has an invalid type or value.
+    InvalidMember(String),
+    /// A [`CoseKey`] parameter has an invalid type or value, or is repeated.
+    InvalidParameter(Label),
+    /// The [`CoseKey`] has parameters (or values of parameters) that cannot be represented in a
+    /// JWK.
+    UnrepresentableParameters(Vec<Label>),
+    /// The JWK has members (or values of members) that cannot be represented in a [`CoseKey`].
+    UnrepresentableMembers(Vec<String>),
+}
+
+impl core::error::Error for JwkError {}
+
+impl core::fmt::Debug for JwkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl core::fmt::Display for JwkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl JwkError {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JwkError::InvalidJson(offset) => write!(f, "invalid JSON at offset {offset}"),
+            JwkError::NotAnObject => write!(f, "JSON value is not an object"),
+            JwkError::MissingMember(name) => write!(f, "missing JWK member '{name}'"),
+            JwkError::DuplicateMember(name) => write!(f, "duplicate JWK member '{name}'"),
+            JwkError::InvalidMember(name) => write!(f, "invalid value for JWK member '{name}'"),
+            JwkError::InvalidParameter(label) => {
+                write!(f, "invalid value for COSE_Key parameter {label:?}")
+            }
+            JwkError::UnrepresentableParameters(labels) => {
+                write!(
+                    f,
+                    "COSE_Key parameters not representable in JWK: {labels:?}"
+                )
+            }
+            JwkError::UnrepresentableMembers(names) => {
+                write!(f, "JWK members not representable in COSE_Key: {names:?}")
+            }
+        }
+    }
+}
+
+/// JOSE names for key types.
+const KEY_TYPES: &[(iana::KeyType, &str)] = &[
+    (iana::KeyType::OKP, "OKP"),
+    (iana::KeyType::EC2, "EC"),
+    (iana::KeyType::RSA, "RSA"),
+    (iana::KeyType::Symmetric, "oct"),
+    (iana::KeyType::AKP, "AKP"),
+];
+
+/// JOSE names for elliptic curves, from the IANA "JSON Web Key Elliptic Curve" registry.
+const CURVES: &[(iana::EllipticCurve, &str)] = &[
+    (iana::EllipticCurve::P_256, "P-256"),
+    (iana::EllipticCurve::P_384, "P-384"),
+    (iana::EllipticCurve::P_521, "P-521"),
+    (iana::EllipticCurve::X25519, "X25519"),
+    (iana::EllipticCurve::X448, "X448"),
+    (iana::EllipticCurve::Ed25519, "Ed25519"),
+    (iana::EllipticCurve::Ed448, "Ed448"),
+    (iana::EllipticCurve::Secp256k1, "secp256k1"),
+];
+
+/// JOSE names for key operations, from RFC 7517 section 4.3.  JOSE has no separate operations
+/// for MACs, so [`iana::KeyOperation::MacCreate`] and [`iana::KeyOperation::MacVerify`] have no
+/// equivalent.
+const KEY_OPS: &[(iana::KeyOperation, &str)] = &[
+    (iana::KeyOperation::Sign, "sign"),
+    (iana::KeyOperation::Verify, "verify"),
+    (iana::KeyOperation::Encrypt, "encrypt"),
+    (iana::KeyOperation::Decrypt, "decrypt"),
+    (iana::KeyOperation::WrapKey, "wrapKey"),
+    (iana::KeyOperation::UnwrapKey, "unwrapKey"),
+    (iana::KeyOperation::DeriveKey, "deriveKey"),
+    (iana::KeyOperation::DeriveBits, "deriveBits"),
+];
+
+/// JOSE names for algorithms that have an identical definition in both registries.
+const ALGORITHMS: &[(iana::Algorithm, &str)] = &[
+    (iana::Algorithm::ES256, "ES256"),
+    (iana::Algorithm::ES384, "ES384"),
+    (iana::Algorithm::ES512, "ES512"),
+    (iana::Algorithm::ES256K, "ES256K"),
+    (iana::Algorithm::ESP256, "ESP256"),
+    (iana::Algorithm::ESP384, "ESP384"),
+    (iana::Algorithm::ESP512, "ESP512"),
+    (iana::Algorithm::EdDSA, "EdDSA"),
+    (iana::Algorithm::Ed25519, "Ed25519"),
+    (iana::Algorithm::Ed448, "Ed448"),
+    (iana::Algorithm::PS256, "PS256"),
+    (iana::Algorithm::PS384, "PS384"),
+    (iana::Algorithm::PS512, "PS512"),
+    (iana::Algorithm::RS256, "RS256"),
+    (iana::Algorithm::RS384, "RS384"),
+    (iana::Algorithm::RS512, "RS512"),
+    (iana::Algorithm::RSAES_OAEP_RFC_8017_default, "RSA-OAEP"),
+    (iana::Algorithm::RSAES_OAEP_SHA_256, "RSA-OAEP-256"),
+    (iana::Algorithm::RSAES_OAEP_SHA_512, "RSA-OAEP-512"),
+    (iana::Algorithm::HMAC_256_256, "HS256"),
+    (iana::Algorithm::HMAC_384_384, "HS384"),
+    (iana::Algorithm::HMAC_512_512, "HS512"),
+    (iana::Algorithm::A128KW, "A128KW"),
+    (iana::Algorithm::A192KW, "A192KW"),
+    (iana::Algorithm::A256KW, "A256KW"),
+    (iana::Algorithm::Direct, "dir"),
+    (iana::Algorithm::A128GCM, "A128GCM"),
+    (iana::Algorithm::A192GCM, "A192GCM"),
+    (iana::Algorithm::A256GCM, "A256GCM"),
+    (iana::Algorithm::ML_DSA_44, "ML-DSA-44"),
+    (iana::Algorithm::ML_DSA_65, "ML-DSA-65"),
+    (iana::Algorithm::ML_DSA_87, "ML-DSA-87"),
+];
+
+fn to_name<T: PartialEq + Copy>(table: &[(T, &'static str)], value: T) -> Option<&'static str> {
+    table.iter().find(|(v, _)| *v == value).map(|(_, n)| *n)
+}
+
+fn from_name<T: Copy>(table: &[(T, &'static str)], name: &str) -> Option<T> {
+    table.iter().find(|(_, n)| *n == name).map(|(v, _)| *v)
+}
+
+/// Return the JOSE name for an elliptic curve, if there is one.
+pub fn jose_curve_name(crv: iana::EllipticCurve) -> Option<&'static str> {
+    to_name(CURVES, crv)
+}
+
+/// Return the elliptic curve with the given JOSE name, if there is one.
+pub fn curve_from_jose_name(name: &str) -> Option<iana::EllipticCurve> {
+    from_name(CURVES, name)
+}
+
+/// Return the JOSE name for a key operation, if there is one.
+pub fn jose_key_op_name(op: iana::KeyOperation) -> Option<&'static str> {
+    to_name(KEY_OPS, op)
+}
+
+/// Return the key operation with the given JOSE name, if there is one.
+pub fn key_op_from_jose_name(name: &str) -> Option<iana::KeyOperation> {
+    from_name(KEY_OPS, name)
+}
+
+/// Return the JOSE name for an algorithm, if there is one.
+pub fn jose_algorithm_name(alg: iana::Algorithm) -> Option<&'static str> {
+    to_name(ALGORITHMS, alg)
+}
+
+/// Return the algorithm with the given JOSE name, if there is one.
+pub fn algorithm_from_jose_name(name: &str) -> Option<iana::Algorithm> {
+    from_name(ALGORITHMS, name)
+}
+
+/// How a key type parameter is represented.
+#[derive(Clone, Copy)]
+enum ParamKind {
+    /// `bstr` in COSE, base64url string in JWK.
+    Bytes,
+    /// Curve identifier.
+    Curve,
+    /// RSA `other` array of prime information.
+    OtherPrimes,
+}
+
+/// Key type parameters as (COSE label, JWK member name, kind), in output order.
+type ParamTable = &'static [(i64, &'static str, ParamKind)];
+
+const OKP_PARAMS: ParamTable = &[
+    (iana::OkpKeyParameter::Crv as i64, "crv", ParamKind::Curve),
+    (iana::OkpKeyParameter::X as i64, "x", ParamKind::Bytes),
+    (iana::OkpKeyParameter::D as i64, "d", ParamKind::Bytes),
+];
+
+const EC2_PARAMS: ParamTable = &[
+    (iana::Ec2KeyParameter::Crv as i64, "crv", ParamKind::Curve),
+    (iana::Ec2KeyParameter::X as i64, "x", ParamKind::Bytes),
+    (iana::Ec2KeyParameter::Y as i64, "y", ParamKind::Bytes),
+    (iana::Ec2KeyParameter::D as i64, "d", ParamKind::Bytes),
+];
+
+const RSA_PARAMS: ParamTable = &[
+    (iana::RsaKeyParameter::N as i64, "n", ParamKind::Bytes),
+    (iana::RsaKeyParameter::E as i64, "e", ParamKind::Bytes),
+    (iana::RsaKeyParameter::D as i64, "d", ParamKind::Bytes),
+    (iana::RsaKeyParameter::P as i64, "p", ParamKind::Bytes),
+    (iana::RsaKeyParameter::Q as i64, "q", ParamKind::Bytes),
+    (iana::RsaKeyParameter::DP as i64, "dp", ParamKind::Bytes),
+    (iana::RsaKeyParameter::DQ as i64, "dq", ParamKind::Bytes),
+    (iana::RsaKeyParameter::QInv as i64, "qi", ParamKind::Bytes),
+    (
+        iana::RsaKeyParameter::Other as i64,
+        "oth",
+        ParamKind::OtherPrimes,
+    ),
+];
+
+/// Members of each entry in the RSA `oth` array, as (COSE label, JWK member name).
+const RSA_OTHER_PRIME_PARAMS: [(i64, &str); 3] = [
+    (iana::RsaKeyParameter::RI as i64, "r"),
+    (iana::RsaKeyParameter::DI as i64, "d"),
+    (iana::RsaKeyParameter::TI as i64, "t"),
+];
+
+const SYMMETRIC_PARAMS: ParamTable =
+    &[(iana::SymmetricKeyParameter::K as i64, "k", ParamKind::Bytes)];
+
+const AKP_PARAMS: ParamTable = &[
+    (iana::AkpKeyParameter::Pub as i64, "pub", ParamKind::Bytes),
+    (iana::AkpKeyParameter::Priv as i64, "priv", ParamKind::Bytes),
+];
+
+fn param_table(kty: iana::KeyType) -> ParamTable {
+    match kty {
+        iana::KeyType::OKP => OKP_PARAMS,
+        iana::KeyType::EC2 => EC2_PARAMS,
+        iana::KeyType::RSA => RSA_PARAMS,
+        iana::KeyType::Symmetric => SYMMETRIC_PARAMS,
+        iana::KeyType::AKP => AKP_PARAMS,
+        _ => &[],
+    }
+}
+
+/// Convert a label that is either registered (and has a JOSE name) or text to its JOSE string.
+/// Text values that collide with a registered JOSE name cannot be represented.
+fn label_to_jose<T: EnumI64 + PartialEq + Copy>(
+    table: &[(T, &'static str)],
+    label: &RegisteredLabel<T>,
+) -> Option<String> {
+    match label {
+        RegisteredLabel::Assigned(v) => to_name(table, *v).map(str::to_owned),
+        RegisteredLabel::Text(t) if from_name(table, t).is_none() => Some(t.clone()),
+        RegisteredLabel::Text(_) => None,
+    }
+}
+
+fn label_from_jose<T: EnumI64 + Copy>(
+    table: &[(T, &'static str)],
+    name: &str,
+) -> RegisteredLabel<T> {
+    match from_name(table, name) {
+        Some(v) => RegisteredLabel::Assigned(v),
+        None => RegisteredLabel::Text(name.to_owned()),
+    }
+}
+
+fn b64_member(value: &Value, label: &Label) -> Result<Json, JwkError> {
+    match value {
+        Value::Bytes(data) => Ok(Json::String(Base64::UrlSafeNoPad.encode(data))),
+        _ => Err(JwkError::InvalidParameter(label.clone())),
+    }
+}
+
+fn b64_param(value: &Json, name: &str) -> Result<Value, JwkError> {
+    match value {
+        Json::String(s) => Base64::UrlSafeNoPad
+            .decode(s)
+            .map(Value::Bytes)
+            .ok_or_else(|| JwkError::InvalidMember(name.to_owned())),
+        _ => Err(JwkError::InvalidMember(name.to_owned())),
+    }
+}
+
+/// Convert a JSON object to its list of members, rejecting duplicates.
+fn into_members(value: Json) -> Result<Vec<(String, Json)>, JwkError> {
+    let members = match value {
+        Json::Object(members) => members,
+        _ => return Err(JwkError::NotAnObject),
+    };
+    let mut seen = BTreeSet::new();
+    for (name, _) in &members {
+        if !seen.insert(name.as_str()) {
+            return Err(JwkError::DuplicateMember(name.clone()));
+        }
+    }
+    Ok(members)
+}
+
+impl CoseKey {
+    /// Convert the key to JWK JSON, as per RFC 7517.
+    ///
+    /// Fails with [`JwkError::UnrepresentableParameters`] (listing all such parameters) if the key
+    /// holds information that has no JWK equivalent.
+    pub fn to_jwk(&self) -> Result<String, JwkError> {
+        let mut out = String::new();
+        let mut json = self.to_json()?;
+        json.write(&mut out);
+        json.wipe();
+        Ok(out)
+    }
+
+    /// Create a key from JWK JSON, as per RFC 7517.
+    ///
+    /// Fails with [`JwkError::UnrepresentableMembers`] (listing all such members) if the JWK holds
+    /// information that has no COSE_Key equivalent.
+    pub fn from_jwk(jwk: &str) -> Result<Self, JwkError> {
+        Self::from_json(Json::parse(jwk).map_err(JwkError::InvalidJson)?)
+    }
+
+    fn to_json(&self) -> Result<Json, JwkError> {
+        let mut unrepresentable = Vec::new();
+        let mut members = Vec::new();
+
+        let kty = match &self.kty {
+            KeyType::Assigned(kty) => to_name(KEY_TYPES, *kty).map(|name| (*kty, name)),
+            KeyType::Text(_) => None,
+        };
+        let table = match kty {
+            Some((kty, name)) => {
+                members.push(("kty".to_owned(), Json::String(name.to_owned())));
+                param_table(kty)
+            }
+            None => {
+                unrepresentable.push(Label::Int(iana::KeyParameter::Kty as i64));
+                &[]
+            }
+        };
+
+        if !self.key_id.is_empty() {
+            match core::str::from_utf8(&self.key_id) {
+                Ok(kid) => members.push(("kid".to_owned(), Json::String(kid.to_owned()))),
+                Err(_) => unrepresentable.push(Label::Int(iana::KeyParameter::Kid as i64)),
+            }
+        }
+
+        if let Some(alg) = &self.alg {
+            let name = match alg {
+                Algorithm::Assigned(alg) => jose_algorithm_name(*alg).map(str::to_owned),
+                Algorithm::Text(t) if algorithm_from_jose_name(t).is_none() => Some(t.clone()),
+                _ => None,
+            };
+            match name {
+                Some(name) => members.push(("alg".to_owned(), Json::String(name))),
+                None => unrepresentable.push(Label::Int(iana::KeyParameter::Alg as i64)),
+            }
+        }
+
+        if !self.key_ops.is_empty() {
+            let ops = self
+                .key_ops
+                .iter()
+                .map(|op| label_to_jose(KEY_OPS, op).map(Json::String))
+                .collect::<Option<Vec<_>>>();
+            match ops {
+                Some(ops) => members.push(("key_ops".to_owned(), Json::Array(ops))),
+                None => unrepresentable.push(Label::Int(iana::KeyParameter::KeyOps as i64)),
+            }
+        }
+
+        if !self.base_iv.is_empty() {
+            unrepresentable.push(Label::Int(iana::KeyParameter::BaseIv as i64));
+        }
+
+        let mut converted: Vec<Option<Json>> = vec![None; table.len()];
+        for (label, value) in &self.params {
+            let idx = match label {
+                Label::Int(l) => table.iter().position(|(p, _, _)| p == l),
+                Label::Text(_) => None,
+            };
+            let idx = match idx {
+                Some(idx) => idx,
+                None => {
+                    unrepresentable.push(label.clone());
+                    continue;
+                }
+            };
+            if converted[idx].is_some() {
+                return Err(JwkError::InvalidParameter(label.clone()));
+            }
+            let json = match (table[idx].2, value) {
+                // An EC2 y-coordinate may be a sign bit, which JWK does not support.
+                (ParamKind::Bytes, Value::Bool(_)) => {
+                    unrepresentable.push(label.clone());
+                    continue;
+                }
+                (ParamKind::Bytes, value) => b64_member(value, label)?,
+                (ParamKind::Curve, Value::Integer(i)) => {
+                    let name = i64::try_from(*i)
+                        .ok()
+                        .and_then(iana::EllipticCurve::from_i64)
+                        .and_then(jose_curve_name);
+                    match name {
+                        Some(name) => Json::String(name.to_owned()),
+                        None => {
+                            unrepresentable.push(label.clone());
+                            continue;
+                        }
+                    }
+                }
+                (ParamKind::Curve, Value::Text(t)) => {
+                    if curve_from_jose_name(t).is_some() {
+                        unrepresentable.push(label.clone());
+                        continue;
+                    }
+                    Json::String(t.clone())
+                }
+                (ParamKind::Curve, _) => return Err(JwkError::InvalidParameter(label.clone())),
+                (ParamKind::OtherPrimes, value) => other_primes_to_json(value, label)?,
+            };
+            converted[idx] = Some(json);
+        }
+        if !unrepresentable.is_empty() {
+            return Err(JwkError::UnrepresentableParameters(unrepresentable));
+        }
+        for ((_, name, _), json) in table.iter().zip(converted) {
+            if let Some(json) = json {
+                members.push(((*name).to_owned(), json));
+            }
+        }
+        Ok(Json::Object(members))
+    }
+
+    fn from_json(value: Json) -> Result<Self, JwkError> {
+        let mut members = into_members(value)?;
+        let kty_idx = members
+            .iter()
+            .position(|(name, _)| name == "kty")
+            .ok_or(JwkError::MissingMember("kty"))?;
+        let kty = match members.remove(kty_idx).1 {
+            Json::String(kty) => kty,
+            _ => return Err(JwkError::InvalidMember("kty".to_owned())),
+        };
+
+        let mut unrepresentable = Vec::new();
+        let mut key = CoseKey::default();
+        let table = match from_name(KEY_TYPES, &kty) {
+            Some(kty) => {
+                key.kty = KeyType::Assigned(kty);
+                param_table(kty)
+            }
+            None => {
+                unrepresentable.push("kty".to_owned());
+                &[]
+            }
+        };
+
+        let mut converted: Vec<Option<Value>> = vec![None; table.len()];
+        for (name, value) in members {
+            match (name.as_str(), value) {
+                ("kid", Json::String(kid)) if kid.is_empty() => unrepresentable.push(name),
+                ("kid", Json::String(kid)) => key.key_id = kid.into_bytes(),
+                ("alg", Json::String(alg)) => {
+                    key.alg = Some(match algorithm_from_jose_name(&alg) {
+                        Some(alg) => Algorithm::Assigned(alg),
+                        None => Algorithm::Text(alg),
+                    })
+                }
+                ("key_ops", Json::Array(ops)) if ops.is_empty() => unrepresentable.push(name),
+                ("key_ops", Json::Array(ops)) => {
+                    for op in ops {
+                        let op = match op {
+                            Json::String(op) => label_from_jose(KEY_OPS, &op),
+                            _ => return Err(JwkError::InvalidMember(name)),
+                        };
+                        if !key.key_ops.insert(op) {
+                            return Err(JwkError::InvalidMember(name));
+                        }
+                    }
+                }
+                ("kid" | "alg" | "key_ops", _) => return Err(JwkError::InvalidMember(name)),
+                (_, value) => match table.iter().position(|(_, n, _)| *n == name) {
+                    Some(idx) => {
+                        converted[idx] = Some(match (table[idx].2, value) {
+                            (ParamKind::Bytes, value) => b64_param(&value, &name)?,
+                            (ParamKind::Curve, Json::String(crv)) => {
+                                match curve_from_jose_name(&crv) {
+                                    Some(crv) => Value::from(crv as i64),
+                                    None => Value::Text(crv),
+                                }
+                            }
+                            (ParamKind::Curve, _) => return Err(JwkError::InvalidMember(name)),
+                            (ParamKind::OtherPrimes, value) => {
+                                other_primes_from_json(value, &name)?
+                            }
+                        })
+                    }
+                    None => unrepresentable.push(name),
+                },
+            }
+        }
+        if !unrepresentable.is_empty() {
+            return Err(JwkError::UnrepresentableMembers(unrepresentable));
+        }
+        for ((label, _, _), value) in table.iter().zip(converted) {
+            if let Some(value) = value {
+                key.params.push((Label::Int(*label), value));
+            }
+        }
+        Ok(key)
+    }
+}
+
+fn other_primes_to_json(value: &Value, label: &Label) -> Result<Json, JwkError> {
+    let invalid = || JwkError::InvalidParameter(label.clone());
+    let entries = value.as_array().ok_or_else(invalid)?;
+    let mut out = Vec::new();
+    for entry in entries {
+        let m = entry.as_map().ok_or_else(invalid)?;
+        if m.len() != RSA_OTHER_PRIME_PARAMS.len() {
+            return Err(invalid());
+        }
+        let mut members = Vec::new();
+        for (param, name) in RSA_OTHER_PRIME_PARAMS {
+            let value = m
+                .iter()
+                .find(|(l, _)| l.as_integer() == Some(param.into()))
+                .map(|(_, v)| v)
+                .ok_or_else(invalid)?;
+            members.push((name.to_owned(), b64_member(value, label)?));
+        }
+        out.push(Json::Object(members));
+    }
+    Ok(Json::Array(out))
+}
+
+fn other_primes_from_json(value: Json, name: &str) -> Result<Value, JwkError> {
+    let invalid = || JwkError::InvalidMember(name.to_owned());
+    let entries = match value {
+        Json::Array(entries) => entries,
+        _ => return Err(invalid()),
+    };
+    let mut out = Vec::new();
+    for entry in entries {
+        let members = into_members(entry).map_err(|_| invalid())?;
+        if members.len() != RSA_OTHER_PRIME_PARAMS.len() {
+            return Err(invalid());
+        }
+        let mut m = Vec::new();
+        for (param, member) in RSA_OTHER_PRIME_PARAMS {
+            let value = members
+                .iter()
+                .find(|(n, _)| n == member)
+                .map(|(_, v)| v)
+                .ok_or_else(invalid)?;
+            m.push((Value::from(param), b64_param(value, name)?));
+        }
+        out.push(Value::Map(m));
+    }
+    Ok(Value::Array(out))
+}
+
+impl CoseKeySet {
+    /// Convert the key set to JWK Set JSON, as per RFC 7517 section 5.
+    pub fn to_jwks(&self) -> Result<String, JwkError> {
+        let keys = self
+            .0
+            .iter()
+            .map(CoseKey::to_json)
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut out = String::new();
+        let mut json = Json::Object(vec![("keys".to_owned(), Json::Array(keys))]);
+        json.write(&mut out);
+        json.wipe();
+        Ok(out)
+    }
+
+    /// Create a key set from JWK Set JSON, as per RFC 7517 section 5.
+    pub fn from_jwks(jwks: &str) -> Result<Self, JwkError> {
+        let members = into_members(Json::parse(jwks).map_err(JwkError::InvalidJson)?)?;
+        let mut keys = None;
+        let mut unrepresentable = Vec::new();
+        for (name, value) in members {
+            match (name.as_str(), value) {
+                ("keys", Json::Array(k)) => keys = Some(k),
+                ("keys", _) => return Err(JwkError::InvalidMember(name)),
+                _ => unrepresentable.push(name),
+            }
+        }
+        if !unrepresentable.is_empty() {
+            return Err(JwkError::UnrepresentableMembers(unrepresentable));
+        }
+        let keys = keys.ok_or(JwkError::MissingMember("keys"))?;
+        Ok(CoseKeySet(
+            keys.into_iter()
+                .map(CoseKey::from_json)
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
@@ -0,0 +1,518 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{
+    util::expect_err, CoseKeyBuilder, KeyOperation, MlDsaVariant, RsaOtherPrimeInfo,
+    RsaPrivateComponents,
+};
+use alloc::{string::ToString, vec};
+
+#[test]
+fn test_jwk_okp_rfc8037() {
+    // Ed25519 key from RFC 8037 appendix A.1 / A.2.
+    let jwk = concat!(
+        r#"{"kty":"OKP","crv":"Ed25519","#,
+        r#""x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo","#,
+        r#""d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A"}"#,
+    );
+    let want = CoseKeyBuilder::new_okp_key()
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::Ed25519 as i64),
+        )
+        .param(
+            iana::OkpKeyParameter::X as i64,
+            Value::Bytes(
+                hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                    .unwrap(),
+            ),
+        )
+        .param(
+            iana::OkpKeyParameter::D as i64,
+            Value::Bytes(
+                hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                    .unwrap(),
+            ),
+        )
+        .build();
+    let key = CoseKey::from_jwk(jwk).unwrap();
+    assert_eq!(key, want);
+    assert_eq!(key.to_jwk().unwrap(), jwk);
+}
+
+#[test]
+fn test_jwk_symmetric_rfc7517() {
+    // Symmetric keys from RFC 7517 appendix A.3.
+    let key =
+        CoseKey::from_jwk(r#"{"kty":"oct","alg":"A128KW", "k":"GawgguFyGrWKav7AX4VKUg"}"#).unwrap();
+    let want =
+        CoseKeyBuilder::new_symmetric_key(hex::decode("19ac2082e1721ab58a6afec05f854a52").unwrap())
+            .algorithm(iana::Algorithm::A128KW)
+            .build();
+    assert_eq!(key, want);
+    assert_eq!(
+        key.to_jwk().unwrap(),
+        r#"{"kty":"oct","alg":"A128KW","k":"GawgguFyGrWKav7AX4VKUg"}"#
+    );
+
+    let jwk = concat!(
+        "{\n",
+        "  \"kty\": \"oct\",\n",
+        "  \"k\": \"AyM1SysPpbyDfgZld3umj1qzKObwVMkoqQ-EstJQLr_T-1qS0gZH75aKtMN3Yj0iPS4hcgUuTwjAzZr1Z9CAow\",\n",
+        "  \"kid\": \"HMAC key used in JWS spec Appendix A.1 example\"\n",
+        "}\n",
+    );
+    let key = CoseKey::from_jwk(jwk).unwrap();
+    assert_eq!(
+        key.key_id,
+        b"HMAC key used in JWS spec Appendix A.1 example"
+    );
+    assert_eq!(key.params.len(), 1);
+    assert_eq!(CoseKey::from_jwk(&key.to_jwk().unwrap()).unwrap(), key);
+}
+
+#[test]
+fn test_jwk_round_trip() {
+    let tests = vec![
+        CoseKeyBuilder::new_ec2_priv_key(
+            iana::EllipticCurve::P_256,
+            vec![1, 2, 3],
+            vec![4, 5, 6],
+            vec![7, 8, 9],
+        )
+        .key_id(b"11".to_vec())
+        .algorithm(iana::Algorithm::ES256)
+        .add_key_op(iana::KeyOperation::Sign)
+        .add_key_op(iana::KeyOperation::Verify)
+        .build(),
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::Secp256k1, vec![1], vec![2])
+            .algorithm(iana::Algorithm::ES256K)
+            .build(),
+        CoseKeyBuilder::new_okp_key()
+            .param(
+                iana::OkpKeyParameter::Crv as i64,
+                Value::from(iana::EllipticCurve::X25519 as i64),
+            )
+            .param(
+                iana::OkpKeyParameter::X as i64,
+                Value::Bytes(vec![0xff; 32]),
+            )
+            .add_key_op(iana::KeyOperation::DeriveBits)
+            .build(),
+        CoseKeyBuilder::new_rsa_priv_key(
+            vec![0xab],
+            vec![0x01, 0x00, 0x01],
+            RsaPrivateComponents {
+                d: vec![0x01],
+                p: vec![0x02],
+                q: vec![0x03],
+                dp: vec![0x04],
+                dq: vec![0x05],
+                qinv: vec![0x06],
+                other: vec![RsaOtherPrimeInfo {
+                    r: vec![0x07],
+                    d: vec![0x08],
+                    t: vec![0x09],
+                }],
+            },
+        )
+        .algorithm(iana::Algorithm::PS256)
+        .build(),
+        CoseKeyBuilder::new_mldsa_pub_key(MlDsaVariant::MlDsa65, vec![0x42; 16]).build(),
+        CoseKeyBuilder::new_symmetric_key(vec![0x42; 16])
+            .algorithm(iana::Algorithm::HMAC_256_256)
+            .build(),
+        CoseKey {
+            kty: KeyType::Assigned(iana::KeyType::EC2),
+            alg: Some(Algorithm::Text("my-alg".to_owned())),
+            key_ops: vec![KeyOperation::Text("frobnicate".to_owned())]
+                .into_iter()
+                .collect(),
+            params: vec![(
+                Label::Int(iana::Ec2KeyParameter::Crv as i64),
+                Value::Text("my-curve".to_owned()),
+            )],
+            ..Default::default()
+        },
+    ];
+    for key in tests {
+        let jwk = key.to_jwk().unwrap();
+        assert_eq!(CoseKey::from_jwk(&jwk).unwrap(), key, "for {jwk}");
+    }
+}
+
+#[test]
+fn test_jwk_rsa_encode() {
+    let key = CoseKeyBuilder::new_rsa_priv_key(
+        vec![0xab],
+        vec![0x01, 0x00, 0x01],
+        RsaPrivateComponents {
+            d: vec![0x01],
+            p: vec![0x02],
+            q: vec![0x03],
+            dp: vec![0x04],
+            dq: vec![0x05],
+            qinv: vec![0x06],
+            other: vec![RsaOtherPrimeInfo {
+                r: vec![0x07],
+                d: vec![0x08],
+                t: vec![0x09],
+            }],
+        },
+    )
+    .build();
+    assert_eq!(
+        key.to_jwk().unwrap(),
+        concat!(
+            r#"{"kty":"RSA","n":"qw","e":"AQAB","d":"AQ","p":"Ag","q":"Aw","dp":"BA","dq":"BQ","#,
+            r#""qi":"Bg","oth":[{"r":"Bw","d":"CA","t":"CQ"}]}"#
+        )
+    );
+}
+
+#[test]
+fn test_jwk_unrepresentable_params() {
+    let key =
+        CoseKeyBuilder::new_ec2_pub_key_y_sign(iana::EllipticCurve::BrainpoolP256R1, vec![1], true)
+            .key_id(vec![0xff, 0xfe])
+            .base_iv(vec![1, 2])
+            .add_key_op(iana::KeyOperation::MacCreate)
+            .param(-70000, Value::Bytes(vec![1]))
+            .build();
+    let result = key.to_jwk();
+    expect_err(result.clone(), "not representable in JWK");
+    match result {
+        Err(JwkError::UnrepresentableParameters(labels)) => assert_eq!(
+            labels,
+            vec![
+                Label::Int(iana::KeyParameter::Kid as i64),
+                Label::Int(iana::KeyParameter::KeyOps as i64),
+                Label::Int(iana::KeyParameter::BaseIv as i64),
+                Label::Int(iana::Ec2KeyParameter::Crv as i64),
+                Label::Int(iana::Ec2KeyParameter::Y as i64),
+                Label::Int(-70000),
+            ]
+        ),
+        other => panic!("unexpected result {:?}", other),
+    }
+
+    let tests = vec![
+        (
+            CoseKeyBuilder::new_symmetric_key(vec![1])
+                .kty(KeyType::Assigned(iana::KeyType::HSS_LMS))
+                .build(),
+            vec![
+                Label::Int(iana::KeyParameter::Kty as i64),
+                Label::Int(iana::SymmetricKeyParameter::K as i64),
+            ],
+        ),
+        (
+            CoseKey {
+                kty: KeyType::Assigned(iana::KeyType::Symmetric),
+                // Text value that collides with a JOSE name.
+                alg: Some(Algorithm::Text("HS256".to_owned())),
+                params: vec![(Label::Text("k".to_owned()), Value::Bytes(vec![1]))],
+                ..Default::default()
+            },
+            vec![
+                Label::Int(iana::KeyParameter::Alg as i64),
+                Label::Text("k".to_owned()),
+            ],
+        ),
+        (
+            CoseKeyBuilder::new_symmetric_key(vec![1])
+                .algorithm(iana::Algorithm::AES_CCM_16_64_128)
+                .build(),
+            vec![Label::Int(iana::KeyParameter::Alg as i64)],
+        ),
+    ];
+    for (key, want) in tests {
+        match key.to_jwk() {
+            Err(JwkError::UnrepresentableParameters(labels)) => assert_eq!(labels, want),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
+
+#[test]
+fn test_jwk_invalid_params() {
+    let tests = vec![
+        (
+            CoseKeyBuilder::new_symmetric_key(vec![1])
+                .param(iana::SymmetricKeyParameter::K as i64, Value::Bytes(vec![2]))
+                .build(),
+            "parameter Int(-1)",
+        ),
+        (
+            CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_256, vec![1], vec![2])
+                .param(iana::Ec2KeyParameter::D as i64, Value::from(1))
+                .build(),
+            "parameter Int(-4)",
+        ),
+        (
+            CoseKey {
+                kty: KeyType::Assigned(iana::KeyType::OKP),
+                params: vec![(
+                    Label::Int(iana::OkpKeyParameter::Crv as i64),
+                    Value::Bytes(vec![1]),
+                )],
+                ..Default::default()
+            },
+            "parameter Int(-1)",
+        ),
+        (
+            CoseKeyBuilder::new_rsa_pub_key(vec![1], vec![3])
+                .param(
+                    iana::RsaKeyParameter::Other as i64,
+                    Value::Array(vec![Value::from(1)]),
+                )
+                .build(),
+            "parameter Int(-9)",
+        ),
+    ];
+    for (key, err_msg) in tests {
+        expect_err(key.to_jwk(), err_msg);
+    }
+}
+
+#[test]
+fn test_jwk_unrepresentable_members() {
+    // EC public key from RFC 7517 appendix A.1, which includes a `use` member.
+    let jwk = concat!(
+        r#"{"kty":"EC","crv":"P-256","#,
+        r#""x":"MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4","#,
+        r#""y":"4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM","#,
+        r#""use":"enc","kid":"1"}"#,
+    );
+    let result = CoseKey::from_jwk(jwk);
+    expect_err(result.clone(), "not representable in COSE_Key");
+    match result {
+        Err(JwkError::UnrepresentableMembers(names)) => assert_eq!(names, vec!["use"]),
+        other => panic!("unexpected result {:?}", other),
+    }
+    let key = CoseKey::from_jwk(&jwk.replace(r#""use":"enc","#, "")).unwrap();
+    assert_eq!(key.key_id, b"1");
+
+    let tests = vec![
+        (
+            r#"{"kty":"oct","k":"AQ","kid":"","x5c":[]}"#,
+            vec!["kid", "x5c"],
+        ),
+        (r#"{"kty":"oct","k":"AQ","key_ops":[]}"#, vec!["key_ops"]),
+        (r#"{"kty":"oct","x":"AQ","crv":"P-256"}"#, vec!["x", "crv"]),
+        (r#"{"kty":"EC2","x":"AQ"}"#, vec!["kty", "x"]),
+    ];
+    for (jwk, want) in tests {
+        match CoseKey::from_jwk(jwk) {
+            Err(JwkError::UnrepresentableMembers(names)) => assert_eq!(names, want),
+            result => panic!("unexpected result {:?} for {}", result, jwk),
+        }
+    }
+}
+
+#[test]
+fn test_jwk_decode_fail() {
+    let tests = vec![
+        ("", "invalid JSON at offset 0"),
+        (r#"{"kty":"oct""#, "invalid JSON at offset 12"),
+        (r#"{"kty":"oct"} x"#, "invalid JSON at offset 14"),
+        ("[]", "not an object"),
+        (r#"{"k":"AQ"}"#, "missing JWK member 'kty'"),
+        (r#"{"kty":1}"#, "member 'kty'"),
+        (
+            r#"{"kty":"oct","k":"AQ","k":"AQ"}"#,
+            "duplicate JWK member 'k'",
+        ),
+        (r#"{"kty":"oct","k":"AQ=="}"#, "member 'k'"),
+        (r#"{"kty":"oct","k":"A+"}"#, "member 'k'"),
+        (r#"{"kty":"oct","k":1}"#, "member 'k'"),
+        (r#"{"kty":"oct","kid":1}"#, "member 'kid'"),
+        (r#"{"kty":"oct","alg":null}"#, "member 'alg'"),
+        (r#"{"kty":"oct","key_ops":"sign"}"#, "member 'key_ops'"),
+        (r#"{"kty":"oct","key_ops":[1]}"#, "member 'key_ops'"),
+        (
+            r#"{"kty":"oct","key_ops":["sign","sign"]}"#,
+            "member 'key_ops'",
+        ),
+        (r#"{"kty":"EC","crv":1}"#, "member 'crv'"),
+        (r#"{"kty":"RSA","oth":{}}"#, "member 'oth'"),
+        (r#"{"kty":"RSA","oth":[1]}"#, "member 'oth'"),
+        (
+            r#"{"kty":"RSA","oth":[{"r":"AQ","d":"AQ"}]}"#,
+            "member 'oth'",
+        ),
+        (
+            r#"{"kty":"RSA","oth":[{"r":"AQ","d":"AQ","x":"AQ"}]}"#,
+            "member 'oth'",
+        ),
+    ];
+    for (jwk, err_msg) in tests {
+        expect_err(CoseKey::from_jwk(jwk), err_msg);
+    }
+}
+
+#[test]
+fn test_jwks() {
+    let keys = CoseKeySet(vec![
+        CoseKeyBuilder::new_symmetric_key(vec![1, 2, 3])
+            .key_id(b"a".to_vec())
+            .build(),
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_384, vec![4], vec![5])
+            .key_id(b"b".to_vec())
+            .build(),
+    ]);
+    let jwks = keys.to_jwks().unwrap();
+    assert_eq!(
+        jwks,
+        concat!(
+            r#"{"keys":[{"kty":"oct","kid":"a","k":"AQID"},"#,
+            r#"{"kty":"EC","kid":"b","crv":"P-384","x":"BA","y":"BQ"}]}"#
+        )
+    );
+    assert_eq!(CoseKeySet::from_jwks(&jwks).unwrap(), keys);
+    assert_eq!(
+        CoseKeySet::from_jwks(r#"{"keys":[]}"#).unwrap(),
+        CoseKeySet(vec![])
+    );
+
+    let bad_keys = CoseKeySet(vec![CoseKeyBuilder::new_symmetric_key(vec![1])
+        .base_iv(vec![1])
+        .build()]);
+    expect_err(bad_keys.to_jwks(), "not representable in JWK");
+
+    let tests = vec![
+        ("{}", "missing JWK member 'keys'"),
+        (r#"{"keys":{}}"#, "member 'keys'"),
+        (r#"{"keys":[1]}"#, "not an object"),
+        (
+            r#"{"keys":[{"kty":"oct","use":"sig"}]}"#,
+            "not representable in COSE_Key",
+        ),
+        (r#"{"keys":[],"other":1}"#, "not representable in COSE_Key"),
+    ];
+    for (jwks, err_msg) in tests {
+        expect_err(CoseKeySet::from_jwks(jwks), err_msg);
+    }
+}
+
+#[test]
+fn test_jose_names() {
+    for (crv, name) in CURVES {
+        assert_eq!(jose_curve_name(*crv), Some(*name));
+        assert_eq!(curve_from_jose_name(name), Some(*crv));
+    }
+    for (op, name) in KEY_OPS {
+        assert_eq!(jose_key_op_name(*op), Some(*name));
+        assert_eq!(key_op_from_jose_name(name), Some(*op));
+    }
+    for (alg, name) in ALGORITHMS {
+        assert_eq!(jose_algorithm_name(*alg), Some(*name));
+        assert_eq!(algorithm_from_jose_name(name), Some(*alg));
+    }
+    assert_eq!(jose_curve_name(iana::EllipticCurve::BrainpoolP256R1), None);
+    assert_eq!(curve_from_jose_name("p-256"), None);
+    assert_eq!(jose_key_op_name(iana::KeyOperation::MacVerify), None);
+    assert_eq!(key_op_from_jose_name("Sign"), None);
+    assert_eq!(jose_algorithm_name(iana::Algorithm::ECDH_ES_A128KW), None);
+    assert_eq!(algorithm_from_jose_name("none"), None);
+}
+
+#[test]
+fn test_json_parse() {
+    let tests = vec![
+        ("null", Json::Null),
+        (" true ", Json::Bool(true)),
+        ("false", Json::Bool(false)),
+        ("-0.5e+10", Json::Number("-0.5e+10".to_owned())),
+        ("12", Json::Number("12".to_owned())),
+        (
+            r#""a\"\\\/\b\f\n\r\té😀""#,
+            Json::String("a\"\\/\u{8}\u{c}\n\r\té\u{1f600}".to_owned()),
+        ),
+        ("\"ü\"", Json::String("ü".to_owned())),
+        (
+            r#"[1, {"a": []}]"#,
+            Json::Array(vec![
+                Json::Number("1".to_owned()),
+                Json::Object(vec![("a".to_owned(), Json::Array(vec![]))]),
+            ]),
+        ),
+        (
+            r#"{"a":1,"a":2}"#,
+            Json::Object(vec![
+                ("a".to_owned(), Json::Number("1".to_owned())),
+                ("a".to_owned(), Json::Number("2".to_owned())),
+            ]),
+        ),
+    ];
+    for (text, want) in tests {
+        assert_eq!(Json::parse(text), Ok(want), "for {text}");
+    }
+}
+
+#[test]
+fn test_json_parse_fail() {
+    let deep = "[".repeat(40) + &"]".repeat(40);
+    let tests = vec![
+        ("", 0),
+        ("nul", 0),
+        ("01", 1),
+        ("-", 1),
+        ("1.", 2),
+        ("1e", 2),
+        ("[1,]", 3),
+        ("[1 2]", 3),
+        (r#"{"a" 1}"#, 5),
+        (r#"{"a":1,}"#, 7),
+        (r#"{1:1}"#, 1),
+        (r#""\x""#, 2),
+        (r#""\u12g4""#, 5),
+        (r#""\ud83d""#, 7),
+        (r#""\ud83dA""#, 7),
+        (r#""\ud83d\u0041""#, 3),
+        (r#""\ude00""#, 3),
+        ("\"a\nb\"", 2),
+        ("\"abc", 4),
+        (&deep, 32),
+    ];
+    for (text, want) in tests {
+        assert_eq!(Json::parse(text), Err(want), "for {text}");
+    }
+}
+
+#[test]
+fn test_json_write() {
+    let value = Json::Object(vec![
+        (
+            "a\"b".to_owned(),
+            Json::Array(vec![Json::Null, Json::Bool(true), Json::Bool(false)]),
+        ),
+        ("c".to_owned(), Json::String("\\\n\r\t\u{1}é".to_owned())),
+        ("d".to_owned(), Json::Number("-1.5".to_owned())),
+    ]);
+    let mut out = String::new();
+    value.write(&mut out);
+    assert_eq!(
+        out,
+        r#"{"a\"b":[null,true,false],"c":"\\\n\r\t\u0001é","d":-1.5}"#
+    );
+    assert_eq!(Json::parse(&out).unwrap(), value);
+    assert_eq!(
+        CoseKey::from_jwk("{").unwrap_err().to_string(),
+        "invalid JSON at offset 1"
+    );
+}
@@ -21,10 +21,11 @@ use crate::{
     common::{AsCborValue, CborOrdering},
     iana,
     iana::EnumI64,
-    util::{to_cbor_array, ValueTryAs},
-    Algorithm, CoseError, Label, Result,
+    util::{to_cbor_array, wipe_value, Base64, ValueTryAs},
+    Algorithm, CborSerializable, CoseError, CoseHeaders, Label, Result,
 };
-use alloc::{collections::BTreeSet, vec, vec::Vec};
+use alloc::{collections::BTreeSet, format, string::String, vec, vec::Vec};
+use core::convert::TryFrom;
 
 #[cfg(test)]
 mod tests;
@@ -47,6 +48,89 @@ pub struct CoseKeySet(pub Vec<CoseKey>);
 
 impl crate::CborSerializable for CoseKeySet {}
 
+impl CoseKeySet {
+    /// Return the first key with the given key identifier, if present.
+    pub fn find_by_key_id(&self, kid: &[u8]) -> Option<&CoseKey> {
+        self.0.iter().find(|key| key.key_id == kid)
+    }
+
+    /// Return an iterator over the keys of the given key type.
+    pub fn filter_by_key_type<'a>(
+        &'a self,
+        kty: &'a KeyType,
+    ) -> impl Iterator<Item = &'a CoseKey> + 'a {
+        self.0.iter().filter(move |key| key.kty == *kty)
+    }
+
+    /// Return an iterator over the keys that may be used with the given algorithm, i.e. those that
+    /// are either restricted to `alg` or have no algorithm restriction.
+    pub fn filter_by_algorithm<'a>(
+        &'a self,
+        alg: &'a Algorithm,
+    ) -> impl Iterator<Item = &'a CoseKey> + 'a {
+        self.0.iter().filter(move |key| key.allows_algorithm(alg))
+    }
+
+    /// Return an iterator over the keys that may be used for the given key operation, i.e. those
+    /// that either include `op` in their `key_ops` or have no `key_ops` restriction.
+    pub fn filter_by_key_op<'a>(
+        &'a self,
+        op: &'a KeyOperation,
+    ) -> impl Iterator<Item = &'a CoseKey> + 'a {
+        self.0
+            .iter()
+            .filter(move |key| key.key_ops.is_empty() || key.key_ops.contains(op))
+    }
+
+    /// Return the candidate keys for verifying or decrypting a COSE message, based on its headers.
+    ///
+    /// - If the message holds a key identifier (preferring the protected header), only keys with
+    ///   that identifier are candidates; otherwise all keys are.
+    /// - If the message holds an algorithm (preferring the protected header), keys restricted to a
+    ///   different algorithm are excluded.
+    ///
+    /// For a [`crate::CoseSign`] or [`crate::CoseEncrypt`], the key identifier is usually held by
+    /// each [`crate::CoseSignature`] or [`crate::CoseRecipient`], which should be passed instead.
+    pub fn resolve<M: CoseHeaders>(&self, msg: &M) -> Vec<&CoseKey> {
+        let (protected, unprotected) = (msg.protected_header(), msg.unprotected_header());
+        let kid = if protected.key_id.is_empty() {
+            &unprotected.key_id
+        } else {
+            &protected.key_id
+        };
+        let alg = protected.alg.as_ref().or(unprotected.alg.as_ref());
+        self.0
+            .iter()
+            .filter(|key| kid.is_empty() || key.key_id == *kid)
+            .filter(|key| alg.map_or(true, |alg| key.allows_algorithm(alg)))
+            .collect()
+    }
+
+    /// Check that no two keys in the set share a (non-empty) key identifier.
+    pub fn check_unique_key_ids(&self) -> Result<(), DuplicateKeyIdError> {
+        let mut seen = BTreeSet::new();
+        for key in &self.0 {
+            if !key.key_id.is_empty() && !seen.insert(&key.key_id) {
+                return Err(DuplicateKeyIdError(key.key_id.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error type returned when a [`CoseKeySet`] holds more than one key with the same key
+/// identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateKeyIdError(pub Vec<u8>);
+
+impl core::error::Error for DuplicateKeyIdError {}
+
+impl core::fmt::Display for DuplicateKeyIdError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "duplicate key ID {:?}", self.0)
+    }
+}
+
 impl AsCborValue for CoseKeySet {
     fn from_cbor_value(value: Value) -> Result<Self> {
         Ok(Self(
@@ -165,6 +249,272 @@ impl core::convert::TryFrom<iana::Algorithm> for MlDsaVariant {
     }
 }
 
+/// Additional prime information for a multi-prime RSA private key, as per RFC 8230 section 4.
+///
+/// ```cddl
+///  other_prime_info = {
+///      -10 => bstr, ; r_i
+///      -11 => bstr, ; d_i
+///      -12 => bstr, ; t_i
+///  }
+/// ```
+///
+/// With the `zeroize` feature, the contents are wiped on drop (and so the type cannot be built
+/// with struct update syntax).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RsaOtherPrimeInfo {
+    /// Prime factor r_i of n, where i >= 3.
+    pub r: Vec<u8>,
+    /// Factor CRT exponent d_i = d mod (r_i - 1).
+    pub d: Vec<u8>,
+    /// Factor CRT coefficient t_i = (r_1 * r_2 * ... * r_(i-1))^(-1) mod r_i.
+    pub t: Vec<u8>,
+}
+
+/// Private components of an RSA key, as per RFC 8230 section 4.  All values are unsigned
+/// big-endian integers.
+///
+/// With the `zeroize` feature, the contents are wiped on drop (and so the type cannot be built
+/// with struct update syntax).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RsaPrivateComponents {
+    /// Private exponent d.
+    pub d: Vec<u8>,
+    /// First prime factor p.
+    pub p: Vec<u8>,
+    /// Second prime factor q.
+    pub q: Vec<u8>,
+    /// First factor CRT exponent dP = d mod (p - 1).
+    pub dp: Vec<u8>,
+    /// Second factor CRT exponent dQ = d mod (q - 1).
+    pub dq: Vec<u8>,
+    /// First CRT coefficient qInv = q^(-1) mod p.
+    pub qinv: Vec<u8>,
+    /// Information about any additional primes, for a multi-prime key.
+    pub other: Vec<RsaOtherPrimeInfo>,
+}
+
+/// The error type returned when the parameters of an RSA [`CoseKey`] are inconsistent with RFC
+/// 8230 section 4.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RsaKeyError {
+    /// The [`CoseKey`] is not an [`iana::KeyType::RSA`] key type.
+    NotRsaKey,
+    /// A required parameter is not present.
+    MissingParameter(iana::RsaKeyParameter),
+    /// A parameter is present that is not allowed in this combination.
+    UnexpectedParameter(iana::RsaKeyParameter),
+    /// A parameter has an invalid CBOR type.
+    InvalidParameterType(iana::RsaKeyParameter),
+    /// A parameter is not encoded with the minimum number of octets.
+    NonMinimalEncoding(iana::RsaKeyParameter),
+}
+
+impl core::error::Error for RsaKeyError {}
+
+impl core::fmt::Display for RsaKeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RsaKeyError::NotRsaKey => write!(f, "not an RSA key"),
+            RsaKeyError::MissingParameter(p) => write!(f, "missing RSA parameter {p:?}"),
+            RsaKeyError::UnexpectedParameter(p) => write!(f, "unexpected RSA parameter {p:?}"),
+            RsaKeyError::InvalidParameterType(p) => {
+                write!(f, "invalid type for RSA parameter {p:?}")
+            }
+            RsaKeyError::NonMinimalEncoding(p) => {
+                write!(f, "non-minimal encoding for RSA parameter {p:?}")
+            }
+        }
+    }
+}
+
+/// The error type returned when an RFC 9679 thumbprint can't be calculated for a [`CoseKey`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThumbprintError {
+    /// RFC 9679 does not define the required parameters for the key type.
+    UnsupportedKeyType(KeyType),
+    /// A parameter that is required for the key type is not present.
+    MissingParameter(Label),
+    /// The thumbprint input could not be CBOR-encoded.
+    EncodeFailed,
+}
+
+impl core::error::Error for ThumbprintError {}
+
+impl core::fmt::Display for ThumbprintError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ThumbprintError::UnsupportedKeyType(kty) => {
+                write!(f, "thumbprint not defined for key type {kty:?}")
+            }
+            ThumbprintError::MissingParameter(l) => {
+                write!(f, "missing required key parameter {l:?}")
+            }
+            ThumbprintError::EncodeFailed => write!(f, "failed to encode thumbprint input"),
+        }
+    }
+}
+
+/// The error type returned when the public key can't be extracted from a [`CoseKey`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicKeyError {
+    /// The key type has no public key.
+    UnsupportedKeyType(KeyType),
+    /// A public key parameter is not present, and can't be derived from the private key with the
+    /// enabled crate features.
+    MissingParameter(Label),
+}
+
+impl core::error::Error for PublicKeyError {}
+
+impl core::fmt::Display for PublicKeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PublicKeyError::UnsupportedKeyType(kty) => {
+                write!(f, "no public key for key type {kty:?}")
+            }
+            PublicKeyError::MissingParameter(l) => {
+                write!(f, "missing public key parameter {l:?}")
+            }
+        }
+    }
+}
+
+/// Return the labels of the parameters that hold public key material for the given key type, or
+/// `None` if the key type has no public key.
+fn public_params(kty: &KeyType) -> Option<&'static [i64]> {
+    match kty {
+        KeyType::Assigned(iana::KeyType::OKP) => Some(&[
+            iana::OkpKeyParameter::Crv as i64,
+            iana::OkpKeyParameter::X as i64,
+        ]),
+        KeyType::Assigned(iana::KeyType::EC2) => Some(&[
+            iana::Ec2KeyParameter::Crv as i64,
+            iana::Ec2KeyParameter::X as i64,
+            iana::Ec2KeyParameter::Y as i64,
+        ]),
+        KeyType::Assigned(iana::KeyType::RSA) => Some(&[
+            iana::RsaKeyParameter::N as i64,
+            iana::RsaKeyParameter::E as i64,
+        ]),
+        KeyType::Assigned(iana::KeyType::AKP) => Some(&[iana::AkpKeyParameter::Pub as i64]),
+        _ => None,
+    }
+}
+
+/// Return the key operation that the public key can perform in place of `op` on the private key,
+/// or `None` if there is no such operation.
+fn public_key_op(op: &KeyOperation) -> Option<KeyOperation> {
+    let op = match op {
+        KeyOperation::Assigned(iana::KeyOperation::Sign | iana::KeyOperation::Verify) => {
+            iana::KeyOperation::Verify
+        }
+        KeyOperation::Assigned(iana::KeyOperation::Decrypt | iana::KeyOperation::Encrypt) => {
+            iana::KeyOperation::Encrypt
+        }
+        KeyOperation::Assigned(iana::KeyOperation::UnwrapKey | iana::KeyOperation::WrapKey) => {
+            iana::KeyOperation::WrapKey
+        }
+        _ => return None,
+    };
+    Some(KeyOperation::Assigned(op))
+}
+
+/// Return the labels of the parameters that hold private key material for the given key type.
+fn private_params(kty: &KeyType) -> &'static [i64] {
+    match kty {
+        KeyType::Assigned(iana::KeyType::OKP) => &[iana::OkpKeyParameter::D as i64],
+        KeyType::Assigned(iana::KeyType::EC2) => &[iana::Ec2KeyParameter::D as i64],
+        KeyType::Assigned(iana::KeyType::RSA) => &[
+            iana::RsaKeyParameter::D as i64,
+            iana::RsaKeyParameter::P as i64,
+            iana::RsaKeyParameter::Q as i64,
+            iana::RsaKeyParameter::DP as i64,
+            iana::RsaKeyParameter::DQ as i64,
+            iana::RsaKeyParameter::QInv as i64,
+            iana::RsaKeyParameter::Other as i64,
+            iana::RsaKeyParameter::RI as i64,
+            iana::RsaKeyParameter::DI as i64,
+            iana::RsaKeyParameter::TI as i64,
+        ],
+        KeyType::Assigned(iana::KeyType::Symmetric) => &[iana::SymmetricKeyParameter::K as i64],
+        KeyType::Assigned(iana::KeyType::AKP) => &[iana::AkpKeyParameter::Priv as i64],
+        _ => &[],
+    }
+}
+
+/// RSA parameters that hold private key material in a two-prime key.
+const RSA_PRIVATE_PARAMS: [iana::RsaKeyParameter; 6] = [
+    iana::RsaKeyParameter::D,
+    iana::RsaKeyParameter::P,
+    iana::RsaKeyParameter::Q,
+    iana::RsaKeyParameter::DP,
+    iana::RsaKeyParameter::DQ,
+    iana::RsaKeyParameter::QInv,
+];
+
+/// RSA parameters that may only appear inside an entry of the `other` array.
+const RSA_OTHER_PRIME_PARAMS: [iana::RsaKeyParameter; 3] = [
+    iana::RsaKeyParameter::RI,
+    iana::RsaKeyParameter::DI,
+    iana::RsaKeyParameter::TI,
+];
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for RsaOtherPrimeInfo {
+    fn zeroize(&mut self) {
+        self.r.zeroize();
+        self.d.zeroize();
+        self.t.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for RsaPrivateComponents {
+    fn zeroize(&mut self) {
+        self.d.zeroize();
+        self.p.zeroize();
+        self.q.zeroize();
+        self.dp.zeroize();
+        self.dq.zeroize();
+        self.qinv.zeroize();
+        self.other.zeroize();
+    }
+}
+
+/// Wipes the contents when dropped.
+#[cfg(feature = "zeroize")]
+impl Drop for RsaOtherPrimeInfo {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for RsaOtherPrimeInfo {}
+
+/// Wipes the contents when dropped.
+#[cfg(feature = "zeroize")]
+impl Drop for RsaPrivateComponents {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for RsaPrivateComponents {}
+
+/// Check that a single RSA integer parameter is a minimally-encoded `bstr`.
+fn check_rsa_integer(param: iana::RsaKeyParameter, value: &Value) -> Result<(), RsaKeyError> {
+    let data = value
+        .as_bytes()
+        .ok_or(RsaKeyError::InvalidParameterType(param))?;
+    match data.first() {
+        None | Some(0) => Err(RsaKeyError::NonMinimalEncoding(param)),
+        Some(_) => Ok(()),
+    }
+}
+
 impl CoseKey {
     /// Re-order the contents of the key so that the contents will be emitted in one of the standard
     /// CBOR sorted orders.
@@ -222,6 +572,311 @@ impl CoseKey {
             _ => Err(ToSec1OctetStringError::InvalidCoordinateType),
         }
     }
+
+    /// Indicate whether the key may be used with `alg`, i.e. whether it is either restricted to
+    /// `alg` or has no algorithm restriction.
+    pub fn allows_algorithm(&self, alg: &Algorithm) -> bool {
+        self.alg.as_ref().map_or(true, |key_alg| key_alg == alg)
+    }
+
+    /// Indicate whether the parameter with the given `label` holds private key material, for this
+    /// key's type.
+    pub fn is_private_param(&self, label: &Label) -> bool {
+        match label {
+            Label::Int(l) => private_params(&self.kty).contains(l),
+            Label::Text(_) => false,
+        }
+    }
+
+    /// Indicate whether the key holds any private key material.
+    pub fn has_private_params(&self) -> bool {
+        self.params.iter().any(|(l, _)| self.is_private_param(l))
+    }
+
+    /// Remove any parameters that hold private key material, wiping their contents if the
+    /// `zeroize` feature is enabled.
+    ///
+    /// Other fields of the key (including `key_ops`) are left unchanged.
+    pub fn strip_private_params(&mut self) {
+        let private = private_params(&self.kty);
+        self.params.retain_mut(|(label, value)| match label {
+            Label::Int(l) if private.contains(l) => {
+                wipe_value(value);
+                false
+            }
+            _ => true,
+        });
+    }
+
+    /// Return the public key corresponding to an OKP, EC2, RSA or AKP key, holding just the public
+    /// key parameters for the key type (with the first instance of each used) along with the key
+    /// ID, algorithm and base IV.
+    ///
+    /// Key operations are mapped to their public key counterparts (`sign` to `verify`, `decrypt`
+    /// to `encrypt`, `unwrapKey` to `wrapKey`), and other operations are dropped.
+    ///
+    /// If the key holds only private key material, the public key is derived from it where the
+    /// enabled crate features allow:
+    ///   - EC2: the `p256`, `p384`, `p521` or `k256` feature for the key's curve
+    ///   - OKP: the `ed25519` or `x25519` feature for the key's curve
+    ///   - AKP: the `mldsa` feature, for a key with an ML-DSA algorithm
+    pub fn to_public_key(&self) -> Result<CoseKey, PublicKeyError> {
+        let labels = public_params(&self.kty)
+            .ok_or_else(|| PublicKeyError::UnsupportedKeyType(self.kty.clone()))?;
+        let mut derived = None;
+        let mut params = Vec::new();
+        for label in labels {
+            let label = Label::Int(*label);
+            let value = match self.params.iter().find(|(l, _)| *l == label) {
+                Some((_, value)) => value.clone(),
+                None => derived
+                    .get_or_insert_with(|| crate::crypto::derive_public_params(self))
+                    .iter()
+                    .find(|(l, _)| *l == label)
+                    .map(|(_, value)| value.clone())
+                    .ok_or(PublicKeyError::MissingParameter(label.clone()))?,
+            };
+            params.push((label, value));
+        }
+        Ok(CoseKey {
+            kty: self.kty.clone(),
+            key_id: self.key_id.clone(),
+            alg: self.alg.clone(),
+            key_ops: self.key_ops.iter().filter_map(public_key_op).collect(),
+            base_iv: self.base_iv.clone(),
+            params,
+        })
+    }
+
+    /// Return the value of the first parameter with the given `label`, if present.
+    pub(crate) fn param(&self, label: i64) -> Option<&Value> {
+        self.params
+            .iter()
+            .find(|(l, _)| *l == Label::Int(label))
+            .map(|(_, v)| v)
+    }
+
+    /// Build the input to an RFC 9679 COSE Key Thumbprint: a COSE_Key holding just the required
+    /// parameters for the key type, in deterministic (encoded-lexicographic) CBOR encoding.
+    ///
+    /// The required parameters are:
+    ///   - OKP: `kty`, `crv`, `x`
+    ///   - EC2: `kty`, `crv`, `x`, `y`
+    ///   - RSA: `kty`, `n`, `e`
+    ///   - Symmetric: `kty`, `k`
+    ///   - AKP: `kty`, `alg`, `pub`
+    ///
+    /// The first instance of each parameter is used, and parameter values are not checked.
+    pub fn thumbprint_input(&self) -> Result<Vec<u8>, ThumbprintError> {
+        let required: &[i64] = match self.kty {
+            KeyType::Assigned(iana::KeyType::OKP) => &[
+                iana::OkpKeyParameter::Crv as i64,
+                iana::OkpKeyParameter::X as i64,
+            ],
+            KeyType::Assigned(iana::KeyType::EC2) => &[
+                iana::Ec2KeyParameter::Crv as i64,
+                iana::Ec2KeyParameter::X as i64,
+                iana::Ec2KeyParameter::Y as i64,
+            ],
+            KeyType::Assigned(iana::KeyType::RSA) => &[
+                iana::RsaKeyParameter::N as i64,
+                iana::RsaKeyParameter::E as i64,
+            ],
+            KeyType::Assigned(iana::KeyType::Symmetric) => &[iana::SymmetricKeyParameter::K as i64],
+            KeyType::Assigned(iana::KeyType::AKP) => &[iana::AkpKeyParameter::Pub as i64],
+            _ => return Err(ThumbprintError::UnsupportedKeyType(self.kty.clone())),
+        };
+        let mut key = CoseKey {
+            kty: self.kty.clone(),
+            ..Default::default()
+        };
+        if self.kty == KeyType::Assigned(iana::KeyType::AKP) {
+            key.alg = Some(self.alg.clone().ok_or(ThumbprintError::MissingParameter(
+                Label::Int(iana::KeyParameter::Alg as i64),
+            ))?);
+        }
+        for label in required {
+            let value = self
+                .param(*label)
+                .ok_or(ThumbprintError::MissingParameter(Label::Int(*label)))?;
+            key.params.push((Label::Int(*label), value.clone()));
+        }
+        key.canonicalize(CborOrdering::Lexicographic);
+        key.to_vec().map_err(|_e| ThumbprintError::EncodeFailed)
+    }
+
+    /// Calculate the RFC 9679 COSE Key Thumbprint of the key, using the supplied `hash` function.
+    pub fn thumbprint<F>(&self, hash: F) -> Result<Vec<u8>, ThumbprintError>
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        Ok(hash(&self.thumbprint_input()?))
+    }
+
+    /// Calculate the RFC 9679 COSE Key Thumbprint URI of the key, using the supplied `hash`
+    /// function. The `hash_name` should be the name of the hash function from the IANA "Named
+    /// Information Hash Algorithm" registry, e.g. `"sha-256"`.
+    pub fn thumbprint_uri<F>(&self, hash_name: &str, hash: F) -> Result<String, ThumbprintError>
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        let thumbprint = self.thumbprint(hash)?;
+        Ok(format!(
+            "urn:ietf:params:oauth:ckt:{hash_name}:{}",
+            Base64::UrlSafeNoPad.encode(&thumbprint)
+        ))
+    }
+
+    /// Calculate the RFC 9679 COSE Key Thumbprint of the key using SHA-256.
+    #[cfg(feature = "sha2")]
+    pub fn thumbprint_sha256(&self) -> Result<Vec<u8>, ThumbprintError> {
+        self.thumbprint(crate::crypto::sha256)
+    }
+
+    /// Calculate the RFC 9679 COSE Key Thumbprint URI of the key using SHA-256.
+    #[cfg(feature = "sha2")]
+    pub fn thumbprint_uri_sha256(&self) -> Result<String, ThumbprintError> {
+        self.thumbprint_uri("sha-256", crate::crypto::sha256)
+    }
+
+    /// Check that an RSA key holds one of the combinations of parameters allowed by RFC 8230
+    /// section 4:
+    ///   - a public key holds just `n` and `e`
+    ///   - a two-prime private key additionally holds `d`, `p`, `q`, `dP`, `dQ` and `qInv`
+    ///   - a multi-prime private key additionally holds a non-empty `other` array, each entry of
+    ///     which holds `r_i`, `d_i` and `t_i`.
+    ///
+    /// All integer values are also checked to be encoded with the minimum number of octets.
+    pub fn check_rsa_key(&self) -> Result<(), RsaKeyError> {
+        if self.kty != KeyType::Assigned(iana::KeyType::RSA) {
+            return Err(RsaKeyError::NotRsaKey);
+        }
+        for param in [iana::RsaKeyParameter::N, iana::RsaKeyParameter::E] {
+            let value = self
+                .param(param as i64)
+                .ok_or(RsaKeyError::MissingParameter(param))?;
+            check_rsa_integer(param, value)?;
+        }
+        for param in RSA_OTHER_PRIME_PARAMS {
+            if self.param(param as i64).is_some() {
+                return Err(RsaKeyError::UnexpectedParameter(param));
+            }
+        }
+
+        let present: Vec<_> = RSA_PRIVATE_PARAMS
+            .iter()
+            .filter(|p| self.param(**p as i64).is_some())
+            .collect();
+        let other = self.param(iana::RsaKeyParameter::Other as i64);
+        if present.is_empty() {
+            // Public key.
+            return match other {
+                Some(_) => Err(RsaKeyError::UnexpectedParameter(
+                    iana::RsaKeyParameter::Other,
+                )),
+                None => Ok(()),
+            };
+        }
+
+        // Private key, so all of the private parameters are needed.
+        for param in RSA_PRIVATE_PARAMS {
+            let value = self
+                .param(param as i64)
+                .ok_or(RsaKeyError::MissingParameter(param))?;
+            check_rsa_integer(param, value)?;
+        }
+        if let Some(other) = other {
+            let infos = match other {
+                Value::Array(a) if !a.is_empty() => a,
+                _ => {
+                    return Err(RsaKeyError::InvalidParameterType(
+                        iana::RsaKeyParameter::Other,
+                    ))
+                }
+            };
+            for info in infos {
+                RsaOtherPrimeInfo::from_value(info)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl RsaOtherPrimeInfo {
+    /// Parse and check an entry in the `other` array of a multi-prime RSA key.
+    pub(crate) fn from_value(value: &Value) -> Result<Self, RsaKeyError> {
+        let m = value.as_map().ok_or(RsaKeyError::InvalidParameterType(
+            iana::RsaKeyParameter::Other,
+        ))?;
+        let mut info = Self::default();
+        for (l, v) in m {
+            let param = l
+                .as_integer()
+                .and_then(|i| i64::try_from(i).ok())
+                .and_then(iana::RsaKeyParameter::from_i64)
+                .filter(|p| RSA_OTHER_PRIME_PARAMS.contains(p))
+                .ok_or(RsaKeyError::UnexpectedParameter(
+                    iana::RsaKeyParameter::Other,
+                ))?;
+            check_rsa_integer(param, v)?;
+            let data = v.as_bytes().cloned().unwrap_or_default();
+            let field = match param {
+                iana::RsaKeyParameter::RI => &mut info.r,
+                iana::RsaKeyParameter::DI => &mut info.d,
+                _ => &mut info.t,
+            };
+            if !field.is_empty() {
+                return Err(RsaKeyError::UnexpectedParameter(param));
+            }
+            *field = data;
+        }
+        for (param, field) in [
+            (iana::RsaKeyParameter::RI, &info.r),
+            (iana::RsaKeyParameter::DI, &info.d),
+            (iana::RsaKeyParameter::TI, &info.t),
+        ] {
+            if field.is_empty() {
+                return Err(RsaKeyError::MissingParameter(param));
+            }
+        }
+        Ok(info)
+    }
+
+    /// Convert to the map form used in the `other` array of a multi-prime RSA key.
+    fn to_value(&self) -> Value {
+        Value::Map(vec![
+            (
+                Value::from(iana::RsaKeyParameter::RI as i64),
+                Value::Bytes(self.r.clone()),
+            ),
+            (
+                Value::from(iana::RsaKeyParameter::DI as i64),
+                Value::Bytes(self.d.clone()),
+            ),
+            (
+                Value::from(iana::RsaKeyParameter::TI as i64),
+                Value::Bytes(self.t.clone()),
+            ),
+        ])
+    }
+}
+
+/// Wipes the contents of the parameters that hold private key material.
+///
+/// Unlike the RSA private key views, a [`CoseKey`] is **not** wiped on drop, even with the
+/// `zeroize` feature: implementing [`Drop`] would break existing code that builds keys with struct
+/// update syntax (`CoseKey { kty, ..Default::default() }`) or moves fields out of a key.  Wrap a
+/// key in [`zeroize::Zeroizing`] to have its private key material wiped when it is dropped.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for CoseKey {
+    fn zeroize(&mut self) {
+        let private = private_params(&self.kty);
+        for (label, value) in self.params.iter_mut() {
+            if matches!(label, Label::Int(l) if private.contains(l)) {
+                wipe_value(value);
+            }
+        }
+    }
 }
 
 impl crate::CborSerializable for CoseKey {}
@@ -395,6 +1050,54 @@ impl CoseKeyBuilder {
         builder
     }
 
+    /// Constructor for an RSA public key specified by modulus `n` and public exponent `e`, both as
+    /// unsigned big-endian integers.
+    pub fn new_rsa_pub_key(n: Vec<u8>, e: Vec<u8>) -> Self {
+        Self(CoseKey {
+            kty: KeyType::Assigned(iana::KeyType::RSA),
+            params: vec![
+                (Label::Int(iana::RsaKeyParameter::N as i64), Value::Bytes(n)),
+                (Label::Int(iana::RsaKeyParameter::E as i64), Value::Bytes(e)),
+            ],
+            ..Default::default()
+        })
+    }
+
+    /// Constructor for an RSA private key specified by modulus `n`, public exponent `e` and the
+    /// `private` components.  If `private.other` is non-empty, the key is a multi-prime key.
+    ///
+    /// Use [`CoseKey::check_rsa_key`] to check the resulting key.
+    pub fn new_rsa_priv_key(n: Vec<u8>, e: Vec<u8>, mut private: RsaPrivateComponents) -> Self {
+        let mut builder = Self::new_rsa_pub_key(n, e);
+        for (param, value) in [
+            (iana::RsaKeyParameter::D, &mut private.d),
+            (iana::RsaKeyParameter::P, &mut private.p),
+            (iana::RsaKeyParameter::Q, &mut private.q),
+            (iana::RsaKeyParameter::DP, &mut private.dp),
+            (iana::RsaKeyParameter::DQ, &mut private.dq),
+            (iana::RsaKeyParameter::QInv, &mut private.qinv),
+        ] {
+            builder.0.params.push((
+                Label::Int(param as i64),
+                Value::Bytes(core::mem::take(value)),
+            ));
+        }
+        if !private.other.is_empty() {
+            builder.0.params.push((
+                Label::Int(iana::RsaKeyParameter::Other as i64),
+                Value::Array(
+                    private
+                        .other
+                        .iter()
+                        .map(RsaOtherPrimeInfo::to_value)
+                        .collect(),
+                ),
+            ));
+        }
+        // With the `zeroize` feature, dropping `private` wipes what is left of it.
+        builder
+    }
+
     /// Constructor for an ML-DSA public key.
     pub fn new_mldsa_pub_key(variant: MlDsaVariant, k: Vec<u8>) -> Self {
         Self(CoseKey {
@@ -1196,3 +1196,866 @@ fn test_mldsa_variant_convert_fail() {
     let result = MlDsaVariant::try_from(iana::Algorithm::A256GCM);
     assert!(matches!(result, Err(CoseError::OutOfRangeIntegerValue)));
 }
+
+#[test]
+fn test_rsa_key_builder() {
+    let key = CoseKeyBuilder::new_rsa_pub_key(vec![0xab, 0xcd], vec![0x01, 0x00, 0x01]).build();
+    assert_eq!(key.kty, KeyType::Assigned(iana::KeyType::RSA));
+    assert_eq!(
+        hex::encode(key.clone().to_vec().unwrap()),
+        concat!(
+            "a3", // 3-map
+            "01", "03", // 1 (kty) => RSA
+            "20", "42", "abcd", // -1 (n) => 2-bstr
+            "21", "43", "010001", // -2 (e) => 3-bstr
+        )
+    );
+    assert_eq!(key.check_rsa_key(), Ok(()));
+
+    let mut private = RsaPrivateComponents {
+        d: vec![0x01],
+        p: vec![0x02],
+        q: vec![0x03],
+        dp: vec![0x04],
+        dq: vec![0x05],
+        qinv: vec![0x06],
+        other: vec![],
+    };
+    let key = CoseKeyBuilder::new_rsa_priv_key(vec![0xab], vec![0x03], private.clone()).build();
+    assert_eq!(
+        hex::encode(key.clone().to_vec().unwrap()),
+        concat!(
+            "a9", // 9-map
+            "01", "03", // 1 (kty) => RSA
+            "20", "41", "ab", // -1 (n) => 1-bstr
+            "21", "41", "03", // -2 (e) => 1-bstr
+            "22", "41", "01", // -3 (d) => 1-bstr
+            "23", "41", "02", // -4 (p) => 1-bstr
+            "24", "41", "03", // -5 (q) => 1-bstr
+            "25", "41", "04", // -6 (dP) => 1-bstr
+            "26", "41", "05", // -7 (dQ) => 1-bstr
+            "27", "41", "06", // -8 (qInv) => 1-bstr
+        )
+    );
+    assert_eq!(key.check_rsa_key(), Ok(()));
+
+    private.other = vec![RsaOtherPrimeInfo {
+        r: vec![0x07],
+        d: vec![0x08],
+        t: vec![0x09],
+    }];
+    let key = CoseKeyBuilder::new_rsa_priv_key(vec![0xab], vec![0x03], private).build();
+    let data = key.clone().to_vec().unwrap();
+    assert!(hex::encode(&data).ends_with(concat!(
+        "28", // -9 (other) =>
+        "81", // 1-array
+        "a3", // 3-map
+        "29", "41", "07", // -10 (r_i) => 1-bstr
+        "2a", "41", "08", // -11 (d_i) => 1-bstr
+        "2b", "41", "09", // -12 (t_i) => 1-bstr
+    )));
+    assert_eq!(key.check_rsa_key(), Ok(()));
+    assert_eq!(CoseKey::from_slice(&data).unwrap(), key);
+}
+
+#[test]
+fn test_check_rsa_key_fail() {
+    let other_info = |entries: Vec<(i64, Value)>| {
+        Value::Array(vec![Value::Map(
+            entries
+                .into_iter()
+                .map(|(l, v)| (Value::from(l), v))
+                .collect(),
+        )])
+    };
+    let full_other = || {
+        other_info(vec![
+            (-10, Value::Bytes(vec![7])),
+            (-11, Value::Bytes(vec![8])),
+            (-12, Value::Bytes(vec![9])),
+        ])
+    };
+    let tests = [
+        (
+            vec![],
+            RsaKeyError::MissingParameter(iana::RsaKeyParameter::N),
+        ),
+        (
+            vec![(-1, Value::Bytes(vec![1]))],
+            RsaKeyError::MissingParameter(iana::RsaKeyParameter::E),
+        ),
+        (
+            vec![
+                (-1, Value::Bytes(vec![1])),
+                (-2, Value::Text("a".to_owned())),
+            ],
+            RsaKeyError::InvalidParameterType(iana::RsaKeyParameter::E),
+        ),
+        (
+            vec![(-1, Value::Bytes(vec![0, 1])), (-2, Value::Bytes(vec![3]))],
+            RsaKeyError::NonMinimalEncoding(iana::RsaKeyParameter::N),
+        ),
+        (
+            vec![(-1, Value::Bytes(vec![])), (-2, Value::Bytes(vec![3]))],
+            RsaKeyError::NonMinimalEncoding(iana::RsaKeyParameter::N),
+        ),
+        (
+            vec![
+                (-1, Value::Bytes(vec![1])),
+                (-2, Value::Bytes(vec![3])),
+                (-3, Value::Bytes(vec![1])),
+            ],
+            RsaKeyError::MissingParameter(iana::RsaKeyParameter::P),
+        ),
+        (
+            vec![
+                (-1, Value::Bytes(vec![1])),
+                (-2, Value::Bytes(vec![3])),
+                (-8, Value::Bytes(vec![1])),
+            ],
+            RsaKeyError::MissingParameter(iana::RsaKeyParameter::D),
+        ),
+        (
+            vec![
+                (-1, Value::Bytes(vec![1])),
+                (-2, Value::Bytes(vec![3])),
+                (-9, full_other()),
+            ],
+            RsaKeyError::UnexpectedParameter(iana::RsaKeyParameter::Other),
+        ),
+        (
+            vec![
+                (-1, Value::Bytes(vec![1])),
+                (-2, Value::Bytes(vec![3])),
+                (-10, Value::Bytes(vec![1])),
+            ],
+            RsaKeyError::UnexpectedParameter(iana::RsaKeyParameter::RI),
+        ),
+    ];
+    for (params, want) in tests {
+        let key = CoseKey {
+            kty: KeyType::Assigned(iana::KeyType::RSA),
+            params: params
+                .into_iter()
+                .map(|(l, v)| (Label::Int(l), v))
+                .collect(),
+            ..Default::default()
+        };
+        assert_eq!(key.check_rsa_key(), Err(want), "for {key:?}");
+    }
+
+    let private_key = |other: Value| CoseKey {
+        kty: KeyType::Assigned(iana::KeyType::RSA),
+        params: (-8..=-1)
+            .rev()
+            .map(|l| (Label::Int(l), Value::Bytes(vec![1])))
+            .chain(core::iter::once((Label::Int(-9), other)))
+            .collect(),
+        ..Default::default()
+    };
+    assert_eq!(private_key(full_other()).check_rsa_key(), Ok(()));
+    let tests = [
+        (
+            Value::Array(vec![]),
+            RsaKeyError::InvalidParameterType(iana::RsaKeyParameter::Other),
+        ),
+        (
+            Value::Array(vec![Value::Null]),
+            RsaKeyError::InvalidParameterType(iana::RsaKeyParameter::Other),
+        ),
+        (
+            other_info(vec![
+                (-10, Value::Bytes(vec![7])),
+                (-11, Value::Bytes(vec![8])),
+            ]),
+            RsaKeyError::MissingParameter(iana::RsaKeyParameter::TI),
+        ),
+        (
+            other_info(vec![
+                (-10, Value::Bytes(vec![7])),
+                (-11, Value::Bytes(vec![8])),
+                (-12, Value::Bytes(vec![9])),
+                (-1, Value::Bytes(vec![9])),
+            ]),
+            RsaKeyError::UnexpectedParameter(iana::RsaKeyParameter::Other),
+        ),
+        (
+            other_info(vec![
+                (-10, Value::Bytes(vec![7])),
+                (-10, Value::Bytes(vec![8])),
+                (-12, Value::Bytes(vec![9])),
+            ]),
+            RsaKeyError::UnexpectedParameter(iana::RsaKeyParameter::RI),
+        ),
+        (
+            other_info(vec![
+                (-10, Value::Bytes(vec![7])),
+                (-11, Value::Bool(true)),
+                (-12, Value::Bytes(vec![9])),
+            ]),
+            RsaKeyError::InvalidParameterType(iana::RsaKeyParameter::DI),
+        ),
+    ];
+    for (other, want) in tests {
+        let key = private_key(other);
+        assert_eq!(key.check_rsa_key(), Err(want), "for {key:?}");
+    }
+
+    let key = CoseKeyBuilder::new_symmetric_key(vec![1]).build();
+    assert_eq!(key.check_rsa_key(), Err(RsaKeyError::NotRsaKey));
+    assert_eq!(RsaKeyError::NotRsaKey.to_string(), "not an RSA key");
+}
+
+#[test]
+fn test_thumbprint() {
+    // Example from RFC 9679 section 6, with the parameters out of order and with additional
+    // parameters that are not included in the thumbprint.
+    let x =
+        hex::decode("65eda5a12577c2bae829437fe338701a10aaa375e1bb5b5de108de439c08551d").unwrap();
+    let y =
+        hex::decode("1e52ed75701163f7f9e40ddf9f341b3dc9ba860af7e0ca7ca7e9eecd0084d19c").unwrap();
+    let key = CoseKey {
+        kty: KeyType::Assigned(iana::KeyType::EC2),
+        key_id: b"meriadoc.brandybuck@buckland.example".to_vec(),
+        alg: Some(Algorithm::Assigned(iana::Algorithm::ES256)),
+        params: vec![
+            (Label::Int(iana::Ec2KeyParameter::Y as i64), Value::Bytes(y)),
+            (
+                Label::Int(iana::Ec2KeyParameter::D as i64),
+                Value::Bytes(vec![1, 2, 3]),
+            ),
+            (Label::Int(iana::Ec2KeyParameter::X as i64), Value::Bytes(x)),
+            (
+                Label::Int(iana::Ec2KeyParameter::Crv as i64),
+                Value::from(iana::EllipticCurve::P_256 as u64),
+            ),
+        ],
+        ..Default::default()
+    };
+    let want = concat!(
+        "a4", // 4-map
+        "01",
+        "02", // 1 (kty) => EC2
+        "20",
+        "01", // -1 (crv) => P-256
+        "21",
+        "5820",
+        "65eda5a12577c2bae829437fe338701a10aaa375e1bb5b5de108de439c08551d", // -2 (x)
+        "22",
+        "5820",
+        "1e52ed75701163f7f9e40ddf9f341b3dc9ba860af7e0ca7ca7e9eecd0084d19c", // -3 (y)
+    );
+    assert_eq!(hex::encode(key.thumbprint_input().unwrap()), want);
+    assert_eq!(
+        hex::encode(key.thumbprint(|data| data.to_vec()).unwrap()),
+        want
+    );
+    assert_eq!(
+        key.thumbprint_uri("identity", |_data| vec![0xfb, 0xff])
+            .unwrap(),
+        "urn:ietf:params:oauth:ckt:identity:-_8"
+    );
+
+    #[cfg(feature = "sha2")]
+    {
+        assert_eq!(
+            hex::encode(key.thumbprint_sha256().unwrap()),
+            "496bd8afadf307e5b08c64b0421bf9dc01528a344a43bda88fadd1669da253ec"
+        );
+        assert_eq!(
+            key.thumbprint_uri_sha256().unwrap(),
+            "urn:ietf:params:oauth:ckt:sha-256:SWvYr63zB-WwjGSwQhv53AFSijRKQ72oj63RZp2iU-w"
+        );
+    }
+
+    let tests = vec![
+        (
+            CoseKeyBuilder::new_okp_key()
+                .param(iana::OkpKeyParameter::X as i64, Value::Bytes(vec![1, 2]))
+                .param(iana::OkpKeyParameter::D as i64, Value::Bytes(vec![3, 4]))
+                .param(iana::OkpKeyParameter::Crv as i64, Value::from(6))
+                .build(),
+            concat!(
+                "a3", // 3-map
+                "01", "01", // 1 (kty) => OKP
+                "20", "06", // -1 (crv) => Ed25519
+                "21", "42", "0102", // -2 (x)
+            ),
+        ),
+        (
+            CoseKeyBuilder::new_ec2_pub_key_y_sign(iana::EllipticCurve::P_256, vec![1, 2], true)
+                .build(),
+            concat!(
+                "a4", // 4-map
+                "01", "02", // 1 (kty) => EC2
+                "20", "01", // -1 (crv) => P-256
+                "21", "42", "0102", // -2 (x)
+                "22", "f5", // -3 (y) => true
+            ),
+        ),
+        (
+            CoseKeyBuilder::new_rsa_priv_key(
+                vec![1, 2],
+                vec![1, 0, 1],
+                RsaPrivateComponents {
+                    d: vec![3],
+                    p: vec![4],
+                    q: vec![5],
+                    dp: vec![6],
+                    dq: vec![7],
+                    qinv: vec![8],
+                    other: vec![],
+                },
+            )
+            .build(),
+            concat!(
+                "a3", // 3-map
+                "01", "03", // 1 (kty) => RSA
+                "20", "42", "0102", // -1 (n)
+                "21", "43", "010001", // -2 (e)
+            ),
+        ),
+        (
+            CoseKeyBuilder::new_symmetric_key(vec![1, 2])
+                .key_id(vec![3])
+                .build(),
+            concat!(
+                "a2", // 2-map
+                "01", "04", // 1 (kty) => Symmetric
+                "20", "42", "0102", // -1 (k)
+            ),
+        ),
+        (
+            CoseKeyBuilder::new_mldsa_pub_key(MlDsaVariant::MlDsa44, vec![1, 2])
+                .param(iana::AkpKeyParameter::Priv as i64, Value::Bytes(vec![3]))
+                .build(),
+            concat!(
+                "a3", // 3-map
+                "01", "07", // 1 (kty) => AKP
+                "03", "382f", // 3 (alg) => ML-DSA-44 (-48)
+                "20", "42", "0102", // -1 (pub)
+            ),
+        ),
+    ];
+    for (key, want) in tests {
+        assert_eq!(
+            hex::encode(key.thumbprint_input().unwrap()),
+            want,
+            "for {key:?}"
+        );
+    }
+}
+
+#[test]
+fn test_thumbprint_fail() {
+    let tests = vec![
+        (
+            CoseKeyBuilder::new_okp_key()
+                .param(iana::OkpKeyParameter::Crv as i64, Value::from(6))
+                .build(),
+            ThumbprintError::MissingParameter(Label::Int(iana::OkpKeyParameter::X as i64)),
+        ),
+        (
+            CoseKey {
+                kty: KeyType::Assigned(iana::KeyType::RSA),
+                params: vec![(
+                    Label::Int(iana::RsaKeyParameter::N as i64),
+                    Value::Bytes(vec![1, 2]),
+                )],
+                ..Default::default()
+            },
+            ThumbprintError::MissingParameter(Label::Int(iana::RsaKeyParameter::E as i64)),
+        ),
+        (
+            CoseKey {
+                kty: KeyType::Assigned(iana::KeyType::AKP),
+                params: vec![(
+                    Label::Int(iana::AkpKeyParameter::Pub as i64),
+                    Value::Bytes(vec![1]),
+                )],
+                ..Default::default()
+            },
+            ThumbprintError::MissingParameter(Label::Int(iana::KeyParameter::Alg as i64)),
+        ),
+        (
+            CoseKey {
+                kty: KeyType::Assigned(iana::KeyType::HSS_LMS),
+                ..Default::default()
+            },
+            ThumbprintError::UnsupportedKeyType(KeyType::Assigned(iana::KeyType::HSS_LMS)),
+        ),
+        (
+            CoseKey {
+                kty: KeyType::Text("unknown".to_owned()),
+                ..Default::default()
+            },
+            ThumbprintError::UnsupportedKeyType(KeyType::Text("unknown".to_owned())),
+        ),
+    ];
+    for (key, want) in tests {
+        assert_eq!(key.thumbprint_input(), Err(want.clone()), "for {key:?}");
+        assert_eq!(
+            key.thumbprint(|data| data.to_vec()),
+            Err(want),
+            "for {key:?}"
+        );
+    }
+    assert_eq!(
+        ThumbprintError::MissingParameter(Label::Int(-1)).to_string(),
+        "missing required key parameter Int(-1)"
+    );
+}
+
+fn key_set() -> CoseKeySet {
+    CoseKeySet(vec![
+        CoseKeyBuilder::new_symmetric_key(vec![1])
+            .key_id(b"sym".to_vec())
+            .algorithm(iana::Algorithm::HMAC_256_256)
+            .build(),
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_256, vec![2], vec![3])
+            .key_id(b"ec".to_vec())
+            .algorithm(iana::Algorithm::ES256)
+            .add_key_op(iana::KeyOperation::Verify)
+            .build(),
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_256, vec![4], vec![5])
+            .key_id(b"ec-any".to_vec())
+            .build(),
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_384, vec![6], vec![7])
+            .algorithm(iana::Algorithm::ES384)
+            .add_key_op(iana::KeyOperation::Sign)
+            .build(),
+    ])
+}
+
+#[test]
+fn test_key_set_query() {
+    let keys = key_set();
+    let kids = |found: Vec<&CoseKey>| -> Vec<Vec<u8>> {
+        found.into_iter().map(|key| key.key_id.clone()).collect()
+    };
+
+    assert_eq!(keys.find_by_key_id(b"ec"), Some(&keys.0[1]));
+    assert_eq!(keys.find_by_key_id(b"sym"), Some(&keys.0[0]));
+    assert_eq!(keys.find_by_key_id(b"missing"), None);
+
+    let ec2 = KeyType::Assigned(iana::KeyType::EC2);
+    assert_eq!(
+        kids(keys.filter_by_key_type(&ec2).collect()),
+        vec![b"ec".to_vec(), b"ec-any".to_vec(), vec![]]
+    );
+    let okp = KeyType::Assigned(iana::KeyType::OKP);
+    assert_eq!(keys.filter_by_key_type(&okp).count(), 0);
+
+    let es256 = Algorithm::Assigned(iana::Algorithm::ES256);
+    assert_eq!(
+        kids(keys.filter_by_algorithm(&es256).collect()),
+        vec![b"ec".to_vec(), b"ec-any".to_vec()]
+    );
+    let private = Algorithm::PrivateUse(-70_000);
+    assert_eq!(
+        kids(keys.filter_by_algorithm(&private).collect()),
+        vec![b"ec-any".to_vec()]
+    );
+
+    let verify = KeyOperation::Assigned(iana::KeyOperation::Verify);
+    assert_eq!(
+        kids(keys.filter_by_key_op(&verify).collect()),
+        vec![b"sym".to_vec(), b"ec".to_vec(), b"ec-any".to_vec()]
+    );
+
+    assert!(keys.0[2].allows_algorithm(&es256));
+    assert!(keys.0[1].allows_algorithm(&es256));
+    assert!(!keys.0[0].allows_algorithm(&es256));
+}
+
+#[test]
+fn test_key_set_resolve() {
+    let keys = key_set();
+    let kids = |found: Vec<&CoseKey>| -> Vec<Vec<u8>> {
+        found.into_iter().map(|key| key.key_id.clone()).collect()
+    };
+    let sign1 = |protected: crate::Header, unprotected: crate::Header| {
+        crate::CoseSign1Builder::new()
+            .protected(protected)
+            .unprotected(unprotected)
+            .build()
+    };
+    let empty = || crate::HeaderBuilder::new().build();
+
+    // Key ID in protected or unprotected header.
+    let msg = sign1(
+        crate::HeaderBuilder::new().key_id(b"ec".to_vec()).build(),
+        empty(),
+    );
+    assert_eq!(kids(keys.resolve(&msg)), vec![b"ec".to_vec()]);
+    let msg = sign1(
+        empty(),
+        crate::HeaderBuilder::new().key_id(b"ec".to_vec()).build(),
+    );
+    assert_eq!(kids(keys.resolve(&msg)), vec![b"ec".to_vec()]);
+
+    // Protected key ID takes precedence.
+    let msg = sign1(
+        crate::HeaderBuilder::new().key_id(b"sym".to_vec()).build(),
+        crate::HeaderBuilder::new().key_id(b"ec".to_vec()).build(),
+    );
+    assert_eq!(kids(keys.resolve(&msg)), vec![b"sym".to_vec()]);
+
+    // Algorithm restricts the candidates.
+    let msg = sign1(
+        crate::HeaderBuilder::new()
+            .algorithm(iana::Algorithm::ES384)
+            .build(),
+        empty(),
+    );
+    assert_eq!(kids(keys.resolve(&msg)), vec![b"ec-any".to_vec(), vec![]]);
+    let msg = sign1(
+        crate::HeaderBuilder::new()
+            .algorithm(iana::Algorithm::ES384)
+            .key_id(b"ec".to_vec())
+            .build(),
+        empty(),
+    );
+    assert!(keys.resolve(&msg).is_empty());
+    let msg = sign1(
+        empty(),
+        crate::HeaderBuilder::new()
+            .algorithm(iana::Algorithm::HMAC_256_256)
+            .build(),
+    );
+    assert_eq!(
+        kids(keys.resolve(&msg)),
+        vec![b"sym".to_vec(), b"ec-any".to_vec()]
+    );
+
+    // No key ID or algorithm gives all keys.
+    let msg = sign1(empty(), empty());
+    assert_eq!(keys.resolve(&msg).len(), 4);
+
+    // Works for other message types.
+    let sig = crate::CoseSignatureBuilder::new()
+        .unprotected(crate::HeaderBuilder::new().key_id(b"ec".to_vec()).build())
+        .build();
+    assert_eq!(kids(keys.resolve(&sig)), vec![b"ec".to_vec()]);
+    let msg = crate::CoseMac0Builder::new()
+        .protected(crate::HeaderBuilder::new().key_id(b"sym".to_vec()).build())
+        .build();
+    assert_eq!(kids(keys.resolve(&msg)), vec![b"sym".to_vec()]);
+    assert_eq!(msg.protected_header().key_id, b"sym".to_vec());
+    assert!(msg.unprotected_header().key_id.is_empty());
+}
+
+#[test]
+fn test_key_set_unique_key_ids() {
+    let mut keys = key_set();
+    assert!(keys.check_unique_key_ids().is_ok());
+
+    // Multiple keys without a key ID are fine.
+    keys.0
+        .push(CoseKeyBuilder::new_symmetric_key(vec![8]).build());
+    assert!(keys.check_unique_key_ids().is_ok());
+
+    keys.0.push(
+        CoseKeyBuilder::new_symmetric_key(vec![9])
+            .key_id(b"ec".to_vec())
+            .build(),
+    );
+    assert_eq!(
+        keys.check_unique_key_ids(),
+        Err(DuplicateKeyIdError(b"ec".to_vec()))
+    );
+    assert_eq!(
+        DuplicateKeyIdError(vec![1, 2]).to_string(),
+        "duplicate key ID [1, 2]"
+    );
+}
+
+#[test]
+fn test_strip_private_params() {
+    let rsa = CoseKeyBuilder::new_rsa_priv_key(
+        vec![0x0b],
+        vec![0x03],
+        RsaPrivateComponents {
+            d: vec![0x07],
+            p: vec![0x05],
+            q: vec![0x03],
+            dp: vec![0x03],
+            dq: vec![0x01],
+            qinv: vec![0x02],
+            other: vec![RsaOtherPrimeInfo {
+                r: vec![0x07],
+                d: vec![0x05],
+                t: vec![0x04],
+            }],
+        },
+    )
+    .build();
+    let tests = vec![
+        (
+            CoseKeyBuilder::new_ec2_priv_key(iana::EllipticCurve::P_256, vec![1], vec![2], vec![3])
+                .add_key_op(iana::KeyOperation::Sign)
+                .build(),
+            CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_256, vec![1], vec![2])
+                .add_key_op(iana::KeyOperation::Sign)
+                .build(),
+        ),
+        (
+            CoseKeyBuilder::new_okp_key()
+                .param(iana::OkpKeyParameter::X as i64, Value::Bytes(vec![1]))
+                .param(iana::OkpKeyParameter::D as i64, Value::Bytes(vec![2]))
+                .build(),
+            CoseKeyBuilder::new_okp_key()
+                .param(iana::OkpKeyParameter::X as i64, Value::Bytes(vec![1]))
+                .build(),
+        ),
+        (
+            CoseKeyBuilder::new_symmetric_key(vec![1, 2, 3])
+                .key_id(b"sym".to_vec())
+                .build(),
+            CoseKeyBuilder::new()
+                .key_type(iana::KeyType::Symmetric)
+                .key_id(b"sym".to_vec())
+                .build(),
+        ),
+        (
+            CoseKeyBuilder::new_mldsa_pub_key(MlDsaVariant::MlDsa44, vec![1])
+                .param(iana::AkpKeyParameter::Priv as i64, Value::Bytes(vec![2]))
+                .build(),
+            CoseKeyBuilder::new_mldsa_pub_key(MlDsaVariant::MlDsa44, vec![1]).build(),
+        ),
+        (
+            rsa,
+            CoseKeyBuilder::new_rsa_pub_key(vec![0x0b], vec![0x03]).build(),
+        ),
+    ];
+    for (i, (key, want)) in tests.into_iter().enumerate() {
+        assert!(key.has_private_params(), "case {}", i);
+        assert!(!want.has_private_params(), "case {}", i);
+        let mut got = key.clone();
+        got.strip_private_params();
+        assert_eq!(got, want, "case {}", i);
+    }
+
+    // Labels are interpreted according to the key type.
+    let key = CoseKeyBuilder::new_okp_key().build();
+    assert!(key.is_private_param(&Label::Int(iana::OkpKeyParameter::D as i64)));
+    assert!(!key.is_private_param(&Label::Int(iana::OkpKeyParameter::X as i64)));
+    assert!(!key.is_private_param(&Label::Int(iana::RsaKeyParameter::DP as i64)));
+    assert!(!key.is_private_param(&Label::Text("d".to_owned())));
+    let key = CoseKeyBuilder::new_rsa_pub_key(vec![1], vec![1]).build();
+    assert!(key.is_private_param(&Label::Int(iana::RsaKeyParameter::P as i64)));
+
+    // Unknown key types have no private parameters.
+    let mut key = CoseKeyBuilder::new()
+        .key_type(iana::KeyType::HSS_LMS)
+        .param(-4, Value::Bytes(vec![1]))
+        .build();
+    assert!(!key.has_private_params());
+    key.strip_private_params();
+    assert_eq!(key.params.len(), 1);
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use zeroize::Zeroize;
+    let mut key =
+        CoseKeyBuilder::new_ec2_priv_key(iana::EllipticCurve::P_256, vec![1], vec![2], vec![3, 4])
+            .build();
+    key.zeroize();
+    assert_eq!(
+        key,
+        CoseKeyBuilder::new_ec2_priv_key(iana::EllipticCurve::P_256, vec![1], vec![2], vec![])
+            .build()
+    );
+
+    let mut key = CoseKeyBuilder::new_symmetric_key(vec![1, 2]).build();
+    key.zeroize();
+    assert_eq!(key, CoseKeyBuilder::new_symmetric_key(vec![]).build());
+
+    let mut private = RsaPrivateComponents {
+        d: vec![1],
+        p: vec![2],
+        q: vec![3],
+        dp: vec![4],
+        dq: vec![5],
+        qinv: vec![6],
+        other: vec![RsaOtherPrimeInfo {
+            r: vec![7],
+            d: vec![8],
+            t: vec![9],
+        }],
+    };
+    private.zeroize();
+    assert!(private.d.is_empty());
+    assert!(private.qinv.is_empty());
+    assert!(private.other.is_empty());
+}
+
+/// Allocator that checks freed memory for a secret that was left behind, to test wiping on drop.
+#[cfg(feature = "zeroize")]
+mod wipe_check {
+    extern crate std;
+    use core::{
+        alloc::{GlobalAlloc, Layout},
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// Secret value that is looked for in freed memory.
+    pub(super) const SECRET: &[u8] = b"coset wipe check";
+
+    /// Number of freed blocks that still held [`SECRET`].
+    static UNWIPED: AtomicUsize = AtomicUsize::new(0);
+
+    struct CheckingAlloc;
+
+    unsafe impl GlobalAlloc for CheckingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let block = core::slice::from_raw_parts(ptr, layout.size());
+            if block.windows(SECRET.len()).any(|w| w == SECRET) {
+                UNWIPED.fetch_add(1, Ordering::SeqCst);
+            }
+            std::alloc::System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOC: CheckingAlloc = CheckingAlloc;
+
+    /// Run `f`, returning the number of freed blocks that still held [`SECRET`].
+    pub(super) fn unwiped<F: FnOnce()>(f: F) -> usize {
+        let before = UNWIPED.load(Ordering::SeqCst);
+        f();
+        UNWIPED.load(Ordering::SeqCst) - before
+    }
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_wipe_on_drop() {
+    use core::hint::black_box;
+    use wipe_check::{unwiped, SECRET};
+
+    // A secret in memory that is freed without wiping is noticed.
+    assert_eq!(unwiped(|| drop(black_box(SECRET.to_vec()))), 1);
+
+    let other = || RsaOtherPrimeInfo {
+        r: SECRET.to_vec(),
+        d: SECRET.to_vec(),
+        t: SECRET.to_vec(),
+    };
+    assert_eq!(unwiped(|| drop(black_box(other()))), 0);
+    let private = || RsaPrivateComponents {
+        d: SECRET.to_vec(),
+        p: SECRET.to_vec(),
+        q: SECRET.to_vec(),
+        dp: SECRET.to_vec(),
+        dq: SECRET.to_vec(),
+        qinv: SECRET.to_vec(),
+        other: vec![other(), other()],
+    };
+    assert_eq!(unwiped(|| drop(black_box(private()))), 0);
+    let key = || {
+        CoseKeyBuilder::new_rsa_priv_key(vec![0xab], vec![0x03], private())
+            .key_id(vec![1])
+            .build()
+    };
+    // A plain `CoseKey` is not wiped on drop, leaving the 6 private parameters and the 2 * 3
+    // other prime values behind.
+    assert_eq!(unwiped(|| drop(black_box(key()))), 12);
+
+    // A `CoseKey` is only wiped on drop when wrapped in `Zeroizing`.
+    assert_eq!(
+        unwiped(|| drop(black_box(zeroize::Zeroizing::new(key())))),
+        0
+    );
+}
+
+#[test]
+fn test_to_public_key() {
+    let private =
+        CoseKeyBuilder::new_ec2_priv_key(iana::EllipticCurve::P_256, vec![1], vec![2], vec![3])
+            .key_id(b"ec".to_vec())
+            .algorithm(iana::Algorithm::ES256)
+            .add_key_op(iana::KeyOperation::Sign)
+            .add_key_op(iana::KeyOperation::Verify)
+            .param(-70000, Value::Bytes(vec![4]))
+            .build();
+    assert_eq!(
+        private.to_public_key().unwrap(),
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_256, vec![1], vec![2])
+            .key_id(b"ec".to_vec())
+            .algorithm(iana::Algorithm::ES256)
+            .add_key_op(iana::KeyOperation::Verify)
+            .build()
+    );
+
+    // Compressed points are kept as-is.
+    let private = CoseKeyBuilder::new_ec2_pub_key_y_sign(iana::EllipticCurve::P_256, vec![1], true)
+        .param(iana::Ec2KeyParameter::D as i64, Value::Bytes(vec![3]))
+        .build();
+    assert_eq!(
+        private.to_public_key().unwrap(),
+        CoseKeyBuilder::new_ec2_pub_key_y_sign(iana::EllipticCurve::P_256, vec![1], true).build()
+    );
+
+    let private = CoseKeyBuilder::new_rsa_pub_key(vec![0x0b], vec![0x03])
+        .param(iana::RsaKeyParameter::D as i64, Value::Bytes(vec![0x07]))
+        .add_key_op(iana::KeyOperation::Decrypt)
+        .add_key_op(iana::KeyOperation::UnwrapKey)
+        .add_key_op(iana::KeyOperation::DeriveKey)
+        .build();
+    assert_eq!(
+        private.to_public_key().unwrap(),
+        CoseKeyBuilder::new_rsa_pub_key(vec![0x0b], vec![0x03])
+            .add_key_op(iana::KeyOperation::Encrypt)
+            .add_key_op(iana::KeyOperation::WrapKey)
+            .build()
+    );
+
+    let private = CoseKeyBuilder::new_mldsa_pub_key(MlDsaVariant::MlDsa87, vec![1])
+        .param(iana::AkpKeyParameter::Priv as i64, Value::Bytes(vec![2]))
+        .build();
+    assert_eq!(
+        private.to_public_key().unwrap(),
+        CoseKeyBuilder::new_mldsa_pub_key(MlDsaVariant::MlDsa87, vec![1]).build()
+    );
+
+    let result = CoseKeyBuilder::new_symmetric_key(vec![1])
+        .build()
+        .to_public_key();
+    assert_eq!(
+        result,
+        Err(PublicKeyError::UnsupportedKeyType(KeyType::Assigned(
+            iana::KeyType::Symmetric
+        )))
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "no public key for key type Assigned(Symmetric)"
+    );
+
+    // X448 public keys can't be derived.
+    let private = CoseKeyBuilder::new_okp_key()
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::X448 as u64),
+        )
+        .param(iana::OkpKeyParameter::D as i64, Value::Bytes(vec![1; 56]))
+        .build();
+    let result = private.to_public_key();
+    assert_eq!(
+        result,
+        Err(PublicKeyError::MissingParameter(Label::Int(
+            iana::OkpKeyParameter::X as i64
+        )))
+    );
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "missing public key parameter Int(-2)"
+    );
+}
@@ -106,9 +106,21 @@ pub use ciborium as cbor;
 #[macro_use]
 pub(crate) mod util;
 
+pub mod cat;
+pub mod crypto;
 pub mod cwt;
+pub mod dice;
+pub mod eat;
 #[macro_use]
 pub mod iana;
+pub mod jwk;
+pub mod mdoc;
+pub mod oscore;
+pub mod pkix;
+pub mod rkp;
+pub mod scitt;
+pub mod suit;
+pub mod webauthn;
 
 mod common;
 pub use common::*;
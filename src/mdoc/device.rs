@@ -0,0 +1,331 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Device authentication of an mdoc.
+
+use super::{embed, text_map, to_text_map, unembed, MdocError};
+use crate::{
+    cbor::value::Value, common::AsCborValue, mac_structure_data, CborSerializable, CoseError,
+    CoseMac0, CoseMac0Builder, CoseSign1, CoseSign1Builder, MacContext,
+};
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+
+/// HKDF info used when deriving the `EMacKey` for a device MAC.
+///
+/// The key is derived as HKDF-SHA-256 with the ECDH shared secret (between the device key and the
+/// reader's ephemeral key) as input keying material, the SHA-256 digest of the
+/// `SessionTranscriptBytes` (see [`DeviceAuthentication::session_transcript_bytes`]) as salt, and
+/// an output length of 32 bytes.
+pub const EMAC_KEY_INFO: &[u8] = b"EMacKey";
+
+/// Device-signed data elements, in their embedded form
+/// (`#6.24(bstr .cbor DeviceNameSpaces)`), retaining the original encoding.
+///
+/// ```cddl
+///  DeviceNameSpaces = { * NameSpace => DeviceSignedItems }
+///  DeviceSignedItems = { + DataElementIdentifier => DataElementValue }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceNameSpacesBytes {
+    /// If this structure was created by parsing serialized data, this field
+    /// holds the entire contents of the original `bstr` data.
+    pub original_data: Option<Vec<u8>>,
+    /// Data elements by name space, each held as an identifier and value.
+    pub name_spaces: Vec<(String, Vec<(String, Value)>)>,
+}
+
+impl AsCborValue for DeviceNameSpacesBytes {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let data = unembed(value)?;
+        let name_spaces = text_map(Value::from_slice(&data)?)?
+            .into_iter()
+            .map(|(ns, items)| Ok((ns, text_map(items)?)))
+            .collect::<Result<Vec<_>, CoseError>>()?;
+        Ok(Self {
+            original_data: Some(data),
+            name_spaces,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let data = match self.original_data {
+            Some(data) => data,
+            None => Value::Map(
+                self.name_spaces
+                    .into_iter()
+                    .map(|(ns, items)| {
+                        let items = items
+                            .into_iter()
+                            .map(|(id, v)| (Value::Text(id), v))
+                            .collect();
+                        (Value::Text(ns), Value::Map(items))
+                    })
+                    .collect(),
+            )
+            .to_vec()?,
+        };
+        Ok(embed(data))
+    }
+}
+
+/// Structure that is signed or MACed by the device, as a detached payload.
+///
+/// ```cddl
+///  DeviceAuthentication = [
+///      "DeviceAuthentication",
+///      SessionTranscript,
+///      DocType,  ; tstr
+///      DeviceNameSpacesBytes,
+///  ]
+///  DeviceAuthenticationBytes = #6.24(bstr .cbor DeviceAuthentication)
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceAuthentication {
+    /// Session transcript, as established between the device and the reader.
+    pub session_transcript: Value,
+    /// Document type.
+    pub doc_type: String,
+    /// Device-signed data elements.
+    pub name_spaces: DeviceNameSpacesBytes,
+}
+
+impl DeviceAuthentication {
+    /// Return the encoded `DeviceAuthenticationBytes`, which is the detached payload of the
+    /// device signature or MAC.
+    pub fn payload(&self) -> Result<Vec<u8>, CoseError> {
+        let value = Value::Array(vec![
+            Value::Text("DeviceAuthentication".to_owned()),
+            self.session_transcript.clone(),
+            Value::Text(self.doc_type.clone()),
+            self.name_spaces.clone().to_cbor_value()?,
+        ]);
+        embed(value.to_vec()?).to_vec()
+    }
+
+    /// Return the encoded `SessionTranscriptBytes`
+    /// (`#6.24(bstr .cbor SessionTranscript)`), whose digest is the salt used when deriving the
+    /// `EMacKey` (see [`EMAC_KEY_INFO`]).
+    pub fn session_transcript_bytes(&self) -> Result<Vec<u8>, CoseError> {
+        embed(self.session_transcript.clone().to_vec()?).to_vec()
+    }
+}
+
+/// Authentication of an mdoc by the device.
+///
+/// ```cddl
+///  DeviceAuth = {
+///      "deviceSignature" : DeviceSignature  ; COSE_Sign1
+///      //
+///      "deviceMac" : DeviceMac  ; COSE_Mac0
+///  }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum DeviceAuth {
+    /// Signature by the device key.
+    Signature(CoseSign1),
+    /// MAC with a key derived from the device key and the reader's ephemeral key.
+    Mac(CoseMac0),
+}
+
+impl AsCborValue for DeviceAuth {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut entries = text_map(value)?;
+        if entries.len() != 1 {
+            return Err(CoseError::UnexpectedItem(
+                "map",
+                "map with deviceSignature or deviceMac",
+            ));
+        }
+        let (k, v) = entries.remove(0);
+        match k.as_str() {
+            "deviceSignature" => Ok(DeviceAuth::Signature(CoseSign1::from_cbor_value(v)?)),
+            "deviceMac" => Ok(DeviceAuth::Mac(CoseMac0::from_cbor_value(v)?)),
+            _ => Err(CoseError::UnexpectedItem(
+                "tstr",
+                "map with deviceSignature or deviceMac",
+            )),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let entry = match self {
+            DeviceAuth::Signature(sign1) => ("deviceSignature", sign1.to_cbor_value()?),
+            DeviceAuth::Mac(mac0) => ("deviceMac", mac0.to_cbor_value()?),
+        };
+        Ok(to_text_map(vec![entry]))
+    }
+}
+
+impl DeviceAuth {
+    /// Create a device signature over `auth`, using `builder` (which should already hold the
+    /// algorithm) and `signer` to generate the signature bytes.
+    pub fn create_signature<F>(
+        auth: &DeviceAuthentication,
+        builder: CoseSign1Builder,
+        signer: F,
+    ) -> Result<Self, CoseError>
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        let payload = auth.payload()?;
+        Ok(DeviceAuth::Signature(
+            builder
+                .create_detached_signature(&payload, b"", signer)
+                .build(),
+        ))
+    }
+
+    /// Create a device MAC over `auth`, using `builder` (which should already hold the algorithm)
+    /// and `create` to generate the tag with the `EMacKey`.
+    pub fn create_mac<F>(
+        auth: &DeviceAuthentication,
+        builder: CoseMac0Builder,
+        create: F,
+    ) -> Result<Self, CoseError>
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        let payload = auth.payload()?;
+        let mut mac0 = builder.build();
+        mac0.payload = None;
+        mac0.tag = create(&mac_structure_data(
+            MacContext::CoseMac0,
+            mac0.protected.clone(),
+            b"",
+            &payload,
+        ));
+        Ok(DeviceAuth::Mac(mac0))
+    }
+
+    /// Verify a device signature over `auth`, using `verifier` on the signature value and
+    /// serialized data (in that order).  The verifier should use the device key from the
+    /// [`DeviceKeyInfo`](super::DeviceKeyInfo) of the verified MSO.
+    pub fn verify_signature<F, E>(
+        &self,
+        auth: &DeviceAuthentication,
+        verifier: F,
+    ) -> Result<(), MdocError<E>>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+    {
+        let sign1 = match self {
+            DeviceAuth::Signature(sign1) => sign1,
+            DeviceAuth::Mac(_) => {
+                return Err(CoseError::UnexpectedItem("deviceMac", "deviceSignature").into())
+            }
+        };
+        if sign1.payload.is_some() {
+            return Err(CoseError::UnexpectedItem("payload", "detached payload").into());
+        }
+        sign1
+            .verify_detached_signature(&auth.payload()?, b"", verifier)
+            .map_err(MdocError::Crypto)
+    }
+
+    /// Verify a device MAC over `auth`, using `verify` on the tag value and the combined
+    /// to-be-MACed data (in that order).  The verifier should use the `EMacKey` (see
+    /// [`EMAC_KEY_INFO`]).
+    pub fn verify_mac<F, E>(
+        &self,
+        auth: &DeviceAuthentication,
+        verify: F,
+    ) -> Result<(), MdocError<E>>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+    {
+        let mac0 = match self {
+            DeviceAuth::Mac(mac0) => mac0,
+            DeviceAuth::Signature(_) => {
+                return Err(CoseError::UnexpectedItem("deviceSignature", "deviceMac").into())
+            }
+        };
+        if mac0.payload.is_some() {
+            return Err(CoseError::UnexpectedItem("payload", "detached payload").into());
+        }
+        let tbm = mac_structure_data(
+            MacContext::CoseMac0,
+            mac0.protected.clone(),
+            b"",
+            &auth.payload()?,
+        );
+        verify(&mac0.tag, &tbm).map_err(MdocError::Crypto)
+    }
+}
+
+/// Device-signed data of an mdoc.
+///
+/// ```cddl
+///  DeviceSigned = {
+///      "nameSpaces" : DeviceNameSpacesBytes,
+///      "deviceAuth" : DeviceAuth,
+///  }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeviceSigned {
+    /// Device-signed data elements.
+    pub name_spaces: DeviceNameSpacesBytes,
+    /// Authentication by the device.
+    pub device_auth: DeviceAuth,
+}
+
+impl CborSerializable for DeviceSigned {}
+
+impl AsCborValue for DeviceSigned {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut name_spaces = None;
+        let mut device_auth = None;
+        for (k, v) in text_map(value)? {
+            match k.as_str() {
+                "nameSpaces" => name_spaces = Some(DeviceNameSpacesBytes::from_cbor_value(v)?),
+                "deviceAuth" => device_auth = Some(DeviceAuth::from_cbor_value(v)?),
+                _ => return Err(CoseError::UnexpectedItem("tstr", "DeviceSigned key")),
+            }
+        }
+        match (name_spaces, device_auth) {
+            (Some(name_spaces), Some(device_auth)) => Ok(Self {
+                name_spaces,
+                device_auth,
+            }),
+            _ => Err(CoseError::UnexpectedItem(
+                "map",
+                "DeviceSigned with all entries",
+            )),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(to_text_map(vec![
+            ("nameSpaces", self.name_spaces.to_cbor_value()?),
+            ("deviceAuth", self.device_auth.to_cbor_value()?),
+        ]))
+    }
+}
+
+impl DeviceSigned {
+    /// Return the [`DeviceAuthentication`] structure for this data, given the session transcript
+    /// and document type.
+    pub fn authentication(
+        &self,
+        session_transcript: Value,
+        doc_type: String,
+    ) -> DeviceAuthentication {
+        DeviceAuthentication {
+            session_transcript,
+            doc_type,
+            name_spaces: self.name_spaces.clone(),
+        }
+    }
+}
@@ -0,0 +1,827 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! ISO/IEC 18013-5 mobile document (mdoc) issuer and device authentication.
+//!
+//! An issuer signs a [`MobileSecurityObject`] (MSO), which holds digests of each
+//! [`IssuerSignedItem`], as the embedded-CBOR payload of a `COSE_Sign1` (the IssuerAuth).  The
+//! holder's device then authenticates a [`DeviceAuthentication`] structure with a key certified
+//! by the MSO, using either a `COSE_Sign1` or a `COSE_Mac0` with a detached payload.
+//!
+//! As elsewhere in this crate, signature, MAC and digest operations are performed by
+//! caller-provided closures.
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    cwt::{ClaimsError, Clock, Timestamp},
+    iana,
+    util::{cbor_type_error, ValueTryAs},
+    CborSerializable, CoseError, CoseKey, CoseSign1, CoseSign1Builder, Label,
+};
+use alloc::{borrow::ToOwned, boxed::Box, format, string::String, vec, vec::Vec};
+use core::convert::TryInto;
+
+mod device;
+pub use device::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Version of the `MobileSecurityObject` schema.
+pub const MSO_VERSION: &str = "1.0";
+
+/// Wrap encoded CBOR data as an embedded data item (`#6.24(bstr)`).
+fn embed(data: Vec<u8>) -> Value {
+    Value::Tag(
+        iana::CborTag::EncodedCborData as u64,
+        Box::new(Value::Bytes(data)),
+    )
+}
+
+/// Extract the encoded CBOR data from an embedded data item (`#6.24(bstr)`).
+fn unembed(value: Value) -> Result<Vec<u8>, CoseError> {
+    match value {
+        Value::Tag(t, inner) if t == iana::CborTag::EncodedCborData as u64 => inner.try_as_bytes(),
+        v => cbor_type_error(&v, "tag 24"),
+    }
+}
+
+/// Return the entries of a map with text keys, failing on duplicate keys.
+fn text_map(value: Value) -> Result<Vec<(String, Value)>, CoseError> {
+    let mut entries: Vec<(String, Value)> = Vec::new();
+    for (k, v) in value.try_as_map()? {
+        let k = k.try_as_string()?;
+        if entries.iter().any(|(seen, _)| *seen == k) {
+            return Err(CoseError::DuplicateMapKey);
+        }
+        entries.push((k, v));
+    }
+    Ok(entries)
+}
+
+/// Convert a list of text-keyed entries into a map.
+fn to_text_map(entries: Vec<(&str, Value)>) -> Value {
+    Value::Map(
+        entries
+            .into_iter()
+            .map(|(k, v)| (Value::Text(k.to_owned()), v))
+            .collect(),
+    )
+}
+
+/// Return the number of days between 1970-01-01 and the given (proleptic Gregorian) date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Return the (proleptic Gregorian) date that is the given number of days after 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Parse a `tdate` (`#6.0(tstr)`) into a number of seconds since the UNIX epoch.  As per ISO/IEC
+/// 18013-5, the date must be of the form `YYYY-MM-DDThh:mm:ssZ`, with no fractional seconds and
+/// no local offset.
+fn tdate_from_cbor(value: Value) -> Result<i64, CoseError> {
+    let text = match value {
+        Value::Tag(t, inner) if t == iana::CborTag::DateTimeString as u64 => {
+            inner.try_as_string()?
+        }
+        v => return cbor_type_error(&v, "tdate"),
+    };
+    let bad = || CoseError::UnexpectedItem("tstr", "tdate of form YYYY-MM-DDThh:mm:ssZ");
+    let b = text.as_bytes();
+    if b.len() != 20 || b[4] != b'-' || b[7] != b'-' || b[10] != b'T' || b[13] != b':' {
+        return Err(bad());
+    }
+    if b[16] != b':' || b[19] != b'Z' {
+        return Err(bad());
+    }
+    let num = |start: usize, len: usize| -> Result<i64, CoseError> {
+        let digits = &b[start..start + len];
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return Err(bad());
+        }
+        Ok(digits
+            .iter()
+            .fold(0, |acc, d| acc * 10 + i64::from(d - b'0')))
+    };
+    let (year, month, day) = (num(0, 4)?, num(5, 2)?, num(8, 2)?);
+    let (hour, minute, second) = (num(11, 2)?, num(14, 2)?, num(17, 2)?);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => return Err(bad()),
+    };
+    if day < 1 || day > days_in_month || hour > 23 || minute > 59 || second > 59 {
+        return Err(bad());
+    }
+    Ok(days_from_civil(year, month, day) * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Encode a number of seconds since the UNIX epoch as a `tdate` (`#6.0(tstr)`).
+fn tdate_to_cbor(t: i64) -> Result<Value, CoseError> {
+    let (year, month, day) = civil_from_days(t.div_euclid(86400));
+    if !(0..=9999).contains(&year) {
+        return Err(CoseError::OutOfRangeIntegerValue);
+    }
+    let secs = t.rem_euclid(86400);
+    let text = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    );
+    Ok(Value::Tag(
+        iana::CborTag::DateTimeString as u64,
+        Box::new(Value::Text(text)),
+    ))
+}
+
+/// Digest algorithm used for the value digests in a [`MobileSecurityObject`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DigestAlgorithm {
+    /// SHA-256.
+    Sha256,
+    /// SHA-384.
+    Sha384,
+    /// SHA-512.
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// Return the text form, as used in a [`MobileSecurityObject`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha384 => "SHA-384",
+            DigestAlgorithm::Sha512 => "SHA-512",
+        }
+    }
+}
+
+impl AsCborValue for DigestAlgorithm {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        match value.try_as_string()?.as_str() {
+            "SHA-256" => Ok(DigestAlgorithm::Sha256),
+            "SHA-384" => Ok(DigestAlgorithm::Sha384),
+            "SHA-512" => Ok(DigestAlgorithm::Sha512),
+            _ => Err(CoseError::UnexpectedItem("tstr", "digest algorithm")),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::Text(self.as_str().to_owned()))
+    }
+}
+
+/// A data element signed by the issuer.
+///
+/// ```cddl
+///  IssuerSignedItem = {
+///      "digestID" : uint,
+///      "random" : bstr,
+///      "elementIdentifier" : DataElementIdentifier,  ; tstr
+///      "elementValue" : DataElementValue,            ; any
+///  }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuerSignedItem {
+    /// Identifier of the digest of this item in the [`MobileSecurityObject`].
+    pub digest_id: u64,
+    /// Random value, which prevents the digest revealing the element value.
+    pub random: Vec<u8>,
+    /// Identifier of the data element.
+    pub element_identifier: String,
+    /// Value of the data element.
+    pub element_value: Value,
+}
+
+impl CborSerializable for IssuerSignedItem {}
+
+impl AsCborValue for IssuerSignedItem {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut digest_id = None;
+        let mut random = None;
+        let mut element_identifier = None;
+        let mut element_value = None;
+        for (k, v) in text_map(value)? {
+            match k.as_str() {
+                "digestID" => digest_id = Some(v.try_as_integer()?.try_into()?),
+                "random" => random = Some(v.try_as_bytes()?),
+                "elementIdentifier" => element_identifier = Some(v.try_as_string()?),
+                "elementValue" => element_value = Some(v),
+                _ => return Err(CoseError::UnexpectedItem("tstr", "IssuerSignedItem key")),
+            }
+        }
+        match (digest_id, random, element_identifier, element_value) {
+            (Some(digest_id), Some(random), Some(element_identifier), Some(element_value)) => {
+                Ok(Self {
+                    digest_id,
+                    random,
+                    element_identifier,
+                    element_value,
+                })
+            }
+            _ => Err(CoseError::UnexpectedItem(
+                "map",
+                "IssuerSignedItem with all entries",
+            )),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(to_text_map(vec![
+            ("digestID", Value::from(self.digest_id)),
+            ("random", Value::Bytes(self.random)),
+            ("elementIdentifier", Value::Text(self.element_identifier)),
+            ("elementValue", self.element_value),
+        ]))
+    }
+}
+
+/// An [`IssuerSignedItem`] in its embedded form (`#6.24(bstr .cbor IssuerSignedItem)`), which
+/// retains the original encoding over which the value digest is calculated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuerSignedItemBytes {
+    /// If this structure was created by parsing serialized data, this field
+    /// holds the entire contents of the original `bstr` data.
+    pub original_data: Option<Vec<u8>>,
+    /// Parsed item.
+    pub item: IssuerSignedItem,
+}
+
+impl From<IssuerSignedItem> for IssuerSignedItemBytes {
+    fn from(item: IssuerSignedItem) -> Self {
+        Self {
+            original_data: None,
+            item,
+        }
+    }
+}
+
+impl IssuerSignedItemBytes {
+    /// Return the encoded `IssuerSignedItemBytes`, over which the value digest is calculated.
+    pub fn digest_input(&self) -> Result<Vec<u8>, CoseError> {
+        self.clone().to_cbor_value()?.to_vec()
+    }
+}
+
+impl AsCborValue for IssuerSignedItemBytes {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let data = unembed(value)?;
+        Ok(Self {
+            item: IssuerSignedItem::from_slice(&data)?,
+            original_data: Some(data),
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let data = match self.original_data {
+            Some(data) => data,
+            None => self.item.to_vec()?,
+        };
+        Ok(embed(data))
+    }
+}
+
+/// Validity period of a [`MobileSecurityObject`], with each time held as a number of seconds since
+/// the UNIX epoch.
+///
+/// ```cddl
+///  ValidityInfo = {
+///      "signed" : tdate,
+///      "validFrom" : tdate,
+///      "validUntil" : tdate,
+///      ? "expectedUpdate" : tdate,
+///  }
+/// ```
+///
+/// When parsing, `validFrom` must be no earlier than `signed`, and `validUntil` must be later
+/// than `validFrom`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidityInfo {
+    /// Time at which the MSO was signed.
+    pub signed: i64,
+    /// Time from which the MSO is valid.
+    pub valid_from: i64,
+    /// Time after which the MSO is no longer valid.
+    pub valid_until: i64,
+    /// Time at which the issuer expects to re-sign the MSO.
+    pub expected_update: Option<i64>,
+}
+
+impl ValidityInfo {
+    /// Check that the current time (as given by `clock`) is within the validity period.
+    pub fn check<C: Clock>(&self, clock: &C) -> Result<(), ClaimsError> {
+        let (not_yet_valid, expired) = match clock.now() {
+            Timestamp::WholeSeconds(now) => (now < self.valid_from, now > self.valid_until),
+            Timestamp::FractionalSeconds(now) if now.is_finite() => {
+                (now < self.valid_from as f64, now > self.valid_until as f64)
+            }
+            Timestamp::FractionalSeconds(_) => return Err(ClaimsError::InvalidTimestamp),
+        };
+        if not_yet_valid {
+            return Err(ClaimsError::NotYetValid);
+        }
+        if expired {
+            return Err(ClaimsError::Expired);
+        }
+        Ok(())
+    }
+}
+
+impl AsCborValue for ValidityInfo {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut signed = None;
+        let mut valid_from = None;
+        let mut valid_until = None;
+        let mut expected_update = None;
+        for (k, v) in text_map(value)? {
+            match k.as_str() {
+                "signed" => signed = Some(tdate_from_cbor(v)?),
+                "validFrom" => valid_from = Some(tdate_from_cbor(v)?),
+                "validUntil" => valid_until = Some(tdate_from_cbor(v)?),
+                "expectedUpdate" => expected_update = Some(tdate_from_cbor(v)?),
+                _ => return Err(CoseError::UnexpectedItem("tstr", "ValidityInfo key")),
+            }
+        }
+        let (signed, valid_from, valid_until) = match (signed, valid_from, valid_until) {
+            (Some(s), Some(f), Some(u)) => (s, f, u),
+            _ => {
+                return Err(CoseError::UnexpectedItem(
+                    "map",
+                    "ValidityInfo with all required entries",
+                ))
+            }
+        };
+        if valid_from < signed || valid_until <= valid_from {
+            return Err(CoseError::UnexpectedItem(
+                "inconsistent ValidityInfo",
+                "signed <= validFrom < validUntil",
+            ));
+        }
+        Ok(Self {
+            signed,
+            valid_from,
+            valid_until,
+            expected_update,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut entries = vec![
+            ("signed", tdate_to_cbor(self.signed)?),
+            ("validFrom", tdate_to_cbor(self.valid_from)?),
+            ("validUntil", tdate_to_cbor(self.valid_until)?),
+        ];
+        if let Some(t) = self.expected_update {
+            entries.push(("expectedUpdate", tdate_to_cbor(t)?));
+        }
+        Ok(to_text_map(entries))
+    }
+}
+
+/// Information about the device key certified by a [`MobileSecurityObject`].
+///
+/// ```cddl
+///  DeviceKeyInfo = {
+///      "deviceKey" : DeviceKey,  ; COSE_Key
+///      ? "keyAuthorizations" : KeyAuthorizations,
+///      ? "keyInfo" : KeyInfo,
+///  }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceKeyInfo {
+    /// Public key of the device.
+    pub device_key: CoseKey,
+    /// Name spaces and data elements that the device key may sign or MAC.
+    pub key_authorizations: Option<Value>,
+    /// Additional information about the device key.
+    pub key_info: Option<Value>,
+}
+
+impl AsCborValue for DeviceKeyInfo {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut device_key = None;
+        let mut info = Self::default();
+        for (k, v) in text_map(value)? {
+            match k.as_str() {
+                "deviceKey" => device_key = Some(CoseKey::from_cbor_value(v)?),
+                "keyAuthorizations" => info.key_authorizations = Some(v),
+                "keyInfo" => info.key_info = Some(v),
+                _ => return Err(CoseError::UnexpectedItem("tstr", "DeviceKeyInfo key")),
+            }
+        }
+        info.device_key = device_key.ok_or(CoseError::UnexpectedItem(
+            "map",
+            "DeviceKeyInfo with deviceKey",
+        ))?;
+        Ok(info)
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut entries = vec![("deviceKey", self.device_key.to_cbor_value()?)];
+        if let Some(v) = self.key_authorizations {
+            entries.push(("keyAuthorizations", v));
+        }
+        if let Some(v) = self.key_info {
+            entries.push(("keyInfo", v));
+        }
+        Ok(to_text_map(entries))
+    }
+}
+
+/// Value digests for a name space, each held as a digest ID and digest.
+pub type DigestIds = Vec<(u64, Vec<u8>)>;
+
+/// Mobile security object, as signed by the issuer.
+///
+/// ```cddl
+///  MobileSecurityObject = {
+///      "version" : tstr,  ; "1.0"
+///      "digestAlgorithm" : tstr,
+///      "valueDigests" : ValueDigests,
+///      "deviceKeyInfo" : DeviceKeyInfo,
+///      "docType" : tstr,
+///      "validityInfo" : ValidityInfo,
+///  }
+///  ValueDigests = { + NameSpace => DigestIDs }
+///  DigestIDs = { + DigestID => Digest }  ; uint => bstr
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MobileSecurityObject {
+    /// Version of the MSO.
+    pub version: String,
+    /// Algorithm used for the value digests.
+    pub digest_algorithm: DigestAlgorithm,
+    /// Digests of the issuer-signed items, by name space and then by digest ID.
+    pub value_digests: Vec<(String, DigestIds)>,
+    /// Information about the device key.
+    pub device_key_info: DeviceKeyInfo,
+    /// Document type.
+    pub doc_type: String,
+    /// Validity period.
+    pub validity_info: ValidityInfo,
+    /// Any other entries.
+    pub rest: Vec<(String, Value)>,
+}
+
+impl CborSerializable for MobileSecurityObject {}
+
+impl AsCborValue for MobileSecurityObject {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut version = None;
+        let mut digest_algorithm = None;
+        let mut value_digests = None;
+        let mut device_key_info = None;
+        let mut doc_type = None;
+        let mut validity_info = None;
+        let mut rest = Vec::new();
+        for (k, v) in text_map(value)? {
+            match k.as_str() {
+                "version" => version = Some(v.try_as_string()?),
+                "digestAlgorithm" => digest_algorithm = Some(DigestAlgorithm::from_cbor_value(v)?),
+                "valueDigests" => {
+                    let mut digests = Vec::new();
+                    for (ns, ids) in text_map(v)? {
+                        let mut ns_digests = DigestIds::new();
+                        for (id, digest) in ids.try_as_map()? {
+                            let id: u64 = id.try_as_integer()?.try_into()?;
+                            if ns_digests.iter().any(|(seen, _)| *seen == id) {
+                                return Err(CoseError::DuplicateMapKey);
+                            }
+                            ns_digests.push((id, digest.try_as_bytes()?));
+                        }
+                        digests.push((ns, ns_digests));
+                    }
+                    value_digests = Some(digests);
+                }
+                "deviceKeyInfo" => device_key_info = Some(DeviceKeyInfo::from_cbor_value(v)?),
+                "docType" => doc_type = Some(v.try_as_string()?),
+                "validityInfo" => validity_info = Some(ValidityInfo::from_cbor_value(v)?),
+                _ => rest.push((k, v)),
+            }
+        }
+        match (
+            version,
+            digest_algorithm,
+            value_digests,
+            device_key_info,
+            doc_type,
+            validity_info,
+        ) {
+            (
+                Some(version),
+                Some(digest_algorithm),
+                Some(value_digests),
+                Some(device_key_info),
+                Some(doc_type),
+                Some(validity_info),
+            ) => Ok(Self {
+                version,
+                digest_algorithm,
+                value_digests,
+                device_key_info,
+                doc_type,
+                validity_info,
+                rest,
+            }),
+            _ => Err(CoseError::UnexpectedItem(
+                "map",
+                "MobileSecurityObject with all required entries",
+            )),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let value_digests = self
+            .value_digests
+            .into_iter()
+            .map(|(ns, ids)| {
+                let ids = ids
+                    .into_iter()
+                    .map(|(id, digest)| (Value::from(id), Value::Bytes(digest)))
+                    .collect();
+                (Value::Text(ns), Value::Map(ids))
+            })
+            .collect();
+        let mut entries = vec![
+            ("version", Value::Text(self.version)),
+            ("digestAlgorithm", self.digest_algorithm.to_cbor_value()?),
+            ("valueDigests", Value::Map(value_digests)),
+            ("deviceKeyInfo", self.device_key_info.to_cbor_value()?),
+            ("docType", Value::Text(self.doc_type)),
+            ("validityInfo", self.validity_info.to_cbor_value()?),
+        ];
+        entries.extend(self.rest.iter().map(|(k, v)| (k.as_str(), v.clone())));
+        Ok(to_text_map(entries))
+    }
+}
+
+impl MobileSecurityObject {
+    /// Return the digest for the given name space and digest ID, if present.
+    pub fn digest(&self, name_space: &str, digest_id: u64) -> Option<&[u8]> {
+        self.value_digests
+            .iter()
+            .find(|(ns, _)| ns == name_space)
+            .and_then(|(_, ids)| ids.iter().find(|(id, _)| *id == digest_id))
+            .map(|(_, digest)| digest.as_slice())
+    }
+
+    /// Check the value digest of every item in `name_spaces`, using `digester` to calculate the
+    /// digest of the encoded `IssuerSignedItemBytes` with the MSO's digest algorithm.
+    pub fn check_digests<F, E>(
+        &self,
+        name_spaces: &[(String, Vec<IssuerSignedItemBytes>)],
+        mut digester: F,
+    ) -> Result<(), MdocError<E>>
+    where
+        F: FnMut(DigestAlgorithm, &[u8]) -> Result<Vec<u8>, E>,
+    {
+        for (ns, items) in name_spaces {
+            for item in items {
+                let digest_id = item.item.digest_id;
+                let want = self
+                    .digest(ns, digest_id)
+                    .ok_or_else(|| MdocError::MissingDigest(ns.clone(), digest_id))?;
+                let got = digester(self.digest_algorithm, &item.digest_input()?)
+                    .map_err(MdocError::Crypto)?;
+                if got != want {
+                    return Err(MdocError::DigestMismatch(ns.clone(), digest_id));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Issuer-signed data of an mdoc.
+///
+/// ```cddl
+///  IssuerSigned = {
+///      ? "nameSpaces" : IssuerNameSpaces,
+///      "issuerAuth" : IssuerAuth,  ; COSE_Sign1
+///  }
+///  IssuerNameSpaces = { + NameSpace => [ + IssuerSignedItemBytes ] }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IssuerSigned {
+    /// Issuer-signed items, by name space.  An empty list is encoded by omitting `nameSpaces`.
+    pub name_spaces: Vec<(String, Vec<IssuerSignedItemBytes>)>,
+    /// Signature by the issuer over the [`MobileSecurityObject`].
+    pub issuer_auth: CoseSign1,
+}
+
+impl CborSerializable for IssuerSigned {}
+
+impl AsCborValue for IssuerSigned {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut name_spaces = Vec::new();
+        let mut issuer_auth = None;
+        for (k, v) in text_map(value)? {
+            match k.as_str() {
+                "nameSpaces" => {
+                    for (ns, items) in text_map(v)? {
+                        let items = items
+                            .try_as_array_then_convert(IssuerSignedItemBytes::from_cbor_value)?;
+                        name_spaces.push((ns, items));
+                    }
+                }
+                "issuerAuth" => issuer_auth = Some(CoseSign1::from_cbor_value(v)?),
+                _ => return Err(CoseError::UnexpectedItem("tstr", "IssuerSigned key")),
+            }
+        }
+        Ok(Self {
+            name_spaces,
+            issuer_auth: issuer_auth.ok_or(CoseError::UnexpectedItem(
+                "map",
+                "IssuerSigned with issuerAuth",
+            ))?,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut entries = Vec::new();
+        if !self.name_spaces.is_empty() {
+            let mut name_spaces = Vec::new();
+            for (ns, items) in self.name_spaces {
+                let items = items
+                    .into_iter()
+                    .map(|item| item.to_cbor_value())
+                    .collect::<Result<Vec<_>, _>>()?;
+                name_spaces.push((Value::Text(ns), Value::Array(items)));
+            }
+            entries.push(("nameSpaces", Value::Map(name_spaces)));
+        }
+        entries.push(("issuerAuth", self.issuer_auth.to_cbor_value()?));
+        Ok(to_text_map(entries))
+    }
+}
+
+impl IssuerSigned {
+    /// Parse the [`MobileSecurityObject`] held in the IssuerAuth, without verification.
+    pub fn mso(&self) -> Result<MobileSecurityObject, CoseError> {
+        let payload = self
+            .issuer_auth
+            .payload
+            .as_ref()
+            .ok_or(CoseError::UnexpectedItem(
+                "nil payload",
+                "MobileSecurityObjectBytes payload",
+            ))?;
+        let data = unembed(Value::from_slice(payload)?)?;
+        MobileSecurityObject::from_slice(&data)
+    }
+
+    /// Return the X.509 certificate chain (DER-encoded, leaf first) held in the `x5chain` header
+    /// of the IssuerAuth.  The header is normally unprotected, but is also accepted in the
+    /// protected header.
+    pub fn x5chain(&self) -> Result<Vec<Vec<u8>>, CoseError> {
+        let label = Label::Int(iana::HeaderParameter::X5Chain as i64);
+        let value = self
+            .issuer_auth
+            .unprotected
+            .rest
+            .iter()
+            .chain(self.issuer_auth.protected.header.rest.iter())
+            .find(|(l, _)| *l == label)
+            .map(|(_, v)| v.clone())
+            .ok_or(CoseError::UnexpectedItem(
+                "no x5chain",
+                "x5chain header parameter",
+            ))?;
+        match value {
+            Value::Bytes(cert) => Ok(vec![cert]),
+            Value::Array(certs) if certs.len() >= 2 => {
+                Value::Array(certs).try_as_array_then_convert(|c| c.try_as_bytes())
+            }
+            v => cbor_type_error(&v, "bstr or array of 2+ bstr"),
+        }
+    }
+
+    /// Verify the IssuerAuth signature, using `verifier` on the signature value and serialized
+    /// data (in that order), and return the [`MobileSecurityObject`].
+    ///
+    /// Note that the certificate chain returned by [`IssuerSigned::x5chain`] is not checked, and
+    /// that the value digests (with [`MobileSecurityObject::check_digests`]) and validity period
+    /// (with [`ValidityInfo::check`]) are left for the caller to check.
+    pub fn verify<F, E>(&self, verifier: F) -> Result<MobileSecurityObject, MdocError<E>>
+    where
+        F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+    {
+        self.issuer_auth
+            .verify_signature(b"", verifier)
+            .map_err(MdocError::Crypto)?;
+        Ok(self.mso()?)
+    }
+}
+
+/// Create an IssuerAuth `COSE_Sign1` holding `mso`, using `builder` (which should already hold
+/// the algorithm) and `signer` to generate the signature bytes.  The DER-encoded certificate
+/// chain `x5chain` (leaf first) is added to the unprotected header.
+pub fn sign_issuer_auth<F>(
+    mso: MobileSecurityObject,
+    builder: CoseSign1Builder,
+    x5chain: Vec<Vec<u8>>,
+    signer: F,
+) -> Result<CoseSign1, CoseError>
+where
+    F: FnOnce(&[u8]) -> Vec<u8>,
+{
+    let mut sign1 = builder.build();
+    let x5chain = match x5chain.len() {
+        0 => {
+            return Err(CoseError::UnexpectedItem(
+                "empty certificate chain",
+                "x5chain with 1+ certificates",
+            ))
+        }
+        1 => Value::Bytes(x5chain.into_iter().next().unwrap()), // safe: len checked
+        _ => Value::Array(x5chain.into_iter().map(Value::Bytes).collect()),
+    };
+    sign1
+        .unprotected
+        .rest
+        .push((Label::Int(iana::HeaderParameter::X5Chain as i64), x5chain));
+    sign1.payload = Some(embed(mso.to_vec()?).to_vec()?);
+    sign1.signature = signer(&sign1.tbs_data(b""));
+    Ok(sign1)
+}
+
+/// The error type returned when mdoc authentication fails.
+pub enum MdocError<E> {
+    /// The mdoc data could not be parsed.
+    Cose(CoseError),
+    /// The signature, MAC or digest operation failed.
+    Crypto(E),
+    /// The MSO holds no digest for an item (by name space and digest ID).
+    MissingDigest(String, u64),
+    /// The digest of an item (by name space and digest ID) does not match the MSO.
+    DigestMismatch(String, u64),
+}
+
+impl<E: core::fmt::Display> core::error::Error for MdocError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Debug for MdocError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for MdocError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> MdocError<E> {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MdocError::Cose(e) => write!(f, "invalid mdoc data: {e}"),
+            MdocError::Crypto(e) => write!(f, "verification failed: {e}"),
+            MdocError::MissingDigest(ns, id) => write!(f, "no digest for {ns:?} item {id}"),
+            MdocError::DigestMismatch(ns, id) => write!(f, "digest mismatch for {ns:?} item {id}"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for MdocError<E> {
+    fn from(e: CoseError) -> Self {
+        MdocError::Cose(e)
+    }
+}
@@ -0,0 +1,623 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{
+    util::{expect_err, test_crypto},
+    CoseKeyBuilder, CoseMac0Builder, HeaderBuilder,
+};
+use alloc::vec;
+
+const DOC_TYPE: &str = "org.iso.18013.5.1.mDL";
+const NAME_SPACE: &str = "org.iso.18013.5.1";
+
+/// SHA-2 digest of `data`.
+fn digester(alg: DigestAlgorithm, data: &[u8]) -> Result<Vec<u8>, String> {
+    let alg = match alg {
+        DigestAlgorithm::Sha256 => iana::Algorithm::SHA_256,
+        DigestAlgorithm::Sha384 => iana::Algorithm::SHA_384,
+        DigestAlgorithm::Sha512 => iana::Algorithm::SHA_512,
+    };
+    test_crypto::digest(alg, data)
+}
+
+fn tdate(text: &str) -> Value {
+    Value::Tag(0, Box::new(Value::Text(text.to_owned())))
+}
+
+fn validity_info() -> ValidityInfo {
+    ValidityInfo {
+        signed: 1704067200,      // 2024-01-01T00:00:00Z
+        valid_from: 1704067200,  // 2024-01-01T00:00:00Z
+        valid_until: 1735689599, // 2024-12-31T23:59:59Z
+        expected_update: None,
+    }
+}
+
+fn items() -> Vec<IssuerSignedItemBytes> {
+    vec![
+        IssuerSignedItem {
+            digest_id: 0,
+            random: vec![0x01; 16],
+            element_identifier: "family_name".to_owned(),
+            element_value: Value::Text("Doe".to_owned()),
+        }
+        .into(),
+        IssuerSignedItem {
+            digest_id: 7,
+            random: vec![0x02; 16],
+            element_identifier: "age_over_18".to_owned(),
+            element_value: Value::Bool(true),
+        }
+        .into(),
+    ]
+}
+
+fn mso(items: &[IssuerSignedItemBytes]) -> MobileSecurityObject {
+    let digests = items
+        .iter()
+        .map(|item| {
+            let data = item.digest_input().unwrap();
+            (
+                item.item.digest_id,
+                digester(DigestAlgorithm::Sha256, &data).unwrap(),
+            )
+        })
+        .collect();
+    MobileSecurityObject {
+        version: MSO_VERSION.to_owned(),
+        digest_algorithm: DigestAlgorithm::Sha256,
+        value_digests: vec![(NAME_SPACE.to_owned(), digests)],
+        device_key_info: DeviceKeyInfo {
+            device_key: CoseKeyBuilder::new_ec2_pub_key(
+                iana::EllipticCurve::P_256,
+                vec![0x03; 32],
+                vec![0x04; 32],
+            )
+            .build(),
+            ..Default::default()
+        },
+        doc_type: DOC_TYPE.to_owned(),
+        validity_info: validity_info(),
+        rest: vec![],
+    }
+}
+
+fn issuer_signed() -> IssuerSigned {
+    let items = items();
+    let builder = CoseSign1Builder::new().protected(
+        HeaderBuilder::new()
+            .algorithm(iana::Algorithm::ES256)
+            .build(),
+    );
+    let issuer_auth = sign_issuer_auth(
+        mso(&items),
+        builder,
+        vec![vec![0x30, 0x01], vec![0x30, 0x02]],
+        |data| test_crypto::sign(1, data),
+    )
+    .unwrap();
+    IssuerSigned {
+        name_spaces: vec![(NAME_SPACE.to_owned(), items)],
+        issuer_auth,
+    }
+}
+
+fn device_authentication() -> DeviceAuthentication {
+    DeviceAuthentication {
+        session_transcript: Value::Array(vec![
+            Value::Null,
+            Value::Null,
+            Value::Text("handover".to_owned()),
+        ]),
+        doc_type: DOC_TYPE.to_owned(),
+        name_spaces: DeviceNameSpacesBytes::default(),
+    }
+}
+
+#[test]
+fn test_tdate() {
+    let tests = [
+        ("1970-01-01T00:00:00Z", 0),
+        ("2000-03-01T00:00:00Z", 951868800),
+        ("2024-02-29T12:34:56Z", 1709210096),
+        ("1969-12-31T23:59:59Z", -1),
+        ("9999-12-31T23:59:59Z", 253402300799),
+    ];
+    for (text, t) in tests {
+        assert_eq!(tdate_from_cbor(tdate(text)).unwrap(), t, "for {}", text);
+        assert_eq!(tdate_to_cbor(t).unwrap(), tdate(text), "for {}", t);
+    }
+    expect_err(tdate_to_cbor(253402300800), "out of range");
+    expect_err(tdate_to_cbor(-62167219201), "out of range");
+
+    let tests = [
+        (tdate("2023-02-29T00:00:00Z"), "tdate of form"),
+        (tdate("2024-13-01T00:00:00Z"), "tdate of form"),
+        (tdate("2024-01-01T24:00:00Z"), "tdate of form"),
+        (tdate("2024-01-01T00:00:00.5Z"), "tdate of form"),
+        (tdate("2024-01-01T00:00:00+01:00"), "tdate of form"),
+        (tdate("2024-01-01 00:00:00Z"), "tdate of form"),
+        (tdate("+024-01-01T00:00:00Z"), "tdate of form"),
+        (Value::Text("2024-01-01T00:00:00Z".to_owned()), "tdate"),
+        (Value::Tag(1, Box::new(Value::from(0))), "tdate"),
+        (Value::Tag(0, Box::new(Value::from(0))), "tstr"),
+    ];
+    for (value, err_msg) in tests {
+        expect_err(tdate_from_cbor(value), err_msg);
+    }
+}
+
+#[test]
+fn test_validity_info() {
+    let info = ValidityInfo {
+        expected_update: Some(1719792000),
+        ..validity_info()
+    };
+    let value = info.clone().to_cbor_value().unwrap();
+    assert_eq!(
+        value,
+        Value::Map(vec![
+            (
+                Value::Text("signed".to_owned()),
+                tdate("2024-01-01T00:00:00Z")
+            ),
+            (
+                Value::Text("validFrom".to_owned()),
+                tdate("2024-01-01T00:00:00Z")
+            ),
+            (
+                Value::Text("validUntil".to_owned()),
+                tdate("2024-12-31T23:59:59Z")
+            ),
+            (
+                Value::Text("expectedUpdate".to_owned()),
+                tdate("2024-07-01T00:00:00Z")
+            ),
+        ])
+    );
+    assert_eq!(ValidityInfo::from_cbor_value(value).unwrap(), info);
+
+    info.check(&Timestamp::WholeSeconds(1704067200)).unwrap();
+    info.check(&Timestamp::WholeSeconds(1735689599)).unwrap();
+    info.check(&|| Timestamp::FractionalSeconds(1720000000.5))
+        .unwrap();
+    assert_eq!(
+        info.check(&Timestamp::WholeSeconds(1704067199)),
+        Err(ClaimsError::NotYetValid)
+    );
+    assert_eq!(
+        info.check(&Timestamp::WholeSeconds(1735689600)),
+        Err(ClaimsError::Expired)
+    );
+    assert_eq!(
+        info.check(&Timestamp::FractionalSeconds(1735689599.5)),
+        Err(ClaimsError::Expired)
+    );
+    assert_eq!(
+        info.check(&Timestamp::FractionalSeconds(f64::NAN)),
+        Err(ClaimsError::InvalidTimestamp)
+    );
+
+    let entries = |signed: &str, from: &str, until: &str| {
+        Value::Map(vec![
+            (Value::Text("signed".to_owned()), tdate(signed)),
+            (Value::Text("validFrom".to_owned()), tdate(from)),
+            (Value::Text("validUntil".to_owned()), tdate(until)),
+        ])
+    };
+    let tests = [
+        (
+            entries(
+                "2024-01-02T00:00:00Z",
+                "2024-01-01T00:00:00Z",
+                "2025-01-01T00:00:00Z",
+            ),
+            "signed <= validFrom < validUntil",
+        ),
+        (
+            entries(
+                "2024-01-01T00:00:00Z",
+                "2024-01-01T00:00:00Z",
+                "2024-01-01T00:00:00Z",
+            ),
+            "signed <= validFrom < validUntil",
+        ),
+        (
+            Value::Map(vec![(
+                Value::Text("signed".to_owned()),
+                tdate("2024-01-01T00:00:00Z"),
+            )]),
+            "ValidityInfo with all required entries",
+        ),
+        (
+            Value::Map(vec![(Value::Text("other".to_owned()), Value::Null)]),
+            "ValidityInfo key",
+        ),
+        (
+            Value::Map(vec![
+                (
+                    Value::Text("signed".to_owned()),
+                    tdate("2024-01-01T00:00:00Z"),
+                ),
+                (
+                    Value::Text("signed".to_owned()),
+                    tdate("2024-01-01T00:00:00Z"),
+                ),
+            ]),
+            "duplicate map key",
+        ),
+    ];
+    for (value, err_msg) in tests {
+        expect_err(ValidityInfo::from_cbor_value(value), err_msg);
+    }
+}
+
+#[test]
+fn test_issuer_signed_item() {
+    let item = items().remove(0);
+    let data = item.digest_input().unwrap();
+    assert_eq!(&data[..2], &[0xd8, 0x18]); // tag 24
+    let parsed = IssuerSignedItemBytes::from_cbor_value(Value::from_slice(&data).unwrap()).unwrap();
+    assert_eq!(parsed.item, item.item);
+    assert_eq!(parsed.digest_input().unwrap(), data);
+
+    // The original encoding is retained, even if not in the preferred form.
+    let original = hex::decode(concat!(
+        "a4",                                   // 4-map
+        "6c656c656d656e7456616c7565",           // "elementValue"
+        "f5",                                   // true
+        "6672616e646f6d",                       // "random"
+        "4100",                                 // h'00'
+        "686469676573744944",                   // "digestID"
+        "190001",                               // 1 (non-preferred)
+        "71656c656d656e744964656e746966696572", // "elementIdentifier"
+        "6161",                                 // "a"
+    ))
+    .unwrap();
+    let value = Value::Tag(24, Box::new(Value::Bytes(original.clone())));
+    let parsed = IssuerSignedItemBytes::from_cbor_value(value.clone()).unwrap();
+    assert_eq!(parsed.item.digest_id, 1);
+    assert_eq!(parsed.original_data, Some(original));
+    assert_eq!(parsed.digest_input().unwrap(), value.to_vec().unwrap());
+
+    let tests = [
+        // {"digestID": 1}
+        (
+            "d8184ba168646967657374494401",
+            "IssuerSignedItem with all entries",
+        ),
+        // {"other": 1}
+        ("d81848a1656f7468657201", "IssuerSignedItem key"),
+        // {1: 1}
+        ("d81843a10101", "tstr"),
+        // h'a0'
+        ("41a0", "tag 24"),
+        // 24(1)
+        ("d81801", "bstr"),
+    ];
+    for (data, err_msg) in tests {
+        let value = Value::from_slice(&hex::decode(data).unwrap()).unwrap();
+        expect_err(IssuerSignedItemBytes::from_cbor_value(value), err_msg);
+    }
+}
+
+#[test]
+fn test_mso() {
+    let mso = mso(&items());
+    let data = mso.clone().to_vec().unwrap();
+    assert_eq!(MobileSecurityObject::from_slice(&data).unwrap(), mso);
+    assert!(mso.digest(NAME_SPACE, 7).is_some());
+    assert!(mso.digest(NAME_SPACE, 1).is_none());
+    assert!(mso.digest("other", 0).is_none());
+
+    let mso_with_rest = MobileSecurityObject {
+        rest: vec![("status".to_owned(), Value::Map(vec![]))],
+        ..mso.clone()
+    };
+    let data = mso_with_rest.clone().to_vec().unwrap();
+    assert_eq!(
+        MobileSecurityObject::from_slice(&data).unwrap(),
+        mso_with_rest
+    );
+
+    let mut value = mso.clone().to_cbor_value().unwrap();
+    if let Value::Map(m) = &mut value {
+        m.retain(|(k, _)| *k != Value::Text("docType".to_owned()));
+    }
+    expect_err(
+        MobileSecurityObject::from_cbor_value(value),
+        "MobileSecurityObject with all required entries",
+    );
+
+    let mut value = mso.clone().to_cbor_value().unwrap();
+    if let Value::Map(m) = &mut value {
+        m[1].1 = Value::Text("MD5".to_owned());
+    }
+    expect_err(
+        MobileSecurityObject::from_cbor_value(value),
+        "digest algorithm",
+    );
+
+    let mut value = mso.clone().to_cbor_value().unwrap();
+    if let Value::Map(m) = &mut value {
+        m[2].1 = Value::Map(vec![(
+            Value::Text(NAME_SPACE.to_owned()),
+            Value::Map(vec![
+                (Value::from(1), Value::Bytes(vec![])),
+                (Value::from(1), Value::Bytes(vec![])),
+            ]),
+        )]);
+    }
+    expect_err(
+        MobileSecurityObject::from_cbor_value(value),
+        "duplicate map key",
+    );
+
+    let mut value = mso.to_cbor_value().unwrap();
+    if let Value::Map(m) = &mut value {
+        m[3].1 = Value::Map(vec![]);
+    }
+    expect_err(
+        MobileSecurityObject::from_cbor_value(value),
+        "DeviceKeyInfo with deviceKey",
+    );
+}
+
+#[test]
+fn test_issuer_signed() {
+    let issuer_signed = issuer_signed();
+    let data = issuer_signed.clone().to_vec().unwrap();
+    let parsed = IssuerSigned::from_slice(&data).unwrap();
+    assert_eq!(parsed.clone().to_vec().unwrap(), data);
+    assert_eq!(
+        parsed.x5chain().unwrap(),
+        vec![vec![0x30, 0x01], vec![0x30, 0x02]]
+    );
+
+    let mso = parsed.verify(test_crypto::verifier(1)).unwrap();
+    assert_eq!(mso, parsed.mso().unwrap());
+    assert_eq!(mso.doc_type, DOC_TYPE);
+    mso.check_digests(&parsed.name_spaces, digester).unwrap();
+    mso.validity_info
+        .check(&Timestamp::WholeSeconds(1710000000))
+        .unwrap();
+
+    // The MSO is held as embedded CBOR.
+    let payload = parsed.issuer_auth.payload.as_ref().unwrap();
+    assert_eq!(&payload[..2], &[0xd8, 0x18]);
+
+    // A single certificate is encoded as a bstr.
+    let issuer_auth = sign_issuer_auth(
+        mso.clone(),
+        CoseSign1Builder::new(),
+        vec![vec![0x30, 0x03]],
+        |data| test_crypto::sign(1, data),
+    )
+    .unwrap();
+    assert_eq!(
+        issuer_auth.unprotected.rest,
+        vec![(Label::Int(33), Value::Bytes(vec![0x30, 0x03]))]
+    );
+    let no_items = IssuerSigned {
+        name_spaces: vec![],
+        issuer_auth,
+    };
+    assert_eq!(no_items.x5chain().unwrap(), vec![vec![0x30, 0x03]]);
+    let data = no_items.clone().to_vec().unwrap();
+    assert_eq!(
+        IssuerSigned::from_slice(&data).unwrap().to_vec().unwrap(),
+        data
+    );
+    expect_err(
+        sign_issuer_auth(mso, CoseSign1Builder::new(), vec![], |_| vec![]),
+        "x5chain with 1+ certificates",
+    );
+}
+
+#[test]
+fn test_issuer_signed_fail() {
+    let issuer_signed = issuer_signed();
+    expect_err(
+        issuer_signed.verify(test_crypto::verifier(2)),
+        "verification failed: bad signature",
+    );
+    let mso = issuer_signed.mso().unwrap();
+
+    let mut tampered = issuer_signed.clone();
+    tampered.name_spaces[0].1[1].item.element_value = Value::Bool(false);
+    expect_err(
+        mso.check_digests(&tampered.name_spaces, digester),
+        "digest mismatch for \"org.iso.18013.5.1\" item 7",
+    );
+
+    let mut unknown_id = issuer_signed.clone();
+    unknown_id.name_spaces[0].1[0].item.digest_id = 3;
+    expect_err(
+        mso.check_digests(&unknown_id.name_spaces, digester),
+        "no digest for \"org.iso.18013.5.1\" item 3",
+    );
+
+    let mut unknown_ns = issuer_signed.clone();
+    unknown_ns.name_spaces[0].0 = "other".to_owned();
+    expect_err(
+        mso.check_digests(&unknown_ns.name_spaces, digester),
+        "no digest for \"other\" item 0",
+    );
+
+    expect_err(
+        mso.check_digests(&issuer_signed.name_spaces, |_, _| {
+            Err::<Vec<u8>, _>("digest failed")
+        }),
+        "verification failed: digest failed",
+    );
+
+    let mut no_payload = issuer_signed.clone();
+    no_payload.issuer_auth.payload = None;
+    expect_err(no_payload.mso(), "MobileSecurityObjectBytes payload");
+
+    let mut untagged = issuer_signed.clone();
+    untagged.issuer_auth.payload = Some(mso.to_vec().unwrap());
+    expect_err(untagged.mso(), "tag 24");
+
+    let mut no_x5chain = issuer_signed.clone();
+    no_x5chain.issuer_auth.unprotected.rest.clear();
+    expect_err(no_x5chain.x5chain(), "x5chain header parameter");
+
+    let mut bad_x5chain = issuer_signed;
+    bad_x5chain.issuer_auth.unprotected.rest[0].1 = Value::Array(vec![Value::Bytes(vec![])]);
+    expect_err(bad_x5chain.x5chain(), "bstr or array of 2+ bstr");
+
+    let tests = [
+        // {}
+        ("a0", "IssuerSigned with issuerAuth"),
+        // {"other": 1}
+        ("a1656f7468657201", "IssuerSigned key"),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(IssuerSigned::from_slice(&data), err_msg);
+    }
+}
+
+#[test]
+fn test_device_auth_signature() {
+    let auth = device_authentication();
+    let payload = auth.payload().unwrap();
+    assert_eq!(&payload[..2], &[0xd8, 0x18]);
+    assert_eq!(
+        auth.session_transcript_bytes().unwrap(),
+        hex::decode("d8184c83f6f66868616e646f766572").unwrap()
+    );
+
+    let builder = CoseSign1Builder::new().protected(
+        HeaderBuilder::new()
+            .algorithm(iana::Algorithm::ES256)
+            .build(),
+    );
+    let device_auth =
+        DeviceAuth::create_signature(&auth, builder, |data| test_crypto::sign(5, data)).unwrap();
+    let signed = DeviceSigned {
+        name_spaces: auth.name_spaces.clone(),
+        device_auth,
+    };
+    let data = signed.clone().to_vec().unwrap();
+    let parsed = DeviceSigned::from_slice(&data).unwrap();
+    assert_eq!(parsed.clone().to_vec().unwrap(), data);
+
+    let auth = parsed.authentication(auth.session_transcript, DOC_TYPE.to_owned());
+    parsed
+        .device_auth
+        .verify_signature(&auth, test_crypto::verifier(5))
+        .unwrap();
+    expect_err(
+        parsed
+            .device_auth
+            .verify_signature(&auth, test_crypto::verifier(6)),
+        "verification failed: bad signature",
+    );
+    let other = DeviceAuthentication {
+        doc_type: "other".to_owned(),
+        ..auth.clone()
+    };
+    expect_err(
+        parsed
+            .device_auth
+            .verify_signature(&other, test_crypto::verifier(5)),
+        "bad signature",
+    );
+    expect_err(
+        parsed
+            .device_auth
+            .verify_mac(&auth, test_crypto::verifier(5)),
+        "deviceMac",
+    );
+
+    let mut attached = parsed.device_auth;
+    if let DeviceAuth::Signature(sign1) = &mut attached {
+        sign1.payload = Some(auth.payload().unwrap());
+    }
+    expect_err(
+        attached.verify_signature(&auth, test_crypto::verifier(5)),
+        "detached payload",
+    );
+}
+
+#[test]
+fn test_device_auth_mac() {
+    let name_spaces = DeviceNameSpacesBytes {
+        original_data: None,
+        name_spaces: vec![(
+            NAME_SPACE.to_owned(),
+            vec![("family_name".to_owned(), Value::Text("Doe".to_owned()))],
+        )],
+    };
+    let auth = DeviceAuthentication {
+        name_spaces,
+        ..device_authentication()
+    };
+    let builder = CoseMac0Builder::new().protected(
+        HeaderBuilder::new()
+            .algorithm(iana::Algorithm::HMAC_256_256)
+            .build(),
+    );
+    let device_auth =
+        DeviceAuth::create_mac(&auth, builder, |data| test_crypto::sign(9, data)).unwrap();
+    let signed = DeviceSigned {
+        name_spaces: auth.name_spaces.clone(),
+        device_auth,
+    };
+    let data = signed.to_vec().unwrap();
+    let parsed = DeviceSigned::from_slice(&data).unwrap();
+    assert_eq!(parsed.name_spaces.name_spaces, auth.name_spaces.name_spaces);
+
+    let auth = parsed.authentication(auth.session_transcript, DOC_TYPE.to_owned());
+    parsed
+        .device_auth
+        .verify_mac(&auth, test_crypto::verifier(9))
+        .unwrap();
+    expect_err(
+        parsed
+            .device_auth
+            .verify_mac(&auth, test_crypto::verifier(8)),
+        "verification failed: bad signature",
+    );
+    expect_err(
+        parsed
+            .device_auth
+            .verify_signature(&auth, test_crypto::verifier(9)),
+        "deviceSignature",
+    );
+
+    let tests = [
+        // {"nameSpaces": 24(h'a0')}
+        (
+            "a16a6e616d65537061636573d81841a0",
+            "DeviceSigned with all entries",
+        ),
+        // {"deviceAuth": {}}
+        ("a16a64657669636541757468a0", "deviceSignature or deviceMac"),
+        // {"deviceAuth": {"other": 1}}
+        (
+            "a16a64657669636541757468a1656f7468657201",
+            "deviceSignature or deviceMac",
+        ),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(DeviceSigned::from_slice(&data), err_msg);
+    }
+}
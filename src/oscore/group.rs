@@ -0,0 +1,666 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Group OSCORE functionality (draft-ietf-core-oscore-groupcomm), for messages protected in group
+//! mode.
+//!
+//! In group mode, the ciphertext of the `COSE_Encrypt0` is followed in the payload by a
+//! `CounterSignature0` countersignature of the sender, which is itself encrypted by XOR with a
+//! keystream derived from the Signature Encryption Key of the group.
+
+use super::{
+    aead_lengths, hkdf_info, nonce, partial_iv, sequence_number, OscoreError, OscoreOption,
+    ReplayWindow, RequestInfo, MAX_SEQUENCE_NUMBER, OSCORE_VERSION,
+};
+use crate::{
+    cbor::value::Value,
+    cwt::{ClaimsSet, ClaimsSetBuilder, Confirmation},
+    enc_structure_data, iana, CborSerializable, CoseError, CoseKey, EncryptionContext,
+    ProtectedHeader,
+};
+use alloc::{borrow::ToOwned, vec, vec::Vec};
+
+/// Return the length of signatures made with `alg` by `key`, or `None` if the algorithm is not a
+/// signature algorithm usable with Group OSCORE.  For EdDSA, the length depends on the curve of the
+/// key.
+pub fn signature_len(alg: iana::Algorithm, key: &CoseKey) -> Option<usize> {
+    use iana::Algorithm as A;
+    Some(match alg {
+        A::ES256 | A::ESP256 | A::ES256K | A::Ed25519 => 64,
+        A::ES384 | A::ESP384 => 96,
+        A::ES512 | A::ESP512 => 132,
+        A::Ed448 => 114,
+        A::EdDSA => {
+            let crv = key.param(iana::OkpKeyParameter::Crv as i64)?;
+            if *crv == Value::from(iana::EllipticCurve::Ed25519 as i64) {
+                64
+            } else if *crv == Value::from(iana::EllipticCurve::Ed448 as i64) {
+                114
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// Create the data that the countersignature of a group mode message is computed over: the
+/// `Countersign_structure` of RFC 9338 section 3.3 with the `CounterSignature0` context, the
+/// empty protected header of the `COSE_Encrypt0` and the ciphertext as payload.
+///
+/// ```cddl
+///   Countersign_structure = [
+///       context : "CounterSignature0",
+///       body_protected : empty_or_serialized_map,
+///       external_aad : bstr,
+///       payload : bstr
+///   ]
+/// ```
+pub(crate) fn countersign_structure_data(external_aad: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    Value::Array(vec![
+        Value::Text("CounterSignature0".to_owned()),
+        Value::Bytes(vec![]),
+        Value::Bytes(external_aad.to_vec()),
+        Value::Bytes(ciphertext.to_vec()),
+    ])
+    .to_vec()
+    .expect("failed to serialize Countersign_structure") // safe: always serializable
+}
+
+/// Cryptographic operations needed for Group OSCORE, provided by the caller.
+pub trait GroupCrypto {
+    /// Error type for failed operations.
+    type Error;
+
+    /// Perform HKDF (with the HKDF algorithm of the group, by default HKDF SHA-256) on the salt,
+    /// input keying material and info, returning `len` bytes of output.
+    fn hkdf(
+        &mut self,
+        salt: &[u8],
+        ikm: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    /// Encrypt `plaintext` with the Group Encryption Algorithm, using the key, nonce and AAD.
+    fn encrypt(
+        &mut self,
+        key: &[u8],
+        nonce: &[u8],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    /// Decrypt `ciphertext` with the Group Encryption Algorithm, using the key, nonce and AAD.
+    fn decrypt(
+        &mut self,
+        key: &[u8],
+        nonce: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Self::Error>;
+
+    /// Sign `data` with the Signature Algorithm, using the private key of this group member.
+    fn sign(&mut self, data: &[u8]) -> Result<Vec<u8>, Self::Error>;
+
+    /// Verify `signature` over `data` with the Signature Algorithm, using the public key of
+    /// another group member.
+    fn verify(&mut self, key: &CoseKey, signature: &[u8], data: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Authentication credential of a group member.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GroupCredential {
+    /// Serialized credential, as included in the external AAD.
+    pub credential: Vec<u8>,
+    /// Public key of the group member, as held in the credential.
+    pub public_key: CoseKey,
+}
+
+impl GroupCredential {
+    /// Create a credential from a serialized CWT Claims Set (CCS), whose `cnf` claim holds the
+    /// public key of the group member as a `COSE_Key`.
+    pub fn from_ccs(credential: Vec<u8>) -> Result<Self, CoseError> {
+        let claims = ClaimsSet::from_slice(&credential)?;
+        match claims.confirmation()? {
+            Some(Confirmation::CoseKey(public_key)) => Ok(Self {
+                credential,
+                public_key,
+            }),
+            _ => Err(CoseError::UnexpectedItem(
+                "CCS",
+                "CCS with COSE_Key confirmation",
+            )),
+        }
+    }
+
+    /// Create a CCS credential holding the claims from `claims`, with `public_key` in the `cnf`
+    /// claim (replacing any confirmation already set).
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `public_key` cannot be CBOR-encoded.
+    pub fn new_ccs(claims: ClaimsSetBuilder, public_key: CoseKey) -> Result<Self, CoseError> {
+        let claims = claims
+            .confirmation(Confirmation::CoseKey(public_key.clone()))
+            .build();
+        Ok(Self {
+            credential: claims.to_vec()?,
+            public_key,
+        })
+    }
+}
+
+/// Input parameters for a Group OSCORE security context.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupContextParams {
+    /// Master Secret.
+    pub master_secret: Vec<u8>,
+    /// Master Salt, which may be empty.
+    pub master_salt: Vec<u8>,
+    /// ID Context, which holds the Group Identifier.
+    pub id_context: Vec<u8>,
+    /// Sender ID of this group member.
+    pub sender_id: Vec<u8>,
+    /// Authentication credential of this group member.
+    pub sender_credential: GroupCredential,
+    /// Serialized authentication credential of the Group Manager, if any.
+    pub gm_credential: Option<Vec<u8>>,
+    /// Group Encryption Algorithm, by default AES-CCM-16-64-128.
+    pub group_enc_alg: iana::Algorithm,
+    /// Signature Algorithm, by default EdDSA.
+    pub sign_alg: iana::Algorithm,
+    /// Pairwise Key Agreement Algorithm, if the group also uses the pairwise mode.  This is only
+    /// included in the external AAD, as the pairwise mode is not supported.
+    pub pairwise_key_agreement_alg: Option<iana::Algorithm>,
+}
+
+impl Default for GroupContextParams {
+    fn default() -> Self {
+        Self {
+            master_secret: Vec::new(),
+            master_salt: Vec::new(),
+            id_context: Vec::new(),
+            sender_id: Vec::new(),
+            sender_credential: GroupCredential::default(),
+            gm_credential: None,
+            group_enc_alg: iana::Algorithm::AES_CCM_16_64_128,
+            sign_alg: iana::Algorithm::EdDSA,
+            pairwise_key_agreement_alg: None,
+        }
+    }
+}
+
+/// Recipient Context of a Group OSCORE security context, for one other group member.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupRecipient {
+    /// Recipient ID, which is the Sender ID of the other group member.
+    pub recipient_id: Vec<u8>,
+    /// Recipient Key.
+    pub recipient_key: Vec<u8>,
+    /// Authentication credential of the other group member.
+    pub credential: GroupCredential,
+    /// Replay window for requests from the other group member.
+    pub replay_window: ReplayWindow,
+}
+
+/// Group OSCORE security context, holding the Common Context, the Sender Context and a Recipient
+/// Context for each known group member.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupSecurityContext {
+    /// Master Secret, kept to derive the Recipient Contexts of new group members.
+    pub master_secret: Vec<u8>,
+    /// Master Salt.
+    pub master_salt: Vec<u8>,
+    /// ID Context, which holds the Group Identifier.
+    pub id_context: Vec<u8>,
+    /// Group Encryption Algorithm.
+    pub group_enc_alg: iana::Algorithm,
+    /// Signature Algorithm.
+    pub sign_alg: iana::Algorithm,
+    /// Pairwise Key Agreement Algorithm, if any.
+    pub pairwise_key_agreement_alg: Option<iana::Algorithm>,
+    /// Common IV.
+    pub common_iv: Vec<u8>,
+    /// Signature Encryption Key.
+    pub signature_encryption_key: Vec<u8>,
+    /// Serialized authentication credential of the Group Manager, if any.
+    pub gm_credential: Option<Vec<u8>>,
+    /// Sender ID.
+    pub sender_id: Vec<u8>,
+    /// Sender Key.
+    pub sender_key: Vec<u8>,
+    /// Sender Sequence Number.
+    pub sender_seq: u64,
+    /// Authentication credential of this group member.
+    pub sender_credential: GroupCredential,
+    /// Recipient Contexts of the other group members.
+    pub recipients: Vec<GroupRecipient>,
+}
+
+impl GroupSecurityContext {
+    /// Derive a security context from its input parameters, with no Recipient Contexts.
+    pub fn derive<C: GroupCrypto>(
+        params: &GroupContextParams,
+        crypto: &mut C,
+    ) -> Result<Self, OscoreError<C::Error>> {
+        let (key_len, nonce_len) = aead_lengths(params.group_enc_alg)
+            .ok_or(OscoreError::UnsupportedAlgorithm(params.group_enc_alg))?;
+        if signature_len(params.sign_alg, &params.sender_credential.public_key).is_none() {
+            return Err(OscoreError::UnsupportedAlgorithm(params.sign_alg));
+        }
+        if params.sender_id.len() > nonce_len - 6 {
+            return Err(OscoreError::InvalidId);
+        }
+        let mut ctx = Self {
+            master_secret: params.master_secret.clone(),
+            master_salt: params.master_salt.clone(),
+            id_context: params.id_context.clone(),
+            group_enc_alg: params.group_enc_alg,
+            sign_alg: params.sign_alg,
+            pairwise_key_agreement_alg: params.pairwise_key_agreement_alg,
+            common_iv: Vec::new(),
+            signature_encryption_key: Vec::new(),
+            gm_credential: params.gm_credential.clone(),
+            sender_id: params.sender_id.clone(),
+            sender_key: Vec::new(),
+            sender_seq: 0,
+            sender_credential: params.sender_credential.clone(),
+            recipients: Vec::new(),
+        };
+        ctx.common_iv = ctx.derive_key(crypto, &[], "IV", nonce_len)?;
+        ctx.signature_encryption_key = ctx.derive_key(crypto, &[], "SEKey", key_len)?;
+        ctx.sender_key = ctx.derive_key(crypto, &params.sender_id, "Key", key_len)?;
+        Ok(ctx)
+    }
+
+    /// Derive `len` bytes of keying material for the given ID and type.
+    fn derive_key<C: GroupCrypto>(
+        &self,
+        crypto: &mut C,
+        id: &[u8],
+        info_type: &str,
+        len: usize,
+    ) -> Result<Vec<u8>, OscoreError<C::Error>> {
+        let info = hkdf_info(
+            id,
+            Some(&self.id_context),
+            self.group_enc_alg,
+            info_type,
+            len,
+        )?;
+        crypto
+            .hkdf(&self.master_salt, &self.master_secret, &info, len)
+            .map_err(OscoreError::Crypto)
+    }
+
+    /// Add a Recipient Context for the group member with the given Sender ID and authentication
+    /// credential, replacing any existing Recipient Context for that ID.
+    pub fn add_recipient<C: GroupCrypto>(
+        &mut self,
+        recipient_id: &[u8],
+        credential: GroupCredential,
+        crypto: &mut C,
+    ) -> Result<(), OscoreError<C::Error>> {
+        let nonce_len = self.common_iv.len();
+        if recipient_id.len() + 6 > nonce_len || recipient_id == self.sender_id.as_slice() {
+            return Err(OscoreError::InvalidId);
+        }
+        if signature_len(self.sign_alg, &credential.public_key).is_none() {
+            return Err(OscoreError::UnsupportedAlgorithm(self.sign_alg));
+        }
+        let recipient_key = self.derive_key(crypto, recipient_id, "Key", self.sender_key.len())?;
+        self.recipients.retain(|r| r.recipient_id != recipient_id);
+        self.recipients.push(GroupRecipient {
+            recipient_id: recipient_id.to_vec(),
+            recipient_key,
+            credential,
+            replay_window: ReplayWindow::default(),
+        });
+        Ok(())
+    }
+
+    /// Return the Recipient Context for the group member with the given Sender ID, if known.
+    pub fn recipient(&self, recipient_id: &[u8]) -> Option<&GroupRecipient> {
+        self.recipients
+            .iter()
+            .find(|r| r.recipient_id == recipient_id)
+    }
+
+    /// Return the external AAD for a message in group mode, which is the CBOR encoding of:
+    ///
+    /// ```cddl
+    ///   aad_array = [
+    ///      oscore_version : uint,
+    ///      algorithms : [
+    ///         alg_group_enc : int / tstr,
+    ///         alg_signature : int / tstr,
+    ///         alg_pairwise_key_agreement : int / tstr / null,
+    ///      ],
+    ///      request_kid : bstr,
+    ///      request_piv : bstr,
+    ///      options : bstr,
+    ///      request_kid_context : bstr,
+    ///      OSCORE_option : bstr,
+    ///      sender_cred : bstr,
+    ///      gm_cred : bstr / null,
+    ///   ]
+    /// ```
+    ///
+    /// where `OSCORE_option` is the OSCORE option value of the message and `sender_cred` the
+    /// authentication credential of its sender.
+    pub fn external_aad(
+        &self,
+        request: &RequestInfo,
+        options: &[u8],
+        oscore_option: &OscoreOption,
+        sender_cred: &[u8],
+    ) -> Result<Vec<u8>, CoseError> {
+        let opt_bytes = |data: &Option<Vec<u8>>| match data {
+            Some(data) => Value::Bytes(data.clone()),
+            None => Value::Null,
+        };
+        Value::Array(vec![
+            Value::from(OSCORE_VERSION),
+            Value::Array(vec![
+                Value::from(self.group_enc_alg as i64),
+                Value::from(self.sign_alg as i64),
+                match self.pairwise_key_agreement_alg {
+                    Some(alg) => Value::from(alg as i64),
+                    None => Value::Null,
+                },
+            ]),
+            Value::Bytes(request.kid.clone()),
+            Value::Bytes(request.partial_iv.clone()),
+            Value::Bytes(options.to_vec()),
+            Value::Bytes(self.id_context.clone()),
+            Value::Bytes(oscore_option.to_vec()?),
+            Value::Bytes(sender_cred.to_vec()),
+            opt_bytes(&self.gm_credential),
+        ])
+        .to_vec()
+    }
+
+    /// Return the keystream for encrypting the countersignature of a message, derived from the
+    /// Signature Encryption Key with the Partial IV (of the message, or else of its request) as
+    /// salt, and the CBOR encoding of the following as info:
+    ///
+    /// ```cddl
+    ///   info = [
+    ///      id : bstr,
+    ///      id_context : bstr,
+    ///      type : bool,
+    ///      L : uint,
+    ///   ]
+    /// ```
+    ///
+    /// where `id` is the Sender ID of the signer and `type` is true for requests.
+    pub fn keystream<C: GroupCrypto>(
+        &self,
+        crypto: &mut C,
+        partial_iv: &[u8],
+        signer_id: &[u8],
+        is_request: bool,
+        len: usize,
+    ) -> Result<Vec<u8>, OscoreError<C::Error>> {
+        let info = Value::Array(vec![
+            Value::Bytes(signer_id.to_vec()),
+            Value::Bytes(self.id_context.clone()),
+            Value::Bool(is_request),
+            Value::from(len as u64),
+        ])
+        .to_vec()?;
+        crypto
+            .hkdf(partial_iv, &self.signature_encryption_key, &info, len)
+            .map_err(OscoreError::Crypto)
+    }
+
+    /// Return the Partial IV for the next sender sequence number, and advance the sequence number.
+    fn next_partial_iv<E>(&mut self) -> Result<Vec<u8>, OscoreError<E>> {
+        if self.sender_seq > MAX_SEQUENCE_NUMBER {
+            return Err(OscoreError::SequenceNumberExhausted);
+        }
+        let piv = partial_iv(self.sender_seq);
+        self.sender_seq += 1;
+        Ok(piv)
+    }
+
+    /// Encrypt and countersign a message from this group member, returning the payload of the
+    /// protected message.  The nonce is that of `request` unless `option` holds a Partial IV.
+    fn seal<C: GroupCrypto>(
+        &self,
+        crypto: &mut C,
+        request: &RequestInfo,
+        option: &OscoreOption,
+        plaintext: &[u8],
+        class_i_options: &[u8],
+        is_request: bool,
+    ) -> Result<Vec<u8>, OscoreError<C::Error>> {
+        let external_aad = self.external_aad(
+            request,
+            class_i_options,
+            option,
+            &self.sender_credential.credential,
+        )?;
+        let aad = enc_structure_data(
+            EncryptionContext::CoseEncrypt0,
+            ProtectedHeader::default(),
+            &external_aad,
+        );
+        let nonce = if option.partial_iv.is_empty() {
+            request.nonce.clone()
+        } else {
+            nonce(&self.common_iv, &self.sender_id, &option.partial_iv)?
+        };
+        let mut payload = crypto
+            .encrypt(&self.sender_key, &nonce, plaintext, &aad)
+            .map_err(OscoreError::Crypto)?;
+
+        let tbs = countersign_structure_data(&external_aad, &payload);
+        let signature = crypto.sign(&tbs).map_err(OscoreError::Crypto)?;
+        let sig_len = signature_len(self.sign_alg, &self.sender_credential.public_key)
+            .ok_or(OscoreError::UnsupportedAlgorithm(self.sign_alg))?;
+        if signature.len() != sig_len {
+            return Err(OscoreError::Cose(CoseError::UnexpectedItem(
+                "signature",
+                "signature of algorithm length",
+            )));
+        }
+        let salt = if option.partial_iv.is_empty() {
+            &request.partial_iv
+        } else {
+            &option.partial_iv
+        };
+        let keystream = self.keystream(crypto, salt, &self.sender_id, is_request, sig_len)?;
+        payload.extend(signature.iter().zip(keystream).map(|(s, k)| s ^ k));
+        Ok(payload)
+    }
+
+    /// Verify and decrypt a message from another group member, returning the plaintext.  The
+    /// nonce is that of `request` unless `option` holds a Partial IV.
+    fn open<C: GroupCrypto>(
+        &self,
+        crypto: &mut C,
+        request: &RequestInfo,
+        option: &OscoreOption,
+        payload: &[u8],
+        class_i_options: &[u8],
+        is_request: bool,
+    ) -> Result<Vec<u8>, OscoreError<C::Error>> {
+        let recipient = &self.recipients[self.find_sender(option)?];
+        let sig_len = signature_len(self.sign_alg, &recipient.credential.public_key)
+            .ok_or(OscoreError::UnsupportedAlgorithm(self.sign_alg))?;
+        if payload.len() < sig_len {
+            return Err(OscoreError::Cose(CoseError::UnexpectedItem(
+                "truncated payload",
+                "ciphertext and countersignature",
+            )));
+        }
+        let (ciphertext, encrypted_signature) = payload.split_at(payload.len() - sig_len);
+        let salt = if option.partial_iv.is_empty() {
+            &request.partial_iv
+        } else {
+            &option.partial_iv
+        };
+        let keystream =
+            self.keystream(crypto, salt, &recipient.recipient_id, is_request, sig_len)?;
+        let signature: Vec<u8> = encrypted_signature
+            .iter()
+            .zip(keystream)
+            .map(|(s, k)| s ^ k)
+            .collect();
+
+        let external_aad = self.external_aad(
+            request,
+            class_i_options,
+            option,
+            &recipient.credential.credential,
+        )?;
+        let tbs = countersign_structure_data(&external_aad, ciphertext);
+        crypto
+            .verify(&recipient.credential.public_key, &signature, &tbs)
+            .map_err(OscoreError::Crypto)?;
+        let aad = enc_structure_data(
+            EncryptionContext::CoseEncrypt0,
+            ProtectedHeader::default(),
+            &external_aad,
+        );
+        let nonce = if option.partial_iv.is_empty() {
+            request.nonce.clone()
+        } else {
+            nonce(&self.common_iv, &recipient.recipient_id, &option.partial_iv)?
+        };
+        crypto
+            .decrypt(&recipient.recipient_key, &nonce, ciphertext, &aad)
+            .map_err(OscoreError::Crypto)
+    }
+
+    /// Return the index of the Recipient Context for the sender of a group mode message.
+    fn find_sender<E>(&self, option: &OscoreOption) -> Result<usize, OscoreError<E>> {
+        let kid = match &option.kid {
+            Some(kid) if option.group => kid,
+            _ => {
+                return Err(OscoreError::Cose(CoseError::UnexpectedItem(
+                    "OSCORE option",
+                    "OSCORE option with Group Flag and kid",
+                )))
+            }
+        };
+        self.recipients
+            .iter()
+            .position(|r| r.recipient_id == *kid)
+            .ok_or(OscoreError::UnknownRecipient)
+    }
+
+    /// Protect a request in group mode.  The `plaintext` is the serialized code, Class E options
+    /// and payload of the request, and `class_i_options` the serialized Class I options (if any).
+    /// Returns the OSCORE option, the payload of the protected request (the ciphertext followed
+    /// by the encrypted countersignature), and the information needed to verify the responses.
+    pub fn protect_request<C: GroupCrypto>(
+        &mut self,
+        plaintext: &[u8],
+        class_i_options: &[u8],
+        crypto: &mut C,
+    ) -> Result<(OscoreOption, Vec<u8>, RequestInfo), OscoreError<C::Error>> {
+        let piv = self.next_partial_iv()?;
+        let nonce = nonce(&self.common_iv, &self.sender_id, &piv)?;
+        let option = OscoreOption {
+            partial_iv: piv.clone(),
+            kid_context: Some(self.id_context.clone()),
+            kid: Some(self.sender_id.clone()),
+            group: true,
+        };
+        let request = RequestInfo {
+            kid: self.sender_id.clone(),
+            partial_iv: piv,
+            nonce,
+        };
+        let payload = self.seal(crypto, &request, &option, plaintext, class_i_options, true)?;
+        Ok((option, payload, request))
+    }
+
+    /// Verify and decrypt a request protected in group mode.  The request's sequence number is
+    /// checked against (and, on success, recorded in) the replay window of its sender.  Returns
+    /// the plaintext and the information needed to protect the response.
+    pub fn unprotect_request<C: GroupCrypto>(
+        &mut self,
+        option: &OscoreOption,
+        payload: &[u8],
+        class_i_options: &[u8],
+        crypto: &mut C,
+    ) -> Result<(Vec<u8>, RequestInfo), OscoreError<C::Error>> {
+        let index = self.find_sender(option)?;
+        if option.partial_iv.is_empty() {
+            return Err(OscoreError::Cose(CoseError::UnexpectedItem(
+                "OSCORE option",
+                "OSCORE option with Partial IV",
+            )));
+        }
+        if option.kid_context.as_ref() != Some(&self.id_context) {
+            return Err(OscoreError::UnknownRecipient);
+        }
+        let seq = sequence_number(&option.partial_iv)?;
+        if !self.recipients[index].replay_window.check(seq) {
+            return Err(OscoreError::Replay);
+        }
+        let kid = self.recipients[index].recipient_id.clone();
+        let request = RequestInfo {
+            nonce: nonce(&self.common_iv, &kid, &option.partial_iv)?,
+            kid,
+            partial_iv: option.partial_iv.clone(),
+        };
+        let plaintext = self.open(crypto, &request, option, payload, class_i_options, true)?;
+        self.recipients[index].replay_window.update(seq);
+        Ok((plaintext, request))
+    }
+
+    /// Protect a response to `request` in group mode.  If `fresh_partial_iv` is set, the response
+    /// uses a Partial IV from the sender sequence number; otherwise it reuses the nonce of the
+    /// request.  Returns the OSCORE option and the payload of the protected response.
+    pub fn protect_response<C: GroupCrypto>(
+        &mut self,
+        request: &RequestInfo,
+        plaintext: &[u8],
+        class_i_options: &[u8],
+        fresh_partial_iv: bool,
+        crypto: &mut C,
+    ) -> Result<(OscoreOption, Vec<u8>), OscoreError<C::Error>> {
+        let mut option = OscoreOption {
+            kid: Some(self.sender_id.clone()),
+            group: true,
+            ..Default::default()
+        };
+        if fresh_partial_iv {
+            option.partial_iv = self.next_partial_iv()?;
+        }
+        let payload = self.seal(crypto, request, &option, plaintext, class_i_options, false)?;
+        Ok((option, payload))
+    }
+
+    /// Verify and decrypt a response to `request` protected in group mode, from any group member
+    /// with a Recipient Context (whose Sender ID is the `kid` of the OSCORE option).  Returns the
+    /// plaintext.
+    pub fn unprotect_response<C: GroupCrypto>(
+        &self,
+        request: &RequestInfo,
+        option: &OscoreOption,
+        payload: &[u8],
+        class_i_options: &[u8],
+        crypto: &mut C,
+    ) -> Result<Vec<u8>, OscoreError<C::Error>> {
+        self.open(crypto, request, option, payload, class_i_options, false)
+    }
+}
@@ -0,0 +1,504 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Object Security for Constrained RESTful Environments (OSCORE) functionality, as per RFC 8613.
+//!
+//! OSCORE protects CoAP messages with a `COSE_Encrypt0` object, whose ciphertext forms the payload
+//! of the protected message and whose remaining fields are compressed into the OSCORE option.  As
+//! elsewhere in this crate, the caller provides closures that perform the cryptographic operations
+//! (HKDF and AEAD); with the `hkdf` feature, `crypto::hkdf_sha256()` can be used for the former.
+//!
+//! Group OSCORE messages protected in group mode are handled by [`GroupSecurityContext`], whose
+//! operations are provided through the [`GroupCrypto`] trait.
+
+use crate::{
+    cbor::value::Value, enc_structure_data, iana, CborSerializable, CoseError, EncryptionContext,
+    ProtectedHeader,
+};
+use alloc::{vec, vec::Vec};
+
+mod group;
+pub use group::*;
+mod option;
+pub use option::*;
+mod replay;
+pub use replay::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Maximum length of a Partial IV (RFC 8613 section 6.1).
+pub const MAX_PARTIAL_IV_LEN: usize = 5;
+
+/// Maximum value of a sender sequence number (RFC 8613 section 7.2.1).
+pub const MAX_SEQUENCE_NUMBER: u64 = (1 << 40) - 1;
+
+/// OSCORE version, as held in the external AAD.
+const OSCORE_VERSION: u64 = 1;
+
+/// Return the key length and nonce length (in that order) for an AEAD algorithm, or `None` if the
+/// algorithm is not an AEAD algorithm usable with OSCORE.
+pub fn aead_lengths(alg: iana::Algorithm) -> Option<(usize, usize)> {
+    use iana::Algorithm as A;
+    Some(match alg {
+        A::AES_CCM_16_64_128 | A::AES_CCM_16_128_128 => (16, 13),
+        A::AES_CCM_16_64_256 | A::AES_CCM_16_128_256 => (32, 13),
+        A::AES_CCM_64_64_128 | A::AES_CCM_64_128_128 => (16, 7),
+        A::AES_CCM_64_64_256 | A::AES_CCM_64_128_256 => (32, 7),
+        A::A128GCM => (16, 12),
+        A::A192GCM => (24, 12),
+        A::A256GCM | A::ChaCha20Poly1305 => (32, 12),
+        _ => return None,
+    })
+}
+
+/// Encode a sequence number as a Partial IV, in the minimal number of bytes.
+pub fn partial_iv(seq: u64) -> Vec<u8> {
+    let bytes = seq.to_be_bytes();
+    let skip = bytes
+        .iter()
+        .take(bytes.len() - 1)
+        .take_while(|b| **b == 0)
+        .count();
+    bytes[skip..].to_vec()
+}
+
+/// Decode a Partial IV into a sequence number.
+pub fn sequence_number(partial_iv: &[u8]) -> Result<u64, CoseError> {
+    if partial_iv.is_empty() || partial_iv.len() > MAX_PARTIAL_IV_LEN {
+        return Err(CoseError::UnexpectedItem(
+            "invalid Partial IV",
+            "Partial IV of 1-5 bytes",
+        ));
+    }
+    Ok(partial_iv.iter().fold(0, |acc, b| (acc << 8) | *b as u64))
+}
+
+/// Compute the AEAD nonce (RFC 8613 section 5.2) from the common IV, the ID of the endpoint that
+/// generated the Partial IV, and the Partial IV.
+pub fn nonce(common_iv: &[u8], id: &[u8], partial_iv: &[u8]) -> Result<Vec<u8>, CoseError> {
+    let nonce_len = common_iv.len();
+    if nonce_len < 7 || id.len() > nonce_len - 6 {
+        return Err(CoseError::UnexpectedItem(
+            "oversized ID",
+            "ID of at most nonce length - 6 bytes",
+        ));
+    }
+    if partial_iv.len() > MAX_PARTIAL_IV_LEN {
+        return Err(CoseError::UnexpectedItem(
+            "oversized Partial IV",
+            "Partial IV of 0-5 bytes",
+        ));
+    }
+    // size of ID || left-padded ID || left-padded Partial IV
+    let mut nonce = vec![0; nonce_len];
+    nonce[0] = id.len() as u8;
+    nonce[nonce_len - MAX_PARTIAL_IV_LEN - id.len()..nonce_len - MAX_PARTIAL_IV_LEN]
+        .copy_from_slice(id);
+    nonce[nonce_len - partial_iv.len()..].copy_from_slice(partial_iv);
+    for (n, iv) in nonce.iter_mut().zip(common_iv) {
+        *n ^= iv;
+    }
+    Ok(nonce)
+}
+
+/// Return the external AAD (RFC 8613 section 5.4), which is the CBOR encoding of:
+///
+/// ```cddl
+///   aad_array = [
+///      oscore_version : uint,
+///      algorithms : [ alg_aead : int / tstr ],
+///      request_kid : bstr,
+///      request_piv : bstr,
+///      options : bstr,
+///   ]
+/// ```
+///
+/// where `options` holds the serialized Class I CoAP options (if any).
+pub fn external_aad(
+    alg: iana::Algorithm,
+    request_kid: &[u8],
+    request_piv: &[u8],
+    options: &[u8],
+) -> Vec<u8> {
+    Value::Array(vec![
+        Value::from(OSCORE_VERSION),
+        Value::Array(vec![Value::from(alg as i64)]),
+        Value::Bytes(request_kid.to_vec()),
+        Value::Bytes(request_piv.to_vec()),
+        Value::Bytes(options.to_vec()),
+    ])
+    .to_vec()
+    .expect("failed to serialize AAD") // safe: always serializable
+}
+
+/// Return the AAD for the AEAD algorithm, which is the `Enc_structure` of a `COSE_Encrypt0` with
+/// an empty protected header and the [`external_aad`].
+pub fn aad(
+    alg: iana::Algorithm,
+    request_kid: &[u8],
+    request_piv: &[u8],
+    options: &[u8],
+) -> Vec<u8> {
+    enc_structure_data(
+        EncryptionContext::CoseEncrypt0,
+        ProtectedHeader::default(),
+        &external_aad(alg, request_kid, request_piv, options),
+    )
+}
+
+/// The error type returned by OSCORE operations.
+#[derive(Debug)]
+pub enum OscoreError<E> {
+    /// Data could not be encoded or parsed.
+    Cose(CoseError),
+    /// The AEAD algorithm is not supported.
+    UnsupportedAlgorithm(iana::Algorithm),
+    /// A sender or recipient ID is invalid for the AEAD algorithm.
+    InvalidId,
+    /// The sender sequence number space is exhausted, so a new security context is needed.
+    SequenceNumberExhausted,
+    /// The kid (or kid context) of a request does not match the security context.
+    UnknownRecipient,
+    /// The request has already been received, or is too old to check.
+    Replay,
+    /// The cryptographic operation failed.
+    Crypto(E),
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> core::error::Error for OscoreError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Display for OscoreError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OscoreError::Cose(e) => write!(f, "invalid OSCORE data: {e}"),
+            OscoreError::UnsupportedAlgorithm(alg) => write!(f, "unsupported algorithm {alg:?}"),
+            OscoreError::InvalidId => write!(f, "invalid sender or recipient ID"),
+            OscoreError::SequenceNumberExhausted => write!(f, "sequence numbers exhausted"),
+            OscoreError::UnknownRecipient => write!(f, "unknown recipient"),
+            OscoreError::Replay => write!(f, "replayed message"),
+            OscoreError::Crypto(e) => write!(f, "cryptographic operation failed: {e}"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for OscoreError<E> {
+    fn from(e: CoseError) -> Self {
+        OscoreError::Cose(e)
+    }
+}
+
+/// Input parameters for the derivation of an OSCORE security context (RFC 8613 section 3.2).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecurityContextParams {
+    /// Master Secret.
+    pub master_secret: Vec<u8>,
+    /// Master Salt, which defaults to empty.
+    pub master_salt: Vec<u8>,
+    /// ID Context, if any.
+    pub id_context: Option<Vec<u8>>,
+    /// Sender ID.
+    pub sender_id: Vec<u8>,
+    /// Recipient ID.
+    pub recipient_id: Vec<u8>,
+    /// AEAD algorithm, which defaults to AES-CCM-16-64-128.
+    pub aead_alg: iana::Algorithm,
+}
+
+impl Default for SecurityContextParams {
+    fn default() -> Self {
+        Self {
+            master_secret: Vec::new(),
+            master_salt: Vec::new(),
+            id_context: None,
+            sender_id: Vec::new(),
+            recipient_id: Vec::new(),
+            aead_alg: iana::Algorithm::AES_CCM_16_64_128,
+        }
+    }
+}
+
+/// Information about a request that is needed to protect or verify the corresponding response.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RequestInfo {
+    /// Key identifier of the request, which is the sender ID of the client.
+    pub kid: Vec<u8>,
+    /// Partial IV of the request.
+    pub partial_iv: Vec<u8>,
+    /// AEAD nonce of the request.
+    pub nonce: Vec<u8>,
+}
+
+/// OSCORE security context (RFC 8613 section 3.1).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecurityContext {
+    /// AEAD algorithm.
+    pub aead_alg: iana::Algorithm,
+    /// ID Context, if any.
+    pub id_context: Option<Vec<u8>>,
+    /// Common IV.
+    pub common_iv: Vec<u8>,
+    /// Sender ID.
+    pub sender_id: Vec<u8>,
+    /// Sender Key.
+    pub sender_key: Vec<u8>,
+    /// Sender Sequence Number, to be used for the next message that needs a Partial IV.
+    pub sender_seq: u64,
+    /// Recipient ID.
+    pub recipient_id: Vec<u8>,
+    /// Recipient Key.
+    pub recipient_key: Vec<u8>,
+    /// Replay window for requests received.
+    pub replay_window: ReplayWindow,
+}
+
+/// Check that an OSCORE option is not for a Group OSCORE message protected in group mode.
+fn check_not_group(option: &OscoreOption) -> Result<(), CoseError> {
+    if option.group {
+        return Err(CoseError::UnexpectedItem(
+            "group mode OSCORE option",
+            "OSCORE option without Group Flag",
+        ));
+    }
+    Ok(())
+}
+
+/// Return the CBOR encoding of the `info` input to HKDF (RFC 8613 section 3.2.1).
+///
+/// ```cddl
+///   info = [
+///      id : bstr,
+///      id_context : bstr / nil,
+///      alg_aead : int / tstr,
+///      type : tstr,
+///      L : uint,
+///   ]
+/// ```
+fn hkdf_info(
+    id: &[u8],
+    id_context: Option<&[u8]>,
+    alg: iana::Algorithm,
+    info_type: &str,
+    len: usize,
+) -> Result<Vec<u8>, CoseError> {
+    Value::Array(vec![
+        Value::Bytes(id.to_vec()),
+        match id_context {
+            Some(id_context) => Value::Bytes(id_context.to_vec()),
+            None => Value::Null,
+        },
+        Value::from(alg as i64),
+        Value::Text(info_type.into()),
+        Value::from(len as u64),
+    ])
+    .to_vec()
+}
+
+impl SecurityContext {
+    /// Derive a security context from its input parameters, using `hkdf` to perform HKDF
+    /// (with the HKDF algorithm of the context, by default HKDF SHA-256) on the salt, input keying
+    /// material, info and output length (in that order).
+    pub fn derive<F, E>(params: &SecurityContextParams, mut hkdf: F) -> Result<Self, OscoreError<E>>
+    where
+        F: FnMut(&[u8], &[u8], &[u8], usize) -> Result<Vec<u8>, E>,
+    {
+        let (key_len, nonce_len) = aead_lengths(params.aead_alg)
+            .ok_or(OscoreError::UnsupportedAlgorithm(params.aead_alg))?;
+        if params.sender_id.len() > nonce_len - 6
+            || params.recipient_id.len() > nonce_len - 6
+            || params.sender_id == params.recipient_id
+        {
+            return Err(OscoreError::InvalidId);
+        }
+        let mut derive = |id: &[u8], info_type: &str, len: usize| {
+            let info = hkdf_info(
+                id,
+                params.id_context.as_deref(),
+                params.aead_alg,
+                info_type,
+                len,
+            )?;
+            hkdf(&params.master_salt, &params.master_secret, &info, len)
+                .map_err(OscoreError::Crypto)
+        };
+        Ok(Self {
+            aead_alg: params.aead_alg,
+            id_context: params.id_context.clone(),
+            common_iv: derive(&[], "IV", nonce_len)?,
+            sender_id: params.sender_id.clone(),
+            sender_key: derive(&params.sender_id, "Key", key_len)?,
+            sender_seq: 0,
+            recipient_id: params.recipient_id.clone(),
+            recipient_key: derive(&params.recipient_id, "Key", key_len)?,
+            replay_window: ReplayWindow::default(),
+        })
+    }
+
+    /// Return the Partial IV for the next sender sequence number, and advance the sequence number.
+    fn next_partial_iv<E>(&mut self) -> Result<Vec<u8>, OscoreError<E>> {
+        if self.sender_seq > MAX_SEQUENCE_NUMBER {
+            return Err(OscoreError::SequenceNumberExhausted);
+        }
+        let piv = partial_iv(self.sender_seq);
+        self.sender_seq += 1;
+        Ok(piv)
+    }
+
+    /// Protect a request, using `cipher` to encrypt the plaintext with the key, nonce and AAD (in
+    /// that order of arguments after the plaintext).  The `plaintext` is the serialized code,
+    /// Class E options and payload of the request, and `class_i_options` the serialized Class I
+    /// options (if any).  Returns the OSCORE option, the ciphertext to be used as the payload of
+    /// the protected request, and the information needed to verify the response.
+    pub fn protect_request<F, E>(
+        &mut self,
+        plaintext: &[u8],
+        class_i_options: &[u8],
+        cipher: F,
+    ) -> Result<(OscoreOption, Vec<u8>, RequestInfo), OscoreError<E>>
+    where
+        F: FnOnce(&[u8], &[u8], &[u8], &[u8]) -> Result<Vec<u8>, E>,
+    {
+        let piv = self.next_partial_iv()?;
+        let nonce = nonce(&self.common_iv, &self.sender_id, &piv)?;
+        let aad = aad(self.aead_alg, &self.sender_id, &piv, class_i_options);
+        let ciphertext =
+            cipher(&self.sender_key, &nonce, plaintext, &aad).map_err(OscoreError::Crypto)?;
+        let option = OscoreOption {
+            partial_iv: piv.clone(),
+            kid_context: self.id_context.clone(),
+            kid: Some(self.sender_id.clone()),
+            group: false,
+        };
+        let request = RequestInfo {
+            kid: self.sender_id.clone(),
+            partial_iv: piv,
+            nonce,
+        };
+        Ok((option, ciphertext, request))
+    }
+
+    /// Verify and decrypt a request, using `decipher` to decrypt the ciphertext with the key,
+    /// nonce and AAD (in that order of arguments after the ciphertext).  The request's sequence
+    /// number is checked against (and, on success, recorded in) the replay window.  Returns the
+    /// plaintext and the information needed to protect the response.
+    pub fn unprotect_request<F, E>(
+        &mut self,
+        option: &OscoreOption,
+        ciphertext: &[u8],
+        class_i_options: &[u8],
+        decipher: F,
+    ) -> Result<(Vec<u8>, RequestInfo), OscoreError<E>>
+    where
+        F: FnOnce(&[u8], &[u8], &[u8], &[u8]) -> Result<Vec<u8>, E>,
+    {
+        check_not_group(option)?;
+        let kid = match &option.kid {
+            Some(kid) if !option.partial_iv.is_empty() => kid,
+            _ => {
+                return Err(OscoreError::Cose(CoseError::UnexpectedItem(
+                    "OSCORE option",
+                    "OSCORE option with kid and Partial IV",
+                )))
+            }
+        };
+        if *kid != self.recipient_id {
+            return Err(OscoreError::UnknownRecipient);
+        }
+        if option.kid_context.is_some() && option.kid_context != self.id_context {
+            return Err(OscoreError::UnknownRecipient);
+        }
+        let seq = sequence_number(&option.partial_iv)?;
+        if !self.replay_window.check(seq) {
+            return Err(OscoreError::Replay);
+        }
+        let nonce = nonce(&self.common_iv, kid, &option.partial_iv)?;
+        let aad = aad(self.aead_alg, kid, &option.partial_iv, class_i_options);
+        let plaintext =
+            decipher(&self.recipient_key, &nonce, ciphertext, &aad).map_err(OscoreError::Crypto)?;
+        self.replay_window.update(seq);
+        let request = RequestInfo {
+            kid: kid.clone(),
+            partial_iv: option.partial_iv.clone(),
+            nonce,
+        };
+        Ok((plaintext, request))
+    }
+
+    /// Protect a response to `request`, using `cipher` as for [`SecurityContext::protect_request`].
+    /// If `fresh_partial_iv` is set, the response uses a Partial IV from the sender sequence number
+    /// (as needed for Observe notifications); otherwise it reuses the nonce of the request.
+    /// Returns the OSCORE option and the ciphertext.
+    pub fn protect_response<F, E>(
+        &mut self,
+        request: &RequestInfo,
+        plaintext: &[u8],
+        class_i_options: &[u8],
+        fresh_partial_iv: bool,
+        cipher: F,
+    ) -> Result<(OscoreOption, Vec<u8>), OscoreError<E>>
+    where
+        F: FnOnce(&[u8], &[u8], &[u8], &[u8]) -> Result<Vec<u8>, E>,
+    {
+        let (option, nonce) = if fresh_partial_iv {
+            let piv = self.next_partial_iv()?;
+            let nonce = nonce(&self.common_iv, &self.sender_id, &piv)?;
+            let option = OscoreOption {
+                partial_iv: piv,
+                ..Default::default()
+            };
+            (option, nonce)
+        } else {
+            (OscoreOption::default(), request.nonce.clone())
+        };
+        let aad = aad(
+            self.aead_alg,
+            &request.kid,
+            &request.partial_iv,
+            class_i_options,
+        );
+        let ciphertext =
+            cipher(&self.sender_key, &nonce, plaintext, &aad).map_err(OscoreError::Crypto)?;
+        Ok((option, ciphertext))
+    }
+
+    /// Verify and decrypt a response to `request`, using `decipher` as for
+    /// [`SecurityContext::unprotect_request`].  Returns the plaintext.
+    pub fn unprotect_response<F, E>(
+        &self,
+        request: &RequestInfo,
+        option: &OscoreOption,
+        ciphertext: &[u8],
+        class_i_options: &[u8],
+        decipher: F,
+    ) -> Result<Vec<u8>, OscoreError<E>>
+    where
+        F: FnOnce(&[u8], &[u8], &[u8], &[u8]) -> Result<Vec<u8>, E>,
+    {
+        check_not_group(option)?;
+        let nonce = if option.partial_iv.is_empty() {
+            request.nonce.clone()
+        } else {
+            nonce(&self.common_iv, &self.recipient_id, &option.partial_iv)?
+        };
+        let aad = aad(
+            self.aead_alg,
+            &request.kid,
+            &request.partial_iv,
+            class_i_options,
+        );
+        decipher(&self.recipient_key, &nonce, ciphertext, &aad).map_err(OscoreError::Crypto)
+    }
+}
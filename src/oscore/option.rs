@@ -0,0 +1,151 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! OSCORE option encoding.
+
+use super::MAX_PARTIAL_IV_LEN;
+use crate::CoseError;
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+const FLAG_PARTIAL_IV_LEN: u8 = 0x07;
+const FLAG_KID: u8 = 0x08;
+const FLAG_KID_CONTEXT: u8 = 0x10;
+const FLAG_GROUP: u8 = 0x20;
+const FLAG_RESERVED: u8 = 0xc0;
+
+/// Value of the OSCORE option, which holds the compressed COSE object (RFC 8613 section 6.1)
+/// apart from the ciphertext.  The Group Flag `g` is only set for Group OSCORE messages protected
+/// in group mode.
+///
+/// ```text
+///    0 1 2 3 4 5 6 7 <------------- n bytes -------------->
+///   +-+-+-+-+-+-+-+-+--------------------------------------
+///   |0 0|g|h|k|  n  |       Partial IV (if any) ...
+///   +-+-+-+-+-+-+-+-+--------------------------------------
+///
+///    <- 1 byte -> <----- s bytes ------>
+///   +------------+----------------------+------------------+
+///   | s (if any) | kid context (if any) | kid (if any) ... |
+///   +------------+----------------------+------------------+
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OscoreOption {
+    /// Partial IV, which is empty if absent.
+    pub partial_iv: Vec<u8>,
+    /// Key identifier context, if present.
+    pub kid_context: Option<Vec<u8>>,
+    /// Key identifier, if present.  Note that an empty key identifier is distinct from an absent
+    /// key identifier.
+    pub kid: Option<Vec<u8>>,
+    /// Whether the Group Flag is set.
+    pub group: bool,
+}
+
+impl OscoreOption {
+    /// Decode an OSCORE option value.
+    pub fn from_slice(data: &[u8]) -> Result<Self, CoseError> {
+        let Some((&flags, mut data)) = data.split_first() else {
+            return Ok(Self::default());
+        };
+        if flags == 0 {
+            return Err(CoseError::UnexpectedItem(
+                "all-zero flags",
+                "empty option value",
+            ));
+        }
+        if flags & FLAG_RESERVED != 0 {
+            return Err(CoseError::UnexpectedItem(
+                "reserved flag bits",
+                "OSCORE option flags",
+            ));
+        }
+        let truncated = || CoseError::UnexpectedItem("truncated data", "complete OSCORE option");
+
+        let n = (flags & FLAG_PARTIAL_IV_LEN) as usize;
+        if n > MAX_PARTIAL_IV_LEN {
+            return Err(CoseError::UnexpectedItem(
+                "reserved Partial IV length",
+                "Partial IV of 0-5 bytes",
+            ));
+        }
+        if data.len() < n {
+            return Err(truncated());
+        }
+        let (partial_iv, rest) = data.split_at(n);
+        data = rest;
+
+        let kid_context = if flags & FLAG_KID_CONTEXT != 0 {
+            let (&s, rest) = data.split_first().ok_or_else(truncated)?;
+            if rest.len() < s as usize {
+                return Err(truncated());
+            }
+            let (kid_context, rest) = rest.split_at(s as usize);
+            data = rest;
+            Some(kid_context.to_vec())
+        } else {
+            None
+        };
+
+        let kid = if flags & FLAG_KID != 0 {
+            Some(data.to_vec())
+        } else if !data.is_empty() {
+            return Err(CoseError::ExtraneousData);
+        } else {
+            None
+        };
+
+        Ok(Self {
+            partial_iv: partial_iv.to_vec(),
+            kid_context,
+            kid,
+            group: flags & FLAG_GROUP != 0,
+        })
+    }
+
+    /// Encode as an OSCORE option value, which is empty if no fields are present.
+    pub fn to_vec(&self) -> Result<Vec<u8>, CoseError> {
+        if self.partial_iv.len() > MAX_PARTIAL_IV_LEN {
+            return Err(CoseError::UnexpectedItem(
+                "oversized Partial IV",
+                "Partial IV of 0-5 bytes",
+            ));
+        }
+        let mut flags = self.partial_iv.len() as u8;
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.partial_iv);
+        if let Some(kid_context) = &self.kid_context {
+            let s: u8 = kid_context.len().try_into().map_err(|_| {
+                CoseError::UnexpectedItem("oversized kid context", "kid context of 0-255 bytes")
+            })?;
+            flags |= FLAG_KID_CONTEXT;
+            data.push(s);
+            data.extend_from_slice(kid_context);
+        }
+        if let Some(kid) = &self.kid {
+            flags |= FLAG_KID;
+            data.extend_from_slice(kid);
+        }
+        if self.group {
+            flags |= FLAG_GROUP;
+        }
+        if flags == 0 {
+            return Ok(Vec::new());
+        }
+        data.insert(0, flags);
+        Ok(data)
+    }
+}
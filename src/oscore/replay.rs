@@ -0,0 +1,107 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! OSCORE replay protection.
+
+/// Default size of a [`ReplayWindow`], as recommended by RFC 8613 section 7.4.
+pub const DEFAULT_REPLAY_WINDOW_SIZE: u32 = 32;
+
+/// Maximum size of a [`ReplayWindow`].
+pub const MAX_REPLAY_WINDOW_SIZE: u32 = 64;
+
+/// Sliding replay window over received sender sequence numbers (RFC 8613 section 7.4), as per
+/// RFC 4303 section 3.4.3.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReplayWindow {
+    size: u32,
+    /// Highest sequence number received, if any.
+    highest: Option<u64>,
+    /// Bit `i` is set if sequence number `highest - i` has been received.
+    received: u64,
+}
+
+impl Default for ReplayWindow {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_WINDOW_SIZE)
+    }
+}
+
+impl ReplayWindow {
+    /// Create an empty replay window that tracks `size` sequence numbers.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `size` is zero or greater than [`MAX_REPLAY_WINDOW_SIZE`].
+    pub fn new(size: u32) -> Self {
+        assert!(
+            size > 0 && size <= MAX_REPLAY_WINDOW_SIZE,
+            "invalid replay window size"
+        ); // safe: documented
+        Self {
+            size,
+            highest: None,
+            received: 0,
+        }
+    }
+
+    /// Return the number of sequence numbers tracked by the window.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Return the highest sequence number received, if any.
+    pub fn highest(&self) -> Option<u64> {
+        self.highest
+    }
+
+    /// Indicate whether a message with sequence number `seq` is acceptable, i.e. is neither a
+    /// replay nor too old to tell.
+    pub fn check(&self, seq: u64) -> bool {
+        match self.highest {
+            None => true,
+            Some(highest) if seq > highest => true,
+            Some(highest) => {
+                let offset = highest - seq;
+                offset < self.size as u64 && self.received & (1 << offset) == 0
+            }
+        }
+    }
+
+    /// Record receipt of a (successfully verified) message with sequence number `seq`.
+    pub fn update(&mut self, seq: u64) {
+        match self.highest {
+            Some(highest) if seq <= highest => {
+                let offset = highest - seq;
+                if offset < self.size as u64 {
+                    self.received |= 1 << offset;
+                }
+            }
+            Some(highest) => {
+                let shift = seq - highest;
+                self.received = if shift < 64 {
+                    (self.received << shift) | 1
+                } else {
+                    1
+                };
+                self.highest = Some(seq);
+            }
+            None => {
+                self.received = 1;
+                self.highest = Some(seq);
+            }
+        }
+    }
+}
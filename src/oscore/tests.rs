@@ -0,0 +1,747 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+mod group;
+
+use super::*;
+use crate::util::expect_err;
+use alloc::{borrow::ToOwned, string::ToString};
+
+const MASTER_SECRET: &str = "0102030405060708090a0b0c0d0e0f10";
+const MASTER_SALT: &str = "9e7ca92223786340";
+const ID_CONTEXT: &str = "37cbf3210017a2d3";
+
+/// Security context parameters for the clients of RFC 8613 Appendix C.1.1, C.2.1 and C.3.1.
+fn client_params() -> Vec<SecurityContextParams> {
+    let secret = hex::decode(MASTER_SECRET).unwrap();
+    let salt = hex::decode(MASTER_SALT).unwrap();
+    vec![
+        SecurityContextParams {
+            master_secret: secret.clone(),
+            master_salt: salt.clone(),
+            sender_id: vec![],
+            recipient_id: vec![0x01],
+            ..Default::default()
+        },
+        SecurityContextParams {
+            master_secret: secret.clone(),
+            sender_id: vec![0x00],
+            recipient_id: vec![0x01],
+            ..Default::default()
+        },
+        SecurityContextParams {
+            master_secret: secret,
+            master_salt: salt,
+            id_context: Some(hex::decode(ID_CONTEXT).unwrap()),
+            sender_id: vec![],
+            recipient_id: vec![0x01],
+            ..Default::default()
+        },
+    ]
+}
+
+/// Derived client security contexts, as listed in RFC 8613 Appendix C.1.1, C.2.1 and C.3.1.
+fn client_contexts() -> Vec<SecurityContext> {
+    let derived = [
+        (
+            "f0910ed7295e6ad4b54fc793154302ff",
+            "ffb14e093c94c9cac9471648b4f98710",
+            "4622d4dd6d944168eefb54987c",
+        ),
+        (
+            "321b26943253c7ffb6003b0b64d74041",
+            "e57b5635815177cd679ab4bcec9d7dda",
+            "be35ae297d2dace910c52e99f9",
+        ),
+        (
+            "af2a1300a5e95788b356336eeecd2b92",
+            "e39a0c7c77b43f03b4b39ab9a268699f",
+            "2ca58fb85ff1b81c0b7181b85e",
+        ),
+    ];
+    client_params()
+        .into_iter()
+        .zip(derived)
+        .map(
+            |(params, (sender_key, recipient_key, common_iv))| SecurityContext {
+                aead_alg: params.aead_alg,
+                id_context: params.id_context,
+                common_iv: hex::decode(common_iv).unwrap(),
+                sender_id: params.sender_id,
+                sender_key: hex::decode(sender_key).unwrap(),
+                sender_seq: 0,
+                recipient_id: params.recipient_id,
+                recipient_key: hex::decode(recipient_key).unwrap(),
+                replay_window: ReplayWindow::default(),
+            },
+        )
+        .collect()
+}
+
+/// Return the server security context corresponding to a client security context.
+fn server_context(client: &SecurityContext) -> SecurityContext {
+    SecurityContext {
+        sender_id: client.recipient_id.clone(),
+        sender_key: client.recipient_key.clone(),
+        recipient_id: client.sender_id.clone(),
+        recipient_key: client.sender_key.clone(),
+        ..client.clone()
+    }
+}
+
+#[cfg(feature = "hkdf")]
+#[test]
+fn test_security_context_derive() {
+    let hkdf = |salt: &[u8], ikm: &[u8], info: &[u8], len: usize| {
+        crate::crypto::hkdf_sha256(salt, ikm, info, len).ok_or("output too long")
+    };
+    for (i, (params, want)) in client_params()
+        .into_iter()
+        .zip(client_contexts())
+        .enumerate()
+    {
+        let got = SecurityContext::derive(&params, hkdf).unwrap();
+        assert_eq!(got, want, "case {}", i);
+
+        let server_params = SecurityContextParams {
+            sender_id: params.recipient_id.clone(),
+            recipient_id: params.sender_id.clone(),
+            ..params
+        };
+        let got = SecurityContext::derive(&server_params, hkdf).unwrap();
+        assert_eq!(got, server_context(&want), "case {}", i);
+    }
+}
+
+#[test]
+fn test_security_context_derive_info() {
+    // HKDF info values from RFC 8613 Appendix C.1.1, C.2.1 and C.3.1.
+    let want = [
+        (
+            "8540f60a634b657910",
+            "854101f60a634b657910",
+            "8540f60a6249560d",
+        ),
+        (
+            "854100f60a634b657910",
+            "854101f60a634b657910",
+            "8540f60a6249560d",
+        ),
+        (
+            "85404837cbf3210017a2d30a634b657910",
+            "8541014837cbf3210017a2d30a634b657910",
+            "85404837cbf3210017a2d30a6249560d",
+        ),
+    ];
+    for (i, (params, (sender_info, recipient_info, iv_info))) in
+        client_params().into_iter().zip(want).enumerate()
+    {
+        let mut infos = Vec::new();
+        let got = SecurityContext::derive(&params, |salt, ikm, info, len| {
+            assert_eq!(hex::encode(salt), hex::encode(&params.master_salt));
+            assert_eq!(hex::encode(ikm), MASTER_SECRET);
+            infos.push((hex::encode(info), len));
+            Ok::<_, ()>(vec![0; len])
+        })
+        .unwrap();
+        assert_eq!(got.sender_key.len(), 16);
+        assert_eq!(got.common_iv.len(), 13);
+        infos.sort();
+        let mut want = vec![
+            (sender_info.to_owned(), 16),
+            (recipient_info.to_owned(), 16),
+            (iv_info.to_owned(), 13),
+        ];
+        want.sort();
+        assert_eq!(infos, want, "case {}", i);
+    }
+}
+
+#[test]
+fn test_security_context_derive_fail() {
+    let hkdf = |_salt: &[u8], _ikm: &[u8], _info: &[u8], len: usize| Ok(vec![0; len]);
+    let tests: Vec<(SecurityContextParams, &str)> = vec![
+        (
+            SecurityContextParams {
+                aead_alg: iana::Algorithm::ES256,
+                recipient_id: vec![1],
+                ..Default::default()
+            },
+            "unsupported algorithm ES256",
+        ),
+        (
+            SecurityContextParams {
+                sender_id: vec![1; 8],
+                ..Default::default()
+            },
+            "invalid sender or recipient ID",
+        ),
+        (
+            SecurityContextParams {
+                aead_alg: iana::Algorithm::AES_CCM_64_64_128,
+                sender_id: vec![1, 2],
+                ..Default::default()
+            },
+            "invalid sender or recipient ID",
+        ),
+        (
+            SecurityContextParams {
+                sender_id: vec![1],
+                recipient_id: vec![1],
+                ..Default::default()
+            },
+            "invalid sender or recipient ID",
+        ),
+    ];
+    for (params, err_msg) in tests {
+        let result = SecurityContext::derive::<_, &str>(&params, hkdf);
+        assert_eq!(result.unwrap_err().to_string(), err_msg);
+    }
+
+    let params = SecurityContextParams {
+        recipient_id: vec![1],
+        ..Default::default()
+    };
+    let result = SecurityContext::derive(&params, |_, _, _, _| Err("hkdf failed"));
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "cryptographic operation failed: hkdf failed"
+    );
+}
+
+#[test]
+fn test_aead_lengths() {
+    use iana::Algorithm as A;
+    assert_eq!(aead_lengths(A::AES_CCM_16_64_128), Some((16, 13)));
+    assert_eq!(aead_lengths(A::AES_CCM_64_128_256), Some((32, 7)));
+    assert_eq!(aead_lengths(A::A128GCM), Some((16, 12)));
+    assert_eq!(aead_lengths(A::ChaCha20Poly1305), Some((32, 12)));
+    assert_eq!(aead_lengths(A::A128KW), None);
+}
+
+#[test]
+fn test_partial_iv() {
+    let tests = [
+        (0, "00"),
+        (0x14, "14"),
+        (0xff, "ff"),
+        (0x100, "0100"),
+        (0x0102030405, "0102030405"),
+        (MAX_SEQUENCE_NUMBER, "ffffffffff"),
+    ];
+    for (seq, want) in tests {
+        assert_eq!(hex::encode(partial_iv(seq)), want, "for {}", seq);
+        assert_eq!(
+            sequence_number(&hex::decode(want).unwrap()).unwrap(),
+            seq,
+            "for {}",
+            seq
+        );
+    }
+    assert_eq!(sequence_number(&[0, 0, 1]).unwrap(), 1);
+    expect_err(sequence_number(&[]), "Partial IV of 1-5 bytes");
+    expect_err(sequence_number(&[1; 6]), "Partial IV of 1-5 bytes");
+}
+
+#[test]
+fn test_nonce() {
+    // Nonces from RFC 8613 Appendix C.1.1, C.2.1, C.3.1 (with Partial IV 0) and C.4-C.6.
+    let tests = [
+        (
+            "4622d4dd6d944168eefb54987c",
+            "",
+            "00",
+            "4622d4dd6d944168eefb54987c",
+        ),
+        (
+            "4622d4dd6d944168eefb54987c",
+            "01",
+            "00",
+            "4722d4dd6d944169eefb54987c",
+        ),
+        (
+            "be35ae297d2dace910c52e99f9",
+            "00",
+            "00",
+            "bf35ae297d2dace910c52e99f9",
+        ),
+        (
+            "be35ae297d2dace910c52e99f9",
+            "01",
+            "00",
+            "bf35ae297d2dace810c52e99f9",
+        ),
+        (
+            "2ca58fb85ff1b81c0b7181b85e",
+            "",
+            "00",
+            "2ca58fb85ff1b81c0b7181b85e",
+        ),
+        (
+            "2ca58fb85ff1b81c0b7181b85e",
+            "01",
+            "00",
+            "2da58fb85ff1b81d0b7181b85e",
+        ),
+        (
+            "4622d4dd6d944168eefb54987c",
+            "",
+            "14",
+            "4622d4dd6d944168eefb549868",
+        ),
+        (
+            "be35ae297d2dace910c52e99f9",
+            "00",
+            "14",
+            "bf35ae297d2dace910c52e99ed",
+        ),
+        (
+            "2ca58fb85ff1b81c0b7181b85e",
+            "",
+            "14",
+            "2ca58fb85ff1b81c0b7181b84a",
+        ),
+        (
+            "00000000000000000000000000",
+            "01020304050607",
+            "0a0b0c0d0e",
+            "07010203040506070a0b0c0d0e",
+        ),
+        ("00000000000000", "01", "", "01010000000000"),
+    ];
+    for (common_iv, id, piv, want) in tests {
+        let got = nonce(
+            &hex::decode(common_iv).unwrap(),
+            &hex::decode(id).unwrap(),
+            &hex::decode(piv).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(hex::encode(got), want, "for {} {} {}", common_iv, id, piv);
+    }
+
+    expect_err(nonce(&[0; 13], &[1; 8], &[0]), "ID of at most");
+    expect_err(nonce(&[0; 7], &[1; 2], &[0]), "ID of at most");
+    expect_err(nonce(&[0; 6], &[], &[0]), "ID of at most");
+    expect_err(nonce(&[0; 13], &[], &[1; 6]), "Partial IV of 0-5 bytes");
+}
+
+#[test]
+fn test_aad() {
+    // AAD values from RFC 8613 Appendix C.4 and C.5.
+    let tests = [
+        (
+            "",
+            "8501810a40411440",
+            "8368456e63727970743040488501810a40411440",
+        ),
+        (
+            "00",
+            "8501810a4100411440",
+            "8368456e63727970743040498501810a4100411440",
+        ),
+    ];
+    for (kid, want_external, want) in tests {
+        let kid = hex::decode(kid).unwrap();
+        let alg = iana::Algorithm::AES_CCM_16_64_128;
+        assert_eq!(
+            hex::encode(external_aad(alg, &kid, &[0x14], &[])),
+            want_external
+        );
+        assert_eq!(hex::encode(aad(alg, &kid, &[0x14], &[])), want);
+    }
+
+    // Class I options are included.
+    assert_eq!(
+        hex::encode(external_aad(iana::Algorithm::A128GCM, &[1], &[2], &[3, 4])),
+        "8501810141014102420304"
+    );
+}
+
+#[test]
+fn test_oscore_option() {
+    let tests = [
+        (OscoreOption::default(), ""),
+        (
+            OscoreOption {
+                partial_iv: vec![0x14],
+                kid: Some(vec![]),
+                ..Default::default()
+            },
+            "0914",
+        ),
+        (
+            OscoreOption {
+                partial_iv: vec![0x14],
+                kid: Some(vec![0x00]),
+                ..Default::default()
+            },
+            "091400",
+        ),
+        (
+            OscoreOption {
+                partial_iv: vec![0x14],
+                kid_context: Some(hex::decode(ID_CONTEXT).unwrap()),
+                kid: Some(vec![]),
+                ..Default::default()
+            },
+            "19140837cbf3210017a2d3",
+        ),
+        (
+            OscoreOption {
+                partial_iv: vec![0x00],
+                ..Default::default()
+            },
+            "0100",
+        ),
+        (
+            OscoreOption {
+                kid_context: Some(vec![]),
+                ..Default::default()
+            },
+            "1000",
+        ),
+        (
+            OscoreOption {
+                partial_iv: vec![1, 2, 3, 4, 5],
+                kid_context: Some(vec![6]),
+                kid: Some(vec![7, 8]),
+                ..Default::default()
+            },
+            "1d01020304050106 0708",
+        ),
+        (
+            OscoreOption {
+                partial_iv: vec![0x14],
+                kid: Some(vec![0x52]),
+                group: true,
+                ..Default::default()
+            },
+            "291452",
+        ),
+        (
+            OscoreOption {
+                group: true,
+                ..Default::default()
+            },
+            "20",
+        ),
+    ];
+    for (option, want) in tests {
+        let want = want.replace(' ', "");
+        assert_eq!(hex::encode(option.to_vec().unwrap()), want);
+        let got = OscoreOption::from_slice(&hex::decode(&want).unwrap()).unwrap();
+        assert_eq!(got, option, "for {}", want);
+    }
+
+    let tests = [
+        ("00", "empty option value"),
+        ("40", "OSCORE option flags"),
+        ("81", "OSCORE option flags"),
+        ("06010203040506", "Partial IV of 0-5 bytes"),
+        ("07", "Partial IV of 0-5 bytes"),
+        ("02", "complete OSCORE option"),
+        ("0201", "complete OSCORE option"),
+        ("10", "complete OSCORE option"),
+        ("1002", "complete OSCORE option"),
+        ("110102ff", "complete OSCORE option"),
+        ("011400", "extraneous data"),
+    ];
+    for (data, err_msg) in tests {
+        expect_err(
+            OscoreOption::from_slice(&hex::decode(data).unwrap()),
+            err_msg,
+        );
+    }
+
+    let option = OscoreOption {
+        partial_iv: vec![1; 6],
+        ..Default::default()
+    };
+    expect_err(option.to_vec(), "Partial IV of 0-5 bytes");
+    let option = OscoreOption {
+        kid_context: Some(vec![1; 256]),
+        ..Default::default()
+    };
+    expect_err(option.to_vec(), "kid context of 0-255 bytes");
+}
+
+#[test]
+fn test_replay_window() {
+    let mut window = ReplayWindow::default();
+    assert_eq!(window.size(), DEFAULT_REPLAY_WINDOW_SIZE);
+    assert_eq!(window.highest(), None);
+    assert!(window.check(0));
+    assert!(window.check(100));
+
+    window.update(5);
+    assert_eq!(window.highest(), Some(5));
+    assert!(!window.check(5));
+    assert!(window.check(4));
+    assert!(window.check(0));
+    assert!(window.check(6));
+
+    window.update(3);
+    assert!(!window.check(3));
+    assert!(window.check(4));
+
+    window.update(40);
+    assert_eq!(window.highest(), Some(40));
+    assert!(!window.check(40));
+    assert!(window.check(9)); // offset 31
+    assert!(!window.check(8)); // too old
+    assert!(!window.check(5));
+
+    window.update(10);
+    assert!(!window.check(10));
+    window.update(8); // too old to record
+    assert!(!window.check(8));
+
+    window.update(200);
+    assert!(!window.check(200));
+    assert!(!window.check(40));
+    assert!(window.check(199));
+
+    let mut window = ReplayWindow::new(64);
+    window.update(0);
+    window.update(63);
+    assert!(!window.check(0));
+    assert!(window.check(1));
+    window.update(64);
+    assert!(!window.check(0));
+    assert!(window.check(1));
+    assert!(!window.check(63));
+
+    let mut window = ReplayWindow::new(1);
+    window.update(7);
+    assert!(!window.check(7));
+    assert!(!window.check(6));
+    assert!(window.check(8));
+}
+
+#[test]
+#[should_panic]
+fn test_replay_window_size_panic() {
+    let _window = ReplayWindow::new(MAX_REPLAY_WINDOW_SIZE + 1);
+}
+
+type CipherResult = Result<Vec<u8>, &'static str>;
+
+/// Fake AEAD cipher that checks its inputs against expected values, and returns canned output.
+fn fake_cipher<'a>(
+    key: &'a [u8],
+    nonce: &'a str,
+    input: &'a str,
+    aad: &'a str,
+    output: &'a str,
+) -> impl FnOnce(&[u8], &[u8], &[u8], &[u8]) -> CipherResult + 'a {
+    move |k, n, i, a| {
+        assert_eq!(hex::encode(k), hex::encode(key));
+        assert_eq!(hex::encode(n), nonce);
+        assert_eq!(hex::encode(i), input);
+        assert_eq!(hex::encode(a), aad);
+        Ok(hex::decode(output).unwrap())
+    }
+}
+
+#[test]
+fn test_protect_request() {
+    // RFC 8613 Appendix C.4, C.5 and C.6.
+    let tests = [
+        (
+            0,
+            "4622d4dd6d944168eefb549868",
+            "8368456e63727970743040488501810a40411440",
+            "612f1092f1776f1c1668b3825e",
+            "0914",
+        ),
+        (
+            1,
+            "bf35ae297d2dace910c52e99ed",
+            "8368456e63727970743040498501810a4100411440",
+            "4ed339a5a379b0b8bc731fffb0",
+            "091400",
+        ),
+        (
+            2,
+            "2ca58fb85ff1b81c0b7181b84a",
+            "8368456e63727970743040488501810a40411440",
+            "72cd7273fd331ac45cffbe55c3",
+            "19140837cbf3210017a2d3",
+        ),
+    ];
+    let plaintext = "01b3747631";
+    for (i, nonce, aad, ciphertext, option_data) in tests {
+        let mut client = client_contexts().remove(i);
+        client.sender_seq = 20;
+        let key = client.sender_key.clone();
+        let (option, got, request) = client
+            .protect_request(
+                &hex::decode(plaintext).unwrap(),
+                &[],
+                fake_cipher(&key, nonce, plaintext, aad, ciphertext),
+            )
+            .unwrap();
+        assert_eq!(hex::encode(got), ciphertext, "case {}", i);
+        assert_eq!(hex::encode(option.to_vec().unwrap()), option_data);
+        assert_eq!(client.sender_seq, 21);
+        assert_eq!(request.kid, client.sender_id);
+        assert_eq!(request.partial_iv, vec![0x14]);
+        assert_eq!(hex::encode(&request.nonce), nonce);
+
+        // The server decrypts the request.
+        let mut server = server_context(&client_contexts()[i]);
+        let key = server.recipient_key.clone();
+        let option = OscoreOption::from_slice(&hex::decode(option_data).unwrap()).unwrap();
+        let (got, server_request) = server
+            .unprotect_request(
+                &option,
+                &hex::decode(ciphertext).unwrap(),
+                &[],
+                fake_cipher(&key, nonce, ciphertext, aad, plaintext),
+            )
+            .unwrap();
+        assert_eq!(hex::encode(got), plaintext, "case {}", i);
+        assert_eq!(server_request, request);
+        assert_eq!(server.replay_window.highest(), Some(20));
+
+        // A replay of the request is rejected, without decryption.
+        let result = server.unprotect_request(
+            &option,
+            &hex::decode(ciphertext).unwrap(),
+            &[],
+            |_, _, _, _| -> Result<Vec<u8>, &str> { panic!("unexpected decryption") },
+        );
+        assert!(matches!(result, Err(OscoreError::Replay)));
+    }
+}
+
+#[test]
+fn test_protect_response() {
+    // RFC 8613 Appendix C.7 and C.8.
+    let request = RequestInfo {
+        kid: vec![],
+        partial_iv: vec![0x14],
+        nonce: hex::decode("4622d4dd6d944168eefb549868").unwrap(),
+    };
+    let aad = "8368456e63727970743040488501810a40411440";
+    let plaintext = "45ff48656c6c6f20576f726c6421";
+    let tests = [
+        (
+            false,
+            "4622d4dd6d944168eefb549868",
+            "dbaad1e9a7e7b2a813d3c31524378303cdafae119106",
+            "",
+        ),
+        (
+            true,
+            "4722d4dd6d944169eefb54987c",
+            "4d4c13669384b67354b2b6175ff4b8658c666a6cf88e",
+            "0100",
+        ),
+    ];
+    for (fresh, nonce, ciphertext, option_data) in tests {
+        let client = client_contexts().remove(0);
+        let mut server = server_context(&client);
+        let key = server.sender_key.clone();
+        let (option, got) = server
+            .protect_response(
+                &request,
+                &hex::decode(plaintext).unwrap(),
+                &[],
+                fresh,
+                fake_cipher(&key, nonce, plaintext, aad, ciphertext),
+            )
+            .unwrap();
+        assert_eq!(hex::encode(got), ciphertext, "for {}", fresh);
+        assert_eq!(hex::encode(option.to_vec().unwrap()), option_data);
+        assert_eq!(server.sender_seq, if fresh { 1 } else { 0 });
+
+        // The client decrypts the response.
+        let key = client.recipient_key.clone();
+        let option = OscoreOption::from_slice(&hex::decode(option_data).unwrap()).unwrap();
+        let got = client
+            .unprotect_response(
+                &request,
+                &option,
+                &hex::decode(ciphertext).unwrap(),
+                &[],
+                fake_cipher(&key, nonce, ciphertext, aad, plaintext),
+            )
+            .unwrap();
+        assert_eq!(hex::encode(got), plaintext, "for {}", fresh);
+    }
+}
+
+#[test]
+fn test_unprotect_request_fail() {
+    let mut server = server_context(&client_contexts()[2]);
+    let decipher = |_: &[u8], _: &[u8], _: &[u8], _: &[u8]| Err("decryption failed");
+    let option = |piv: &[u8], kid_context: Option<&[u8]>, kid: Option<&[u8]>| OscoreOption {
+        partial_iv: piv.to_vec(),
+        kid_context: kid_context.map(|c| c.to_vec()),
+        kid: kid.map(|k| k.to_vec()),
+        group: false,
+    };
+    let id_context = hex::decode(ID_CONTEXT).unwrap();
+    let tests = [
+        (option(&[1], None, None), "kid and Partial IV"),
+        (
+            OscoreOption {
+                group: true,
+                ..option(&[1], None, Some(&[]))
+            },
+            "without Group Flag",
+        ),
+        (option(&[], None, Some(&[])), "kid and Partial IV"),
+        (option(&[1], None, Some(&[1])), "unknown recipient"),
+        (option(&[1], Some(&[1]), Some(&[])), "unknown recipient"),
+        (
+            option(&[1], Some(&id_context), Some(&[])),
+            "decryption failed",
+        ),
+        (option(&[1], None, Some(&[])), "decryption failed"),
+    ];
+    for (option, err_msg) in tests {
+        let result = server.unprotect_request(&option, &[0; 8], &[], decipher);
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains(err_msg),
+            "'{}' not found in '{}'",
+            err_msg,
+            err
+        );
+    }
+    // Failed decryption does not update the replay window.
+    assert_eq!(server.replay_window.highest(), None);
+}
+
+#[test]
+fn test_sequence_number_exhausted() {
+    let mut client = client_contexts().remove(0);
+    client.sender_seq = MAX_SEQUENCE_NUMBER;
+    let cipher = |_: &[u8], _: &[u8], _: &[u8], _: &[u8]| Ok::<_, ()>(vec![]);
+    let (option, _, _) = client.protect_request(b"", &[], cipher).unwrap();
+    assert_eq!(option.partial_iv, vec![0xff; 5]);
+    let result = client.protect_request(b"", &[], cipher);
+    assert!(matches!(result, Err(OscoreError::SequenceNumberExhausted)));
+
+    let request = RequestInfo::default();
+    let result = client.protect_response(&request, b"", &[], true, cipher);
+    assert!(matches!(result, Err(OscoreError::SequenceNumberExhausted)));
+    assert!(client
+        .protect_response(&request, b"", &[], false, cipher)
+        .is_ok());
+}
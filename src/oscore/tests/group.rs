@@ -0,0 +1,571 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use crate::{
+    cbor::value::Value,
+    cwt::{ClaimsSetBuilder, Confirmation},
+    enc_structure_data, iana,
+    oscore::{group::countersign_structure_data, *},
+    util::expect_err,
+    CoseKey, CoseKeyBuilder, EncryptionContext, ProtectedHeader,
+};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+const CLIENT_ID: &[u8] = &[0x25];
+const SERVER_ID: &[u8] = &[0x52];
+const GROUP_ID: &[u8] = &[0xdd, 0x11];
+
+/// Combine the inputs into a single byte.
+fn checksum(inputs: &[&[u8]]) -> u8 {
+    inputs
+        .iter()
+        .flat_map(|input| input.iter())
+        .fold(0u8, |acc, b| acc.wrapping_mul(31).wrapping_add(*b))
+}
+
+/// Fake cryptographic operations, with a keyed XOR cipher and a one-byte tag in place of AEAD, and
+/// a checksum over the signer's public key in place of signatures.
+#[derive(Default)]
+struct FakeCrypto {
+    /// Public key of this group member, which stands in for its private key.
+    signing_key: Vec<u8>,
+    /// HKDF info values seen, with their output lengths.
+    hkdf_infos: Vec<(Vec<u8>, usize)>,
+}
+
+impl FakeCrypto {
+    fn new(signing_key: &[u8]) -> Self {
+        Self {
+            signing_key: signing_key.to_vec(),
+            hkdf_infos: Vec::new(),
+        }
+    }
+
+    fn signature(key: &[u8], data: &[u8]) -> Vec<u8> {
+        let seed = checksum(&[key, data]);
+        (0..64u8).map(|i| seed ^ i).collect()
+    }
+}
+
+impl GroupCrypto for FakeCrypto {
+    type Error = &'static str;
+
+    fn hkdf(
+        &mut self,
+        salt: &[u8],
+        ikm: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.hkdf_infos.push((info.to_vec(), len));
+        let seed = checksum(&[salt, ikm, info]);
+        Ok((0..len).map(|i| seed.wrapping_add(i as u8)).collect())
+    }
+
+    fn encrypt(
+        &mut self,
+        key: &[u8],
+        nonce: &[u8],
+        plaintext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        let mut ciphertext: Vec<u8> = plaintext
+            .iter()
+            .zip(key.iter().cycle())
+            .map(|(p, k)| p ^ k)
+            .collect();
+        ciphertext.push(checksum(&[key, nonce, aad]));
+        Ok(ciphertext)
+    }
+
+    fn decrypt(
+        &mut self,
+        key: &[u8],
+        nonce: &[u8],
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        let (tag, ciphertext) = ciphertext.split_last().ok_or("no tag")?;
+        if *tag != checksum(&[key, nonce, aad]) {
+            return Err("bad tag");
+        }
+        Ok(ciphertext
+            .iter()
+            .zip(key.iter().cycle())
+            .map(|(c, k)| c ^ k)
+            .collect())
+    }
+
+    fn sign(&mut self, data: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Ok(Self::signature(&self.signing_key, data))
+    }
+
+    fn verify(&mut self, key: &CoseKey, signature: &[u8], data: &[u8]) -> Result<(), Self::Error> {
+        let x = match key.param(iana::OkpKeyParameter::X as i64) {
+            Some(Value::Bytes(x)) => x,
+            _ => return Err("no public key"),
+        };
+        if signature != Self::signature(x, data) {
+            return Err("bad signature");
+        }
+        Ok(())
+    }
+}
+
+/// Check that an OSCORE operation failed with an error whose description contains `err_msg`.
+fn expect_oscore_err<T: core::fmt::Debug>(result: Result<T, OscoreError<&str>>, err_msg: &str) {
+    let err = result.unwrap_err().to_string();
+    assert!(
+        err.contains(err_msg),
+        "{} doesn't contain '{}'",
+        err,
+        err_msg
+    );
+}
+
+/// Return an Ed25519 public key with the given value.
+fn public_key(x: &[u8]) -> CoseKey {
+    CoseKeyBuilder::new_okp_key()
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::Ed25519 as i64),
+        )
+        .param(iana::OkpKeyParameter::X as i64, Value::Bytes(x.to_vec()))
+        .build()
+}
+
+/// Return a CCS credential holding an Ed25519 public key with the given value.
+fn credential(subject: &str, x: &[u8]) -> GroupCredential {
+    let claims = ClaimsSetBuilder::new().subject(subject.to_owned());
+    GroupCredential::new_ccs(claims, public_key(x)).unwrap()
+}
+
+fn group_params(sender_id: &[u8], sender_credential: GroupCredential) -> GroupContextParams {
+    GroupContextParams {
+        master_secret: hex::decode("0102030405060708090a0b0c0d0e0f10").unwrap(),
+        master_salt: hex::decode("9e7ca92223786340").unwrap(),
+        id_context: GROUP_ID.to_vec(),
+        sender_id: sender_id.to_vec(),
+        sender_credential,
+        gm_credential: Some(vec![0xa0]),
+        ..Default::default()
+    }
+}
+
+/// Return the security contexts and fake cryptographic operations for a client and a server,
+/// each knowing the other.
+fn client_server() -> (
+    (GroupSecurityContext, FakeCrypto),
+    (GroupSecurityContext, FakeCrypto),
+) {
+    let client_cred = credential("client", &[1; 32]);
+    let server_cred = credential("server", &[2; 32]);
+    let mut client_crypto = FakeCrypto::new(&[1; 32]);
+    let mut server_crypto = FakeCrypto::new(&[2; 32]);
+    let mut client = GroupSecurityContext::derive(
+        &group_params(CLIENT_ID, client_cred.clone()),
+        &mut client_crypto,
+    )
+    .unwrap();
+    let mut server = GroupSecurityContext::derive(
+        &group_params(SERVER_ID, server_cred.clone()),
+        &mut server_crypto,
+    )
+    .unwrap();
+    client
+        .add_recipient(SERVER_ID, server_cred, &mut client_crypto)
+        .unwrap();
+    server
+        .add_recipient(CLIENT_ID, client_cred, &mut server_crypto)
+        .unwrap();
+    ((client, client_crypto), (server, server_crypto))
+}
+
+#[test]
+fn test_countersign_structure_data() {
+    let got = hex::encode(countersign_structure_data(&[0x01, 0x02], &[0x11, 0x12]));
+    assert_eq!(
+        got,
+        concat!(
+            "84",                                 // 4-arr
+            "71",                                 // 17-tstr
+            "436f756e7465725369676e617475726530", // "CounterSignature0"
+            "40",                                 // empty bstr for protected
+            "420102",                             // bstr for aad
+            "421112",                             // bstr for payload
+        )
+    );
+}
+
+#[test]
+fn test_signature_len() {
+    let ed448 = CoseKeyBuilder::new_okp_key()
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::Ed448 as i64),
+        )
+        .build();
+    let x25519 = CoseKeyBuilder::new_okp_key()
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::X25519 as i64),
+        )
+        .build();
+    let tests = [
+        (iana::Algorithm::ES256, CoseKey::default(), Some(64)),
+        (iana::Algorithm::ES384, CoseKey::default(), Some(96)),
+        (iana::Algorithm::ES512, CoseKey::default(), Some(132)),
+        (iana::Algorithm::Ed448, CoseKey::default(), Some(114)),
+        (iana::Algorithm::EdDSA, public_key(&[]), Some(64)),
+        (iana::Algorithm::EdDSA, ed448, Some(114)),
+        (iana::Algorithm::EdDSA, x25519, None),
+        (iana::Algorithm::EdDSA, CoseKey::default(), None),
+        (iana::Algorithm::A128GCM, CoseKey::default(), None),
+    ];
+    for (i, (alg, key, want)) in tests.iter().enumerate() {
+        assert_eq!(signature_len(*alg, key), *want, "case {}", i);
+    }
+}
+
+#[test]
+fn test_group_credential() {
+    let cred = credential("client", &[1; 32]);
+    assert_eq!(cred.public_key, public_key(&[1; 32]));
+    let got = GroupCredential::from_ccs(cred.credential.clone()).unwrap();
+    assert_eq!(got, cred);
+
+    // Any existing confirmation is replaced rather than duplicated.
+    let claims = ClaimsSetBuilder::new()
+        .subject("client".to_owned())
+        .confirmation(Confirmation::CoseKey(public_key(&[2; 32])));
+    let got = GroupCredential::new_ccs(claims, public_key(&[1; 32])).unwrap();
+    assert_eq!(got, cred);
+
+    let claims = ClaimsSetBuilder::new()
+        .subject("client".to_owned())
+        .build()
+        .to_vec()
+        .unwrap();
+    expect_err(
+        GroupCredential::from_ccs(claims),
+        "CCS with COSE_Key confirmation",
+    );
+    expect_err(GroupCredential::from_ccs(vec![0x80]), "map");
+}
+
+#[test]
+fn test_group_context_derive() {
+    let mut crypto = FakeCrypto::default();
+    let ctx = GroupSecurityContext::derive(
+        &group_params(CLIENT_ID, credential("client", &[1; 32])),
+        &mut crypto,
+    )
+    .unwrap();
+    assert_eq!(ctx.common_iv.len(), 13);
+    assert_eq!(ctx.signature_encryption_key.len(), 16);
+    assert_eq!(ctx.sender_key.len(), 16);
+    assert!(ctx.recipients.is_empty());
+    let infos: Vec<(String, usize)> = crypto
+        .hkdf_infos
+        .iter()
+        .map(|(info, len)| (hex::encode(info), *len))
+        .collect();
+    assert_eq!(
+        infos,
+        vec![
+            // [h'', h'dd11', 10, "IV", 13]
+            ("854042dd110a6249560d".to_owned(), 13),
+            // [h'', h'dd11', 10, "SEKey", 16]
+            ("854042dd110a6553454b657910".to_owned(), 16),
+            // [h'25', h'dd11', 10, "Key", 16]
+            ("85412542dd110a634b657910".to_owned(), 16),
+        ]
+    );
+}
+
+#[test]
+fn test_group_context_derive_fail() {
+    let cred = credential("client", &[1; 32]);
+    let tests = vec![
+        (
+            GroupContextParams {
+                group_enc_alg: iana::Algorithm::EdDSA,
+                ..group_params(CLIENT_ID, cred.clone())
+            },
+            "unsupported algorithm EdDSA",
+        ),
+        (
+            GroupContextParams {
+                sign_alg: iana::Algorithm::A128GCM,
+                ..group_params(CLIENT_ID, cred.clone())
+            },
+            "unsupported algorithm A128GCM",
+        ),
+        (
+            group_params(CLIENT_ID, GroupCredential::default()),
+            "unsupported algorithm EdDSA",
+        ),
+        (
+            group_params(&[1; 8], cred.clone()),
+            "invalid sender or recipient ID",
+        ),
+    ];
+    for (params, err_msg) in tests {
+        expect_oscore_err(
+            GroupSecurityContext::derive(&params, &mut FakeCrypto::default()),
+            err_msg,
+        );
+    }
+
+    let mut crypto = FakeCrypto::default();
+    let mut ctx =
+        GroupSecurityContext::derive(&group_params(CLIENT_ID, cred.clone()), &mut crypto).unwrap();
+    expect_oscore_err(
+        ctx.add_recipient(CLIENT_ID, cred.clone(), &mut crypto),
+        "invalid sender or recipient ID",
+    );
+    expect_oscore_err(
+        ctx.add_recipient(&[1; 8], cred.clone(), &mut crypto),
+        "invalid sender or recipient ID",
+    );
+    expect_oscore_err(
+        ctx.add_recipient(SERVER_ID, GroupCredential::default(), &mut crypto),
+        "unsupported algorithm EdDSA",
+    );
+    ctx.add_recipient(SERVER_ID, cred.clone(), &mut crypto)
+        .unwrap();
+    ctx.add_recipient(SERVER_ID, cred, &mut crypto).unwrap();
+    assert_eq!(ctx.recipients.len(), 1);
+    assert!(ctx.recipient(SERVER_ID).is_some());
+    assert!(ctx.recipient(CLIENT_ID).is_none());
+}
+
+#[test]
+fn test_group_external_aad() {
+    let ((client, _), _) = client_server();
+    let request = RequestInfo {
+        kid: CLIENT_ID.to_vec(),
+        partial_iv: vec![0x05],
+        nonce: vec![],
+    };
+    let option = OscoreOption {
+        partial_iv: vec![0x05],
+        kid_context: Some(GROUP_ID.to_vec()),
+        kid: Some(CLIENT_ID.to_vec()),
+        group: true,
+    };
+    let got = client
+        .external_aad(&request, &[], &option, &[0xa1])
+        .unwrap();
+    assert_eq!(
+        hex::encode(got),
+        concat!(
+            "89",             // 9-array
+            "01",             // oscore_version
+            "830a27f6",       // [10, -8, null]
+            "4125",           // request_kid
+            "4105",           // request_piv
+            "40",             // options
+            "42dd11",         // request_kid_context
+            "46390502dd1125", // OSCORE_option
+            "41a1",           // sender_cred
+            "41a0",           // gm_cred
+        )
+    );
+
+    let client = GroupSecurityContext {
+        pairwise_key_agreement_alg: Some(iana::Algorithm::ECDH_SS_HKDF_256),
+        gm_credential: None,
+        ..client
+    };
+    let got = client
+        .external_aad(&request, &[], &option, &[0xa1])
+        .unwrap();
+    assert_eq!(
+        hex::encode(got),
+        "8901830a27381a412541054042dd1146390502dd112541a1f6"
+    );
+}
+
+#[test]
+fn test_group_round_trip() {
+    let ((mut client, mut client_crypto), (mut server, mut server_crypto)) = client_server();
+    let plaintext = b"\x01\xb3foo".to_vec();
+    let (option, payload, request) = client
+        .protect_request(&plaintext, &[], &mut client_crypto)
+        .unwrap();
+    assert_eq!(
+        option,
+        OscoreOption {
+            partial_iv: vec![0x00],
+            kid_context: Some(GROUP_ID.to_vec()),
+            kid: Some(CLIENT_ID.to_vec()),
+            group: true,
+        }
+    );
+    assert_eq!(payload.len(), plaintext.len() + 1 + 64);
+    assert_eq!(client.sender_seq, 1);
+
+    // The countersignature is encrypted with the keystream.
+    let (ciphertext, encrypted_signature) = payload.split_at(payload.len() - 64);
+    let external_aad = client
+        .external_aad(&request, &[], &option, &client.sender_credential.credential)
+        .unwrap();
+    let tbs = countersign_structure_data(&external_aad, ciphertext);
+    let keystream = client
+        .keystream(&mut client_crypto, &[0x00], CLIENT_ID, true, 64)
+        .unwrap();
+    let signature: Vec<u8> = encrypted_signature
+        .iter()
+        .zip(keystream)
+        .map(|(s, k)| s ^ k)
+        .collect();
+    assert_eq!(signature, FakeCrypto::signature(&[1; 32], &tbs));
+    let aad = enc_structure_data(
+        EncryptionContext::CoseEncrypt0,
+        ProtectedHeader::default(),
+        &external_aad,
+    );
+    let decrypted = client_crypto
+        .decrypt(&client.sender_key, &request.nonce, ciphertext, &aad)
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+
+    let (got, server_request) = server
+        .unprotect_request(&option, &payload, &[], &mut server_crypto)
+        .unwrap();
+    assert_eq!(got, plaintext);
+    assert_eq!(server_request, request);
+    expect_oscore_err(
+        server.unprotect_request(&option, &payload, &[], &mut server_crypto),
+        "replayed message",
+    );
+
+    for fresh_partial_iv in [false, true] {
+        let response = b"\x45bar".to_vec();
+        let (option, payload) = server
+            .protect_response(
+                &server_request,
+                &response,
+                &[],
+                fresh_partial_iv,
+                &mut server_crypto,
+            )
+            .unwrap();
+        assert_eq!(option.kid.as_deref(), Some(SERVER_ID));
+        assert!(option.group);
+        assert_eq!(option.kid_context, None);
+        assert_eq!(option.partial_iv.is_empty(), !fresh_partial_iv);
+        let got = client
+            .unprotect_response(&request, &option, &payload, &[], &mut client_crypto)
+            .unwrap();
+        assert_eq!(got, response, "for {:?}", fresh_partial_iv);
+    }
+}
+
+#[test]
+fn test_group_unprotect_fail() {
+    let ((mut client, mut client_crypto), (mut server, mut server_crypto)) = client_server();
+    let (option, payload, _) = client
+        .protect_request(b"\x01", &[], &mut client_crypto)
+        .unwrap();
+
+    let mut tampered = payload.clone();
+    *tampered.last_mut().unwrap() ^= 1;
+    let tests = vec![
+        (
+            OscoreOption {
+                kid: Some(vec![0x99]),
+                ..option.clone()
+            },
+            payload.clone(),
+            "unknown recipient",
+        ),
+        (
+            OscoreOption {
+                kid_context: Some(vec![0x99]),
+                ..option.clone()
+            },
+            payload.clone(),
+            "unknown recipient",
+        ),
+        (
+            OscoreOption {
+                kid_context: None,
+                ..option.clone()
+            },
+            payload.clone(),
+            "unknown recipient",
+        ),
+        (
+            OscoreOption {
+                group: false,
+                ..option.clone()
+            },
+            payload.clone(),
+            "OSCORE option with Group Flag and kid",
+        ),
+        (
+            OscoreOption {
+                partial_iv: vec![],
+                ..option.clone()
+            },
+            payload.clone(),
+            "OSCORE option with Partial IV",
+        ),
+        (
+            option.clone(),
+            payload[..63].to_vec(),
+            "ciphertext and countersignature",
+        ),
+        (
+            option.clone(),
+            tampered,
+            "cryptographic operation failed: bad signature",
+        ),
+        (
+            OscoreOption {
+                partial_iv: vec![0x01],
+                ..option.clone()
+            },
+            payload.clone(),
+            "cryptographic operation failed: bad signature",
+        ),
+    ];
+    for (option, payload, err_msg) in tests {
+        let result = server.unprotect_request(&option, &payload, &[], &mut server_crypto);
+        expect_oscore_err(result, err_msg);
+    }
+    // Failures do not update the replay window.
+    server
+        .unprotect_request(&option, &payload, &[], &mut server_crypto)
+        .unwrap();
+
+    let request = RequestInfo::default();
+    let result = client.unprotect_response(
+        &request,
+        &OscoreOption::default(),
+        &payload,
+        &[],
+        &mut client_crypto,
+    );
+    expect_oscore_err(result, "OSCORE option with Group Flag and kid");
+}
@@ -0,0 +1,179 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Minimal DER (X.690) support, sufficient for the key structures in this module.
+
+use super::PkixError;
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+pub(crate) const INTEGER: u8 = 0x02;
+pub(crate) const BIT_STRING: u8 = 0x03;
+pub(crate) const OCTET_STRING: u8 = 0x04;
+pub(crate) const NULL: u8 = 0x05;
+pub(crate) const OID: u8 = 0x06;
+pub(crate) const SEQUENCE: u8 = 0x30;
+pub(crate) const CONTEXT_0: u8 = 0x80;
+pub(crate) const CONTEXT_1: u8 = 0x81;
+pub(crate) const CONTEXT_CONSTRUCTED_0: u8 = 0xa0;
+pub(crate) const CONTEXT_CONSTRUCTED_1: u8 = 0xa1;
+
+/// Reader for a sequence of DER-encoded TLVs.
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Read a TLV with the given tag, returning its contents.
+    pub(crate) fn read(&mut self, tag: u8) -> Result<&'a [u8], PkixError> {
+        self.read_optional(tag)?.ok_or(PkixError::InvalidDer)
+    }
+
+    /// Read a TLV with the given tag if it is next, returning its contents.
+    pub(crate) fn read_optional(&mut self, tag: u8) -> Result<Option<&'a [u8]>, PkixError> {
+        match self.data.first() {
+            Some(t) if *t == tag => {}
+            _ => return Ok(None),
+        }
+        let (len, hdr_len) = match self.data.get(1) {
+            None => return Err(PkixError::InvalidDer),
+            Some(len) if *len < 0x80 => (*len as usize, 2),
+            Some(len) => {
+                let n = (*len & 0x7f) as usize;
+                if n == 0 || n > core::mem::size_of::<usize>() {
+                    return Err(PkixError::InvalidDer);
+                }
+                let bytes = self.data.get(2..2 + n).ok_or(PkixError::InvalidDer)?;
+                // DER requires the minimal length encoding.
+                if bytes[0] == 0 {
+                    return Err(PkixError::InvalidDer);
+                }
+                let len = bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+                if len < 0x80 {
+                    return Err(PkixError::InvalidDer);
+                }
+                (len, 2 + n)
+            }
+        };
+        let end = hdr_len.checked_add(len).ok_or(PkixError::InvalidDer)?;
+        let contents = self.data.get(hdr_len..end).ok_or(PkixError::InvalidDer)?;
+        self.data = &self.data[end..];
+        Ok(Some(contents))
+    }
+
+    /// Read a non-negative INTEGER, returning its minimal unsigned big-endian encoding.
+    pub(crate) fn read_uint(&mut self) -> Result<Vec<u8>, PkixError> {
+        let contents = self.read(INTEGER)?;
+        match contents {
+            [] => Err(PkixError::InvalidDer),
+            // Negative.
+            [b, ..] if b & 0x80 != 0 => Err(PkixError::InvalidDer),
+            // Non-minimal.
+            [0, b, ..] if b & 0x80 == 0 => Err(PkixError::InvalidDer),
+            [0, rest @ ..] if !rest.is_empty() => Ok(rest.to_vec()),
+            _ => Ok(contents.to_vec()),
+        }
+    }
+
+    /// Read a BIT STRING with no unused bits, returning its contents.
+    pub(crate) fn read_bit_string(&mut self) -> Result<&'a [u8], PkixError> {
+        bit_string_contents(self.read(BIT_STRING)?)
+    }
+
+    /// Check that all of the data has been consumed.
+    pub(crate) fn finish(self) -> Result<(), PkixError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(PkixError::InvalidDer)
+        }
+    }
+}
+
+/// Return the contents of a BIT STRING with no unused bits.
+pub(crate) fn bit_string_contents(contents: &[u8]) -> Result<&[u8], PkixError> {
+    match contents.split_first() {
+        Some((0, rest)) => Ok(rest),
+        _ => Err(PkixError::InvalidDer),
+    }
+}
+
+/// Encode a TLV.
+pub(crate) fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(contents.len() + 6);
+    out.push(tag);
+    let len = contents.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        out.push(0x80 | (bytes.len() - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+    out.extend_from_slice(contents);
+    out
+}
+
+/// Encode a SEQUENCE holding the given already-encoded items.
+pub(crate) fn sequence(items: &[&[u8]]) -> Vec<u8> {
+    tlv(SEQUENCE, &items.concat())
+}
+
+/// Encode an unsigned big-endian integer as an INTEGER.
+pub(crate) fn uint(value: &[u8]) -> Vec<u8> {
+    let skip = value.iter().take_while(|b| **b == 0).count();
+    let value = &value[skip..];
+    match value.first() {
+        None => tlv(INTEGER, &[0]),
+        Some(b) if b & 0x80 != 0 => tlv(INTEGER, &[&[0], value].concat()),
+        Some(_) => tlv(INTEGER, value),
+    }
+}
+
+/// Encode a BIT STRING with no unused bits.
+pub(crate) fn bit_string(tag: u8, value: &[u8]) -> Vec<u8> {
+    tlv(tag, &[&[0], value].concat())
+}
+
+/// Render the contents of an OBJECT IDENTIFIER in dotted-decimal form.
+pub(crate) fn oid_to_string(contents: &[u8]) -> String {
+    let mut out = String::new();
+    let mut arc: u64 = 0;
+    for b in contents {
+        arc = (arc << 7) | u64::from(b & 0x7f);
+        if b & 0x80 != 0 {
+            continue;
+        }
+        if out.is_empty() {
+            let first = core::cmp::min(arc / 40, 2);
+            let _ = write!(out, "{}.{}", first, arc - 40 * first);
+        } else {
+            let _ = write!(out, ".{arc}");
+        }
+        arc = 0;
+    }
+    if contents.last().is_some_and(|b| b & 0x80 == 0) {
+        return out;
+    }
+    // Empty or truncated.
+    out.push_str("...");
+    out
+}
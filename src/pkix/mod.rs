@@ -0,0 +1,791 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Conversion between COSE_Key and the public key infrastructure formats used with X.509:
+//! SubjectPublicKeyInfo (RFC 5280 section 4.1), PKCS#8 private keys (RFC 5958) and their PEM
+//! encodings (RFC 7468).
+//!
+//! The following key types are supported:
+//!
+//! - EC2 keys on the P-256, P-384, P-521, secp256k1 and brainpool curves (RFC 5480, RFC 5915,
+//!   RFC 5639).
+//! - OKP keys on the Ed25519, Ed448, X25519 and X448 curves (RFC 8410).
+//! - RSA keys, including multi-prime keys (RFC 8017).
+//! - ML-DSA keys (RFC 9881), where the private key is held as a seed.
+//!
+//! The formats have no equivalent of the `kid`, `key_ops` or `base_iv` COSE_Key parameters, so
+//! these are ignored on export.  The algorithm is only relevant for ML-DSA keys, where it
+//! identifies the parameter set.
+
+use crate::{
+    cbor::value::Value,
+    iana,
+    iana::EnumI64,
+    util::{wipe, Base64},
+    Algorithm, CoseKey, CoseKeyBuilder, KeyType, Label, MlDsaVariant, RsaKeyError,
+    RsaOtherPrimeInfo, RsaPrivateComponents,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::convert::TryFrom;
+
+pub(crate) mod der;
+use der::Reader;
+
+#[cfg(test)]
+mod tests;
+
+/// Error type for failures in SPKI, PKCS#8 and PEM conversion.
+#[derive(Clone, PartialEq, Eq)]
+pub enum PkixError {
+    /// The input is not valid DER, or does not have the expected structure.
+    InvalidDer,
+    /// The input is not valid PEM.
+    InvalidPem,
+    /// The PEM type label is not supported.
+    UnsupportedPemLabel(String),
+    /// The algorithm (identified by its dotted-decimal OID) is not supported.
+    UnsupportedAlgorithm(String),
+    /// The [`CoseKey`] has a key type, curve, algorithm or private key format that is not supported.
+    UnsupportedKey,
+    /// A required [`CoseKey`] parameter is missing.
+    MissingParameter(Label),
+    /// A [`CoseKey`] parameter has an invalid type or value.
+    InvalidParameter(Label),
+    /// The [`CoseKey`] does not hold private key material.
+    NotPrivateKey,
+    /// The private key is held in more than one form, and the forms do not match.
+    InconsistentKey,
+}
+
+impl core::error::Error for PkixError {}
+
+impl core::fmt::Debug for PkixError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl core::fmt::Display for PkixError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl PkixError {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PkixError::InvalidDer => write!(f, "invalid DER"),
+            PkixError::InvalidPem => write!(f, "invalid PEM"),
+            PkixError::UnsupportedPemLabel(label) => write!(f, "unsupported PEM label '{label}'"),
+            PkixError::UnsupportedAlgorithm(oid) => write!(f, "unsupported algorithm OID {oid}"),
+            PkixError::UnsupportedKey => write!(f, "unsupported key"),
+            PkixError::MissingParameter(label) => {
+                write!(f, "missing COSE_Key parameter {label:?}")
+            }
+            PkixError::InvalidParameter(label) => {
+                write!(f, "invalid value for COSE_Key parameter {label:?}")
+            }
+            PkixError::NotPrivateKey => write!(f, "not a private key"),
+            PkixError::InconsistentKey => write!(f, "inconsistent private key forms"),
+        }
+    }
+}
+
+/// PEM type label for a SubjectPublicKeyInfo.
+const PEM_PUBLIC_KEY: &str = "PUBLIC KEY";
+/// PEM type label for a PKCS#8 private key.
+const PEM_PRIVATE_KEY: &str = "PRIVATE KEY";
+
+/// OID for id-ecPublicKey (1.2.840.10045.2.1), from RFC 5480.
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// OID for rsaEncryption (1.2.840.113549.1.1.1), from RFC 8017.
+const OID_RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+/// Named curve OIDs for EC2 keys.
+const EC2_CURVES: &[(iana::EllipticCurve, &[u8])] = &[
+    // 1.2.840.10045.3.1.7
+    (
+        iana::EllipticCurve::P_256,
+        &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07],
+    ),
+    // 1.3.132.0.34
+    (iana::EllipticCurve::P_384, &[0x2b, 0x81, 0x04, 0x00, 0x22]),
+    // 1.3.132.0.35
+    (iana::EllipticCurve::P_521, &[0x2b, 0x81, 0x04, 0x00, 0x23]),
+    // 1.3.132.0.10
+    (
+        iana::EllipticCurve::Secp256k1,
+        &[0x2b, 0x81, 0x04, 0x00, 0x0a],
+    ),
+    // 1.3.36.3.3.2.8.1.1.7
+    (
+        iana::EllipticCurve::BrainpoolP256R1,
+        &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x07],
+    ),
+    // 1.3.36.3.3.2.8.1.1.9
+    (
+        iana::EllipticCurve::BrainpoolP320R1,
+        &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x09],
+    ),
+    // 1.3.36.3.3.2.8.1.1.11
+    (
+        iana::EllipticCurve::BrainpoolP384R1,
+        &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0b],
+    ),
+    // 1.3.36.3.3.2.8.1.1.13
+    (
+        iana::EllipticCurve::BrainpoolP512R1,
+        &[0x2b, 0x24, 0x03, 0x03, 0x02, 0x08, 0x01, 0x01, 0x0d],
+    ),
+];
+
+/// Algorithm OIDs for OKP keys, from RFC 8410.
+const OKP_CURVES: &[(iana::EllipticCurve, &[u8])] = &[
+    // 1.3.101.110
+    (iana::EllipticCurve::X25519, &[0x2b, 0x65, 0x6e]),
+    // 1.3.101.111
+    (iana::EllipticCurve::X448, &[0x2b, 0x65, 0x6f]),
+    // 1.3.101.112
+    (iana::EllipticCurve::Ed25519, &[0x2b, 0x65, 0x70]),
+    // 1.3.101.113
+    (iana::EllipticCurve::Ed448, &[0x2b, 0x65, 0x71]),
+];
+
+/// Algorithm OIDs for ML-DSA keys, from RFC 9881.
+const ML_DSA_VARIANTS: &[(MlDsaVariant, &[u8])] = &[
+    // 2.16.840.1.101.3.4.3.17
+    (
+        MlDsaVariant::MlDsa44,
+        &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x11],
+    ),
+    // 2.16.840.1.101.3.4.3.18
+    (
+        MlDsaVariant::MlDsa65,
+        &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x12],
+    ),
+    // 2.16.840.1.101.3.4.3.19
+    (
+        MlDsaVariant::MlDsa87,
+        &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x13],
+    ),
+];
+
+/// Length of an ML-DSA private key seed.
+const ML_DSA_SEED_LEN: usize = 32;
+
+/// The algorithm of a key, as identified by an X.509 AlgorithmIdentifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyAlgorithm {
+    Ec2(iana::EllipticCurve),
+    Okp(iana::EllipticCurve),
+    Rsa,
+    MlDsa(MlDsaVariant),
+}
+
+fn lookup_oid<T: Copy>(table: &[(T, &[u8])], oid: &[u8]) -> Option<T> {
+    table.iter().find(|(_, o)| *o == oid).map(|(v, _)| *v)
+}
+
+/// Return the OID for `value`, which has already been checked to be present in `table`.
+fn lookup_value<T: PartialEq>(table: &'static [(T, &'static [u8])], value: T) -> &'static [u8] {
+    table
+        .iter()
+        .find(|(v, _)| *v == value)
+        .map(|(_, o)| *o)
+        .unwrap_or_default()
+}
+
+impl KeyAlgorithm {
+    /// Determine the algorithm for a [`CoseKey`].
+    fn from_key(key: &CoseKey) -> Result<Self, PkixError> {
+        match key.kty {
+            KeyType::Assigned(iana::KeyType::EC2) => {
+                let crv = curve(key, iana::Ec2KeyParameter::Crv as i64)?;
+                check_curve(EC2_CURVES, crv).map(KeyAlgorithm::Ec2)
+            }
+            KeyType::Assigned(iana::KeyType::OKP) => {
+                let crv = curve(key, iana::OkpKeyParameter::Crv as i64)?;
+                check_curve(OKP_CURVES, crv).map(KeyAlgorithm::Okp)
+            }
+            KeyType::Assigned(iana::KeyType::RSA) => Ok(KeyAlgorithm::Rsa),
+            KeyType::Assigned(iana::KeyType::AKP) => match &key.alg {
+                Some(Algorithm::Assigned(alg)) => MlDsaVariant::try_from(*alg)
+                    .map(KeyAlgorithm::MlDsa)
+                    .map_err(|_| PkixError::UnsupportedKey),
+                Some(_) => Err(PkixError::UnsupportedKey),
+                None => Err(PkixError::MissingParameter(Label::Int(
+                    iana::KeyParameter::Alg as i64,
+                ))),
+            },
+            _ => Err(PkixError::UnsupportedKey),
+        }
+    }
+
+    /// Parse the contents of an AlgorithmIdentifier.
+    fn from_der(contents: &[u8]) -> Result<Self, PkixError> {
+        let mut r = Reader::new(contents);
+        let oid = r.read(der::OID)?;
+        let alg = if oid == OID_EC_PUBLIC_KEY {
+            let crv_oid = r.read(der::OID)?;
+            let crv = lookup_oid(EC2_CURVES, crv_oid)
+                .ok_or_else(|| PkixError::UnsupportedAlgorithm(der::oid_to_string(crv_oid)))?;
+            KeyAlgorithm::Ec2(crv)
+        } else if oid == OID_RSA_ENCRYPTION {
+            // Parameters are a NULL, although some encoders omit them.
+            if let Some(null) = r.read_optional(der::NULL)? {
+                if !null.is_empty() {
+                    return Err(PkixError::InvalidDer);
+                }
+            }
+            KeyAlgorithm::Rsa
+        } else if let Some(crv) = lookup_oid(OKP_CURVES, oid) {
+            KeyAlgorithm::Okp(crv)
+        } else if let Some(variant) = lookup_oid(ML_DSA_VARIANTS, oid) {
+            KeyAlgorithm::MlDsa(variant)
+        } else {
+            return Err(PkixError::UnsupportedAlgorithm(der::oid_to_string(oid)));
+        };
+        r.finish()?;
+        Ok(alg)
+    }
+
+    /// Encode as an AlgorithmIdentifier.
+    fn to_der(self) -> Vec<u8> {
+        match self {
+            KeyAlgorithm::Ec2(crv) => der::sequence(&[
+                &der::tlv(der::OID, OID_EC_PUBLIC_KEY),
+                &der::tlv(der::OID, lookup_value(EC2_CURVES, crv)),
+            ]),
+            KeyAlgorithm::Okp(crv) => {
+                der::sequence(&[&der::tlv(der::OID, lookup_value(OKP_CURVES, crv))])
+            }
+            KeyAlgorithm::Rsa => der::sequence(&[
+                &der::tlv(der::OID, OID_RSA_ENCRYPTION),
+                &der::tlv(der::NULL, &[]),
+            ]),
+            KeyAlgorithm::MlDsa(variant) => {
+                der::sequence(&[&der::tlv(der::OID, lookup_value(ML_DSA_VARIANTS, variant))])
+            }
+        }
+    }
+}
+
+/// Check that `crv` is one of the curves in `table`.
+fn check_curve(
+    table: &[(iana::EllipticCurve, &[u8])],
+    crv: iana::EllipticCurve,
+) -> Result<iana::EllipticCurve, PkixError> {
+    if table.iter().any(|(c, _)| *c == crv) {
+        Ok(crv)
+    } else {
+        Err(PkixError::UnsupportedKey)
+    }
+}
+
+fn curve(key: &CoseKey, label: i64) -> Result<iana::EllipticCurve, PkixError> {
+    match key.param(label) {
+        None => Err(PkixError::MissingParameter(Label::Int(label))),
+        Some(Value::Integer(i)) => i64::try_from(*i)
+            .ok()
+            .and_then(iana::EllipticCurve::from_i64)
+            .ok_or(PkixError::UnsupportedKey),
+        Some(Value::Text(_)) => Err(PkixError::UnsupportedKey),
+        Some(_) => Err(PkixError::InvalidParameter(Label::Int(label))),
+    }
+}
+
+/// Return the contents of a `bstr` parameter of `key`, if present.
+fn optional_bytes(key: &CoseKey, label: i64) -> Result<Option<&[u8]>, PkixError> {
+    match key.param(label) {
+        None => Ok(None),
+        Some(Value::Bytes(b)) => Ok(Some(b)),
+        Some(_) => Err(PkixError::InvalidParameter(Label::Int(label))),
+    }
+}
+
+/// Return the contents of a mandatory `bstr` parameter of `key`.
+fn bytes(key: &CoseKey, label: i64) -> Result<&[u8], PkixError> {
+    optional_bytes(key, label)?.ok_or(PkixError::MissingParameter(Label::Int(label)))
+}
+
+fn rsa_key_error(e: RsaKeyError) -> PkixError {
+    match e {
+        RsaKeyError::MissingParameter(p) => PkixError::MissingParameter(Label::Int(p as i64)),
+        RsaKeyError::UnexpectedParameter(p)
+        | RsaKeyError::InvalidParameterType(p)
+        | RsaKeyError::NonMinimalEncoding(p) => PkixError::InvalidParameter(Label::Int(p as i64)),
+        RsaKeyError::NotRsaKey => PkixError::UnsupportedKey,
+    }
+}
+
+/// Encode the public key of `key` in the form held in the `subjectPublicKey` field of a
+/// SubjectPublicKeyInfo.
+fn public_key(alg: KeyAlgorithm, key: &CoseKey) -> Result<Vec<u8>, PkixError> {
+    match alg {
+        KeyAlgorithm::Ec2(_) => {
+            let y = iana::Ec2KeyParameter::Y as i64;
+            bytes(key, iana::Ec2KeyParameter::X as i64)?;
+            key.param(y)
+                .ok_or(PkixError::MissingParameter(Label::Int(y)))?;
+            key.to_sec1_octet_string()
+                .map_err(|_| PkixError::InvalidParameter(Label::Int(y)))
+        }
+        KeyAlgorithm::Okp(_) => Ok(bytes(key, iana::OkpKeyParameter::X as i64)?.to_vec()),
+        KeyAlgorithm::Rsa => Ok(der::sequence(&[
+            &der::uint(bytes(key, iana::RsaKeyParameter::N as i64)?),
+            &der::uint(bytes(key, iana::RsaKeyParameter::E as i64)?),
+        ])),
+        KeyAlgorithm::MlDsa(_) => Ok(bytes(key, iana::AkpKeyParameter::Pub as i64)?.to_vec()),
+    }
+}
+
+/// Return whether `key` includes public key information.
+fn has_public_key(alg: KeyAlgorithm, key: &CoseKey) -> bool {
+    let label = match alg {
+        KeyAlgorithm::Ec2(_) => iana::Ec2KeyParameter::X as i64,
+        KeyAlgorithm::Okp(_) => iana::OkpKeyParameter::X as i64,
+        KeyAlgorithm::Rsa => iana::RsaKeyParameter::N as i64,
+        KeyAlgorithm::MlDsa(_) => iana::AkpKeyParameter::Pub as i64,
+    };
+    key.param(label).is_some()
+}
+
+/// Build a public key from the contents of the `subjectPublicKey` field of a
+/// SubjectPublicKeyInfo.
+fn key_from_public(alg: KeyAlgorithm, data: &[u8]) -> Result<CoseKeyBuilder, PkixError> {
+    match alg {
+        KeyAlgorithm::Ec2(crv) => CoseKeyBuilder::new_ec2_pub_key_sec1_octet_string(crv, data)
+            .map_err(|_| PkixError::InvalidDer),
+        KeyAlgorithm::Okp(crv) => Ok(CoseKeyBuilder::new_okp_key()
+            .param(iana::OkpKeyParameter::Crv as i64, Value::from(crv as i64))
+            .param(iana::OkpKeyParameter::X as i64, Value::Bytes(data.to_vec()))),
+        KeyAlgorithm::Rsa => {
+            let mut r = Reader::new(data);
+            let mut seq = Reader::new(r.read(der::SEQUENCE)?);
+            r.finish()?;
+            let n = seq.read_uint()?;
+            let e = seq.read_uint()?;
+            seq.finish()?;
+            Ok(CoseKeyBuilder::new_rsa_pub_key(n, e))
+        }
+        KeyAlgorithm::MlDsa(variant) => {
+            Ok(CoseKeyBuilder::new_mldsa_pub_key(variant, data.to_vec()))
+        }
+    }
+}
+
+/// Build a key that holds only the curve (or algorithm) for `alg`, ready for the private key to
+/// be added.
+fn key_without_public(alg: KeyAlgorithm) -> CoseKey {
+    match alg {
+        KeyAlgorithm::Ec2(crv) => CoseKey {
+            kty: KeyType::Assigned(iana::KeyType::EC2),
+            params: vec![(
+                Label::Int(iana::Ec2KeyParameter::Crv as i64),
+                Value::from(crv as i64),
+            )],
+            ..Default::default()
+        },
+        KeyAlgorithm::Okp(crv) => CoseKeyBuilder::new_okp_key()
+            .param(iana::OkpKeyParameter::Crv as i64, Value::from(crv as i64))
+            .build(),
+        KeyAlgorithm::Rsa => CoseKeyBuilder::new().key_type(iana::KeyType::RSA).build(),
+        KeyAlgorithm::MlDsa(variant) => CoseKeyBuilder::new()
+            .key_type(iana::KeyType::AKP)
+            .algorithm(variant.into())
+            .build(),
+    }
+}
+
+/// Encode the private key of `key` in the form held in the `privateKey` field of a PKCS#8
+/// OneAsymmetricKey.
+fn private_key(alg: KeyAlgorithm, key: &CoseKey) -> Result<Vec<u8>, PkixError> {
+    match alg {
+        KeyAlgorithm::Ec2(_) => {
+            // ECPrivateKey, from RFC 5915.  The curve is already given in the AlgorithmIdentifier.
+            let d = optional_bytes(key, iana::Ec2KeyParameter::D as i64)?
+                .ok_or(PkixError::NotPrivateKey)?;
+            let mut items = vec![der::uint(&[1]), der::tlv(der::OCTET_STRING, d)];
+            if has_public_key(alg, key) {
+                let pub_key = der::bit_string(der::BIT_STRING, &public_key(alg, key)?);
+                items.push(der::tlv(der::CONTEXT_CONSTRUCTED_1, &pub_key));
+            }
+            let seq = der::sequence(&items.iter().map(Vec::as_slice).collect::<Vec<_>>());
+            wipe(&mut items);
+            Ok(seq)
+        }
+        KeyAlgorithm::Okp(_) => {
+            // CurvePrivateKey, from RFC 8410.
+            let d = optional_bytes(key, iana::OkpKeyParameter::D as i64)?
+                .ok_or(PkixError::NotPrivateKey)?;
+            Ok(der::tlv(der::OCTET_STRING, d))
+        }
+        KeyAlgorithm::Rsa => {
+            // RSAPrivateKey, from RFC 8017.
+            if key.param(iana::RsaKeyParameter::D as i64).is_none() {
+                return Err(PkixError::NotPrivateKey);
+            }
+            key.check_rsa_key().map_err(rsa_key_error)?;
+            let labels = [
+                iana::RsaKeyParameter::N,
+                iana::RsaKeyParameter::E,
+                iana::RsaKeyParameter::D,
+                iana::RsaKeyParameter::P,
+                iana::RsaKeyParameter::Q,
+                iana::RsaKeyParameter::DP,
+                iana::RsaKeyParameter::DQ,
+                iana::RsaKeyParameter::QInv,
+            ];
+            let other = key
+                .param(iana::RsaKeyParameter::Other as i64)
+                .and_then(Value::as_array);
+            // Version is two-prime(0) or multi(1).
+            let mut items = vec![der::uint(&[u8::from(other.is_some())])];
+            for label in labels {
+                items.push(der::uint(bytes(key, label as i64)?));
+            }
+            if let Some(other) = other {
+                let mut infos = Vec::new();
+                for info in other {
+                    let mut info = RsaOtherPrimeInfo::from_value(info).map_err(rsa_key_error)?;
+                    let mut d = der::uint(&info.d);
+                    infos.extend(der::sequence(&[
+                        &der::uint(&info.r),
+                        &d,
+                        &der::uint(&info.t),
+                    ]));
+                    wipe(&mut d);
+                    wipe(&mut info);
+                }
+                items.push(der::tlv(der::SEQUENCE, &infos));
+                wipe(&mut infos);
+            }
+            let seq = der::sequence(&items.iter().map(Vec::as_slice).collect::<Vec<_>>());
+            wipe(&mut items);
+            Ok(seq)
+        }
+        KeyAlgorithm::MlDsa(_) => {
+            // ML-DSA-PrivateKey, from RFC 9881, in the `seed` form.
+            let seed = optional_bytes(key, iana::AkpKeyParameter::Priv as i64)?
+                .ok_or(PkixError::NotPrivateKey)?;
+            if seed.len() != ML_DSA_SEED_LEN {
+                return Err(PkixError::InvalidParameter(Label::Int(
+                    iana::AkpKeyParameter::Priv as i64,
+                )));
+            }
+            Ok(der::tlv(der::CONTEXT_0, seed))
+        }
+    }
+}
+
+/// Parse the contents of the `privateKey` field of a PKCS#8 OneAsymmetricKey into `key`, which
+/// may already hold public key information.
+fn add_private_key(alg: KeyAlgorithm, data: &[u8], key: CoseKey) -> Result<CoseKey, PkixError> {
+    let mut r = Reader::new(data);
+    let mut key = key;
+    match alg {
+        KeyAlgorithm::Ec2(crv) => {
+            let mut seq = Reader::new(r.read(der::SEQUENCE)?);
+            if seq.read_uint()? != [1] {
+                return Err(PkixError::InvalidDer);
+            }
+            let d = seq.read(der::OCTET_STRING)?;
+            if let Some(params) = seq.read_optional(der::CONTEXT_CONSTRUCTED_0)? {
+                let mut params = Reader::new(params);
+                if lookup_oid(EC2_CURVES, params.read(der::OID)?) != Some(crv) {
+                    return Err(PkixError::InvalidDer);
+                }
+                params.finish()?;
+            }
+            if let Some(pub_key) = seq.read_optional(der::CONTEXT_CONSTRUCTED_1)? {
+                let mut pub_key = Reader::new(pub_key);
+                let pub_key_data = pub_key.read_bit_string()?;
+                pub_key.finish()?;
+                if !has_public_key(alg, &key) {
+                    key = key_from_public(alg, pub_key_data)?.build();
+                }
+            }
+            seq.finish()?;
+            key.params.push((
+                Label::Int(iana::Ec2KeyParameter::D as i64),
+                Value::Bytes(d.to_vec()),
+            ));
+        }
+        KeyAlgorithm::Okp(_) => {
+            let d = r.read(der::OCTET_STRING)?;
+            key.params.push((
+                Label::Int(iana::OkpKeyParameter::D as i64),
+                Value::Bytes(d.to_vec()),
+            ));
+        }
+        KeyAlgorithm::Rsa => {
+            let mut seq = Reader::new(r.read(der::SEQUENCE)?);
+            let multi_prime = match seq.read_uint()?.as_slice() {
+                [0] => false,
+                [1] => true,
+                _ => return Err(PkixError::InvalidDer),
+            };
+            let n = seq.read_uint()?;
+            let e = seq.read_uint()?;
+            let mut private = RsaPrivateComponents {
+                d: seq.read_uint()?,
+                p: seq.read_uint()?,
+                q: seq.read_uint()?,
+                dp: seq.read_uint()?,
+                dq: seq.read_uint()?,
+                qinv: seq.read_uint()?,
+                other: vec![],
+            };
+            if multi_prime {
+                let mut infos = Reader::new(seq.read(der::SEQUENCE)?);
+                while let Some(info) = infos.read_optional(der::SEQUENCE)? {
+                    let mut info = Reader::new(info);
+                    private.other.push(RsaOtherPrimeInfo {
+                        r: info.read_uint()?,
+                        d: info.read_uint()?,
+                        t: info.read_uint()?,
+                    });
+                    info.finish()?;
+                }
+                infos.finish()?;
+                if private.other.is_empty() {
+                    return Err(PkixError::InvalidDer);
+                }
+            }
+            seq.finish()?;
+            key = CoseKeyBuilder::new_rsa_priv_key(n, e, private).build();
+        }
+        KeyAlgorithm::MlDsa(variant) => {
+            let seed = if let Some(seed) = r.read_optional(der::CONTEXT_0)? {
+                if seed.len() != ML_DSA_SEED_LEN {
+                    return Err(PkixError::InvalidDer);
+                }
+                seed
+            } else if let Some(both) = r.read_optional(der::SEQUENCE)? {
+                let mut both = Reader::new(both);
+                let seed = both.read(der::OCTET_STRING)?;
+                let expanded = both.read(der::OCTET_STRING)?;
+                both.finish()?;
+                if seed.len() != ML_DSA_SEED_LEN {
+                    return Err(PkixError::InvalidDer);
+                }
+                check_mldsa_expanded_key(variant, seed, expanded)?;
+                seed
+            } else {
+                // The `expandedKey` form has no COSE_Key representation.
+                r.read(der::OCTET_STRING)?;
+                return Err(PkixError::UnsupportedKey);
+            };
+            key.params.push((
+                Label::Int(iana::AkpKeyParameter::Priv as i64),
+                Value::Bytes(seed.to_vec()),
+            ));
+        }
+    }
+    r.finish()?;
+    Ok(key)
+}
+
+/// Check that the expanded ML-DSA private key from the `both` form of an `ML-DSA-PrivateKey`
+/// matches the one derived from the seed, so that only the seed needs to be kept.
+#[cfg(feature = "mldsa")]
+fn check_mldsa_expanded_key(
+    variant: MlDsaVariant,
+    seed: &[u8],
+    expanded: &[u8],
+) -> Result<(), PkixError> {
+    let mut seed = <[u8; ML_DSA_SEED_LEN]>::try_from(seed).map_err(|_| PkixError::InvalidDer)?;
+    let mut derived = crate::crypto::mldsa_expanded_private_key(variant, &seed);
+    let matches = derived == expanded;
+    wipe(&mut seed);
+    wipe(&mut derived);
+    if matches {
+        Ok(())
+    } else {
+        Err(PkixError::InconsistentKey)
+    }
+}
+
+/// Without the `mldsa` feature the expanded ML-DSA private key in the `both` form of an
+/// `ML-DSA-PrivateKey` can't be checked against the seed, so the form is rejected rather than
+/// silently dropping the expanded key.
+#[cfg(not(feature = "mldsa"))]
+fn check_mldsa_expanded_key(
+    _variant: MlDsaVariant,
+    _seed: &[u8],
+    _expanded: &[u8],
+) -> Result<(), PkixError> {
+    Err(PkixError::UnsupportedKey)
+}
+
+impl CoseKey {
+    /// Encode the public key as a DER SubjectPublicKeyInfo, as per RFC 5280 section 4.1.
+    pub fn to_spki_der(&self) -> Result<Vec<u8>, PkixError> {
+        let alg = KeyAlgorithm::from_key(self)?;
+        let pub_key = public_key(alg, self)?;
+        Ok(der::sequence(&[
+            &alg.to_der(),
+            &der::bit_string(der::BIT_STRING, &pub_key),
+        ]))
+    }
+
+    /// Create a public key from a DER SubjectPublicKeyInfo, as per RFC 5280 section 4.1.
+    pub fn from_spki_der(data: &[u8]) -> Result<Self, PkixError> {
+        let mut r = Reader::new(data);
+        let mut spki = Reader::new(r.read(der::SEQUENCE)?);
+        r.finish()?;
+        let alg = KeyAlgorithm::from_der(spki.read(der::SEQUENCE)?)?;
+        let pub_key = spki.read_bit_string()?;
+        spki.finish()?;
+        Ok(key_from_public(alg, pub_key)?.build())
+    }
+
+    /// Encode the private key as a DER PKCS#8 OneAsymmetricKey, as per RFC 5958.
+    ///
+    /// For OKP and ML-DSA keys, the public key is included (in a version 2 structure) if present.
+    /// For EC2 keys, the public key is included in the embedded ECPrivateKey if present.
+    pub fn to_pkcs8_der(&self) -> Result<Vec<u8>, PkixError> {
+        let alg = KeyAlgorithm::from_key(self)?;
+        let mut priv_key = private_key(alg, self)?;
+        let pub_key = match alg {
+            KeyAlgorithm::Okp(_) | KeyAlgorithm::MlDsa(_) if has_public_key(alg, self) => {
+                Some(der::bit_string(der::CONTEXT_1, &public_key(alg, self)?))
+            }
+            _ => None,
+        };
+        let version = der::uint(&[u8::from(pub_key.is_some())]);
+        let alg = alg.to_der();
+        let mut priv_key_str = der::tlv(der::OCTET_STRING, &priv_key);
+        wipe(&mut priv_key);
+        let mut items: Vec<&[u8]> = vec![&version, &alg, &priv_key_str];
+        if let Some(pub_key) = &pub_key {
+            items.push(pub_key);
+        }
+        let result = der::sequence(&items);
+        wipe(&mut priv_key_str);
+        Ok(result)
+    }
+
+    /// Create a private key from a DER PKCS#8 PrivateKeyInfo or OneAsymmetricKey, as per RFC 5958.
+    /// Any attributes are ignored.
+    ///
+    /// An ML-DSA private key in the `both` form is only accepted with the `mldsa` feature, which
+    /// checks that the expanded key matches the seed.
+    pub fn from_pkcs8_der(data: &[u8]) -> Result<Self, PkixError> {
+        let mut r = Reader::new(data);
+        let mut seq = Reader::new(r.read(der::SEQUENCE)?);
+        r.finish()?;
+        let v2 = match seq.read_uint()?.as_slice() {
+            [0] => false,
+            [1] => true,
+            _ => return Err(PkixError::InvalidDer),
+        };
+        let alg = KeyAlgorithm::from_der(seq.read(der::SEQUENCE)?)?;
+        let priv_key = seq.read(der::OCTET_STRING)?;
+        seq.read_optional(der::CONTEXT_CONSTRUCTED_0)?;
+        let key = match seq.read_optional(der::CONTEXT_1)? {
+            Some(pub_key) if v2 => {
+                key_from_public(alg, der::bit_string_contents(pub_key)?)?.build()
+            }
+            Some(_) => return Err(PkixError::InvalidDer),
+            None => key_without_public(alg),
+        };
+        seq.finish()?;
+        add_private_key(alg, priv_key, key)
+    }
+
+    /// Encode the public key as a PEM SubjectPublicKeyInfo (`PUBLIC KEY`), as per RFC 7468
+    /// section 13.
+    pub fn to_spki_pem(&self) -> Result<String, PkixError> {
+        Ok(pem_encode(PEM_PUBLIC_KEY, &self.to_spki_der()?))
+    }
+
+    /// Encode the private key as a PEM PKCS#8 structure (`PRIVATE KEY`), as per RFC 7468
+    /// section 10.
+    pub fn to_pkcs8_pem(&self) -> Result<String, PkixError> {
+        let mut der = self.to_pkcs8_der()?;
+        let pem = pem_encode(PEM_PRIVATE_KEY, &der);
+        wipe(&mut der);
+        Ok(pem)
+    }
+
+    /// Create a key from the first PEM block in `pem`, which must be either a `PUBLIC KEY` or a
+    /// `PRIVATE KEY`.
+    pub fn from_pem(pem: &str) -> Result<Self, PkixError> {
+        let (label, mut data) = pem_decode(pem)?;
+        let result = match label {
+            PEM_PUBLIC_KEY => Self::from_spki_der(&data),
+            PEM_PRIVATE_KEY => Self::from_pkcs8_der(&data),
+            _ => Err(PkixError::UnsupportedPemLabel(String::from(label))),
+        };
+        wipe(&mut data);
+        result
+    }
+}
+
+const PEM_BEGIN: &str = "-----BEGIN ";
+const PEM_END: &str = "-----END ";
+const PEM_DASHES: &str = "-----";
+/// Maximum line length for PEM base64 data.
+const PEM_LINE_LEN: usize = 64;
+
+fn pem_encode(label: &str, data: &[u8]) -> String {
+    let mut b64 = Base64::Standard.encode(data);
+    let mut out = String::new();
+    out.push_str(PEM_BEGIN);
+    out.push_str(label);
+    out.push_str(PEM_DASHES);
+    out.push('\n');
+    for line in b64.as_bytes().chunks(PEM_LINE_LEN) {
+        // safe: base64 output is ASCII
+        out.push_str(core::str::from_utf8(line).unwrap());
+        out.push('\n');
+    }
+    out.push_str(PEM_END);
+    out.push_str(label);
+    out.push_str(PEM_DASHES);
+    out.push('\n');
+    wipe(&mut b64);
+    out
+}
+
+/// Decode the first PEM block in `pem`, returning its label and contents.  Text outside the block
+/// is ignored.
+fn pem_decode(pem: &str) -> Result<(&str, Vec<u8>), PkixError> {
+    let start = pem.find(PEM_BEGIN).ok_or(PkixError::InvalidPem)?;
+    let rest = &pem[start + PEM_BEGIN.len()..];
+    let label_end = rest.find(PEM_DASHES).ok_or(PkixError::InvalidPem)?;
+    let label = &rest[..label_end];
+    if label.contains('\n') {
+        return Err(PkixError::InvalidPem);
+    }
+    let rest = &rest[label_end + PEM_DASHES.len()..];
+    let end = rest.find(PEM_END).ok_or(PkixError::InvalidPem)?;
+    let trailer = &rest[end + PEM_END.len()..];
+    if !trailer
+        .strip_prefix(label)
+        .is_some_and(|t| t.starts_with(PEM_DASHES))
+    {
+        return Err(PkixError::InvalidPem);
+    }
+    let mut b64: String = rest[..end]
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let data = Base64::Standard.decode(&b64);
+    wipe(&mut b64);
+    Ok((label, data.ok_or(PkixError::InvalidPem)?))
+}
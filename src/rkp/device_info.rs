@@ -0,0 +1,303 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Device information held in an RKP certificate signing request.
+
+use crate::{
+    cbor::value::Value, common::AsCborValue, util::ValueTryAs, CborSerializable, CoseError, Label,
+};
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::{cmp::Ordering, convert::TryInto};
+
+/// Verified boot state, as held in the `vb_state` entry of a [`DeviceInfo`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifiedBootState {
+    /// The device booted with the OEM keys.
+    Green,
+    /// The device booted with a user-installed key.
+    Yellow,
+    /// The device booted with an unlocked bootloader.
+    Orange,
+}
+
+/// Bootloader state, as held in the `bootloader_state` entry of a [`DeviceInfo`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BootloaderState {
+    /// The bootloader is locked.
+    Locked,
+    /// The bootloader is unlocked.
+    Unlocked,
+}
+
+/// Security level of the key generating component, as held in the `security_level` entry of a
+/// [`DeviceInfo`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SecurityLevel {
+    /// Trusted Execution Environment.
+    Tee,
+    /// StrongBox secure element.
+    StrongBox,
+}
+
+/// Implement text conversion for an enum held as a text string.
+macro_rules! text_enum {
+    ( $etype:ident, $want:literal, $( $variant:ident => $text:literal ),+ $(,)? ) => {
+        impl $etype {
+            /// Return the text form.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $( $etype::$variant => $text, )+
+                }
+            }
+        }
+
+        impl AsCborValue for $etype {
+            fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+                match value.try_as_string()?.as_str() {
+                    $( $text => Ok($etype::$variant), )+
+                    _ => Err(CoseError::UnexpectedItem("tstr", $want)),
+                }
+            }
+
+            fn to_cbor_value(self) -> Result<Value, CoseError> {
+                Ok(Value::Text(self.as_str().to_owned()))
+            }
+        }
+    };
+}
+
+text_enum!(VerifiedBootState, "verified boot state",
+    Green => "green",
+    Yellow => "yellow",
+    Orange => "orange",
+);
+text_enum!(BootloaderState, "bootloader state",
+    Locked => "locked",
+    Unlocked => "unlocked",
+);
+text_enum!(SecurityLevel, "security level",
+    Tee => "tee",
+    StrongBox => "strongbox",
+);
+
+/// Device information, as held in a CSR payload.
+///
+/// ```cddl
+///  DeviceInfo = {
+///      "brand" : tstr,
+///      "manufacturer" : tstr,
+///      "product" : tstr,
+///      "model" : tstr,
+///      "device" : tstr,
+///      "vb_state" : "green" / "yellow" / "orange",
+///      "bootloader_state" : "locked" / "unlocked",
+///      "vbmeta_digest": bstr,
+///      ? "os_version" : tstr,
+///      "system_patch_level" : uint,
+///      "boot_patch_level" : uint,
+///      "vendor_patch_level" : uint,
+///      "security_level" : "tee" / "strongbox",
+///      "fused": 1 / 0,
+///  }
+/// ```
+///
+/// The map must be canonically encoded, with its keys in length-first order (RFC 7049 section 3.9).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DeviceInfo {
+    /// Brand of the device.
+    pub brand: Option<String>,
+    /// Manufacturer of the device.
+    pub manufacturer: Option<String>,
+    /// Product name of the device.
+    pub product: Option<String>,
+    /// Model of the device.
+    pub model: Option<String>,
+    /// Device name.
+    pub device: Option<String>,
+    /// Verified boot state.
+    pub vb_state: Option<VerifiedBootState>,
+    /// Bootloader state.
+    pub bootloader_state: Option<BootloaderState>,
+    /// Digest of the verified boot metadata.
+    pub vbmeta_digest: Option<Vec<u8>>,
+    /// Android OS version.
+    pub os_version: Option<String>,
+    /// System patch level, as YYYYMM.
+    pub system_patch_level: Option<u64>,
+    /// Boot patch level, as YYYYMMDD.
+    pub boot_patch_level: Option<u64>,
+    /// Vendor patch level, as YYYYMMDD.
+    pub vendor_patch_level: Option<u64>,
+    /// Security level of the key generating component.
+    pub security_level: Option<SecurityLevel>,
+    /// Whether secure boot is enforced by fuses.
+    pub fused: Option<bool>,
+    /// Any other entries.
+    pub rest: Vec<(String, Value)>,
+}
+
+impl CborSerializable for DeviceInfo {}
+
+const BRAND: &str = "brand";
+const MANUFACTURER: &str = "manufacturer";
+const PRODUCT: &str = "product";
+const MODEL: &str = "model";
+const DEVICE: &str = "device";
+const VB_STATE: &str = "vb_state";
+const BOOTLOADER_STATE: &str = "bootloader_state";
+const VBMETA_DIGEST: &str = "vbmeta_digest";
+const OS_VERSION: &str = "os_version";
+const SYSTEM_PATCH_LEVEL: &str = "system_patch_level";
+const BOOT_PATCH_LEVEL: &str = "boot_patch_level";
+const VENDOR_PATCH_LEVEL: &str = "vendor_patch_level";
+const SECURITY_LEVEL: &str = "security_level";
+const FUSED: &str = "fused";
+
+/// Order text map keys canonically, by length and then lexicographically.
+fn cmp_canonical(a: &str, b: &str) -> Ordering {
+    Label::Text(a.to_owned()).cmp_canonical(&Label::Text(b.to_owned()))
+}
+
+impl DeviceInfo {
+    /// Check that all of the entries required for version 3 of the CSR payload are present.
+    pub fn check_complete(&self) -> Result<(), CoseError> {
+        let complete = self.brand.is_some()
+            && self.manufacturer.is_some()
+            && self.product.is_some()
+            && self.model.is_some()
+            && self.device.is_some()
+            && self.vb_state.is_some()
+            && self.bootloader_state.is_some()
+            && self.vbmeta_digest.is_some()
+            && self.system_patch_level.is_some()
+            && self.boot_patch_level.is_some()
+            && self.vendor_patch_level.is_some()
+            && self.security_level.is_some()
+            && self.fused.is_some();
+        if !complete {
+            return Err(CoseError::UnexpectedItem(
+                "incomplete DeviceInfo",
+                "DeviceInfo with all required entries",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl AsCborValue for DeviceInfo {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut info = Self::default();
+        let mut prev: Option<String> = None;
+        for (k, v) in value.try_as_map()? {
+            let k = k.try_as_string()?;
+            if let Some(prev) = &prev {
+                match cmp_canonical(prev, &k) {
+                    Ordering::Less => {}
+                    Ordering::Equal => return Err(CoseError::DuplicateMapKey),
+                    Ordering::Greater => {
+                        return Err(CoseError::UnexpectedItem(
+                            "unordered map",
+                            "canonically ordered map",
+                        ))
+                    }
+                }
+            }
+            match k.as_str() {
+                BRAND => info.brand = Some(v.try_as_string()?),
+                MANUFACTURER => info.manufacturer = Some(v.try_as_string()?),
+                PRODUCT => info.product = Some(v.try_as_string()?),
+                MODEL => info.model = Some(v.try_as_string()?),
+                DEVICE => info.device = Some(v.try_as_string()?),
+                VB_STATE => info.vb_state = Some(VerifiedBootState::from_cbor_value(v)?),
+                BOOTLOADER_STATE => {
+                    info.bootloader_state = Some(BootloaderState::from_cbor_value(v)?)
+                }
+                VBMETA_DIGEST => info.vbmeta_digest = Some(v.try_as_bytes()?),
+                OS_VERSION => info.os_version = Some(v.try_as_string()?),
+                SYSTEM_PATCH_LEVEL => {
+                    info.system_patch_level = Some(v.try_as_integer()?.try_into()?)
+                }
+                BOOT_PATCH_LEVEL => info.boot_patch_level = Some(v.try_as_integer()?.try_into()?),
+                VENDOR_PATCH_LEVEL => {
+                    info.vendor_patch_level = Some(v.try_as_integer()?.try_into()?)
+                }
+                SECURITY_LEVEL => info.security_level = Some(SecurityLevel::from_cbor_value(v)?),
+                FUSED => {
+                    let fused: u8 = v.try_as_integer()?.try_into()?;
+                    info.fused = Some(match fused {
+                        0 => false,
+                        1 => true,
+                        _ => return Err(CoseError::OutOfRangeIntegerValue),
+                    });
+                }
+                _ => info.rest.push((k.clone(), v)),
+            }
+            prev = Some(k);
+        }
+        Ok(info)
+    }
+
+    /// Encode the device information as a canonically ordered map.
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut entries: Vec<(String, Value)> = Vec::new();
+        let mut text = |key: &str, value: Option<String>| {
+            if let Some(value) = value {
+                entries.push((key.to_owned(), Value::Text(value)));
+            }
+        };
+        text(BRAND, self.brand);
+        text(MANUFACTURER, self.manufacturer);
+        text(PRODUCT, self.product);
+        text(MODEL, self.model);
+        text(DEVICE, self.device);
+        text(OS_VERSION, self.os_version);
+        let enums = [
+            (VB_STATE, self.vb_state.map(|v| v.as_str())),
+            (BOOTLOADER_STATE, self.bootloader_state.map(|v| v.as_str())),
+            (SECURITY_LEVEL, self.security_level.map(|v| v.as_str())),
+        ];
+        for (key, value) in enums {
+            if let Some(value) = value {
+                entries.push((key.to_owned(), Value::Text(value.to_owned())));
+            }
+        }
+        if let Some(digest) = self.vbmeta_digest {
+            entries.push((VBMETA_DIGEST.to_owned(), Value::Bytes(digest)));
+        }
+        let uints = [
+            (SYSTEM_PATCH_LEVEL, self.system_patch_level),
+            (BOOT_PATCH_LEVEL, self.boot_patch_level),
+            (VENDOR_PATCH_LEVEL, self.vendor_patch_level),
+            (FUSED, self.fused.map(u64::from)),
+        ];
+        for (key, value) in uints {
+            if let Some(value) = value {
+                entries.push((key.to_owned(), Value::from(value)));
+            }
+        }
+        entries.extend(self.rest);
+        entries.sort_by(|(a, _), (b, _)| cmp_canonical(a, b));
+        if entries.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err(CoseError::DuplicateMapKey);
+        }
+        Ok(Value::Map(
+            entries
+                .into_iter()
+                .map(|(k, v)| (Value::Text(k), v))
+                .collect(),
+        ))
+    }
+}
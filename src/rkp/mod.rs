@@ -0,0 +1,352 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Android Remote Key Provisioning (RKP) functionality, for version 3 certificate signing requests.
+//!
+//! An [`AuthenticatedRequest`] holds a DICE chain and a `COSE_Sign1` (signed by the leaf key of
+//! the chain) over a challenge and a [`CsrPayload`], which in turn holds the [`DeviceInfo`] and the
+//! public keys to be certified.  [`AuthenticatedRequest::verify`] checks the DICE chain and the
+//! signature, using a caller-provided verifier as elsewhere in this crate.
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    dice::{DiceChain, DiceClaims, DiceError},
+    util::{cbor_type_error, ValueTryAs},
+    CborSerializable, CoseError, CoseKey, CoseMac0, CoseSign1,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::convert::TryInto;
+
+mod device_info;
+pub use device_info::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Version of the `AuthenticatedRequest` schema.
+pub const AUTHENTICATED_REQUEST_VERSION: u64 = 1;
+
+/// Version of the `CsrPayload` schema.
+pub const CSR_PAYLOAD_VERSION: u64 = 3;
+
+/// Maximum length of the challenge in a CSR.
+pub const MAX_CHALLENGE_LEN: usize = 64;
+
+/// COSE_Key parameter label that marks a test key, which production backends should reject.
+pub const TEST_KEY_PARAM: i64 = -70000;
+
+/// Check that an array starts with the expected schema version, and return the remaining entries.
+fn check_version(value: Value, version: u64, len: usize) -> Result<Vec<Value>, CoseError> {
+    let mut a = value.try_as_array()?;
+    if a.len() != len {
+        return Err(CoseError::UnexpectedItem(
+            "array",
+            "array with expected entry count",
+        ));
+    }
+    let got: u64 = a.remove(0).try_as_integer()?.try_into()?;
+    if got != version {
+        return Err(CoseError::UnexpectedItem(
+            "unsupported version",
+            "supported schema version",
+        ));
+    }
+    Ok(a)
+}
+
+/// Payload of a CSR, holding the device information and the public keys to be certified.
+///
+/// ```cddl
+///  CsrPayload = [
+///      version: 3,
+///      CertificateType: tstr,   ; "TEE" or "StrongBox"
+///      DeviceInfo,
+///      KeysToSign,
+///  ]
+///  KeysToSign = [ * PublicKey ]  ; COSE_Key
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CsrPayload {
+    /// Type of certificate requested, such as "TEE" or "StrongBox".
+    pub certificate_type: String,
+    /// Device information.
+    pub device_info: DeviceInfo,
+    /// Public keys to be certified.
+    pub keys_to_sign: Vec<CoseKey>,
+}
+
+impl CborSerializable for CsrPayload {}
+
+impl AsCborValue for CsrPayload {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = check_version(value, CSR_PAYLOAD_VERSION, 4)?.into_iter();
+        Ok(Self {
+            certificate_type: a.next().unwrap().try_as_string()?, // safe: len checked
+            device_info: DeviceInfo::from_cbor_value(a.next().unwrap())?, // safe: len checked
+            keys_to_sign: a
+                .next()
+                .unwrap() // safe: len checked
+                .try_as_array_then_convert(CoseKey::from_cbor_value)?,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::Array(vec![
+            Value::from(CSR_PAYLOAD_VERSION),
+            Value::Text(self.certificate_type),
+            self.device_info.to_cbor_value()?,
+            Value::Array(
+                self.keys_to_sign
+                    .into_iter()
+                    .map(|k| k.to_cbor_value())
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        ]))
+    }
+}
+
+/// Authenticated CSR, as sent to the RKP backend.
+///
+/// ```cddl
+///  AuthenticatedRequest<CsrPayload> = [
+///      version: 1,
+///      UdsCerts,
+///      DiceCertChain,
+///      SignedData<[
+///          challenge: bstr .size (0..64),
+///          bstr .cbor CsrPayload,
+///      ]>,
+///  ]
+///  UdsCerts = {
+///      * SignerName => UdsCertChain
+///  }
+///  SignerName = tstr
+///  UdsCertChain = [ 2* X509Certificate ]  ; DER-encoded, root first
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuthenticatedRequest {
+    /// X.509 certificate chains for the UDS public key, keyed by signer name.
+    pub uds_certs: Vec<(String, Vec<Vec<u8>>)>,
+    /// DICE chain, rooted at the UDS public key.
+    pub dice_chain: DiceChain,
+    /// Signature by the leaf key of the DICE chain over the challenge and CSR payload.
+    pub signed_data: CoseSign1,
+}
+
+impl CborSerializable for AuthenticatedRequest {}
+
+impl AsCborValue for AuthenticatedRequest {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = check_version(value, AUTHENTICATED_REQUEST_VERSION, 4)?.into_iter();
+        let mut uds_certs: Vec<(String, Vec<Vec<u8>>)> = Vec::new();
+        let uds = a.next().unwrap(); // safe: len checked
+        for (k, v) in uds.try_as_map()? {
+            let name = k.try_as_string()?;
+            if uds_certs.iter().any(|(n, _)| *n == name) {
+                return Err(CoseError::DuplicateMapKey);
+            }
+            let chain = v.try_as_array_then_convert(|c| c.try_as_bytes())?;
+            if chain.len() < 2 {
+                return Err(CoseError::UnexpectedItem(
+                    "array",
+                    "certificate chain of 2+ certificates",
+                ));
+            }
+            uds_certs.push((name, chain));
+        }
+        Ok(Self {
+            uds_certs,
+            dice_chain: DiceChain::from_cbor_value(a.next().unwrap())?, // safe: len checked
+            signed_data: CoseSign1::from_cbor_value(a.next().unwrap())?, // safe: len checked
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let uds_certs = self
+            .uds_certs
+            .into_iter()
+            .map(|(name, chain)| {
+                (
+                    Value::Text(name),
+                    Value::Array(chain.into_iter().map(Value::Bytes).collect()),
+                )
+            })
+            .collect();
+        Ok(Value::Array(vec![
+            Value::from(AUTHENTICATED_REQUEST_VERSION),
+            Value::Map(uds_certs),
+            self.dice_chain.to_cbor_value()?,
+            self.signed_data.to_cbor_value()?,
+        ]))
+    }
+}
+
+/// Contents of a verified [`AuthenticatedRequest`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifiedRequest {
+    /// Claims of each certificate in the DICE chain.
+    pub dice_claims: Vec<DiceClaims>,
+    /// Challenge provided by the backend.
+    pub challenge: Vec<u8>,
+    /// The CSR payload.
+    pub payload: CsrPayload,
+}
+
+/// The error type returned when an RKP request fails verification.
+pub enum RkpError<E> {
+    /// The request could not be parsed.
+    Cose(CoseError),
+    /// The DICE chain failed verification.
+    Dice(DiceError<E>),
+    /// The signature or MAC failed verification.
+    Crypto(E),
+}
+
+impl<E: core::fmt::Display> core::error::Error for RkpError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Debug for RkpError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for RkpError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> RkpError<E> {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RkpError::Cose(e) => write!(f, "invalid RKP data: {e}"),
+            RkpError::Dice(e) => write!(f, "{e}"),
+            RkpError::Crypto(e) => write!(f, "verification failed: {e}"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for RkpError<E> {
+    fn from(e: CoseError) -> Self {
+        RkpError::Cose(e)
+    }
+}
+
+impl<E> From<DiceError<E>> for RkpError<E> {
+    fn from(e: DiceError<E>) -> Self {
+        match e {
+            DiceError::Cose(e) => RkpError::Cose(e),
+            e => RkpError::Dice(e),
+        }
+    }
+}
+
+impl AuthenticatedRequest {
+    /// Parse the signed challenge and CSR payload, without verification.
+    pub fn signed_payload(&self) -> Result<(Vec<u8>, CsrPayload), CoseError> {
+        let data = self
+            .signed_data
+            .payload
+            .as_ref()
+            .ok_or(CoseError::UnexpectedItem(
+                "nil payload",
+                "signed challenge and CSR payload",
+            ))?;
+        let mut a = Value::from_slice(data)?.try_as_array()?;
+        if a.len() != 2 {
+            return Err(CoseError::UnexpectedItem(
+                "array",
+                "array with challenge and CSR payload",
+            ));
+        }
+        let payload = CsrPayload::from_slice(&a.remove(1).try_as_bytes()?)?;
+        let challenge = match a.remove(0) {
+            Value::Bytes(c) if c.len() <= MAX_CHALLENGE_LEN => c,
+            Value::Bytes(_) => {
+                return Err(CoseError::UnexpectedItem(
+                    "oversized challenge",
+                    "challenge of 0-64 bytes",
+                ))
+            }
+            v => return cbor_type_error(&v, "bstr"),
+        };
+        Ok((challenge, payload))
+    }
+
+    /// Verify the request and return its contents.  The DICE chain is verified as per
+    /// [`DiceChain::verify`], and the signed data is then verified with the subject public key of
+    /// the final DICE certificate.  As for [`DiceChain::verify`], `verifier` is given the public
+    /// key, signature value and serialized data (in that order).
+    ///
+    /// Note that the UDS certificate chains are not verified, and that the challenge is returned
+    /// for the caller to check.
+    pub fn verify<F, E>(&self, mut verifier: F) -> Result<VerifiedRequest, RkpError<E>>
+    where
+        F: FnMut(&CoseKey, &[u8], &[u8]) -> Result<(), E>,
+    {
+        let dice_claims = self.dice_chain.verify(&mut verifier)?;
+        let leaf_key = dice_claims
+            .last()
+            .and_then(|c| c.subject_public_key.as_ref())
+            .ok_or(CoseError::UnexpectedItem(
+                "empty DICE chain",
+                "DICE chain with certificates",
+            ))?;
+        self.signed_data
+            .verify_signature(b"", |sig, data| verifier(leaf_key, sig, data))
+            .map_err(RkpError::Crypto)?;
+        let (challenge, payload) = self.signed_payload()?;
+        Ok(VerifiedRequest {
+            dice_claims,
+            challenge,
+            payload,
+        })
+    }
+}
+
+/// Verify a MACed public key (as produced by the KeyMint HAL), using `verify` on the tag value and
+/// the combined to-be-MACed data (in that order), and return the public key.
+///
+/// ```cddl
+///  MacedPublicKey = [       ; COSE_Mac0
+///      protected: bstr .cbor { 1 : 5 },  ; HMAC-256
+///      unprotected: { },
+///      payload: bstr .cbor PublicKey,
+///      tag: bstr
+///  ]
+/// ```
+pub fn verify_maced_public_key<F, E>(mac0: &CoseMac0, verify: F) -> Result<CoseKey, RkpError<E>>
+where
+    F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+{
+    let missing = || {
+        RkpError::Cose(CoseError::UnexpectedItem(
+            "nil payload",
+            "public key payload",
+        ))
+    };
+    mac0.verify_payload_tag(b"", missing, |tag, data| {
+        verify(tag, data).map_err(RkpError::Crypto)
+    })?;
+    let payload = mac0.payload.as_deref().unwrap_or_default();
+    Ok(CoseKey::from_slice(payload)?)
+}
+
+/// Indicate whether a public key is marked as a test key.
+pub fn is_test_key(key: &CoseKey) -> bool {
+    key.param(TEST_KEY_PARAM).is_some()
+}
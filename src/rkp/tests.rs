@@ -0,0 +1,548 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{
+    cwt::ClaimsSetBuilder,
+    dice::DiceClaimsBuilder,
+    iana,
+    util::{
+        expect_err,
+        test_crypto::{self, public_key},
+    },
+    Algorithm, CoseKeyBuilder, CoseMac0Builder, CoseSign1Builder, HeaderBuilder, KeyType,
+};
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec,
+};
+use core::convert::TryFrom;
+
+fn device_info() -> DeviceInfo {
+    DeviceInfo {
+        brand: Some("Google".to_owned()),
+        manufacturer: Some("Google".to_owned()),
+        product: Some("pixel".to_owned()),
+        model: Some("Pixel".to_owned()),
+        device: Some("pixel".to_owned()),
+        vb_state: Some(VerifiedBootState::Green),
+        bootloader_state: Some(BootloaderState::Locked),
+        vbmeta_digest: Some(vec![0x11; 32]),
+        os_version: Some("14".to_owned()),
+        system_patch_level: Some(202406),
+        boot_patch_level: Some(20240605),
+        vendor_patch_level: Some(20240605),
+        security_level: Some(SecurityLevel::Tee),
+        fused: Some(true),
+        rest: vec![],
+    }
+}
+
+fn csr_payload() -> CsrPayload {
+    CsrPayload {
+        certificate_type: "TEE".to_owned(),
+        device_info: device_info(),
+        keys_to_sign: vec![public_key(7), public_key(8)],
+    }
+}
+
+/// Return a DICE chain of `n` certificates rooted at the UDS key 0.
+fn dice_chain(n: u8) -> DiceChain {
+    let certs = (1..=n)
+        .map(|i| {
+            let claims = DiceClaimsBuilder::new()
+                .claims(
+                    ClaimsSetBuilder::new()
+                        .issuer(i.saturating_sub(1).to_string())
+                        .subject(i.to_string())
+                        .build(),
+                )
+                .mode(crate::dice::DiceMode::Normal)
+                .subject_public_key(public_key(i))
+                .key_usage(vec![crate::dice::KEY_USAGE_CERT_SIGN])
+                .build();
+            CoseSign1Builder::new()
+                .protected(
+                    HeaderBuilder::new()
+                        .algorithm(iana::Algorithm::EdDSA)
+                        .build(),
+                )
+                .payload(claims.to_vec().unwrap())
+                .create_signature(b"", |data| test_crypto::sign(i - 1, data))
+                .build()
+        })
+        .collect();
+    DiceChain {
+        root_key: public_key(0),
+        certs,
+    }
+}
+
+/// Return a signed challenge and CSR payload, signed by the key with the given value.
+fn signed_data(challenge: &[u8], payload: CsrPayload, signer: u8) -> CoseSign1 {
+    let data = Value::Array(vec![
+        Value::Bytes(challenge.to_vec()),
+        Value::Bytes(payload.to_vec().unwrap()),
+    ])
+    .to_vec()
+    .unwrap();
+    CoseSign1Builder::new()
+        .protected(
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::EdDSA)
+                .build(),
+        )
+        .payload(data)
+        .create_signature(b"", |data| test_crypto::sign(signer, data))
+        .build()
+}
+
+/// CSR with real signatures, laid out as produced by the KeyMint HAL: the DICE chain holds an
+/// Ed25519 UDS key, an Ed25519 bootloader certificate and a P-256 KeyMint certificate, and the
+/// KeyMint key signs (with ES256) the challenge and a payload with two P-256 keys to sign.
+const CSR: &str = concat!(
+    "8401a083a4010103272006215820fd2bda237feae7a009b828b9fe0bda7a18f43707ffdf6ec39b2230210019",
+    "cb868443a10127a058faa8017828376438386532623932613862323730303033343766666266646139663162",
+    "6437623032663932663502782835343063336466343562343939653562396535366461613532356265316564",
+    "3030373438373237643a004744505820a0f8cb25cab77308bf91b195f0365944351fb30762e8052eaf65a140",
+    "760deaef3a0047445350a23a000111716341424c3a00011172013a0047445458206c3d23d32ccdf8c2b3e3f6",
+    "a78e1d284e2b3b01d9368f8594c7e3ed06fc7085b63a0047445641013a00474457582aa40101032720062158",
+    "20a2e4ca3228f626127bf7dd5feb89adcdec7a6c9726e6131599469118b70630d13a00474458412058401527",
+    "4889219f2156362e2018f1bc966f8407afa52e7ddbf8ced8fab304d9a81465ce4806d4a67a008c34afa5ded0",
+    "34d94871a1520d3483324c0cf6ec7a02fc078443a10127a0590121a801782835343063336466343562343939",
+    "6535623965353664616135323562653165643030373438373237640278283230383163643834383363386637",
+    "65326663663865353438623663333331356333613535373130373a0047445058204aaabac77d6024d97a758a",
+    "adbecebf3e30afc5e85b0d1e09da9ddb0a283488a53a0047445354a23a00011171674b65794d696e743a0001",
+    "1172023a0047445458201a57f50f5ea5d0025bdb48e8169de27454f7c505853c972d004dba4f4cae13183a00",
+    "47445641013a00474457584da50102032620012158201cb929bc724cd320e700581a9de363383b05d66ca88c",
+    "6413cc01d7297102f1ca2258200073376b7b3a2e9914ea0fbe14581c34f4f8296b689d1d997fb39f598fe057",
+    "473a0047445841205840a64d4f23bccbb696dbbbce38c78cc147a564db3b1311e8be48335a25f4d498118540",
+    "d0dd7295c2cadb241a8b13b48912ff93fc6a68d1d849cd054173a17718038443a10126a05901f78258202dd0",
+    "0bd77e0222ced882665481a9c1d9f907309d16e05ed007a1ea63928477a95901d1840363544545ae65627261",
+    "6e6466476f6f676c6565667573656401656d6f64656c77437574746c6566697368207838365f36342070686f",
+    "6e65666465766963656b76736f635f7838365f36346770726f647563746f63665f7838365f36345f70686f6e",
+    "656876625f737461746565677265656e6a6f735f76657273696f6e6231346c6d616e75666163747572657266",
+    "476f6f676c656d76626d6574615f6469676573745820a0c6f07a4b3a17fb9348db981de3c5602e2685d62659",
+    "9be1bd909195c694a57b6e73656375726974795f6c6576656c6374656570626f6f745f70617463685f6c6576",
+    "656c1a0134d8dd70626f6f746c6f616465725f7374617465666c6f636b65647273797374656d5f7061746368",
+    "5f6c6576656c1a000316a67276656e646f725f70617463685f6c6576656c1a0134d8dd82a501020326200121",
+    "58203a96ede937e59086f86f84af4994e267ef987e2c23b64d0d186696324592492e225820b42b0432b62430",
+    "9de2f1f1d7f1494f633d5e4022a9b940c2061a500d3aa77347a50102032620012158207db025c5c8e46a8fb0",
+    "2f24d47ef0138f388f3cc7897ad06069b9ffd7391dbc07225820605b8bb1740cf6f18d2bc74e89d855d913aa",
+    "11b5ebfa8f36c6592cb153eddbde5840653731d4e12d2acf1f60cd118f10f29670ebc75ccfffc589efa7d16d",
+    "ff17d78a62de2e510b33844bfdc17055ef832f18e4560d30d2f39218e1e190707613857d",
+);
+
+fn request() -> AuthenticatedRequest {
+    AuthenticatedRequest {
+        uds_certs: vec![(
+            "vendor".to_owned(),
+            vec![vec![0x30, 0x01], vec![0x30, 0x02]],
+        )],
+        dice_chain: dice_chain(2),
+        signed_data: signed_data(&[0xcc; 16], csr_payload(), 2),
+    }
+}
+
+#[test]
+fn test_device_info() {
+    let info = device_info();
+    info.check_complete().unwrap();
+    let value = info.clone().to_cbor_value().unwrap();
+    let keys: Vec<String> = value
+        .clone()
+        .try_as_map()
+        .unwrap()
+        .into_iter()
+        .map(|(k, _)| k.try_as_string().unwrap())
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            "brand",
+            "fused",
+            "model",
+            "device",
+            "product",
+            "vb_state",
+            "os_version",
+            "manufacturer",
+            "vbmeta_digest",
+            "security_level",
+            "boot_patch_level",
+            "bootloader_state",
+            "system_patch_level",
+            "vendor_patch_level",
+        ]
+    );
+    assert_eq!(DeviceInfo::from_cbor_value(value).unwrap(), info);
+
+    let info = DeviceInfo {
+        vb_state: Some(VerifiedBootState::Orange),
+        bootloader_state: Some(BootloaderState::Unlocked),
+        security_level: Some(SecurityLevel::StrongBox),
+        fused: Some(false),
+        os_version: None,
+        rest: vec![("dice_issuer".to_owned(), Value::Null)],
+        ..device_info()
+    };
+    let data = info.clone().to_vec().unwrap();
+    assert_eq!(DeviceInfo::from_slice(&data).unwrap(), info);
+
+    expect_err(
+        DeviceInfo::default().check_complete(),
+        "DeviceInfo with all required entries",
+    );
+    let info = DeviceInfo {
+        rest: vec![("brand".to_owned(), Value::Null)],
+        ..device_info()
+    };
+    expect_err(info.to_vec(), "duplicate map key");
+
+    let tests = [
+        // {"model": "x", "brand": "y"}
+        (
+            "a2656d6f64656c6178656272616e646179",
+            "canonically ordered map",
+        ),
+        // {"device": "x", "brand": "y"}
+        (
+            "a2666465766963656178656272616e646179",
+            "canonically ordered map",
+        ),
+        // {"brand": "x", "brand": "y"}
+        ("a2656272616e646178656272616e646179", "duplicate map key"),
+        // {"vb_state": "red"}
+        ("a16876625f737461746563726564", "verified boot state"),
+        // {"bootloader_state": "open"}
+        (
+            "a170626f6f746c6f616465725f7374617465646f70656e",
+            "bootloader state",
+        ),
+        // {"security_level": "ree"}
+        ("a16e73656375726974795f6c6576656c63726565", "security level"),
+        // {"fused": 2}
+        ("a165667573656402", "out of range"),
+        // {"fused": true}
+        ("a1656675736564f5", "int"),
+        // {1: "x"}
+        ("a1016178", "tstr"),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(DeviceInfo::from_slice(&data), err_msg);
+    }
+}
+
+#[test]
+fn test_csr_payload() {
+    let payload = csr_payload();
+    let data = payload.clone().to_vec().unwrap();
+    assert_eq!(hex::encode(&data[..5]), "8403635445");
+    assert_eq!(CsrPayload::from_slice(&data).unwrap(), payload);
+
+    let tests = [
+        // [2, "TEE", {}, []]
+        ("8402635445454080", "supported schema version"),
+        // [3, "TEE", {}]
+        ("830363544545a0", "expected entry count"),
+        // [3, "TEE", {}, [1]]
+        ("840363544545a08101", "map"),
+        // [3, 1, {}, []]
+        ("840301a080", "tstr"),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(CsrPayload::from_slice(&data), err_msg);
+    }
+}
+
+#[test]
+fn test_authenticated_request() {
+    let data = request().to_vec().unwrap();
+    let req = AuthenticatedRequest::from_slice(&data).unwrap();
+    assert_eq!(req.uds_certs, request().uds_certs);
+    assert_eq!(req.clone().to_vec().unwrap(), data);
+
+    let (challenge, payload) = req.signed_payload().unwrap();
+    assert_eq!(challenge, vec![0xcc; 16]);
+    assert_eq!(payload, csr_payload());
+
+    let verified = req.verify(test_crypto::verify_with_key).unwrap();
+    assert_eq!(verified.challenge, vec![0xcc; 16]);
+    assert_eq!(verified.payload, csr_payload());
+    assert_eq!(verified.dice_claims.len(), 2);
+    assert_eq!(
+        verified.dice_claims[1].subject_public_key,
+        Some(public_key(2))
+    );
+
+    // A degenerate DICE chain holds a single certificate signed by the UDS key.
+    let req = AuthenticatedRequest {
+        dice_chain: dice_chain(1),
+        signed_data: signed_data(&[], csr_payload(), 1),
+        ..request()
+    };
+    let verified = req.verify(test_crypto::verify_with_key).unwrap();
+    assert!(verified.challenge.is_empty());
+}
+
+#[test]
+fn test_authenticated_request_fail() {
+    let mut wrong_signer = request();
+    wrong_signer.signed_data = signed_data(&[0xcc; 16], csr_payload(), 1);
+
+    let mut bad_chain = request();
+    bad_chain.dice_chain.root_key = public_key(9);
+
+    let mut long_challenge = request();
+    long_challenge.signed_data = signed_data(&[0xcc; 65], csr_payload(), 2);
+
+    let mut bad_payload = request();
+    bad_payload.signed_data = CoseSign1Builder::new()
+        .payload(vec![0x81, 0x40])
+        .create_signature(b"", |data| test_crypto::sign(2, data))
+        .build();
+
+    let tests = [
+        (wrong_signer, "verification failed: bad signature"),
+        (
+            bad_chain,
+            "certificate 0 verification failed: bad signature",
+        ),
+        (long_challenge, "challenge of 0-64 bytes"),
+        (bad_payload, "array with challenge and CSR payload"),
+    ];
+    for (req, err_msg) in tests {
+        let err = req
+            .verify(test_crypto::verify_with_key)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(err_msg),
+            "{} doesn't contain '{}'",
+            err,
+            err_msg
+        );
+    }
+
+    let mut no_payload = request();
+    no_payload.signed_data.payload = None;
+    expect_err(
+        no_payload.signed_payload(),
+        "signed challenge and CSR payload",
+    );
+
+    let mut value = request().to_cbor_value().unwrap();
+    if let Value::Array(a) = &mut value {
+        a[0] = Value::from(2);
+    }
+    expect_err(
+        AuthenticatedRequest::from_cbor_value(value),
+        "supported schema version",
+    );
+    let mut value = request().to_cbor_value().unwrap();
+    if let Value::Array(a) = &mut value {
+        a[1] = Value::Map(vec![(
+            Value::Text("vendor".to_owned()),
+            Value::Array(vec![Value::Bytes(vec![0x30])]),
+        )]);
+    }
+    expect_err(
+        AuthenticatedRequest::from_cbor_value(value),
+        "certificate chain of 2+ certificates",
+    );
+    let mut value = request().to_cbor_value().unwrap();
+    if let Value::Array(a) = &mut value {
+        let entry = (
+            Value::Text("vendor".to_owned()),
+            Value::Array(vec![Value::Bytes(vec![0x30]), Value::Bytes(vec![0x30])]),
+        );
+        a[1] = Value::Map(vec![entry.clone(), entry]);
+    }
+    expect_err(
+        AuthenticatedRequest::from_cbor_value(value),
+        "duplicate map key",
+    );
+}
+
+#[test]
+fn test_maced_public_key() {
+    let key = CoseKeyBuilder::new_okp_key()
+        .param(iana::OkpKeyParameter::X as i64, Value::Bytes(vec![7; 32]))
+        .param(TEST_KEY_PARAM, Value::Null)
+        .build();
+    assert!(is_test_key(&key));
+    assert!(!is_test_key(&public_key(7)));
+
+    let mac0 = CoseMac0Builder::new()
+        .protected(
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::HMAC_256_256)
+                .build(),
+        )
+        .payload(key.clone().to_vec().unwrap())
+        .create_tag(b"", |data| test_crypto::sign(5, data))
+        .build();
+    let verify = |tag: &[u8], data: &[u8]| test_crypto::verify(5, tag, data);
+    assert_eq!(verify_maced_public_key(&mac0, verify).unwrap(), key);
+
+    let mut bad_tag = mac0.clone();
+    bad_tag.tag[0] ^= 1;
+    let err = verify_maced_public_key(&bad_tag, verify).unwrap_err();
+    assert_eq!(err.to_string(), "verification failed: bad signature");
+
+    let mut no_payload = mac0.clone();
+    no_payload.payload = None;
+    let err = verify_maced_public_key(&no_payload, verify).unwrap_err();
+    assert!(err.to_string().contains("public key payload"), "{}", err);
+
+    let mut bad_key = mac0;
+    bad_key.payload = Some(vec![0x01]);
+    let err = verify_maced_public_key(&bad_key, |_, _| Ok::<(), &str>(())).unwrap_err();
+    assert!(err.to_string().contains("invalid RKP data"), "{}", err);
+}
+
+/// Verify an EdDSA (Ed25519) or ES256 (P-256) signature.
+fn verify_signature(key: &CoseKey, sig: &[u8], data: &[u8]) -> Result<(), String> {
+    let param = |label: i64| match key.param(label) {
+        Some(Value::Bytes(b)) => Ok(b.as_slice()),
+        _ => Err(format!("missing key parameter {label}")),
+    };
+    match &key.alg {
+        Some(Algorithm::Assigned(iana::Algorithm::EdDSA)) => {
+            use ed25519_dalek::{Signature, VerifyingKey};
+            let x = param(iana::OkpKeyParameter::X as i64)?;
+            let key = VerifyingKey::try_from(x).map_err(|e| e.to_string())?;
+            let sig = Signature::from_slice(sig).map_err(|e| e.to_string())?;
+            key.verify_strict(data, &sig).map_err(|e| e.to_string())
+        }
+        Some(Algorithm::Assigned(iana::Algorithm::ES256)) => {
+            use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+            let point = [
+                &[0x04][..],
+                param(iana::Ec2KeyParameter::X as i64)?,
+                param(iana::Ec2KeyParameter::Y as i64)?,
+            ]
+            .concat();
+            let key = VerifyingKey::from_sec1_bytes(&point).map_err(|e| e.to_string())?;
+            let sig = Signature::from_slice(sig).map_err(|e| e.to_string())?;
+            key.verify(data, &sig).map_err(|e| e.to_string())
+        }
+        alg => Err(format!("unsupported algorithm {alg:?}")),
+    }
+}
+
+#[test]
+fn test_authenticated_request_real_signatures() {
+    let data = hex::decode(CSR).unwrap();
+    let request = AuthenticatedRequest::from_slice(&data).unwrap();
+    assert!(request.uds_certs.is_empty());
+    assert_eq!(request.dice_chain.certs.len(), 2);
+    assert_eq!(request.clone().to_vec().unwrap(), data);
+
+    let verified = request.verify(verify_signature).unwrap();
+    let names: Vec<_> = verified
+        .dice_claims
+        .iter()
+        .map(|c| c.config_descriptor().unwrap().unwrap().component_name)
+        .collect();
+    assert_eq!(
+        names,
+        vec![Some("ABL".to_owned()), Some("KeyMint".to_owned())]
+    );
+    assert!(verified.dice_claims.iter().all(|c| c.allows_cert_sign()));
+    assert_eq!(
+        hex::encode(&verified.challenge),
+        "2dd00bd77e0222ced882665481a9c1d9f907309d16e05ed007a1ea63928477a9"
+    );
+
+    let payload = verified.payload;
+    assert_eq!(payload.certificate_type, "TEE");
+    let device_info = payload.device_info;
+    device_info.check_complete().unwrap();
+    assert_eq!(
+        device_info,
+        DeviceInfo {
+            brand: Some("Google".to_owned()),
+            manufacturer: Some("Google".to_owned()),
+            product: Some("cf_x86_64_phone".to_owned()),
+            model: Some("Cuttlefish x86_64 phone".to_owned()),
+            device: Some("vsoc_x86_64".to_owned()),
+            vb_state: Some(VerifiedBootState::Green),
+            bootloader_state: Some(BootloaderState::Locked),
+            vbmeta_digest: Some(
+                hex::decode("a0c6f07a4b3a17fb9348db981de3c5602e2685d626599be1bd909195c694a57b")
+                    .unwrap()
+            ),
+            os_version: Some("14".to_owned()),
+            system_patch_level: Some(202406),
+            boot_patch_level: Some(20240605),
+            vendor_patch_level: Some(20240605),
+            security_level: Some(SecurityLevel::Tee),
+            fused: Some(true),
+            rest: vec![],
+        }
+    );
+
+    let keys: Vec<_> = payload
+        .keys_to_sign
+        .iter()
+        .map(|key| {
+            assert_eq!(key.kty, KeyType::Assigned(iana::KeyType::EC2));
+            assert_eq!(key.alg, Some(Algorithm::Assigned(iana::Algorithm::ES256)));
+            assert!(!is_test_key(key));
+            let x = key.param(iana::Ec2KeyParameter::X as i64).unwrap();
+            let y = key.param(iana::Ec2KeyParameter::Y as i64).unwrap();
+            (
+                hex::encode(x.as_bytes().unwrap()),
+                hex::encode(y.as_bytes().unwrap()),
+            )
+        })
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            (
+                "3a96ede937e59086f86f84af4994e267ef987e2c23b64d0d186696324592492e".to_owned(),
+                "b42b0432b624309de2f1f1d7f1494f633d5e4022a9b940c2061a500d3aa77347".to_owned()
+            ),
+            (
+                "7db025c5c8e46a8fb02f24d47ef0138f388f3cc7897ad06069b9ffd7391dbc07".to_owned(),
+                "605b8bb1740cf6f18d2bc74e89d855d913aa11b5ebfa8f36c6592cb153eddbde".to_owned()
+            ),
+        ]
+    );
+
+    // Any change to the DICE chain or the signed data is detected.
+    let mut bad_cert = request.clone();
+    bad_cert.dice_chain.certs[0].signature[10] ^= 0x01;
+    expect_err(
+        bad_cert.verify(verify_signature),
+        "certificate 0 verification failed",
+    );
+    let mut bad_sig = request.clone();
+    bad_sig.signed_data.signature[10] ^= 0x01;
+    expect_err(bad_sig.verify(verify_signature), "verification failed");
+    let mut bad_payload = request;
+    if let Some(payload) = &mut bad_payload.signed_data.payload {
+        let last = payload.len() - 1;
+        payload[last] ^= 0x01;
+    }
+    expect_err(bad_payload.verify(verify_signature), "verification failed");
+}
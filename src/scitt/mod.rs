@@ -0,0 +1,372 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Supply chain integrity, transparency and trust (SCITT) signed statements and COSE receipts.
+//!
+//! A signed statement is a `COSE_Sign1` whose protected header carries the algorithm, the `typ`
+//! of the statement and CWT claims identifying its issuer and subject.  A transparency service
+//! registers the statement in a verifiable data structure and returns a receipt, which is a
+//! `COSE_Sign1` over the root of that structure (as a detached payload), carrying the proofs that
+//! tie the registered entry to the root.  Receipts are attached to a statement with the
+//! `receipts` header parameter (see [`Header::receipts`](crate::Header::receipts)).  See
+//! draft-ietf-cose-merkle-tree-proofs.
+//!
+//! Only the [`iana::VerifiableDataStructure::RFC9162_SHA256`] structure (the binary Merkle tree
+//! of RFC 9162) and its inclusion proofs are currently supported.  As elsewhere in this crate,
+//! signature and hash operations are performed by caller-provided closures.
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    cwt::ClaimsSet,
+    iana::{self, EnumI64},
+    util::{cbor_type_error, ValueTryAs},
+    CborSerializable, CoseError, CoseHeaders, CoseSign1, CoseSign1Builder, Label,
+};
+use alloc::{vec, vec::Vec};
+use core::convert::TryInto;
+
+#[cfg(test)]
+mod tests;
+
+const VDS: Label = Label::Int(iana::HeaderParameter::Vds as i64);
+const VDP: Label = Label::Int(iana::HeaderParameter::Vdp as i64);
+
+/// Domain separation prefix for the hash of a Merkle tree leaf (RFC 9162 section 2.1.1).
+const LEAF_PREFIX: u8 = 0x00;
+/// Domain separation prefix for the hash of a Merkle tree interior node (RFC 9162 section 2.1.1).
+const NODE_PREFIX: u8 = 0x01;
+
+/// Return the hash of a Merkle tree leaf holding `entry` (`HASH(0x00 || entry)`), using `hasher`
+/// to perform the hash (SHA-256 for [`iana::VerifiableDataStructure::RFC9162_SHA256`]).
+pub fn leaf_hash<F, E>(entry: &[u8], hasher: F) -> Result<Vec<u8>, E>
+where
+    F: FnOnce(&[u8]) -> Result<Vec<u8>, E>,
+{
+    let mut data = Vec::with_capacity(1 + entry.len());
+    data.push(LEAF_PREFIX);
+    data.extend_from_slice(entry);
+    hasher(&data)
+}
+
+/// Return the hash of a Merkle tree interior node (`HASH(0x01 || left || right)`).
+fn node_hash<F, E>(left: &[u8], right: &[u8], hasher: &mut F) -> Result<Vec<u8>, E>
+where
+    F: FnMut(&[u8]) -> Result<Vec<u8>, E>,
+{
+    let mut data = Vec::with_capacity(1 + left.len() + right.len());
+    data.push(NODE_PREFIX);
+    data.extend_from_slice(left);
+    data.extend_from_slice(right);
+    hasher(&data)
+}
+
+/// Inclusion proof for an [`iana::VerifiableDataStructure::RFC9162_SHA256`] Merkle tree.
+///
+/// ```cddl
+///  inclusion-proof = bstr .cbor [
+///      tree-size: uint
+///      leaf-index: uint
+///      inclusion-path: [ + bstr ]
+///  ]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InclusionProof {
+    /// Number of leaves in the tree.
+    pub tree_size: u64,
+    /// Zero-based index of the leaf.
+    pub leaf_index: u64,
+    /// Hashes of the sibling nodes on the path from the leaf to the root, starting at the leaf.
+    pub path: Vec<Vec<u8>>,
+}
+
+impl CborSerializable for InclusionProof {}
+
+impl AsCborValue for InclusionProof {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let a = value.try_as_array()?;
+        let [tree_size, leaf_index, path]: [Value; 3] = a
+            .try_into()
+            .map_err(|_| CoseError::UnexpectedItem("array", "array with 3 items"))?;
+        Ok(Self {
+            tree_size: tree_size.try_as_integer()?.try_into()?,
+            leaf_index: leaf_index.try_as_integer()?.try_into()?,
+            path: path.try_as_array_then_convert(|v| v.try_as_bytes())?,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        Ok(Value::Array(vec![
+            Value::from(self.tree_size),
+            Value::from(self.leaf_index),
+            Value::Array(self.path.into_iter().map(Value::Bytes).collect()),
+        ]))
+    }
+}
+
+impl InclusionProof {
+    /// Recompute the root of the tree from the hash of the leaf (see [`leaf_hash`]), using
+    /// `hasher` to perform the hash, following RFC 9162 section 2.1.3.2.
+    pub fn root<F, E>(&self, leaf_hash: &[u8], mut hasher: F) -> Result<Vec<u8>, ScittError<E>>
+    where
+        F: FnMut(&[u8]) -> Result<Vec<u8>, E>,
+    {
+        if self.leaf_index >= self.tree_size {
+            return Err(ScittError::InvalidProof);
+        }
+        let mut fnode = self.leaf_index;
+        let mut snode = self.tree_size - 1;
+        let mut r = leaf_hash.to_vec();
+        for p in &self.path {
+            if snode == 0 {
+                return Err(ScittError::InvalidProof);
+            }
+            if fnode & 1 == 1 || fnode == snode {
+                r = node_hash(p, &r, &mut hasher).map_err(ScittError::Crypto)?;
+                while fnode & 1 == 0 && fnode != 0 {
+                    fnode >>= 1;
+                    snode >>= 1;
+                }
+            } else {
+                r = node_hash(&r, p, &mut hasher).map_err(ScittError::Crypto)?;
+            }
+            fnode >>= 1;
+            snode >>= 1;
+        }
+        if snode != 0 {
+            return Err(ScittError::InvalidProof);
+        }
+        Ok(r)
+    }
+}
+
+/// Return the inclusion proofs held in the `vdp` unprotected header parameter of `receipt`.
+/// Fails if the protected header of the receipt does not identify the
+/// [`iana::VerifiableDataStructure::RFC9162_SHA256`] structure, or if there are no inclusion
+/// proofs.
+pub fn inclusion_proofs(receipt: &CoseSign1) -> Result<Vec<InclusionProof>, CoseError> {
+    match receipt
+        .protected_header()
+        .rest
+        .iter()
+        .find(|(l, _)| *l == VDS)
+    {
+        Some((_, Value::Integer(i))) => {
+            let i: i64 = (*i).try_into()?;
+            if iana::VerifiableDataStructure::from_i64(i)
+                != Some(iana::VerifiableDataStructure::RFC9162_SHA256)
+            {
+                return Err(CoseError::UnregisteredIanaValue);
+            }
+        }
+        Some((_, v)) => return cbor_type_error(v, "int"),
+        None => return Err(CoseError::UnexpectedItem("no vds", "protected vds")),
+    }
+    let vdp = match receipt
+        .unprotected_header()
+        .rest
+        .iter()
+        .find(|(l, _)| *l == VDP)
+    {
+        Some((_, v)) => v.clone().try_as_map()?,
+        None => return Err(CoseError::UnexpectedItem("no vdp", "unprotected vdp")),
+    };
+    let inclusion = Value::from(iana::Rfc9162Sha256ProofType::InclusionProof as i64);
+    let proofs = match vdp.into_iter().find(|(k, _)| *k == inclusion) {
+        Some((_, v)) => {
+            v.try_as_array_then_convert(|v| InclusionProof::from_slice(&v.try_as_bytes()?))?
+        }
+        None => vec![],
+    };
+    if proofs.is_empty() {
+        return Err(CoseError::UnexpectedItem(
+            "no inclusion proofs",
+            "1+ inclusion proofs",
+        ));
+    }
+    Ok(proofs)
+}
+
+/// Create a receipt for the leaf covered by `proof`, using `builder` (which should already hold
+/// the algorithm and any key identifier) and `signer` to generate a signature over `root` as a
+/// detached payload.  The `vds` and `vdp` header parameters are filled in.
+pub fn create_receipt<F>(
+    builder: CoseSign1Builder,
+    proof: InclusionProof,
+    root: &[u8],
+    signer: F,
+) -> Result<CoseSign1, CoseError>
+where
+    F: FnOnce(&[u8]) -> Vec<u8>,
+{
+    let mut receipt = builder.build();
+    receipt.payload = None;
+    let protected = &mut receipt.protected;
+    protected.original_data = None;
+    protected.header.rest.retain(|(l, _)| *l != VDS);
+    protected.header.rest.push((
+        VDS,
+        Value::from(iana::VerifiableDataStructure::RFC9162_SHA256 as i64),
+    ));
+    receipt.unprotected.rest.retain(|(l, _)| *l != VDP);
+    receipt.unprotected.rest.push((
+        VDP,
+        Value::Map(vec![(
+            Value::from(iana::Rfc9162Sha256ProofType::InclusionProof as i64),
+            Value::Array(vec![Value::Bytes(proof.to_vec()?)]),
+        )]),
+    ));
+    receipt.signature = signer(&receipt.tbs_detached_data(root, b""));
+    Ok(receipt)
+}
+
+/// Verify a receipt for the leaf with hash `leaf_hash` (see [`leaf_hash`]), and return the
+/// root of the tree.  The root is recomputed from each of the inclusion proofs in the receipt
+/// using `hasher`, then the signature over the root (as a detached payload) is checked using
+/// `verifier` on the signature value and serialized data (in that order).
+///
+/// Note that the receipt's key and algorithm, which identify the transparency service, are left
+/// for the verifier to check.
+pub fn verify_receipt<F, G, E>(
+    receipt: &CoseSign1,
+    leaf_hash: &[u8],
+    mut hasher: F,
+    verifier: G,
+) -> Result<Vec<u8>, ScittError<E>>
+where
+    F: FnMut(&[u8]) -> Result<Vec<u8>, E>,
+    G: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+{
+    if receipt.payload.is_some() {
+        return Err(CoseError::UnexpectedItem("payload", "detached payload").into());
+    }
+    let mut root = None;
+    for proof in inclusion_proofs(receipt)? {
+        let r = proof.root(leaf_hash, &mut hasher)?;
+        match &root {
+            Some(root) if *root != r => return Err(ScittError::InvalidProof),
+            Some(_) => {}
+            None => root = Some(r),
+        }
+    }
+    let root = root.ok_or(ScittError::InvalidProof)?;
+    receipt
+        .verify_detached_signature(&root, b"", verifier)
+        .map_err(ScittError::Crypto)?;
+    Ok(root)
+}
+
+/// Return the CWT claims of a signed statement, after checking that its protected header holds
+/// an algorithm, a `typ` and CWT claims with an issuer and a subject.
+pub fn signed_statement_claims(statement: &CoseSign1) -> Result<ClaimsSet, CoseError> {
+    let protected = statement.protected_header();
+    if protected.alg.is_none() {
+        return Err(CoseError::UnexpectedItem("no alg", "protected alg"));
+    }
+    if protected.typ()?.is_none() {
+        return Err(CoseError::UnexpectedItem("no typ", "protected typ"));
+    }
+    let claims = statement
+        .protected_cwt_claims()?
+        .ok_or(CoseError::UnexpectedItem(
+            "no CWT claims",
+            "protected CWT claims",
+        ))?;
+    if claims.issuer.is_none() || claims.subject.is_none() {
+        return Err(CoseError::UnexpectedItem(
+            "CWT claims",
+            "CWT claims with iss and sub",
+        ));
+    }
+    Ok(claims)
+}
+
+/// Create a signed statement over `payload`, using `builder` (whose protected header should
+/// already hold the algorithm, the `typ` and the CWT claims) and `signer` to generate the
+/// signature bytes.
+pub fn sign_statement<F>(
+    builder: CoseSign1Builder,
+    payload: Vec<u8>,
+    signer: F,
+) -> Result<CoseSign1, CoseError>
+where
+    F: FnOnce(&[u8]) -> Vec<u8>,
+{
+    let mut statement = builder.payload(payload).build();
+    signed_statement_claims(&statement)?;
+    statement.signature = signer(&statement.tbs_data(b""));
+    Ok(statement)
+}
+
+/// Verify a signed statement, using `verifier` on the signature value and serialized data (in
+/// that order), and return its CWT claims.  Any receipts in the unprotected header are not
+/// checked; use [`verify_receipt`] for each of those.
+pub fn verify_signed_statement<F, E>(
+    statement: &CoseSign1,
+    verifier: F,
+) -> Result<ClaimsSet, ScittError<E>>
+where
+    F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+{
+    let claims = signed_statement_claims(statement)?;
+    if statement.payload.is_none() {
+        return Err(CoseError::UnexpectedItem("detached payload", "payload").into());
+    }
+    statement
+        .verify_signature(b"", verifier)
+        .map_err(ScittError::Crypto)?;
+    Ok(claims)
+}
+
+/// The error type returned when SCITT verification fails.
+pub enum ScittError<E> {
+    /// The SCITT data could not be parsed.
+    Cose(CoseError),
+    /// The signature or hash operation failed.
+    Crypto(E),
+    /// An inclusion proof is not consistent with its tree, or with the other proofs.
+    InvalidProof,
+}
+
+impl<E: core::fmt::Display> core::error::Error for ScittError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Debug for ScittError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for ScittError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> ScittError<E> {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ScittError::Cose(e) => write!(f, "invalid SCITT data: {e}"),
+            ScittError::Crypto(e) => write!(f, "verification failed: {e}"),
+            ScittError::InvalidProof => write!(f, "invalid inclusion proof"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for ScittError<E> {
+    fn from(e: CoseError) -> Self {
+        ScittError::Cose(e)
+    }
+}
@@ -0,0 +1,417 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{
+    cwt::ClaimsSetBuilder,
+    util::{expect_err, test_crypto},
+    HeaderBuilder, MediaType, TaggedCborSerializable,
+};
+use alloc::{borrow::ToOwned, string::String, vec};
+
+/// SHA-256 hash of `data`.
+fn hash(data: &[u8]) -> Result<Vec<u8>, String> {
+    test_crypto::digest(iana::Algorithm::SHA_256, data)
+}
+
+/// Largest power of two less than `n`.
+fn split(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+/// Merkle tree hash of the given leaf entries (RFC 9162 section 2.1.1).
+fn mth(entries: &[Vec<u8>]) -> Vec<u8> {
+    match entries.len() {
+        0 => hash(&[]).unwrap(),
+        1 => leaf_hash(&entries[0], hash).unwrap(),
+        n => {
+            let k = split(n);
+            let mut data = vec![0x01];
+            data.extend_from_slice(&mth(&entries[..k]));
+            data.extend_from_slice(&mth(&entries[k..]));
+            hash(&data).unwrap()
+        }
+    }
+}
+
+/// Merkle audit path for the entry with index `m` (RFC 9162 section 2.1.3.1).
+fn path(m: usize, entries: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let n = entries.len();
+    if n <= 1 {
+        return vec![];
+    }
+    let k = split(n);
+    let (mut path, sibling) = if m < k {
+        (path(m, &entries[..k]), mth(&entries[k..]))
+    } else {
+        (path(m - k, &entries[k..]), mth(&entries[..k]))
+    };
+    path.push(sibling);
+    path
+}
+
+fn entries(n: usize) -> Vec<Vec<u8>> {
+    (0..n).map(|i| vec![i as u8; i + 1]).collect()
+}
+
+fn proof(m: usize, entries: &[Vec<u8>]) -> InclusionProof {
+    InclusionProof {
+        tree_size: entries.len() as u64,
+        leaf_index: m as u64,
+        path: path(m, entries),
+    }
+}
+
+fn receipt(m: usize, entries: &[Vec<u8>]) -> CoseSign1 {
+    create_receipt(
+        CoseSign1Builder::new()
+            .protected(
+                HeaderBuilder::new()
+                    .algorithm(iana::Algorithm::ES256)
+                    .build(),
+            )
+            .unprotected(HeaderBuilder::new().key_id(b"ts".to_vec()).build()),
+        proof(m, entries),
+        &mth(entries),
+        |data| test_crypto::sign(7, data),
+    )
+    .unwrap()
+}
+
+fn statement_builder() -> CoseSign1Builder {
+    CoseSign1Builder::new().protected(
+        HeaderBuilder::new()
+            .algorithm(iana::Algorithm::ES256)
+            .typ_media_type(MediaType::parse("application/example+cose").unwrap())
+            .cwt_claims(
+                ClaimsSetBuilder::new()
+                    .issuer("did:example:issuer".to_owned())
+                    .subject("pkg:example/widget@1.0".to_owned())
+                    .build(),
+            )
+            .build(),
+    )
+}
+
+#[test]
+fn test_inclusion_proof_encode() {
+    let proof = InclusionProof {
+        tree_size: 3,
+        leaf_index: 1,
+        path: vec![vec![0x01, 0x02], vec![0x03]],
+    };
+    let data = proof.clone().to_vec().unwrap();
+    assert_eq!(
+        hex::encode(&data),
+        concat!(
+            "83", // 3-arr
+            "03", // tree-size
+            "01", // leaf-index
+            "82", "420102", "4103", // inclusion-path
+        )
+    );
+    assert_eq!(InclusionProof::from_slice(&data).unwrap(), proof);
+
+    let tests = [
+        ("a0", "expected array"),
+        ("820301", "expected array with 3 items"),
+        ("8403018040", "expected array with 3 items"),
+        ("83200180", "out of range integer value"),
+        ("8303410080", "expected int"),
+        ("83030141ff", "expected array"),
+        ("8303018101", "expected bstr"),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(InclusionProof::from_slice(&data), err_msg);
+    }
+}
+
+#[test]
+fn test_inclusion_proof_root() {
+    for n in 1..=11 {
+        let entries = entries(n);
+        let root = mth(&entries);
+        for m in 0..n {
+            let leaf = leaf_hash(&entries[m], hash).unwrap();
+            let got = proof(m, &entries).root(&leaf, hash).unwrap();
+            assert_eq!(got, root, "tree size {} leaf {}", n, m);
+
+            // A proof for a different leaf gives a different root.
+            let other = proof((m + 1) % n, &entries);
+            if n > 1 {
+                match other.root(&leaf, hash) {
+                    Ok(got) => assert_ne!(got, root, "tree size {} leaf {}", n, m),
+                    Err(ScittError::InvalidProof) => {}
+                    Err(e) => panic!("unexpected error {}", e),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_inclusion_proof_root_fail() {
+    let entries = entries(5);
+    let leaf = leaf_hash(&entries[2], hash).unwrap();
+
+    let mut bad = proof(2, &entries);
+    bad.leaf_index = 5;
+    expect_err(bad.root(&leaf, hash), "invalid inclusion proof");
+
+    let mut short = proof(2, &entries);
+    short.path.pop();
+    expect_err(short.root(&leaf, hash), "invalid inclusion proof");
+
+    let mut long = proof(2, &entries);
+    long.path.push(vec![0; 16]);
+    expect_err(long.root(&leaf, hash), "invalid inclusion proof");
+
+    let mut wrong_size = proof(2, &entries);
+    wrong_size.tree_size = 4;
+    let got = wrong_size.root(&leaf, hash);
+    assert!(got.map(|r| r != mth(&entries)).unwrap_or(true));
+
+    let empty = InclusionProof::default();
+    expect_err(empty.root(&leaf, hash), "invalid inclusion proof");
+
+    expect_err(
+        proof(2, &entries).root(&leaf, |_data| Err("no hash".to_owned())),
+        "verification failed: no hash",
+    );
+}
+
+#[test]
+fn test_receipt_roundtrip() {
+    let entries = entries(6);
+    let receipt = receipt(4, &entries);
+    let data = receipt.clone().to_tagged_vec().unwrap();
+    let receipt = CoseSign1::from_tagged_slice(&data).unwrap();
+    assert_eq!(receipt.payload, None);
+    assert_eq!(
+        inclusion_proofs(&receipt).unwrap(),
+        vec![proof(4, &entries)]
+    );
+
+    let leaf = leaf_hash(&entries[4], hash).unwrap();
+    let root = verify_receipt(&receipt, &leaf, hash, test_crypto::verifier(7)).unwrap();
+    assert_eq!(root, mth(&entries));
+
+    // Attaching the receipt to a signed statement leaves the statement signature intact.
+    let statement = sign_statement(statement_builder(), b"payload".to_vec(), |data| {
+        test_crypto::sign(1, data)
+    })
+    .unwrap();
+    let mut transparent = statement.clone();
+    transparent.unprotected = HeaderBuilder::new().add_receipt(receipt.clone()).build();
+    let transparent = CoseSign1::from_slice(&transparent.to_vec().unwrap()).unwrap();
+    verify_signed_statement(&transparent, test_crypto::verifier(1)).unwrap();
+    let receipts = transparent.unprotected.receipts().unwrap();
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(
+        verify_receipt(&receipts[0], &leaf, hash, test_crypto::verifier(7)).unwrap(),
+        root
+    );
+}
+
+#[test]
+fn test_receipt_verify_fail() {
+    let entries = entries(6);
+    let receipt = receipt(4, &entries);
+    let leaf = leaf_hash(&entries[4], hash).unwrap();
+    let verify = |receipt: &CoseSign1, leaf: &[u8]| {
+        verify_receipt(receipt, leaf, hash, test_crypto::verifier(7))
+    };
+
+    let other_leaf = leaf_hash(&entries[3], hash).unwrap();
+    expect_err(verify(&receipt, &other_leaf), "bad signature");
+
+    let mut bad_sig = receipt.clone();
+    bad_sig.signature[0] ^= 0x01;
+    expect_err(
+        verify(&bad_sig, &leaf),
+        "verification failed: bad signature",
+    );
+
+    let mut attached = receipt.clone();
+    attached.payload = Some(mth(&entries));
+    expect_err(verify(&attached, &leaf), "expected detached payload");
+
+    let mut no_vds = receipt.clone();
+    no_vds.protected.header.rest.clear();
+    no_vds.protected.original_data = None;
+    expect_err(verify(&no_vds, &leaf), "expected protected vds");
+
+    let mut other_vds = no_vds.clone();
+    other_vds
+        .protected
+        .header
+        .rest
+        .push((Label::Int(395), Value::from(2)));
+    expect_err(verify(&other_vds, &leaf), "expected recognized IANA value");
+
+    let mut text_vds = no_vds.clone();
+    text_vds
+        .protected
+        .header
+        .rest
+        .push((Label::Int(395), Value::Text("1".to_owned())));
+    expect_err(verify(&text_vds, &leaf), "expected int");
+
+    let mut unprotected_vds = no_vds;
+    unprotected_vds
+        .unprotected
+        .rest
+        .push((Label::Int(395), Value::from(1)));
+    expect_err(verify(&unprotected_vds, &leaf), "expected protected vds");
+
+    let mut no_vdp = receipt.clone();
+    no_vdp.unprotected.rest.clear();
+    expect_err(verify(&no_vdp, &leaf), "expected unprotected vdp");
+
+    let mut consistency_only = no_vdp.clone();
+    consistency_only.unprotected.rest.push((
+        Label::Int(396),
+        Value::Map(vec![(Value::from(-2), Value::Array(vec![]))]),
+    ));
+    expect_err(
+        verify(&consistency_only, &leaf),
+        "expected 1+ inclusion proofs",
+    );
+
+    let mut bad_vdp = no_vdp.clone();
+    bad_vdp
+        .unprotected
+        .rest
+        .push((Label::Int(396), Value::Array(vec![])));
+    expect_err(verify(&bad_vdp, &leaf), "expected map");
+
+    // Two proofs that give different roots.
+    let mut inconsistent = no_vdp;
+    inconsistent.unprotected.rest.push((
+        Label::Int(396),
+        Value::Map(vec![(
+            Value::from(-1),
+            Value::Array(vec![
+                Value::Bytes(proof(4, &entries).to_vec().unwrap()),
+                Value::Bytes(proof(4, &entries[..5]).to_vec().unwrap()),
+            ]),
+        )]),
+    ));
+    expect_err(verify(&inconsistent, &leaf), "invalid inclusion proof");
+}
+
+#[test]
+fn test_signed_statement() {
+    let statement = sign_statement(statement_builder(), b"payload".to_vec(), |data| {
+        test_crypto::sign(1, data)
+    })
+    .unwrap();
+    let data = statement.to_tagged_vec().unwrap();
+    let statement = CoseSign1::from_tagged_slice(&data).unwrap();
+    let claims = verify_signed_statement(&statement, test_crypto::verifier(1)).unwrap();
+    assert_eq!(claims.issuer.as_deref(), Some("did:example:issuer"));
+    assert_eq!(claims.subject.as_deref(), Some("pkg:example/widget@1.0"));
+    assert_eq!(signed_statement_claims(&statement).unwrap(), claims);
+
+    expect_err(
+        verify_signed_statement(&statement, test_crypto::verifier(2)),
+        "verification failed: bad signature",
+    );
+
+    let mut detached = statement;
+    detached.payload = None;
+    expect_err(
+        verify_signed_statement(&detached, test_crypto::verifier(1)),
+        "expected payload",
+    );
+}
+
+#[test]
+fn test_signed_statement_fail() {
+    let claims = ClaimsSetBuilder::new()
+        .issuer("iss".to_owned())
+        .subject("sub".to_owned())
+        .build();
+    let typ = MediaType::parse("application/example+cose").unwrap();
+    let tests = [
+        (
+            HeaderBuilder::new()
+                .typ_media_type(typ.clone())
+                .cwt_claims(claims.clone())
+                .build(),
+            HeaderBuilder::new().build(),
+            "expected protected alg",
+        ),
+        (
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::ES256)
+                .cwt_claims(claims.clone())
+                .build(),
+            HeaderBuilder::new().build(),
+            "expected protected typ",
+        ),
+        (
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::ES256)
+                .typ_media_type(typ.clone())
+                .build(),
+            HeaderBuilder::new().build(),
+            "expected protected CWT claims",
+        ),
+        (
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::ES256)
+                .typ_media_type(typ.clone())
+                .build(),
+            HeaderBuilder::new().cwt_claims(claims.clone()).build(),
+            "expected protected CWT claims",
+        ),
+        (
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::ES256)
+                .typ_media_type(typ.clone())
+                .cwt_claims(ClaimsSetBuilder::new().issuer("iss".to_owned()).build())
+                .build(),
+            HeaderBuilder::new().build(),
+            "expected CWT claims with iss and sub",
+        ),
+        (
+            HeaderBuilder::new()
+                .algorithm(iana::Algorithm::ES256)
+                .value(16, Value::Text("bad".to_owned()))
+                .cwt_claims(claims)
+                .build(),
+            HeaderBuilder::new().build(),
+            "text of form type/subtype",
+        ),
+    ];
+    for (protected, unprotected, err_msg) in tests {
+        let builder = CoseSign1Builder::new()
+            .protected(protected)
+            .unprotected(unprotected);
+        expect_err(
+            sign_statement(builder, b"payload".to_vec(), |data| {
+                test_crypto::sign(1, data)
+            }),
+            err_msg,
+        );
+    }
+}
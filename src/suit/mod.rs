@@ -0,0 +1,402 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Software Updates for Internet of Things (SUIT) manifest authentication.
+//!
+//! A SUIT envelope authenticates its manifest with an authentication wrapper, which holds a
+//! [`SuitDigest`] of the manifest followed by one or more `COSE_Sign1` or `COSE_Mac0` objects
+//! whose detached payload is the encoded digest.  See RFC 9124 and draft-ietf-suit-manifest.
+//!
+//! As elsewhere in this crate, signature, MAC and digest operations are performed by
+//! caller-provided closures.
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    iana::{self, EnumI64},
+    util::{cbor_type_error, ValueTryAs},
+    CborSerializable, CoseError, CoseMac0, CoseMac0Builder, CoseSign1, CoseSign1Builder,
+    TaggedCborSerializable,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::convert::TryInto;
+
+#[cfg(test)]
+mod tests;
+
+/// Envelope key for the authentication wrapper (`suit-authentication-wrapper`).
+pub const ENVELOPE_AUTHENTICATION_WRAPPER: i64 = 2;
+/// Envelope key for the manifest (`suit-manifest`).
+pub const ENVELOPE_MANIFEST: i64 = 3;
+
+/// Hash algorithms that may be used in a [`SuitDigest`] (`suit-cose-hash-algs`).
+pub const DIGEST_ALGORITHMS: &[iana::Algorithm] = &[
+    iana::Algorithm::SHA_256,
+    iana::Algorithm::SHAKE128,
+    iana::Algorithm::SHA_384,
+    iana::Algorithm::SHA_512,
+    iana::Algorithm::SHAKE256,
+];
+
+/// Return the data that the digest of an encoded `SUIT_Manifest` is calculated over, which is the
+/// entire `bstr` that wraps the manifest in the envelope.
+pub fn manifest_digest_input(manifest: &[u8]) -> Result<Vec<u8>, CoseError> {
+    Value::Bytes(manifest.to_vec()).to_vec()
+}
+
+/// A digest, as used throughout SUIT.
+///
+/// ```cddl
+///  SUIT_Digest = [
+///      suit-digest-algorithm-id : suit-cose-hash-algs,
+///      suit-digest-bytes : bstr,
+///      * $$SUIT_Digest-extensions
+///  ]
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuitDigest {
+    /// Hash algorithm, which is one of [`DIGEST_ALGORITHMS`].
+    pub algorithm: iana::Algorithm,
+    /// Digest value.
+    pub bytes: Vec<u8>,
+    /// Any extension entries.
+    pub extensions: Vec<Value>,
+}
+
+impl CborSerializable for SuitDigest {}
+
+impl AsCborValue for SuitDigest {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?;
+        if a.len() < 2 {
+            return Err(CoseError::UnexpectedItem("array", "array with 2+ items"));
+        }
+        let extensions = a.split_off(2);
+        let bytes = a.remove(1).try_as_bytes()?;
+        let algorithm = match a.remove(0) {
+            Value::Integer(i) => {
+                let i: i64 = i.try_into()?;
+                iana::Algorithm::from_i64(i)
+                    .filter(|alg| DIGEST_ALGORITHMS.contains(alg))
+                    .ok_or(CoseError::UnregisteredIanaValue)?
+            }
+            v => return cbor_type_error(&v, "int"),
+        };
+        Ok(Self {
+            algorithm,
+            bytes,
+            extensions,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        if !DIGEST_ALGORITHMS.contains(&self.algorithm) {
+            return Err(CoseError::UnregisteredIanaValue);
+        }
+        let mut a = vec![Value::from(self.algorithm as i64), Value::Bytes(self.bytes)];
+        a.extend(self.extensions);
+        Ok(Value::Array(a))
+    }
+}
+
+impl SuitDigest {
+    /// Calculate the digest of `data` with `algorithm`, using `digester` to perform the hash.
+    pub fn calculate<F, E>(algorithm: iana::Algorithm, data: &[u8], digester: F) -> Result<Self, E>
+    where
+        F: FnOnce(iana::Algorithm, &[u8]) -> Result<Vec<u8>, E>,
+    {
+        Ok(Self {
+            algorithm,
+            bytes: digester(algorithm, data)?,
+            extensions: vec![],
+        })
+    }
+
+    /// Check that this is the digest of `data`, using `digester` to perform the hash.
+    pub fn check<F, E>(&self, data: &[u8], digester: F) -> Result<(), SuitError<E>>
+    where
+        F: FnOnce(iana::Algorithm, &[u8]) -> Result<Vec<u8>, E>,
+    {
+        let digest = digester(self.algorithm, data).map_err(SuitError::Crypto)?;
+        if digest != self.bytes {
+            return Err(SuitError::DigestMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// A single authentication block, which covers the encoded [`SuitDigest`] of the manifest as a
+/// detached payload.
+///
+/// ```cddl
+///  SUIT_Authentication_Block /= COSE_Mac_Tagged
+///  SUIT_Authentication_Block /= COSE_Sign_Tagged
+///  SUIT_Authentication_Block /= COSE_Mac0_Tagged
+///  SUIT_Authentication_Block /= COSE_Sign1_Tagged
+/// ```
+///
+/// Only `COSE_Sign1` and `COSE_Mac0` blocks are currently supported.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SuitAuthenticationBlock {
+    /// Signature over the manifest digest.
+    Sign1(CoseSign1),
+    /// MAC over the manifest digest.
+    Mac0(CoseMac0),
+}
+
+impl AsCborValue for SuitAuthenticationBlock {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let (tag, inner) = value.try_as_tag()?;
+        match tag {
+            CoseSign1::TAG => Ok(SuitAuthenticationBlock::Sign1(CoseSign1::from_cbor_value(
+                *inner,
+            )?)),
+            CoseMac0::TAG => Ok(SuitAuthenticationBlock::Mac0(CoseMac0::from_cbor_value(
+                *inner,
+            )?)),
+            _ => Err(CoseError::UnexpectedItem(
+                "tag",
+                "COSE_Sign1 or COSE_Mac0 tag",
+            )),
+        }
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let (tag, inner) = match self {
+            SuitAuthenticationBlock::Sign1(sign1) => (CoseSign1::TAG, sign1.to_cbor_value()?),
+            SuitAuthenticationBlock::Mac0(mac0) => (CoseMac0::TAG, mac0.to_cbor_value()?),
+        };
+        Ok(Value::Tag(tag, Box::new(inner)))
+    }
+}
+
+/// The authentication wrapper of a SUIT envelope.
+///
+/// ```cddl
+///  SUIT_Authentication = [
+///      bstr .cbor SUIT_Digest,
+///      * bstr .cbor SUIT_Authentication_Block
+///  ]
+/// ```
+///
+/// In the envelope, the encoded form of this structure is itself wrapped in a `bstr`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuitAuthentication {
+    /// If this structure was created by parsing serialized data, this field holds the contents of
+    /// the original `bstr` that holds the digest, which is the detached payload of each block.
+    pub original_digest_data: Option<Vec<u8>>,
+    /// Digest of the manifest.
+    pub digest: SuitDigest,
+    /// Authentication blocks.
+    pub blocks: Vec<SuitAuthenticationBlock>,
+}
+
+impl CborSerializable for SuitAuthentication {}
+
+impl AsCborValue for SuitAuthentication {
+    fn from_cbor_value(value: Value) -> Result<Self, CoseError> {
+        let mut a = value.try_as_array()?.into_iter();
+        let digest_data = match a.next() {
+            Some(v) => v.try_as_bytes()?,
+            None => {
+                return Err(CoseError::UnexpectedItem(
+                    "empty array",
+                    "array with digest",
+                ))
+            }
+        };
+        let digest = SuitDigest::from_slice(&digest_data)?;
+        let blocks = a
+            .map(|v| {
+                SuitAuthenticationBlock::from_cbor_value(Value::from_slice(&v.try_as_bytes()?)?)
+            })
+            .collect::<Result<Vec<_>, CoseError>>()?;
+        Ok(Self {
+            original_digest_data: Some(digest_data),
+            digest,
+            blocks,
+        })
+    }
+
+    fn to_cbor_value(self) -> Result<Value, CoseError> {
+        let mut a = vec![Value::Bytes(match self.original_digest_data {
+            Some(data) => data,
+            None => self.digest.to_vec()?,
+        })];
+        for block in self.blocks {
+            a.push(Value::Bytes(block.to_cbor_value()?.to_vec()?));
+        }
+        Ok(Value::Array(a))
+    }
+}
+
+impl SuitAuthentication {
+    /// Create an authentication wrapper for the given manifest digest, with no authentication
+    /// blocks.
+    pub fn new(digest: SuitDigest) -> Self {
+        Self {
+            original_digest_data: None,
+            digest,
+            blocks: vec![],
+        }
+    }
+
+    /// Return the encoded digest, which is the detached payload of each authentication block.
+    pub fn digest_data(&self) -> Result<Vec<u8>, CoseError> {
+        match &self.original_digest_data {
+            Some(data) => Ok(data.clone()),
+            None => self.digest.clone().to_vec(),
+        }
+    }
+
+    /// Add a signature block, using `builder` (which should already hold the algorithm and any
+    /// key identifier) and `signer` to generate the signature bytes.
+    pub fn add_signature<F>(
+        &mut self,
+        builder: CoseSign1Builder,
+        signer: F,
+    ) -> Result<(), CoseError>
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        let payload = self.digest_data()?;
+        self.blocks.push(SuitAuthenticationBlock::Sign1(
+            builder
+                .create_detached_signature(&payload, b"", signer)
+                .build(),
+        ));
+        Ok(())
+    }
+
+    /// Add a MAC block, using `builder` (which should already hold the algorithm and any key
+    /// identifier) and `create` to generate the tag.
+    pub fn add_mac<F>(&mut self, builder: CoseMac0Builder, create: F) -> Result<(), CoseError>
+    where
+        F: FnOnce(&[u8]) -> Vec<u8>,
+    {
+        let payload = self.digest_data()?;
+        let mut mac0 = builder.payload(payload).create_tag(b"", create).build();
+        mac0.payload = None;
+        self.blocks.push(SuitAuthenticationBlock::Mac0(mac0));
+        Ok(())
+    }
+
+    /// Verify every authentication block, using `verify_signature` for `COSE_Sign1` blocks and
+    /// `verify_mac` for `COSE_Mac0` blocks.  Each is given the block (so that the appropriate key
+    /// can be selected from its headers), followed by the signature or tag value and the
+    /// to-be-signed or to-be-MACed data.
+    ///
+    /// Verification fails if there are no authentication blocks, or if any block has an attached
+    /// payload.  Note that this does not check that the digest matches the manifest; use
+    /// [`SuitAuthentication::verify_manifest`] for that.
+    pub fn verify<F, G, E>(
+        &self,
+        mut verify_signature: F,
+        mut verify_mac: G,
+    ) -> Result<(), SuitError<E>>
+    where
+        F: FnMut(&CoseSign1, &[u8], &[u8]) -> Result<(), E>,
+        G: FnMut(&CoseMac0, &[u8], &[u8]) -> Result<(), E>,
+    {
+        if self.blocks.is_empty() {
+            return Err(CoseError::UnexpectedItem(
+                "no authentication blocks",
+                "1+ authentication blocks",
+            )
+            .into());
+        }
+        let payload = self.digest_data()?;
+        for block in &self.blocks {
+            match block {
+                SuitAuthenticationBlock::Sign1(sign1) => {
+                    if sign1.payload.is_some() {
+                        return Err(CoseError::UnexpectedItem("payload", "detached payload").into());
+                    }
+                    sign1
+                        .verify_detached_signature(&payload, b"", |sig, data| {
+                            verify_signature(sign1, sig, data)
+                        })
+                        .map_err(SuitError::Crypto)?;
+                }
+                SuitAuthenticationBlock::Mac0(mac0) => {
+                    if mac0.payload.is_some() {
+                        return Err(CoseError::UnexpectedItem("payload", "detached payload").into());
+                    }
+                    let mut attached = mac0.clone();
+                    attached.payload = Some(payload.clone());
+                    attached
+                        .verify_payload_tag(
+                            b"",
+                            || unreachable!(), // safe: payload set above
+                            |tag, data| verify_mac(mac0, tag, data),
+                        )
+                        .map_err(SuitError::Crypto)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the digest matches the encoded `SUIT_Manifest`, which is the contents of the
+    /// `suit-manifest` `bstr` in the envelope, using `digester` to perform the hash.
+    pub fn verify_manifest<F, E>(&self, manifest: &[u8], digester: F) -> Result<(), SuitError<E>>
+    where
+        F: FnOnce(iana::Algorithm, &[u8]) -> Result<Vec<u8>, E>,
+    {
+        self.digest
+            .check(&manifest_digest_input(manifest)?, digester)
+    }
+}
+
+/// The error type returned when SUIT authentication fails.
+pub enum SuitError<E> {
+    /// The SUIT data could not be parsed.
+    Cose(CoseError),
+    /// The signature, MAC or digest operation failed.
+    Crypto(E),
+    /// The digest does not match the manifest.
+    DigestMismatch,
+}
+
+impl<E: core::fmt::Display> core::error::Error for SuitError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Debug for SuitError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for SuitError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> SuitError<E> {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SuitError::Cose(e) => write!(f, "invalid SUIT data: {e}"),
+            SuitError::Crypto(e) => write!(f, "verification failed: {e}"),
+            SuitError::DigestMismatch => write!(f, "manifest digest mismatch"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for SuitError<E> {
+    fn from(e: CoseError) -> Self {
+        SuitError::Cose(e)
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{
+    util::{expect_err, test_crypto},
+    HeaderBuilder,
+};
+use alloc::{borrow::ToOwned, string::String, vec};
+
+fn verify(auth: &SuitAuthentication) -> Result<(), SuitError<String>> {
+    auth.verify(
+        |sign1, sig, data| test_crypto::verify(sign1.unprotected.key_id[0], sig, data),
+        |mac0, tag, data| test_crypto::verify(mac0.unprotected.key_id[0], tag, data),
+    )
+}
+
+const MANIFEST: &[u8] = &[0xa1, 0x01, 0x01]; // {1: 1}
+
+fn authentication() -> SuitAuthentication {
+    let digest = SuitDigest::calculate(
+        iana::Algorithm::SHA_256,
+        &manifest_digest_input(MANIFEST).unwrap(),
+        test_crypto::digest,
+    )
+    .unwrap();
+    let mut auth = SuitAuthentication::new(digest);
+    auth.add_signature(
+        CoseSign1Builder::new()
+            .protected(
+                HeaderBuilder::new()
+                    .algorithm(iana::Algorithm::ES256)
+                    .build(),
+            )
+            .unprotected(HeaderBuilder::new().key_id(vec![1]).build()),
+        |data| test_crypto::sign(1, data),
+    )
+    .unwrap();
+    auth.add_mac(
+        CoseMac0Builder::new()
+            .protected(
+                HeaderBuilder::new()
+                    .algorithm(iana::Algorithm::HMAC_256_256)
+                    .build(),
+            )
+            .unprotected(HeaderBuilder::new().key_id(vec![2]).build()),
+        |data| test_crypto::sign(2, data),
+    )
+    .unwrap();
+    auth
+}
+
+#[test]
+fn test_suit_digest_encode() {
+    let tests = [
+        (
+            SuitDigest {
+                algorithm: iana::Algorithm::SHA_256,
+                bytes: vec![0x01, 0x02],
+                extensions: vec![],
+            },
+            "822f420102",
+        ),
+        (
+            SuitDigest {
+                algorithm: iana::Algorithm::SHAKE128,
+                bytes: vec![],
+                extensions: vec![],
+            },
+            "823140",
+        ),
+        (
+            SuitDigest {
+                algorithm: iana::Algorithm::SHA_512,
+                bytes: vec![0xff],
+                extensions: vec![Value::from(7)],
+            },
+            "83382b41ff07",
+        ),
+    ];
+    for (i, (digest, want)) in tests.iter().enumerate() {
+        let got = digest.clone().to_vec().unwrap();
+        assert_eq!(*want, hex::encode(&got), "case {}", i);
+        let recovered = SuitDigest::from_slice(&got).unwrap();
+        assert_eq!(*digest, recovered, "case {}", i);
+    }
+}
+
+#[test]
+fn test_suit_digest_decode_fail() {
+    let tests = vec![
+        ("a0", "expected array"),
+        ("812f", "expected array with 2+ items"),
+        ("822d420102", "expected recognized IANA value"), // SHA-1
+        ("8226420102", "expected recognized IANA value"), // ES256
+        ("82612f420102", "expected int"),
+        ("822f6101", "expected bstr"),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(SuitDigest::from_slice(&data), err_msg);
+    }
+
+    let digest = SuitDigest {
+        algorithm: iana::Algorithm::ES256,
+        bytes: vec![],
+        extensions: vec![],
+    };
+    expect_err(digest.to_vec(), "expected recognized IANA value");
+}
+
+#[test]
+fn test_manifest_digest() {
+    assert_eq!(
+        "43a10101",
+        hex::encode(manifest_digest_input(MANIFEST).unwrap())
+    );
+
+    let auth = authentication();
+    auth.verify_manifest(MANIFEST, test_crypto::digest).unwrap();
+    expect_err(
+        auth.verify_manifest(&[0xa1, 0x01, 0x02], test_crypto::digest),
+        "manifest digest mismatch",
+    );
+    expect_err(
+        auth.verify_manifest(MANIFEST, |_alg, _data| Err("no hash".to_owned())),
+        "verification failed: no hash",
+    );
+}
+
+#[test]
+fn test_authentication_roundtrip() {
+    let auth = authentication();
+    let digest_data = auth.digest_data().unwrap();
+    assert_eq!(digest_data, auth.digest.clone().to_vec().unwrap());
+    verify(&auth).unwrap();
+
+    let data = auth.clone().to_vec().unwrap();
+    let recovered = SuitAuthentication::from_slice(&data).unwrap();
+    assert_eq!(recovered.original_digest_data, Some(digest_data));
+    assert_eq!(recovered.digest, auth.digest);
+    assert_eq!(recovered.blocks.len(), 2);
+    verify(&recovered).unwrap();
+    assert_eq!(data, recovered.to_vec().unwrap());
+
+    // Each block is a tagged COSE object, wrapped in a bstr, with no payload.
+    let value = Value::from_slice(&data).unwrap().try_as_array().unwrap();
+    assert_eq!(value.len(), 3);
+    let sign1 = CoseSign1::from_tagged_slice(&value[1].as_bytes().unwrap()[..]).unwrap();
+    assert_eq!(sign1.payload, None);
+    let mac0 = CoseMac0::from_tagged_slice(&value[2].as_bytes().unwrap()[..]).unwrap();
+    assert_eq!(mac0.payload, None);
+}
+
+#[test]
+fn test_authentication_original_digest_data() {
+    // Digest with an indefinite-length bstr, which is not re-encoded the same way.
+    let digest_data = hex::decode("822f5f41014102ff").unwrap();
+    let mut auth =
+        SuitAuthentication::from_cbor_value(Value::Array(vec![Value::Bytes(digest_data.clone())]))
+            .unwrap();
+    assert_eq!(auth.digest.bytes, vec![0x01, 0x02]);
+    assert_ne!(auth.digest.clone().to_vec().unwrap(), digest_data);
+    assert_eq!(auth.digest_data().unwrap(), digest_data);
+
+    auth.add_signature(
+        CoseSign1Builder::new().unprotected(HeaderBuilder::new().key_id(vec![3]).build()),
+        |data| test_crypto::sign(3, data),
+    )
+    .unwrap();
+    verify(&auth).unwrap();
+
+    // Dropping the original data changes the payload, so verification fails.
+    auth.original_digest_data = None;
+    expect_err(verify(&auth), "verification failed: bad signature");
+}
+
+#[test]
+fn test_authentication_decode_fail() {
+    let tests = vec![
+        ("a0", "expected array"),
+        ("80", "expected array with digest"),
+        ("81822f420102", "expected bstr"),
+        ("8245822f42010201", "expected bstr"),
+        // Untagged COSE_Sign1.
+        ("8245822f420102458440a0f640", "expected tag"),
+        // COSE_Sign tag.
+        (
+            "8245822f42010247d8628440a0f640",
+            "expected COSE_Sign1 or COSE_Mac0 tag",
+        ),
+    ];
+    for (data, err_msg) in tests {
+        let data = hex::decode(data).unwrap();
+        expect_err(SuitAuthentication::from_slice(&data), err_msg);
+    }
+}
+
+#[test]
+fn test_authentication_verify_fail() {
+    let auth = authentication();
+
+    let mut no_blocks = auth.clone();
+    no_blocks.blocks.clear();
+    expect_err(verify(&no_blocks), "expected 1+ authentication blocks");
+
+    let mut bad_sig = auth.clone();
+    if let SuitAuthenticationBlock::Sign1(sign1) = &mut bad_sig.blocks[0] {
+        sign1.signature[0] ^= 0x01;
+    }
+    expect_err(verify(&bad_sig), "verification failed: bad signature");
+
+    let mut bad_tag = auth.clone();
+    if let SuitAuthenticationBlock::Mac0(mac0) = &mut bad_tag.blocks[1] {
+        mac0.tag[0] ^= 0x01;
+    }
+    expect_err(verify(&bad_tag), "verification failed: bad signature");
+
+    let mut attached_sign1 = auth.clone();
+    if let SuitAuthenticationBlock::Sign1(sign1) = &mut attached_sign1.blocks[0] {
+        sign1.payload = Some(auth.digest_data().unwrap());
+    }
+    expect_err(verify(&attached_sign1), "expected detached payload");
+
+    let mut attached_mac0 = auth.clone();
+    if let SuitAuthenticationBlock::Mac0(mac0) = &mut attached_mac0.blocks[1] {
+        mac0.payload = Some(auth.digest_data().unwrap());
+    }
+    expect_err(verify(&attached_mac0), "expected detached payload");
+
+    let mut other_digest = auth;
+    other_digest.digest.bytes[0] ^= 0x01;
+    expect_err(verify(&other_digest), "verification failed: bad signature");
+}
@@ -23,6 +23,8 @@ use crate::{
 };
 use alloc::{boxed::Box, string::String, vec::Vec};
 
+#[cfg(test)]
+pub(crate) mod test_crypto;
 #[cfg(test)]
 mod tests;
 
@@ -158,6 +160,133 @@ where
     ))
 }
 
+/// Set an optional field, failing if it has already been set.
+pub(crate) fn set_once<T>(field: &mut Option<T>, value: T) -> Result<()> {
+    if field.is_some() {
+        return Err(CoseError::DuplicateMapKey);
+    }
+    *field = Some(value);
+    Ok(())
+}
+
+/// Set a multi-entry field, failing if it has already been set.
+pub(crate) fn set_once_vec<T>(field: &mut Vec<T>, value: Vec<T>) -> Result<()> {
+    if !field.is_empty() {
+        return Err(CoseError::DuplicateMapKey);
+    }
+    *field = value;
+    Ok(())
+}
+
+/// Base64 variants, as per RFC 4648.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Base64 {
+    /// Standard alphabet with `=` padding (RFC 4648 section 4).
+    Standard,
+    /// URL and filename safe alphabet without padding (RFC 4648 section 5), as used by JOSE.
+    UrlSafeNoPad,
+}
+
+impl Base64 {
+    fn alphabet(self) -> &'static [u8; 64] {
+        match self {
+            Base64::Standard => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+            Base64::UrlSafeNoPad => {
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+            }
+        }
+    }
+
+    /// Encode `data` as base64.
+    pub(crate) fn encode(self, data: &[u8]) -> String {
+        let alphabet = self.alphabet();
+        let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+        for chunk in data.chunks(3) {
+            let b = [
+                chunk[0],
+                chunk.get(1).copied().unwrap_or(0),
+                chunk.get(2).copied().unwrap_or(0),
+            ];
+            let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+            for i in 0..=chunk.len() {
+                out.push(alphabet[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            }
+            if self == Base64::Standard {
+                for _ in chunk.len()..3 {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    /// Decode base64 `data`, returning `None` if it is not in the canonical encoding for this
+    /// variant.
+    pub(crate) fn decode(self, data: &str) -> Option<Vec<u8>> {
+        let alphabet = self.alphabet();
+        let mut data = data.as_bytes();
+        if self == Base64::Standard {
+            if data.len() % 4 != 0 {
+                return None;
+            }
+            let pad = data
+                .iter()
+                .rev()
+                .take(2)
+                .take_while(|c| **c == b'=')
+                .count();
+            data = &data[..data.len() - pad];
+        }
+        if data.len() % 4 == 1 {
+            return None;
+        }
+        let mut out = Vec::with_capacity(data.len() * 3 / 4);
+        for chunk in data.chunks(4) {
+            let mut n = 0u32;
+            for (i, c) in chunk.iter().enumerate() {
+                let v = alphabet.iter().position(|a| a == c)? as u32;
+                n |= v << (18 - 6 * i);
+            }
+            let bytes = n.to_be_bytes();
+            let len = chunk.len() - 1;
+            // Reject encodings with non-zero trailing bits, which are not canonical.
+            if bytes[1 + len..].iter().any(|b| *b != 0) {
+                return None;
+            }
+            out.extend_from_slice(&bytes[1..1 + len]);
+        }
+        Some(out)
+    }
+}
+
+/// Wipe data that held secret key material, if the `zeroize` feature is enabled.
+#[cfg(feature = "zeroize")]
+#[inline]
+pub(crate) fn wipe<Z: zeroize::Zeroize + ?Sized>(data: &mut Z) {
+    data.zeroize();
+}
+
+/// Wipe data that held secret key material, if the `zeroize` feature is enabled.
+#[cfg(not(feature = "zeroize"))]
+#[inline]
+pub(crate) fn wipe<Z: ?Sized>(_data: &mut Z) {}
+
+/// Wipe the contents of a [`Value`] that held secret key material, if the `zeroize` feature is
+/// enabled.
+pub(crate) fn wipe_value(value: &mut Value) {
+    match value {
+        Value::Bytes(b) => wipe(b),
+        Value::Text(t) => wipe(t),
+        Value::Array(a) => a.iter_mut().for_each(wipe_value),
+        Value::Map(m) => m.iter_mut().for_each(|(k, v)| {
+            wipe_value(k);
+            wipe_value(v);
+        }),
+        Value::Tag(_, v) => wipe_value(v),
+        _ => {}
+    }
+}
+
 /// Check for an expected error.
 #[cfg(test)]
 pub fn expect_err<T: core::fmt::Debug, E: core::fmt::Debug + core::fmt::Display>(
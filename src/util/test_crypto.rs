@@ -0,0 +1,77 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! Test stand-ins for the signature, MAC and digest operations that callers provide, built on
+//! HMAC-SHA-256 and SHA-2 so that any change to the protected data is detected.
+
+use crate::{cbor::value::Value, iana, CoseKey, CoseKeyBuilder};
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Return an Ed25519 public key with the given value, which stands for the [`sign`] key with the
+/// same value.
+pub(crate) fn public_key(x: u8) -> CoseKey {
+    CoseKeyBuilder::new_okp_key()
+        .algorithm(iana::Algorithm::EdDSA)
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::Ed25519 as i64),
+        )
+        .param(iana::OkpKeyParameter::X as i64, Value::Bytes(vec![x; 32]))
+        .build()
+}
+
+fn hmac(x: u8, data: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&[x; 32]).unwrap();
+    mac.update(data);
+    mac
+}
+
+/// Signature (or MAC) over `data` by the key with the given value, as HMAC-SHA-256.
+pub(crate) fn sign(x: u8, data: &[u8]) -> Vec<u8> {
+    hmac(x, data).finalize().into_bytes().to_vec()
+}
+
+/// Verify a [`sign`] signature (or MAC) over `data` by the key with the given value.
+pub(crate) fn verify(x: u8, sig: &[u8], data: &[u8]) -> Result<(), String> {
+    hmac(x, data)
+        .verify_slice(sig)
+        .map_err(|_| "bad signature".to_owned())
+}
+
+/// Return a verifier for [`sign`] signatures by the key with the given value.
+pub(crate) fn verifier(x: u8) -> impl FnOnce(&[u8], &[u8]) -> Result<(), String> {
+    move |sig, data| verify(x, sig, data)
+}
+
+/// Verify a [`sign`] signature over `data` by the key that `key` (from [`public_key`]) stands for.
+pub(crate) fn verify_with_key(key: &CoseKey, sig: &[u8], data: &[u8]) -> Result<(), String> {
+    match key.param(iana::OkpKeyParameter::X as i64) {
+        Some(Value::Bytes(x)) if !x.is_empty() => verify(x[0], sig, data),
+        _ => Err("no public key".to_owned()),
+    }
+}
+
+/// SHA-2 digest of `data`.
+pub(crate) fn digest(alg: iana::Algorithm, data: &[u8]) -> Result<Vec<u8>, String> {
+    match alg {
+        iana::Algorithm::SHA_256 => Ok(Sha256::digest(data).to_vec()),
+        iana::Algorithm::SHA_384 => Ok(Sha384::digest(data).to_vec()),
+        iana::Algorithm::SHA_512 => Ok(Sha512::digest(data).to_vec()),
+        _ => Err("unsupported digest algorithm".to_owned()),
+    }
+}
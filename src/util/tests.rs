@@ -70,3 +70,52 @@ fn test_expect_err_wrong_display_msg() {
     // The expected text appears in the `Debug` output but not the `Display` output.
     expect_err(err, "text");
 }
+
+#[test]
+fn test_set_once() {
+    let mut field = None;
+    set_once(&mut field, 1).unwrap();
+    assert_eq!(field, Some(1));
+    expect_err(set_once(&mut field, 2), "duplicate map key");
+    assert_eq!(field, Some(1));
+
+    let mut field = vec![];
+    set_once_vec(&mut field, vec![1, 2]).unwrap();
+    assert_eq!(field, vec![1, 2]);
+    expect_err(set_once_vec(&mut field, vec![3]), "duplicate map key");
+    assert_eq!(field, vec![1, 2]);
+}
+
+#[test]
+fn test_base64() {
+    // Test vectors from RFC 4648 section 10.
+    let cases = [
+        ("", "", ""),
+        ("f", "Zg==", "Zg"),
+        ("fo", "Zm8=", "Zm8"),
+        ("foo", "Zm9v", "Zm9v"),
+        ("foob", "Zm9vYg==", "Zm9vYg"),
+        ("fooba", "Zm9vYmE=", "Zm9vYmE"),
+        ("foobar", "Zm9vYmFy", "Zm9vYmFy"),
+    ];
+    for (data, std, url) in cases {
+        assert_eq!(Base64::Standard.encode(data.as_bytes()), std);
+        assert_eq!(Base64::UrlSafeNoPad.encode(data.as_bytes()), url);
+        assert_eq!(Base64::Standard.decode(std).unwrap(), data.as_bytes());
+        assert_eq!(Base64::UrlSafeNoPad.decode(url).unwrap(), data.as_bytes());
+    }
+    assert_eq!(Base64::Standard.encode(&[0xfb, 0xff]), "+/8=");
+    assert_eq!(Base64::UrlSafeNoPad.encode(&[0xfb, 0xff]), "-_8");
+}
+
+#[test]
+fn test_base64_decode_fail() {
+    let std_cases = ["Zg", "Zg=", "Zh==", "Z===", "Zm9v!A==", "-_8=", "Zg==Zg=="];
+    for data in std_cases {
+        assert_eq!(Base64::Standard.decode(data), None, "for {data}");
+    }
+    let url_cases = ["Zg==", "Zh", "Z", "+/8", "Zm9v\n"];
+    for data in url_cases {
+        assert_eq!(Base64::UrlSafeNoPad.decode(data), None, "for {data}");
+    }
+}
@@ -0,0 +1,545 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+//! WebAuthn and CTAP2 functionality.
+//!
+//! FIDO authenticators return credential public keys as `COSE_Key` structures inside the
+//! (binary) authenticator data.  This module parses that data, normalizes the credential public
+//! keys, negotiates algorithms from `pubKeyCredParams`, and verifies assertion signatures (which
+//! for ECDSA use the DER signature format, rather than the fixed-length format used by COSE).
+
+use crate::{
+    cbor::value::Value,
+    common::AsCborValue,
+    iana::{self, EnumI64},
+    pkix::der,
+    util::{cbor_type_error, ValueTryAs},
+    Algorithm, CborSerializable, CoseError, CoseKey, KeyType, Label,
+};
+use alloc::{collections::BTreeSet, vec, vec::Vec};
+use core::convert::{TryFrom, TryInto};
+
+#[cfg(test)]
+mod tests;
+
+/// Authenticator data flag: user present.
+pub const FLAG_UP: u8 = 0x01;
+/// Authenticator data flag: user verified.
+pub const FLAG_UV: u8 = 0x04;
+/// Authenticator data flag: backup eligible.
+pub const FLAG_BE: u8 = 0x08;
+/// Authenticator data flag: backed up.
+pub const FLAG_BS: u8 = 0x10;
+/// Authenticator data flag: attested credential data included.
+pub const FLAG_AT: u8 = 0x40;
+/// Authenticator data flag: extension data included.
+pub const FLAG_ED: u8 = 0x80;
+
+/// Maximum length of a credential ID.
+pub const MAX_CREDENTIAL_ID_LEN: usize = 1023;
+
+/// Split `len` bytes off the front of `data`.
+fn take<'a>(data: &mut &'a [u8], len: usize) -> Result<&'a [u8], CoseError> {
+    if data.len() < len {
+        return Err(CoseError::UnexpectedItem(
+            "truncated data",
+            "complete authenticator data",
+        ));
+    }
+    let (head, tail) = data.split_at(len);
+    *data = tail;
+    Ok(head)
+}
+
+/// Read a single CBOR item from the front of `data`.
+fn take_value(data: &mut &[u8]) -> Result<Value, CoseError> {
+    Ok(crate::cbor::de::from_reader(data)?)
+}
+
+/// Attested credential data, as included in the authenticator data when a credential is created.
+///
+/// ```text
+///  aaguid (16 bytes) || credentialIdLength (2 bytes, big-endian) || credentialId
+///      || credentialPublicKey (CBOR-encoded COSE_Key)
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttestedCredentialData {
+    /// Authenticator Attestation GUID.
+    pub aaguid: [u8; 16],
+    /// Credential ID.
+    pub credential_id: Vec<u8>,
+    /// Credential public key, as returned by the authenticator.
+    pub credential_public_key: CoseKey,
+}
+
+impl AttestedCredentialData {
+    /// Parse attested credential data from the front of `data`, advancing `data` past it.  Any
+    /// following data (such as extensions) is left in `data`.
+    pub fn parse(data: &mut &[u8]) -> Result<Self, CoseError> {
+        let aaguid = take(data, 16)?.try_into().unwrap(); // safe: len checked
+        let len = take(data, 2)?;
+        let len = usize::from(u16::from_be_bytes([len[0], len[1]]));
+        if len > MAX_CREDENTIAL_ID_LEN {
+            return Err(CoseError::UnexpectedItem(
+                "oversized credential ID",
+                "credential ID of at most 1023 bytes",
+            ));
+        }
+        let credential_id = take(data, len)?.to_vec();
+        let credential_public_key = CoseKey::from_cbor_value(take_value(data)?)?;
+        Ok(Self {
+            aaguid,
+            credential_id,
+            credential_public_key,
+        })
+    }
+
+    /// Parse attested credential data, failing if any data follows it.
+    pub fn from_slice(mut data: &[u8]) -> Result<Self, CoseError> {
+        let acd = Self::parse(&mut data)?;
+        if !data.is_empty() {
+            return Err(CoseError::ExtraneousData);
+        }
+        Ok(acd)
+    }
+
+    /// Serialize the attested credential data.
+    pub fn to_vec(self) -> Result<Vec<u8>, CoseError> {
+        let len: u16 = match self.credential_id.len() {
+            len if len <= MAX_CREDENTIAL_ID_LEN => len as u16,
+            _ => {
+                return Err(CoseError::UnexpectedItem(
+                    "oversized credential ID",
+                    "credential ID of at most 1023 bytes",
+                ))
+            }
+        };
+        let mut data = self.aaguid.to_vec();
+        data.extend_from_slice(&len.to_be_bytes());
+        data.extend_from_slice(&self.credential_id);
+        data.extend_from_slice(&self.credential_public_key.to_vec()?);
+        Ok(data)
+    }
+}
+
+/// Authenticator data, as returned by the authenticator for both registration and assertion.
+///
+/// ```text
+///  rpIdHash (32 bytes) || flags (1 byte) || signCount (4 bytes, big-endian)
+///      || [attestedCredentialData] || [extensions (CBOR map)]
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuthenticatorData {
+    /// SHA-256 hash of the relying party ID.
+    pub rp_id_hash: [u8; 32],
+    /// Flags, as a combination of the `FLAG_*` values.  The [`FLAG_AT`] and [`FLAG_ED`] flags
+    /// are set (or cleared) on serialization according to the presence of the corresponding data.
+    pub flags: u8,
+    /// Signature counter.
+    pub sign_count: u32,
+    /// Attested credential data, present when a credential is created.
+    pub attested_credential_data: Option<AttestedCredentialData>,
+    /// Authenticator extension outputs.
+    pub extensions: Option<Value>,
+}
+
+impl AuthenticatorData {
+    /// Parse authenticator data.
+    pub fn from_slice(mut data: &[u8]) -> Result<Self, CoseError> {
+        let data = &mut data;
+        let rp_id_hash = take(data, 32)?.try_into().unwrap(); // safe: len checked
+        let flags = take(data, 1)?[0];
+        let sign_count = u32::from_be_bytes(take(data, 4)?.try_into().unwrap()); // safe: len checked
+        let attested_credential_data = if flags & FLAG_AT != 0 {
+            Some(AttestedCredentialData::parse(data)?)
+        } else {
+            None
+        };
+        let extensions = if flags & FLAG_ED != 0 {
+            let extensions = take_value(data)?;
+            if !matches!(extensions, Value::Map(_)) {
+                return cbor_type_error(&extensions, "map");
+            }
+            Some(extensions)
+        } else {
+            None
+        };
+        if !data.is_empty() {
+            return Err(CoseError::ExtraneousData);
+        }
+        Ok(Self {
+            rp_id_hash,
+            flags,
+            sign_count,
+            attested_credential_data,
+            extensions,
+        })
+    }
+
+    /// Serialize the authenticator data.
+    pub fn to_vec(self) -> Result<Vec<u8>, CoseError> {
+        let mut flags = self.flags & !(FLAG_AT | FLAG_ED);
+        if self.attested_credential_data.is_some() {
+            flags |= FLAG_AT;
+        }
+        if self.extensions.is_some() {
+            flags |= FLAG_ED;
+        }
+        let mut data = self.rp_id_hash.to_vec();
+        data.push(flags);
+        data.extend_from_slice(&self.sign_count.to_be_bytes());
+        if let Some(acd) = self.attested_credential_data {
+            data.extend_from_slice(&acd.to_vec()?);
+        }
+        if let Some(extensions) = self.extensions {
+            data.extend_from_slice(&extensions.to_vec()?);
+        }
+        Ok(data)
+    }
+
+    /// Indicate whether the user present flag is set.
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_UP != 0
+    }
+
+    /// Indicate whether the user verified flag is set.
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_UV != 0
+    }
+}
+
+/// Return a copy of the integer-labelled parameter `label` of `key`, if present.
+fn param(key: &CoseKey, label: i64) -> Option<Value> {
+    key.param(label).cloned()
+}
+
+/// Return `value` with any leading zero bytes removed, for a bstr holding an unsigned integer.
+fn strip_leading_zeros(value: Value) -> Result<Value, CoseError> {
+    let bytes = value.try_as_bytes()?;
+    let skip = bytes.iter().take_while(|b| **b == 0).count();
+    Ok(Value::Bytes(bytes[skip..].to_vec()))
+}
+
+/// Normalize a credential public key returned by an authenticator, producing a public key that
+/// holds only the key type, algorithm and public key parameters.
+///
+/// The following quirks are handled:
+///   - Parameters other than the public key parameters for the key type (such as key IDs, key
+///     operations or vendor-specific fields) are dropped.
+///   - A missing algorithm is inferred from the key type and curve: ES256, ES384, ES512 or
+///     ES256K for EC2 keys, EdDSA for OKP keys and RS256 for RSA keys.
+///   - Leading zero bytes (as found in DER-derived encodings) are stripped from the RSA modulus
+///     and exponent.
+///
+/// EC2 keys must use one of the P-256, P-384, P-521 or secp256k1 curves, and OKP keys must use
+/// Ed25519 or Ed448.
+pub fn normalize_credential_key(key: &CoseKey) -> Result<CoseKey, CoseError> {
+    use iana::{Algorithm as A, EllipticCurve as C};
+    let missing = || CoseError::UnexpectedItem("incomplete key", "credential public key");
+    let crv = |label: i64| -> Result<C, CoseError> {
+        let crv = param(key, label).ok_or_else(missing)?.try_as_integer()?;
+        let crv: i64 = crv.try_into()?;
+        C::from_i64(crv).ok_or(CoseError::UnregisteredIanaValue)
+    };
+    let (default_alg, params) = match key.kty {
+        KeyType::Assigned(iana::KeyType::EC2) => {
+            let curve = crv(iana::Ec2KeyParameter::Crv as i64)?;
+            let default_alg = match curve {
+                C::P_256 => A::ES256,
+                C::P_384 => A::ES384,
+                C::P_521 => A::ES512,
+                C::Secp256k1 => A::ES256K,
+                _ => {
+                    return Err(CoseError::UnexpectedItem(
+                        "curve",
+                        "P-256, P-384, P-521 or secp256k1 curve",
+                    ))
+                }
+            };
+            let x = param(key, iana::Ec2KeyParameter::X as i64).ok_or_else(missing)?;
+            let y = param(key, iana::Ec2KeyParameter::Y as i64).ok_or_else(missing)?;
+            let params = vec![
+                (
+                    Label::Int(iana::Ec2KeyParameter::Crv as i64),
+                    Value::from(curve as i64),
+                ),
+                (Label::Int(iana::Ec2KeyParameter::X as i64), x),
+                (Label::Int(iana::Ec2KeyParameter::Y as i64), y),
+            ];
+            (default_alg, params)
+        }
+        KeyType::Assigned(iana::KeyType::OKP) => {
+            let curve = crv(iana::OkpKeyParameter::Crv as i64)?;
+            if curve != C::Ed25519 && curve != C::Ed448 {
+                return Err(CoseError::UnexpectedItem("curve", "Ed25519 or Ed448 curve"));
+            }
+            let x = param(key, iana::OkpKeyParameter::X as i64).ok_or_else(missing)?;
+            let params = vec![
+                (
+                    Label::Int(iana::OkpKeyParameter::Crv as i64),
+                    Value::from(curve as i64),
+                ),
+                (Label::Int(iana::OkpKeyParameter::X as i64), x),
+            ];
+            (A::EdDSA, params)
+        }
+        KeyType::Assigned(iana::KeyType::RSA) => {
+            let n = param(key, iana::RsaKeyParameter::N as i64).ok_or_else(missing)?;
+            let e = param(key, iana::RsaKeyParameter::E as i64).ok_or_else(missing)?;
+            let params = vec![
+                (
+                    Label::Int(iana::RsaKeyParameter::N as i64),
+                    strip_leading_zeros(n)?,
+                ),
+                (
+                    Label::Int(iana::RsaKeyParameter::E as i64),
+                    strip_leading_zeros(e)?,
+                ),
+            ];
+            (A::RS256, params)
+        }
+        _ => {
+            return Err(CoseError::UnexpectedItem(
+                "key type",
+                "EC2, OKP or RSA credential public key",
+            ))
+        }
+    };
+    Ok(CoseKey {
+        kty: key.kty.clone(),
+        key_id: Vec::new(),
+        alg: Some(key.alg.clone().unwrap_or(Algorithm::Assigned(default_alg))),
+        key_ops: BTreeSet::new(),
+        base_iv: Vec::new(),
+        params,
+    })
+}
+
+/// Indicate whether `key` can be used with `alg`.  As well as an exact match of the key's
+/// algorithm, a key marked with a polymorphic algorithm (such as EdDSA or ES256) also matches the
+/// corresponding fully-specified algorithm (such as Ed25519 or ESP256) for the key's curve, and
+/// vice versa.
+pub fn key_supports_algorithm(key: &CoseKey, alg: iana::Algorithm) -> bool {
+    use iana::{Algorithm as A, EllipticCurve as C};
+    let key_alg = match &key.alg {
+        Some(Algorithm::Assigned(key_alg)) => *key_alg,
+        _ => return false,
+    };
+    if key_alg == alg {
+        return true;
+    }
+    // The curve parameter has the same label for EC2 and OKP keys.
+    let crv = key
+        .param(iana::Ec2KeyParameter::Crv as i64)
+        .and_then(|v| v.as_integer())
+        .and_then(|v| i64::try_from(v).ok())
+        .and_then(C::from_i64);
+    [
+        (A::EdDSA, A::Ed25519, C::Ed25519),
+        (A::EdDSA, A::Ed448, C::Ed448),
+        (A::ES256, A::ESP256, C::P_256),
+        (A::ES384, A::ESP384, C::P_384),
+        (A::ES512, A::ESP512, C::P_521),
+    ]
+    .iter()
+    .any(|(polymorphic, specified, curve)| {
+        crv == Some(*curve)
+            && ((key_alg == *polymorphic && alg == *specified)
+                || (key_alg == *specified && alg == *polymorphic))
+    })
+}
+
+/// Parse the algorithms from a CTAP2 `pubKeyCredParams` array, in order of preference.
+///
+/// ```cddl
+///  pubKeyCredParams = [ * PublicKeyCredentialParameters ]
+///  PublicKeyCredentialParameters = {
+///      "type" : tstr,  ; "public-key"
+///      "alg" : int,
+///  }
+/// ```
+///
+/// As required by WebAuthn, entries with a type other than "public-key" or an unrecognized
+/// algorithm are ignored.
+pub fn cred_params_algorithms(value: Value) -> Result<Vec<iana::Algorithm>, CoseError> {
+    let mut algs = Vec::new();
+    for entry in value.try_as_array()? {
+        let mut typ = None;
+        let mut alg = None;
+        for (k, v) in entry.try_as_map()? {
+            match k.try_as_string()?.as_str() {
+                "type" if typ.is_some() => return Err(CoseError::DuplicateMapKey),
+                "type" => typ = Some(v.try_as_string()?),
+                "alg" if alg.is_some() => return Err(CoseError::DuplicateMapKey),
+                "alg" => alg = Some(i64::try_from(v.try_as_integer()?)?),
+                _ => {}
+            }
+        }
+        let (typ, alg) = match (typ, alg) {
+            (Some(typ), Some(alg)) => (typ, alg),
+            _ => {
+                return Err(CoseError::UnexpectedItem(
+                    "map",
+                    "PublicKeyCredentialParameters with type and alg",
+                ))
+            }
+        };
+        if typ != "public-key" {
+            continue;
+        }
+        if let Some(alg) = iana::Algorithm::from_i64(alg) {
+            algs.push(alg);
+        }
+    }
+    Ok(algs)
+}
+
+/// Return the first of the `requested` algorithms (in the relying party's order of preference)
+/// that is also in `supported`.
+pub fn negotiate_algorithm(
+    requested: &[iana::Algorithm],
+    supported: &[iana::Algorithm],
+) -> Option<iana::Algorithm> {
+    requested.iter().find(|a| supported.contains(a)).copied()
+}
+
+/// Return the length of each of the two integers in a fixed-length ECDSA signature made by
+/// `key`, or `None` if the key is not an EC2 key on a supported curve.
+fn ecdsa_int_len(key: &CoseKey) -> Option<usize> {
+    if key.kty != KeyType::Assigned(iana::KeyType::EC2) {
+        return None;
+    }
+    let crv = key.param(iana::Ec2KeyParameter::Crv as i64)?;
+    [
+        (iana::EllipticCurve::P_256, 32),
+        (iana::EllipticCurve::P_384, 48),
+        (iana::EllipticCurve::P_521, 66),
+        (iana::EllipticCurve::Secp256k1, 32),
+    ]
+    .iter()
+    .find(|(c, _)| *crv == Value::from(*c as i64))
+    .map(|(_, len)| *len)
+}
+
+/// Convert a DER-encoded ECDSA signature (`SEQUENCE { r INTEGER, s INTEGER }`) into the
+/// fixed-length form used by COSE (`r || s`), with each integer occupying `int_len` bytes.
+pub fn ecdsa_signature_from_der(der: &[u8], int_len: usize) -> Result<Vec<u8>, CoseError> {
+    let invalid = |_| CoseError::UnexpectedItem("invalid DER", "DER-encoded ECDSA signature");
+    let mut outer = der::Reader::new(der);
+    let mut seq = der::Reader::new(outer.read(der::SEQUENCE).map_err(invalid)?);
+    outer.finish().map_err(invalid)?;
+    let r = seq.read_uint().map_err(invalid)?;
+    let s = seq.read_uint().map_err(invalid)?;
+    seq.finish().map_err(invalid)?;
+    let mut sig = vec![0; 2 * int_len];
+    for (i, v) in [r, s].iter().enumerate() {
+        let v = match v.as_slice() {
+            [0] => &[][..],
+            v => v,
+        };
+        if v.len() > int_len {
+            return Err(CoseError::UnexpectedItem(
+                "oversized integer",
+                "ECDSA signature integer of curve size",
+            ));
+        }
+        sig[(i + 1) * int_len - v.len()..(i + 1) * int_len].copy_from_slice(v);
+    }
+    Ok(sig)
+}
+
+/// Convert a fixed-length ECDSA signature as used by COSE (`r || s`) into the DER encoding
+/// (`SEQUENCE { r INTEGER, s INTEGER }`) used by WebAuthn.
+pub fn ecdsa_signature_to_der(sig: &[u8]) -> Result<Vec<u8>, CoseError> {
+    if sig.is_empty() || sig.len() % 2 != 0 {
+        return Err(CoseError::UnexpectedItem(
+            "odd-length signature",
+            "ECDSA signature of r || s",
+        ));
+    }
+    let (r, s) = sig.split_at(sig.len() / 2);
+    Ok(der::sequence(&[&der::uint(r), &der::uint(s)]))
+}
+
+/// Return the data signed by an authenticator for an assertion: the authenticator data followed
+/// by the SHA-256 hash of the client data JSON.
+pub fn assertion_signed_data(authenticator_data: &[u8], client_data_hash: &[u8]) -> Vec<u8> {
+    [authenticator_data, client_data_hash].concat()
+}
+
+/// Verify a WebAuthn assertion signature by the credential public key `key`, using `verifier`
+/// on the signature value (in COSE form) and the signed data (in that order).
+///
+/// For ECDSA keys, the DER-encoded signature used by WebAuthn is converted to the fixed-length
+/// form used by COSE before being passed to `verifier`; other signatures are passed unchanged.
+///
+/// Note that the caller is responsible for hashing the client data JSON to produce
+/// `client_data_hash`, and for checking the contents of the authenticator data (such as the RP ID
+/// hash, flags and signature counter).
+pub fn verify_assertion<F, E>(
+    key: &CoseKey,
+    authenticator_data: &[u8],
+    client_data_hash: &[u8],
+    signature: &[u8],
+    verifier: F,
+) -> Result<(), WebAuthnError<E>>
+where
+    F: FnOnce(&[u8], &[u8]) -> Result<(), E>,
+{
+    let data = assertion_signed_data(authenticator_data, client_data_hash);
+    let signature = match ecdsa_int_len(key) {
+        Some(int_len) => ecdsa_signature_from_der(signature, int_len)?,
+        None => signature.to_vec(),
+    };
+    verifier(&signature, &data).map_err(WebAuthnError::Crypto)
+}
+
+/// The error type returned when a WebAuthn assertion fails verification.
+pub enum WebAuthnError<E> {
+    /// The WebAuthn data could not be parsed.
+    Cose(CoseError),
+    /// The signature failed verification.
+    Crypto(E),
+}
+
+impl<E: core::fmt::Display> core::error::Error for WebAuthnError<E> {}
+
+impl<E: core::fmt::Display> core::fmt::Debug for WebAuthnError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for WebAuthnError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.fmt_msg(f)
+    }
+}
+
+impl<E: core::fmt::Display> WebAuthnError<E> {
+    fn fmt_msg(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WebAuthnError::Cose(e) => write!(f, "invalid WebAuthn data: {e}"),
+            WebAuthnError::Crypto(e) => write!(f, "verification failed: {e}"),
+        }
+    }
+}
+
+impl<E> From<CoseError> for WebAuthnError<E> {
+    fn from(e: CoseError) -> Self {
+        WebAuthnError::Cose(e)
+    }
+}
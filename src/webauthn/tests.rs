@@ -0,0 +1,399 @@
+// Copyright 2026 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+////////////////////////////////////////////////////////////////////////////////
+
+use super::*;
+use crate::{iana::Algorithm as A, util::expect_err, CoseKeyBuilder};
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+};
+
+fn p256_key() -> CoseKey {
+    CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_256, vec![0x01; 32], vec![0x02; 32])
+        .algorithm(A::ES256)
+        .build()
+}
+
+fn ed25519_key(alg: A) -> CoseKey {
+    CoseKeyBuilder::new_okp_key()
+        .algorithm(alg)
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            Value::from(iana::EllipticCurve::Ed25519 as i64),
+        )
+        .param(
+            iana::OkpKeyParameter::X as i64,
+            Value::Bytes(vec![0x03; 32]),
+        )
+        .build()
+}
+
+fn acd() -> AttestedCredentialData {
+    AttestedCredentialData {
+        aaguid: [0xaa; 16],
+        credential_id: vec![0xc1, 0xc2, 0xc3],
+        credential_public_key: p256_key(),
+    }
+}
+
+#[test]
+fn test_attested_credential_data() {
+    let data = acd().to_vec().unwrap();
+    assert_eq!(
+        &data[..19],
+        &[[0xaa; 16].as_slice(), &[0x00, 0x03, 0xc1]].concat()[..]
+    );
+    assert_eq!(AttestedCredentialData::from_slice(&data).unwrap(), acd());
+
+    // Parsing leaves any following data in place.
+    let mut with_trailer = data.clone();
+    with_trailer.extend_from_slice(&[0xa0]);
+    let mut rest = with_trailer.as_slice();
+    assert_eq!(AttestedCredentialData::parse(&mut rest).unwrap(), acd());
+    assert_eq!(rest, &[0xa0]);
+    expect_err(
+        AttestedCredentialData::from_slice(&with_trailer),
+        "extraneous data",
+    );
+
+    expect_err(
+        AttestedCredentialData::from_slice(&data[..17]),
+        "complete authenticator data",
+    );
+    expect_err(
+        AttestedCredentialData::from_slice(&data[..20]),
+        "complete authenticator data",
+    );
+    expect_err(
+        AttestedCredentialData::from_slice(&data[..data.len() - 1]),
+        "decode CBOR failure",
+    );
+
+    let mut oversized = data;
+    oversized[16] = 0x04;
+    expect_err(
+        AttestedCredentialData::from_slice(&oversized),
+        "credential ID of at most 1023 bytes",
+    );
+    let oversized = AttestedCredentialData {
+        credential_id: vec![0; 1024],
+        ..acd()
+    };
+    expect_err(oversized.to_vec(), "credential ID of at most 1023 bytes");
+}
+
+#[test]
+fn test_authenticator_data() {
+    let auth_data = AuthenticatorData {
+        rp_id_hash: [0x11; 32],
+        flags: FLAG_UP | FLAG_UV | FLAG_AT | FLAG_ED,
+        sign_count: 0x01020304,
+        attested_credential_data: Some(acd()),
+        extensions: Some(Value::Map(vec![(
+            Value::Text("credProtect".to_owned()),
+            Value::from(2),
+        )])),
+    };
+    let data = auth_data.clone().to_vec().unwrap();
+    assert_eq!(&data[32..37], &[0xc5, 0x01, 0x02, 0x03, 0x04]);
+    let parsed = AuthenticatorData::from_slice(&data).unwrap();
+    assert_eq!(parsed, auth_data);
+    assert!(parsed.user_present());
+    assert!(parsed.user_verified());
+
+    // An assertion holds neither attested credential data nor extensions, and the AT and ED flags
+    // follow their presence.
+    let assertion = AuthenticatorData {
+        flags: FLAG_UP | FLAG_AT,
+        attested_credential_data: None,
+        extensions: None,
+        ..auth_data
+    };
+    let data = assertion.to_vec().unwrap();
+    assert_eq!(data.len(), 37);
+    let parsed = AuthenticatorData::from_slice(&data).unwrap();
+    assert_eq!(parsed.flags, FLAG_UP);
+    assert!(!parsed.user_verified());
+    assert_eq!(parsed.sign_count, 0x01020304);
+
+    let mut no_acd = data.clone();
+    no_acd[32] |= FLAG_AT;
+    expect_err(
+        AuthenticatorData::from_slice(&no_acd),
+        "complete authenticator data",
+    );
+    let mut bad_extensions = data.clone();
+    bad_extensions[32] |= FLAG_ED;
+    bad_extensions.push(0x01);
+    expect_err(AuthenticatorData::from_slice(&bad_extensions), "map");
+    let mut trailer = data.clone();
+    trailer.push(0xa0);
+    expect_err(AuthenticatorData::from_slice(&trailer), "extraneous data");
+    expect_err(
+        AuthenticatorData::from_slice(&data[..36]),
+        "complete authenticator data",
+    );
+}
+
+#[test]
+fn test_normalize_credential_key() {
+    // EC2 key with no algorithm and extra fields.
+    let mut key =
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_384, vec![0x01; 48], vec![0x02; 48])
+            .key_id(b"kid".to_vec())
+            .add_key_op(iana::KeyOperation::Verify)
+            .param(-70000, Value::Null)
+            .build();
+    key.params
+        .push((Label::Text("vendor".to_owned()), Value::Null));
+    let want =
+        CoseKeyBuilder::new_ec2_pub_key(iana::EllipticCurve::P_384, vec![0x01; 48], vec![0x02; 48])
+            .algorithm(A::ES384)
+            .build();
+    assert_eq!(normalize_credential_key(&key).unwrap(), want);
+    assert_eq!(normalize_credential_key(&want).unwrap(), want);
+
+    // Ed25519 key marked with the polymorphic EdDSA algorithm is kept as is.
+    let key = ed25519_key(A::EdDSA);
+    assert_eq!(normalize_credential_key(&key).unwrap(), key);
+    let mut no_alg = key.clone();
+    no_alg.alg = None;
+    assert_eq!(normalize_credential_key(&no_alg).unwrap(), key);
+
+    // RSA key with leading zero bytes and extra fields.
+    let key = CoseKeyBuilder::new_rsa_pub_key(vec![0x00, 0xc1, 0xc2], vec![0x00, 0x01, 0x00, 0x01])
+        .algorithm(A::PS256)
+        .param(iana::RsaKeyParameter::D as i64, Value::Bytes(vec![0x05]))
+        .build();
+    let want = CoseKeyBuilder::new_rsa_pub_key(vec![0xc1, 0xc2], vec![0x01, 0x00, 0x01])
+        .algorithm(A::PS256)
+        .build();
+    assert_eq!(normalize_credential_key(&key).unwrap(), want);
+    let mut no_alg = key;
+    no_alg.alg = None;
+    assert_eq!(
+        normalize_credential_key(&no_alg).unwrap().alg,
+        Some(Algorithm::Assigned(A::RS256))
+    );
+
+    let mut no_y = p256_key();
+    no_y.params
+        .retain(|(l, _)| *l != Label::Int(iana::Ec2KeyParameter::Y as i64));
+    let mut bad_curve = p256_key();
+    bad_curve.params[0].1 = Value::from(iana::EllipticCurve::X25519 as i64);
+    let mut unknown_curve = p256_key();
+    unknown_curve.params[0].1 = Value::from(-1000);
+    let mut x25519 = ed25519_key(A::EdDSA);
+    x25519.params[0].1 = Value::from(iana::EllipticCurve::X25519 as i64);
+    let tests = [
+        (no_y, "credential public key"),
+        (bad_curve, "P-256, P-384, P-521 or secp256k1 curve"),
+        (unknown_curve, "recognized IANA value"),
+        (x25519, "Ed25519 or Ed448 curve"),
+        (
+            CoseKeyBuilder::new_symmetric_key(vec![0x01; 16]).build(),
+            "EC2, OKP or RSA credential public key",
+        ),
+    ];
+    for (key, err_msg) in tests {
+        expect_err(normalize_credential_key(&key), err_msg);
+    }
+}
+
+#[test]
+fn test_key_supports_algorithm() {
+    let tests = [
+        (ed25519_key(A::EdDSA), A::EdDSA, true),
+        (ed25519_key(A::EdDSA), A::Ed25519, true),
+        (ed25519_key(A::Ed25519), A::EdDSA, true),
+        (ed25519_key(A::EdDSA), A::Ed448, false),
+        (ed25519_key(A::EdDSA), A::ES256, false),
+        (p256_key(), A::ES256, true),
+        (p256_key(), A::ESP256, true),
+        (p256_key(), A::ESP384, false),
+        (p256_key(), A::ES384, false),
+    ];
+    for (key, alg, want) in tests {
+        assert_eq!(
+            key_supports_algorithm(&key, alg),
+            want,
+            "for {:?} with {:?}",
+            key.alg,
+            alg
+        );
+    }
+    let mut no_alg = p256_key();
+    no_alg.alg = None;
+    assert!(!key_supports_algorithm(&no_alg, A::ES256));
+}
+
+#[test]
+fn test_cred_params() {
+    let param = |typ: &str, alg: i64| {
+        Value::Map(vec![
+            (Value::Text("alg".to_owned()), Value::from(alg)),
+            (Value::Text("type".to_owned()), Value::Text(typ.to_owned())),
+        ])
+    };
+    let params = Value::Array(vec![
+        param("public-key", -8),
+        param("other", -7),
+        param("public-key", -65000),
+        param("public-key", -7),
+        param("public-key", -257),
+    ]);
+    let algs = cred_params_algorithms(params).unwrap();
+    assert_eq!(algs, vec![A::EdDSA, A::ES256, A::RS256]);
+
+    assert_eq!(
+        negotiate_algorithm(&algs, &[A::RS256, A::ES256]),
+        Some(A::ES256)
+    );
+    assert_eq!(negotiate_algorithm(&algs, &[A::ES384]), None);
+    assert_eq!(negotiate_algorithm(&[], &[A::ES256]), None);
+
+    let tests = [
+        (
+            Value::Array(vec![Value::Map(vec![(
+                Value::Text("type".to_owned()),
+                Value::Text("public-key".to_owned()),
+            )])]),
+            "PublicKeyCredentialParameters with type and alg",
+        ),
+        (
+            Value::Array(vec![Value::Map(vec![
+                (Value::Text("alg".to_owned()), Value::from(-7)),
+                (Value::Text("alg".to_owned()), Value::from(-7)),
+            ])]),
+            "duplicate map key",
+        ),
+        (Value::Array(vec![Value::from(-7)]), "map"),
+        (param("public-key", -7), "array"),
+    ];
+    for (value, err_msg) in tests {
+        expect_err(cred_params_algorithms(value), err_msg);
+    }
+}
+
+#[test]
+fn test_ecdsa_signature_der() {
+    let tests = [
+        (
+            // r with high bit set, s short.
+            [vec![0x80; 32], [vec![0x00; 31], vec![0x01]].concat()].concat(),
+            concat!(
+                "3026",
+                "022100",
+                "8080808080808080808080808080808080808080808080808080808080808080",
+                "020101",
+            ),
+        ),
+        (
+            [vec![0x7f; 32], vec![0x00; 32]].concat(),
+            concat!(
+                "3025",
+                "0220",
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "020100",
+            ),
+        ),
+    ];
+    for (raw, der) in tests {
+        let der = hex::decode(der).unwrap();
+        assert_eq!(ecdsa_signature_to_der(&raw).unwrap(), der);
+        assert_eq!(ecdsa_signature_from_der(&der, 32).unwrap(), raw);
+    }
+
+    let p521 = [vec![0x01; 66], vec![0x02; 66]].concat();
+    let der = ecdsa_signature_to_der(&p521).unwrap();
+    assert_eq!(&der[..3], &[0x30, 0x81, 0x88]);
+    assert_eq!(ecdsa_signature_from_der(&der, 66).unwrap(), p521);
+
+    let tests = [
+        // Trailing data.
+        ("3006020101020101 00", "DER-encoded ECDSA signature"),
+        // Negative integer.
+        ("3006020181020101", "DER-encoded ECDSA signature"),
+        // Non-minimal integer.
+        ("300702020001020101", "DER-encoded ECDSA signature"),
+        // Missing s.
+        ("3003020101", "DER-encoded ECDSA signature"),
+        // Not a SEQUENCE.
+        ("3103020101", "DER-encoded ECDSA signature"),
+        // r longer than the curve size.
+        (
+            "30260221 01 0101010101010101010101010101010101010101010101010101010101010101 020101",
+            "ECDSA signature integer of curve size",
+        ),
+    ];
+    for (der, err_msg) in tests {
+        let der = hex::decode(der.replace(' ', "")).unwrap();
+        expect_err(ecdsa_signature_from_der(&der, 32), err_msg);
+    }
+    expect_err(
+        ecdsa_signature_to_der(&[1, 2, 3]),
+        "ECDSA signature of r || s",
+    );
+    expect_err(ecdsa_signature_to_der(&[]), "ECDSA signature of r || s");
+}
+
+#[test]
+fn test_verify_assertion() {
+    let auth_data = AuthenticatorData {
+        rp_id_hash: [0x11; 32],
+        flags: FLAG_UP,
+        sign_count: 7,
+        ..Default::default()
+    }
+    .to_vec()
+    .unwrap();
+    let client_data_hash = [0x22; 32];
+    let signed = assertion_signed_data(&auth_data, &client_data_hash);
+    assert_eq!(signed.len(), 69);
+
+    let raw = [vec![0x80; 32], vec![0x01; 32]].concat();
+    let der = ecdsa_signature_to_der(&raw).unwrap();
+    let verifier = |sig: &[u8], data: &[u8]| {
+        if sig == raw.as_slice() && data == signed.as_slice() {
+            Ok(())
+        } else {
+            Err("bad signature".to_owned())
+        }
+    };
+    verify_assertion(&p256_key(), &auth_data, &client_data_hash, &der, verifier).unwrap();
+
+    // Non-ECDSA signatures are passed through unchanged.
+    verify_assertion(
+        &ed25519_key(A::EdDSA),
+        &auth_data,
+        &client_data_hash,
+        &raw,
+        verifier,
+    )
+    .unwrap();
+
+    let err: WebAuthnError<String> =
+        verify_assertion(&p256_key(), &auth_data, &[0x33; 32], &der, verifier).unwrap_err();
+    assert_eq!(err.to_string(), "verification failed: bad signature");
+    let err: WebAuthnError<String> =
+        verify_assertion(&p256_key(), &auth_data, &client_data_hash, &raw, verifier).unwrap_err();
+    assert!(
+        err.to_string().starts_with("invalid WebAuthn data: ")
+            && err.to_string().contains("DER-encoded ECDSA signature"),
+        "{}",
+        err
+    );
+}